@@ -0,0 +1,122 @@
+extern crate cursive;
+
+// This example runs a future, and shows its result in a SelectView.
+//
+// Cursive doesn't come with an executor. A real application would use the
+// one from its runtime, like `tokio::spawn`; here each task simply gets its
+// own thread.
+//
+// Run with `cargo run --example async --features async`.
+
+#[cfg(feature = "async")]
+fn main() {
+    planets::run();
+}
+
+#[cfg(not(feature = "async"))]
+fn main() {
+    eprintln!("This example needs the `async` feature.");
+}
+
+#[cfg(feature = "async")]
+mod planets {
+    use cursive::views::{Dialog, SelectView, TextView};
+    use cursive::{Cursive, Task};
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::{Arc, Mutex};
+    use std::task::{Context, Poll, Wake, Waker};
+    use std::thread::{self, Thread};
+    use std::time::Duration;
+
+    pub fn run() {
+        let mut siv = Cursive::default();
+        siv.set_executor(|task| {
+            thread::spawn(move || block_on(task));
+        });
+
+        siv.add_layer(Dialog::around(TextView::new(
+            "Fetching the planets...",
+        )));
+
+        siv.spawn(Fetch::new(), |s, planets| {
+            let select = SelectView::new().with_all_str(planets).on_submit(
+                |s, planet: &String| {
+                    s.add_layer(Dialog::info(format!(
+                        "Welcome to {}!",
+                        planet
+                    )));
+                },
+            );
+
+            s.pop_layer();
+            s.add_layer(
+                Dialog::around(select)
+                    .title("Pick a planet")
+                    .button("Quit", Cursive::quit),
+            );
+        });
+
+        siv.run();
+    }
+
+    // Pretends to download a list of planets, which takes a second.
+    struct Fetch {
+        result: Arc<Mutex<Option<Vec<String>>>>,
+        started: bool,
+    }
+
+    impl Fetch {
+        fn new() -> Self {
+            Fetch {
+                result: Arc::new(Mutex::new(None)),
+                started: false,
+            }
+        }
+    }
+
+    impl Future for Fetch {
+        type Output = Vec<String>;
+
+        fn poll(
+            mut self: Pin<&mut Self>, cx: &mut Context<'_>,
+        ) -> Poll<Vec<String>> {
+            if let Some(planets) = self.result.lock().unwrap().take() {
+                return Poll::Ready(planets);
+            }
+
+            if !self.started {
+                self.started = true;
+                let result = Arc::clone(&self.result);
+                let waker = cx.waker().clone();
+                thread::spawn(move || {
+                    thread::sleep(Duration::from_secs(1));
+                    let planets = ["Mercury", "Venus", "Earth", "Mars"];
+                    *result.lock().unwrap() =
+                        Some(planets.iter().map(|p| p.to_string()).collect());
+                    waker.wake();
+                });
+            }
+
+            Poll::Pending
+        }
+    }
+
+    // Wakes the thread running a task.
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    // Runs a task on the current thread until it completes.
+    fn block_on(mut task: Task) {
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        while task.as_mut().poll(&mut cx).is_pending() {
+            thread::park();
+        }
+    }
+}
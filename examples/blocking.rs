@@ -0,0 +1,47 @@
+extern crate cursive;
+
+use cursive::traits::*;
+use cursive::views::{Button, Dialog, ProgressBar};
+use cursive::Cursive;
+use std::thread;
+use std::time::Duration;
+
+// This example runs a long job right in a callback, without any thread.
+//
+// The event loop only draws again once the callback returns, so the callback
+// calls `Cursive::refresh()` to show its progress. Input is not processed
+// until the job is done.
+
+fn main() {
+    let mut siv = Cursive::default();
+
+    siv.add_layer(
+        Dialog::around(Button::new("Start", run_job))
+            .title("Blocking job")
+            .button("Quit", Cursive::quit),
+    );
+
+    siv.run();
+}
+
+fn run_job(s: &mut Cursive) {
+    let n_max = 50;
+
+    s.add_layer(Dialog::around(
+        ProgressBar::new()
+            .range(0, n_max)
+            .with_id("progress")
+            .fixed_width(30),
+    ));
+
+    for i in 0..=n_max {
+        // Pretend to do some work.
+        thread::sleep(Duration::from_millis(40));
+
+        s.call_on_id("progress", |bar: &mut ProgressBar| bar.set_value(i));
+        s.refresh();
+    }
+
+    s.pop_layer();
+    s.add_layer(Dialog::info("Done!"));
+}
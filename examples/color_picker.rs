@@ -0,0 +1,31 @@
+extern crate cursive;
+
+use cursive::theme::ColorStyle;
+use cursive::utils::markup::StyledString;
+use cursive::views::{ColorPicker, Dialog, TextView};
+use cursive::Cursive;
+
+// This example lets the user pick a color, then prints some text with it.
+
+fn main() {
+    let mut siv = Cursive::default();
+
+    // Only show the colors this terminal can display.
+    let picker = ColorPicker::new()
+        .color_support(siv.color_support())
+        .on_submit(|s, color| {
+            let text = StyledString::styled(
+                "Isn't it a nice color?",
+                ColorStyle::new(color, ColorStyle::primary().back),
+            );
+            s.pop_layer();
+            s.add_layer(
+                Dialog::around(TextView::new(text))
+                    .button("Quit", |s| s.quit()),
+            );
+        });
+
+    siv.add_layer(Dialog::around(picker).title("Pick a color"));
+
+    siv.run();
+}
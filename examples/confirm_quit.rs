@@ -0,0 +1,35 @@
+extern crate cursive;
+
+use cursive::traits::*;
+use cursive::views::{Dialog, TextView};
+use cursive::Cursive;
+
+// This example asks for confirmation before quitting, whether it is from
+// `Ctrl-C` or the "Quit" button.
+
+fn main() {
+    let mut siv = Cursive::default();
+
+    siv.set_on_quit(|s| {
+        // Another `Ctrl-C` while the dialog is shown quits for good.
+        if s.find_id::<Dialog>("confirm").is_some() {
+            return true;
+        }
+
+        s.add_layer(
+            Dialog::text("Do you really want to quit?")
+                .title("Quit")
+                .button("Yes", Cursive::force_quit)
+                .dismiss_button("No")
+                .with_id("confirm"),
+        );
+        false
+    });
+
+    siv.add_layer(
+        Dialog::around(TextView::new("Press Ctrl-C to quit."))
+            .button("Quit", Cursive::quit),
+    );
+
+    siv.run();
+}
@@ -0,0 +1,32 @@
+extern crate cursive;
+
+use cursive::views::{Dialog, FileDialog, TextView};
+use cursive::Cursive;
+
+// This example lets the user pick a Rust file from the current directory,
+// then prints its size.
+
+fn main() {
+    let mut siv = Cursive::default();
+
+    siv.add_layer(FileDialog::new(".").extensions(["rs"]).on_submit(
+        |s, path| {
+            let text = match path.metadata() {
+                Ok(metadata) => format!(
+                    "{} is {} bytes long.",
+                    path.display(),
+                    metadata.len()
+                ),
+                Err(err) => {
+                    format!("Could not read {}: {}", path.display(), err)
+                }
+            };
+            s.add_layer(Dialog::around(TextView::new(text)).button(
+                "Quit",
+                |s| s.quit(),
+            ));
+        },
+    ));
+
+    siv.run();
+}
@@ -0,0 +1,46 @@
+extern crate cursive;
+
+use cursive::event::EventResult;
+use cursive::traits::*;
+use cursive::views::{Dialog, EditView, FocusTracker, LinearLayout, TextView};
+use cursive::Cursive;
+
+// This example checks a field when it loses the focus.
+// Type something in the "Age" field, then press <Tab> to leave it.
+
+fn main() {
+    let mut siv = Cursive::default();
+
+    let age = FocusTracker::new(EditView::new())
+        .on_focus(|_| EventResult::with_cb(|s| set_status(s, "")))
+        .on_blur(|edit| {
+            let content = edit.get_content();
+            let message = match content.parse::<u32>() {
+                Ok(_) => "",
+                Err(_) => "The age must be a number.",
+            };
+            EventResult::with_cb(move |s| set_status(s, message))
+        });
+
+    siv.add_layer(
+        Dialog::around(
+            LinearLayout::vertical()
+                .child(TextView::new("Name"))
+                .child(EditView::new())
+                .child(TextView::new("Age"))
+                .child(age)
+                .child(TextView::new("").with_id("status")),
+        )
+        .title("Form")
+        .button("Quit", Cursive::quit)
+        .fixed_width(30),
+    );
+
+    siv.run();
+}
+
+fn set_status(siv: &mut Cursive, message: &str) {
+    siv.call_on_id("status", |view: &mut TextView| {
+        view.set_content(message);
+    });
+}
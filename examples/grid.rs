@@ -0,0 +1,54 @@
+extern crate cursive;
+
+use cursive::align::Align;
+use cursive::traits::*;
+use cursive::views::{Dialog, EditView, GridLayout, SelectView, TextView};
+use cursive::Cursive;
+
+// This example uses a GridLayout to align the fields of a form.
+//
+// Labels all share the first column, so the fields are aligned, however
+// long each label is.
+
+fn main() {
+    let mut siv = Cursive::default();
+
+    let form = GridLayout::new()
+        .gap((2, 1))
+        .child((0, 0), TextView::new("Name"))
+        .child((1, 0), EditView::new().with_id("name").fixed_width(20))
+        .child((0, 1), TextView::new("Email address"))
+        .child((1, 1), EditView::new().fixed_width(20))
+        .child((0, 2), TextView::new("Role"))
+        .child(
+            (1, 2),
+            SelectView::new()
+                .popup()
+                .item_str("Developer")
+                .item_str("Designer")
+                .item_str("Manager"),
+        )
+        .child((0, 3), TextView::new("All fields are optional."))
+        .span((2, 1))
+        .align(Align::center());
+
+    siv.add_layer(
+        Dialog::around(form)
+            .title("Sign up")
+            .button("Ok", |s| {
+                let name = s
+                    .call_on_id("name", |view: &mut EditView| {
+                        view.get_content()
+                    })
+                    .unwrap();
+                s.pop_layer();
+                s.add_layer(
+                    Dialog::text(format!("Welcome, {}!", name))
+                        .button("Quit", |s| s.quit()),
+                );
+            })
+            .button("Quit", |s| s.quit()),
+    );
+
+    siv.run();
+}
@@ -0,0 +1,43 @@
+extern crate cursive;
+
+use cursive::theme::{BaseColor, Color};
+use cursive::traits::*;
+use cursive::utils::markup::StyledString;
+use cursive::views::{Dialog, LazyListView, ListSource};
+use cursive::Cursive;
+
+// This example shows a list of a million rows. They are computed when they
+// become visible, so the list starts instantly and stays fast.
+
+struct Squares;
+
+impl ListSource for Squares {
+    fn len(&self) -> usize {
+        1_000_000
+    }
+
+    fn row(&self, index: usize) -> StyledString {
+        let mut row = StyledString::plain(format!("{:>7}² = ", index));
+        row.append_styled(
+            (index as u64 * index as u64).to_string(),
+            Color::Dark(BaseColor::Blue),
+        );
+        row
+    }
+}
+
+fn main() {
+    let mut siv = Cursive::default();
+
+    let list = LazyListView::new(Squares).on_submit(|s, index| {
+        s.add_layer(Dialog::info(format!("You picked row {}.", index)));
+    });
+
+    siv.add_layer(
+        Dialog::around(list.fixed_size((30, 15)))
+            .title("A million squares")
+            .button("Quit", Cursive::quit),
+    );
+
+    siv.run();
+}
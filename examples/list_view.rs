@@ -23,7 +23,8 @@ fn main() {
                     .child("Name", EditView::new().fixed_width(10))
                     .child(
                         "Receive spam?",
-                        Checkbox::new().on_change(|s, checked| {
+                        Checkbox::new().on_change(|s, state| {
+                            let checked = state.is_checked();
                             // Enable/Disable the next field depending on this checkbox
                             for name in &["email1", "email2"] {
                                 s.call_on_id(name, |view: &mut EditView| {
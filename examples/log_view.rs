@@ -0,0 +1,53 @@
+extern crate cursive;
+
+use cursive::traits::*;
+use cursive::views::LogView;
+use cursive::Cursive;
+use std::thread;
+use std::time::Duration;
+
+// This example shows a stream of logs, generated from a separate thread.
+//
+// Each line is sent to the UI thread through the callback sink, and appended
+// to a `LogView`. Scroll up to stop following new lines.
+
+fn main() {
+    // As usual, create the Cursive root
+    let mut siv = Cursive::default();
+
+    siv.add_global_callback('q', |s| s.quit());
+
+    // Only the last 200 lines are kept.
+    siv.add_layer(LogView::new(200).with_id("log").full_screen());
+
+    // Generate data in a separate thread.
+    let sink = siv.cb_sink().clone();
+    thread::spawn(move || {
+        generate_logs(|line| {
+            let append = move |s: &mut Cursive| {
+                s.call_on_id("log", |log: &mut LogView| log.append(line));
+            };
+            // The send will fail when the other side is dropped.
+            // (When the application ends).
+            sink.send(Box::new(append)).is_ok()
+        });
+    });
+
+    siv.run();
+}
+
+// We will only simulate log generation here.
+// In real life, this may come from a running task, a separate process, ...
+//
+// `send` returns `false` once the application is closed.
+fn generate_logs<F: Fn(String) -> bool>(send: F) {
+    let mut i = 1;
+    loop {
+        let line = format!("Interesting log line {}", i);
+        i += 1;
+        if !send(line) {
+            return;
+        }
+        thread::sleep(Duration::from_millis(30));
+    }
+}
@@ -1,10 +1,11 @@
 extern crate cursive;
 
+use cursive::theme;
 use cursive::theme::BaseColor;
 use cursive::theme::Color;
 use cursive::theme::Effect;
 use cursive::theme::Style;
-use cursive::utils::markup::StyledString;
+use cursive::utils::markup::{cursup, StyledString};
 use cursive::views::{Dialog, TextView};
 use cursive::Cursive;
 
@@ -17,6 +18,23 @@ fn main() {
         "cool?",
         Style::from(Color::Light(BaseColor::Blue)).combine(Effect::Bold),
     ));
+    styled.append_plain("\n\n");
+    styled.append_styled(
+        "Warning: this text blinks!",
+        Style::from(Color::Dark(BaseColor::Red)).combine(Effect::Blink),
+    );
+    styled.append_plain("\n\n");
+
+    // The same kind of text can be written with the cursup markup.
+    styled.append(cursup::parse(
+        "/light_blue+bold{Markup} is /underline{shorter} to write.",
+    ));
+    styled.append_plain("\n\n");
+    styled.append(theme::gradient(
+        "And this text fades from red to blue.",
+        Color::Rgb(255, 0, 0),
+        Color::Rgb(0, 0, 255),
+    ));
 
     // TextView can natively accept StyledString.
     siv.add_layer(
@@ -1,6 +1,6 @@
 extern crate cursive;
 
-use cursive::event::Key;
+use cursive::event::{Event, Key};
 use cursive::menu::MenuTree;
 use cursive::traits::*;
 use cursive::views::Dialog;
@@ -75,9 +75,10 @@ fn main() {
 
     // siv.set_autohide_menu(false);
 
-    siv.add_global_callback(Key::Esc, |s| s.select_menubar());
+    // <Esc> selects the menu by default. Another key can be used instead.
+    siv.set_menubar_trigger(Some(Event::Key(Key::F10)));
 
-    siv.add_layer(Dialog::text("Hit <Esc> to show the menu!"));
+    siv.add_layer(Dialog::text("Hit <F10> to show the menu!"));
 
     siv.run();
 }
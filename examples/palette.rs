@@ -0,0 +1,32 @@
+extern crate cursive;
+
+use cursive::theme::PaletteColor;
+use cursive::views::{Dialog, TextView};
+use cursive::Cursive;
+
+// This example changes the palette of the running application.
+// Press `i` to swap the view and primary colors.
+
+fn main() {
+    let mut siv = Cursive::default();
+
+    siv.add_global_callback('i', invert_colors);
+
+    siv.add_layer(
+        Dialog::around(TextView::new("Press <i> to invert the colors."))
+            .title("Palette")
+            .button("Quit", Cursive::quit),
+    );
+
+    siv.run();
+}
+
+fn invert_colors(siv: &mut Cursive) {
+    siv.with_theme(|theme| {
+        let view = theme.palette[PaletteColor::View];
+        let primary = theme.palette[PaletteColor::Primary];
+
+        theme.palette[PaletteColor::View] = primary;
+        theme.palette[PaletteColor::Primary] = view;
+    });
+}
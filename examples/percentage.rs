@@ -0,0 +1,30 @@
+extern crate cursive;
+
+use cursive::traits::*;
+use cursive::views::{LinearLayout, Panel, TextView};
+use cursive::Cursive;
+
+// This example splits the screen 30/70 between two panels.
+// Resize the terminal: the panels keep the same proportions.
+
+fn main() {
+    let mut siv = Cursive::default();
+
+    siv.add_fullscreen_layer(
+        LinearLayout::horizontal()
+            .child(
+                Panel::new(TextView::new("This panel takes 30% of the width."))
+                    .percentage_width(30),
+            )
+            .child(
+                Panel::new(TextView::new(
+                    "This one takes the remaining 70%.\n\nPress q to quit.",
+                )).percentage_width(70),
+            )
+            .full_screen(),
+    );
+
+    siv.add_global_callback('q', |s| s.quit());
+
+    siv.run();
+}
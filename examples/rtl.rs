@@ -0,0 +1,33 @@
+extern crate cursive;
+
+use cursive::direction::TextDirection;
+use cursive::traits::*;
+use cursive::views::{Dialog, EditView, LinearLayout, TextView};
+use cursive::Cursive;
+
+// This example shows right-to-left text, in Hebrew.
+// The text starts on the right, and the arrow keys follow it visually.
+
+fn main() {
+    let mut siv = Cursive::default();
+
+    siv.add_layer(
+        Dialog::around(
+            LinearLayout::vertical()
+                .child(
+                    // "Hello world! What is your name?"
+                    TextView::new("שלום עולם! מה שמך?")
+                        .direction(TextDirection::RightToLeft),
+                )
+                .child(
+                    EditView::new()
+                        .direction(TextDirection::RightToLeft)
+                        .fixed_width(20),
+                ),
+        )
+        .title("RTL")
+        .button("Quit", |s| s.quit()),
+    );
+
+    siv.run();
+}
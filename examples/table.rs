@@ -0,0 +1,91 @@
+extern crate cursive;
+
+use cursive::align::HAlign;
+use cursive::traits::*;
+use cursive::views::{Dialog, TableView, TableViewItem};
+use cursive::Cursive;
+use std::cmp::Ordering;
+
+// This example shows a table of planets. Click on a column header to sort
+// the table, and click again to reverse the order.
+
+#[derive(Clone, Copy, PartialEq)]
+enum Column {
+    Name,
+    Moons,
+    Distance,
+}
+
+struct Planet {
+    name: &'static str,
+    moons: u32,
+    // Average distance to the sun, in millions of km.
+    distance: f64,
+}
+
+impl TableViewItem<Column> for Planet {
+    fn to_column(&self, column: Column) -> String {
+        match column {
+            Column::Name => self.name.to_string(),
+            Column::Moons => self.moons.to_string(),
+            Column::Distance => format!("{:.1}", self.distance),
+        }
+    }
+
+    fn compare(&self, other: &Self, column: Column) -> Ordering {
+        match column {
+            Column::Name => self.name.cmp(other.name),
+            Column::Moons => self.moons.cmp(&other.moons),
+            Column::Distance => self
+                .distance
+                .partial_cmp(&other.distance)
+                .unwrap_or(Ordering::Equal),
+        }
+    }
+}
+
+fn planets() -> Vec<Planet> {
+    vec![
+        Planet { name: "Mercury", moons: 0, distance: 57.9 },
+        Planet { name: "Venus", moons: 0, distance: 108.2 },
+        Planet { name: "Earth", moons: 1, distance: 149.6 },
+        Planet { name: "Mars", moons: 2, distance: 227.9 },
+        Planet { name: "Jupiter", moons: 79, distance: 778.6 },
+        Planet { name: "Saturn", moons: 82, distance: 1433.5 },
+        Planet { name: "Uranus", moons: 27, distance: 2872.5 },
+        Planet { name: "Neptune", moons: 14, distance: 4495.1 },
+    ]
+}
+
+fn main() {
+    let mut siv = Cursive::default();
+
+    let table = TableView::new()
+        .column(Column::Name, "Name", |c| c.width(10))
+        .column(Column::Moons, "Moons", |c| {
+            c.width_percent(30).align(HAlign::Right)
+        })
+        .column(Column::Distance, "Distance (Mkm)", |c| {
+            c.align(HAlign::Right)
+        })
+        .items(planets())
+        .on_submit(|s, index| {
+            let text = s
+                .call_on_id("planets", |table: &mut TableView<_, Column>| {
+                    let planet: &Planet = table.borrow_item(index).unwrap();
+                    format!("{} has {} moons.", planet.name, planet.moons)
+                })
+                .unwrap();
+            s.add_layer(Dialog::info(text));
+        })
+        .with_id("planets")
+        .fixed_size((50, 8));
+
+    siv.add_layer(
+        Dialog::around(table)
+            .title("Planets")
+            .button("Quit", Cursive::quit),
+    );
+
+    siv.run();
+}
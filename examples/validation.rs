@@ -0,0 +1,57 @@
+extern crate cursive;
+
+use cursive::traits::*;
+use cursive::views::{Dialog, EditView, ListView, Validation};
+use cursive::Cursive;
+
+// This example shows how to check or change what the user types.
+
+fn main() {
+    let mut siv = Cursive::default();
+
+    siv.add_layer(
+        Dialog::around(
+            ListView::new()
+                // Only digits can be typed in this field.
+                .child(
+                    "Age",
+                    EditView::new()
+                        .validator(|content, _| {
+                            if content.chars().all(|c| c.is_ascii_digit()) {
+                                Validation::Accept
+                            } else {
+                                Validation::Reject
+                            }
+                        })
+                        .fixed_width(10),
+                )
+                // Everything typed here is turned to uppercase.
+                .child(
+                    "Code",
+                    EditView::new()
+                        .validator(|content, cursor| {
+                            Validation::Replace(content.to_uppercase(), cursor)
+                        })
+                        .fixed_width(10),
+                )
+                // Anything can be typed, but the field turns red until it
+                // looks like an email address.
+                .child(
+                    "Email",
+                    EditView::new()
+                        .validator(|content, _| {
+                            if content.contains('@') {
+                                Validation::Accept
+                            } else {
+                                Validation::Invalid
+                            }
+                        })
+                        .fixed_width(20),
+                ),
+        )
+        .title("Sign up")
+        .button("Quit", Cursive::quit),
+    );
+
+    siv.run();
+}
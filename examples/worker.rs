@@ -0,0 +1,54 @@
+extern crate cursive;
+
+use cursive::traits::*;
+use cursive::views::{Dialog, ProgressBar};
+use cursive::Cursive;
+use std::thread;
+use std::time::Duration;
+
+// This example updates a ProgressBar from a worker thread.
+//
+// Each step of the work sends an update through the sink. Updates wake the
+// event loop up, so no `set_fps` is needed.
+
+fn main() {
+    let mut siv = Cursive::default();
+
+    let n_max = 200;
+    siv.add_layer(
+        Dialog::around(
+            ProgressBar::new()
+                .max(n_max)
+                .with_id("progress")
+                .fixed_width(40),
+        )
+        .title("Working..."),
+    );
+
+    let sink = siv.update_sink();
+    thread::spawn(move || {
+        for i in 1..=n_max {
+            // Pretend to do something useful.
+            thread::sleep(Duration::from_millis(20));
+
+            let update = move |s: &mut Cursive| {
+                s.call_on_id("progress", |bar: &mut ProgressBar| {
+                    bar.set_value(i);
+                });
+            };
+            if !sink.send_update(update) {
+                // The application was closed.
+                return;
+            }
+        }
+
+        sink.send_update(|s| {
+            s.pop_layer();
+            s.add_layer(
+                Dialog::text("All done!").button("Quit", Cursive::quit),
+            );
+        });
+    });
+
+    siv.run();
+}
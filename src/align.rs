@@ -1,7 +1,9 @@
 //! Tools to control view alignment.
 
+use vec::Vec2;
+
 /// Specifies the alignment along both horizontal and vertical directions.
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Align {
     /// Horizontal alignment policy
     pub h: HAlign,
@@ -32,17 +34,28 @@ impl Align {
 
     /// Creates a bottom-right alignment.
     pub fn bot_right() -> Self {
-        Align::new(HAlign::Right, VAlign::Top)
+        Align::new(HAlign::Right, VAlign::Bottom)
     }
 
     /// Creates an alignment centered both horizontally and vertically.
     pub fn center() -> Self {
         Align::new(HAlign::Center, VAlign::Center)
     }
+
+    /// Returns the offset required to position a view.
+    ///
+    /// Same as `HAlign::get_offset()` and `VAlign::get_offset()`, along
+    /// both axes.
+    pub fn get_offset(&self, content: Vec2, container: Vec2) -> Vec2 {
+        Vec2::new(
+            self.h.get_offset(content.x, container.x),
+            self.v.get_offset(content.y, container.y),
+        )
+    }
 }
 
 /// Horizontal alignment
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum HAlign {
     /// Place the element to the left of available space
     Left,
@@ -53,7 +66,7 @@ pub enum HAlign {
 }
 
 /// Vertical alignment
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum VAlign {
     /// Place the element at the top of available space
     Top,
@@ -74,13 +74,14 @@ impl Backend {
                 BltEvent::MouseMove { x, y } => {
                     self.mouse_position = Vec2::new(x as usize, y as usize);
                     // TODO: find out if a button is pressed?
-                    match self.buttons_pressed.iter().next() {
-                        None => Event::Refresh,
-                        Some(btn) => Event::Mouse {
-                            event: MouseEvent::Hold(*btn),
-                            position: self.mouse_position,
-                            offset: Vec2::zero(),
-                        },
+                    let event = match self.buttons_pressed.iter().next() {
+                        None => MouseEvent::Hover,
+                        Some(btn) => MouseEvent::Hold(*btn),
+                    };
+                    Event::Mouse {
+                        event,
+                        position: self.mouse_position,
+                        offset: Vec2::zero(),
                     }
                 }
                 BltEvent::MouseScroll { delta } => Event::Mouse {
@@ -270,6 +271,9 @@ impl backend::Backend for Backend {
             Effect::Bold
             | Effect::Italic
             | Effect::Underline
+            | Effect::Strikethrough
+            | Effect::Blink
+            | Effect::Dim
             | Effect::Simple => {}
             // TODO: how to do this correctly?`
             //       BLT itself doesn't do this kind of thing,
@@ -287,6 +291,9 @@ impl backend::Backend for Backend {
             Effect::Bold
             | Effect::Italic
             | Effect::Underline
+            | Effect::Strikethrough
+            | Effect::Blink
+            | Effect::Dim
             | Effect::Simple => {}
             // The process of reversing is the same as unreversing
             Effect::Reverse => {
@@ -0,0 +1,359 @@
+//! Double buffer in front of another backend.
+//!
+//! Views are drawn to an in-memory copy of the screen. On refresh, only the
+//! cells that changed since the last frame are sent to the real backend,
+//! which saves a lot of output on slow connections.
+use std::cell::{Cell, RefCell};
+
+use enumset::EnumSet;
+
+use backend::puppet::{ObservedScreen, ObservedStyle};
+use backend::{self, InputRequest};
+use crossbeam_channel::{Receiver, Sender};
+use event::Event;
+use theme;
+use vec::Vec2;
+
+/// Backend sending only the changed cells to another one.
+pub(crate) struct Buffered {
+    inner: Box<backend::Backend>,
+
+    // Frame being drawn.
+    back: RefCell<ObservedScreen>,
+    // What the real screen shows, or `None` if it's unknown.
+    front: Option<ObservedScreen>,
+
+    current_style: Cell<theme::ColorPair>,
+    current_effects: Cell<EnumSet<theme::Effect>>,
+
+    // Style last given to `inner`, or `None` if it's unknown.
+    inner_style: Option<ObservedStyle>,
+}
+
+impl Buffered {
+    /// Wraps `inner`, whose screen content is unknown.
+    pub fn new(inner: Box<backend::Backend>) -> Self {
+        let back = ObservedScreen::new(inner.screen_size());
+        Buffered {
+            inner,
+            back: RefCell::new(back),
+            front: None,
+            current_style: Cell::new(theme::ColorPair {
+                front: theme::Color::TerminalDefault,
+                back: theme::Color::TerminalDefault,
+            }),
+            current_effects: Cell::new(EnumSet::new()),
+            inner_style: None,
+        }
+    }
+
+    fn current_style(&self) -> ObservedStyle {
+        ObservedStyle {
+            colors: self.current_style.get(),
+            effects: self.current_effects.get(),
+        }
+    }
+
+    // Forgets what the real screen shows, to redraw everything.
+    fn invalidate(&mut self) {
+        self.front = None;
+        self.inner_style = None;
+    }
+
+    // Prints `text` with the given style on the real backend.
+    fn emit(&mut self, pos: Vec2, text: &str, style: ObservedStyle) {
+        let inner = &self.inner;
+        match self.inner_style {
+            Some(current) => {
+                if current.colors != style.colors {
+                    inner.set_color(style.colors);
+                }
+                for effect in current.effects.iter() {
+                    if !style.effects.contains(effect) {
+                        inner.unset_effect(effect);
+                    }
+                }
+                for effect in style.effects.iter() {
+                    if !current.effects.contains(effect) {
+                        inner.set_effect(effect);
+                    }
+                }
+            }
+            None => {
+                inner.set_color(style.colors);
+                for effect in EnumSet::<theme::Effect>::all().iter() {
+                    if style.effects.contains(effect) {
+                        inner.set_effect(effect);
+                    } else {
+                        inner.unset_effect(effect);
+                    }
+                }
+            }
+        }
+        self.inner_style = Some(style);
+
+        inner.print_at(pos, text);
+    }
+
+    // Returns the runs of cells that changed since the last frame, with
+    // the style to print them with.
+    //
+    // Consecutive changes with the same style are grouped together.
+    fn changes(&self) -> Vec<(Vec2, String, ObservedStyle)> {
+        let back = self.back.borrow();
+        let front = self.front.as_ref();
+        let front = front.filter(|front| front.size() == back.size());
+        let size = back.size();
+
+        let mut changes = Vec::new();
+        for y in 0..size.y {
+            let cell = |x| back.cell(Vec2::new(x, y)).unwrap();
+            let changed = |x| {
+                let pos = Vec2::new(x, y);
+                front.and_then(|front| front.cell(pos)) != back.cell(pos)
+            };
+
+            let mut x = 0;
+            while x < size.x {
+                if !changed(x) {
+                    x += 1;
+                    continue;
+                }
+
+                // When only the end of a wide character changed, the whole
+                // character is printed again.
+                let mut start = x;
+                while start > 0 && cell(start).letter.is_empty() {
+                    start -= 1;
+                }
+
+                let style = cell(start).style;
+                let mut text = String::new();
+                x = start;
+                while x < size.x {
+                    let next = cell(x);
+                    let ends = x > start
+                        && !next.letter.is_empty()
+                        && (next.style != style || !changed(x));
+                    if ends {
+                        break;
+                    }
+                    text.push_str(&next.letter);
+                    x += 1;
+                }
+
+                changes.push((Vec2::new(start, y), text, style));
+            }
+        }
+
+        changes
+    }
+}
+
+impl backend::Backend for Buffered {
+    fn finish(&mut self) {
+        self.inner.finish();
+    }
+
+    fn suspend(&mut self) {
+        self.inner.suspend();
+    }
+
+    fn resume(&mut self) {
+        self.inner.resume();
+        self.invalidate();
+    }
+
+    fn start_input_thread(
+        &mut self, event_sink: Sender<Option<Event>>,
+        input_request: Receiver<InputRequest>,
+    ) {
+        self.inner.start_input_thread(event_sink, input_request);
+    }
+
+    fn prepare_input(&mut self, input_request: InputRequest) {
+        self.inner.prepare_input(input_request);
+    }
+
+    fn set_title(&self, title: &str) {
+        self.inner.set_title(title);
+    }
+
+    fn cursor_position(&self) -> Option<Vec2> {
+        self.inner.cursor_position()
+    }
+
+    fn refresh(&mut self) {
+        for (pos, text, style) in self.changes() {
+            self.emit(pos, &text, style);
+        }
+        self.inner.refresh();
+
+        self.front = Some(self.back.get_mut().clone());
+    }
+
+    fn has_colors(&self) -> bool {
+        self.inner.has_colors()
+    }
+
+    fn color_support(&self) -> backend::ColorSupport {
+        self.inner.color_support()
+    }
+
+    fn screen_size(&self) -> Vec2 {
+        let size = self.inner.screen_size();
+        if self.back.borrow().size() != size {
+            // Everything will be drawn again anyway.
+            *self.back.borrow_mut() = ObservedScreen::new(size);
+        }
+        size
+    }
+
+    fn print_at(&self, pos: Vec2, text: &str) {
+        let style = self.current_style();
+        self.back.borrow_mut().print(pos, text, style);
+    }
+
+    fn clear(&self, color: theme::Color) {
+        self.back.borrow_mut().clear(ObservedStyle {
+            colors: theme::ColorPair {
+                front: color,
+                back: color,
+            },
+            effects: EnumSet::new(),
+        });
+    }
+
+    fn set_color(&self, colors: theme::ColorPair) -> theme::ColorPair {
+        self.current_style.replace(colors)
+    }
+
+    fn set_effect(&self, effect: theme::Effect) {
+        let mut effects = self.current_effects.get();
+        effects.insert(effect);
+        self.current_effects.set(effects);
+    }
+
+    fn unset_effect(&self, effect: theme::Effect) {
+        let mut effects = self.current_effects.get();
+        effects.remove(effect);
+        self.current_effects.set(effects);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backend::Backend;
+    use std::rc::Rc;
+
+    type Prints = Rc<RefCell<Vec<(Vec2, String)>>>;
+
+    // Keeps track of what is printed.
+    struct Recorder {
+        prints: Prints,
+    }
+
+    impl backend::Backend for Recorder {
+        fn finish(&mut self) {}
+
+        fn refresh(&mut self) {}
+
+        fn has_colors(&self) -> bool {
+            true
+        }
+
+        fn screen_size(&self) -> Vec2 {
+            Vec2::new(80, 24)
+        }
+
+        fn print_at(&self, pos: Vec2, text: &str) {
+            self.prints.borrow_mut().push((pos, text.to_string()));
+        }
+
+        fn clear(&self, _: theme::Color) {}
+
+        fn set_color(&self, colors: theme::ColorPair) -> theme::ColorPair {
+            colors
+        }
+
+        fn set_effect(&self, _: theme::Effect) {}
+
+        fn unset_effect(&self, _: theme::Effect) {}
+    }
+
+    fn recorder() -> (Box<Backend>, Prints) {
+        let prints = Rc::new(RefCell::new(Vec::new()));
+        let backend = Recorder {
+            prints: Rc::clone(&prints),
+        };
+        (Box::new(backend), prints)
+    }
+
+    // Draws a full screen of text, with `letter` at (40, 12).
+    fn draw(backend: &mut Backend, letter: &str) {
+        let size = backend.screen_size();
+        backend.clear(theme::Color::TerminalDefault);
+        for y in 0..size.y {
+            backend.print_at(Vec2::new(0, y), &"-".repeat(size.x));
+        }
+        backend.print_at(Vec2::new(40, 12), letter);
+        backend.refresh();
+    }
+
+    fn bytes(prints: &Prints) -> usize {
+        prints.borrow_mut().drain(..).map(|(_, text)| text.len()).sum()
+    }
+
+    #[test]
+    fn test_single_change() {
+        let (mut direct, direct_prints) = recorder();
+        let (inner, buffered_prints) = recorder();
+        let mut buffered = Buffered::new(inner);
+
+        draw(&mut *direct, "a");
+        draw(&mut buffered, "a");
+        assert_eq!(bytes(&direct_prints), 80 * 24 + 1);
+        assert_eq!(bytes(&buffered_prints), 80 * 24);
+
+        draw(&mut *direct, "b");
+        draw(&mut buffered, "b");
+        assert_eq!(bytes(&direct_prints), 80 * 24 + 1);
+        assert_eq!(
+            *buffered_prints.borrow(),
+            vec![(Vec2::new(40, 12), "b".to_string())]
+        );
+
+        // Nothing changed.
+        buffered_prints.borrow_mut().clear();
+        draw(&mut buffered, "b");
+        assert!(buffered_prints.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_wide_change() {
+        let (inner, prints) = recorder();
+        let mut buffered = Buffered::new(inner);
+        draw(&mut buffered, "日");
+        prints.borrow_mut().clear();
+
+        // Replacing the right half reprints the left half too.
+        draw(&mut buffered, "日");
+        buffered.print_at(Vec2::new(41, 12), "x");
+        buffered.refresh();
+        assert_eq!(
+            *prints.borrow(),
+            vec![(Vec2::new(40, 12), " x".to_string())]
+        );
+
+        // Going back to the wide character prints it in one go.
+        draw(&mut buffered, "日");
+        assert_eq!(
+            *prints.borrow(),
+            vec![
+                (Vec2::new(40, 12), " x".to_string()),
+                (Vec2::new(40, 12), "日".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,562 @@
+//! Backend using the pure-rust crossterm library.
+//!
+//! Requires the `crossterm-backend` feature.
+//!
+//! Unlike termion, crossterm also works on Windows.
+#![cfg(feature = "crossterm")]
+
+extern crate crossterm;
+
+use self::crossterm::event::{
+    self as cevent, Event as CEvent, KeyCode as CKeyCode,
+    KeyEvent as CKeyEvent, KeyEventKind as CKeyEventKind,
+    KeyModifiers as CKeyModifiers, MouseButton as CMouseButton,
+    MouseEvent as CMouseEvent, MouseEventKind as CMouseEventKind,
+};
+use self::crossterm::style::{
+    Attribute as CAttribute, Color as CColor, SetAttribute,
+    SetBackgroundColor, SetForegroundColor,
+};
+use self::crossterm::{cursor, terminal, Command};
+use crossbeam_channel::{Receiver, Sender};
+use enumset::EnumSet;
+
+use backend;
+use event::{Event, Key, MouseButton, MouseEvent};
+use theme;
+use vec::Vec2;
+
+use std::cell::{Cell, RefCell};
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+/// Backend using crossterm
+pub struct Backend {
+    // Output for the current frame, written to the terminal on `refresh()`.
+    buffer: RefCell<Vec<u8>>,
+
+    current_style: Cell<theme::ColorPair>,
+    current_effects: Cell<EnumSet<theme::Effect>>,
+    color_support: backend::ColorSupport,
+
+    // Set when the original window title was saved, to restore it later.
+    title_saved: Cell<bool>,
+
+    // How long `InputParser::peek()` waits for input.
+    peek_timeout: Option<Duration>,
+}
+
+/// Default time to wait for more input after an event.
+pub const DEFAULT_PEEK_TIMEOUT: Option<Duration> =
+    Some(Duration::from_millis(10));
+
+struct InputParser {
+    // Inner state required to parse input
+    last_button: Option<MouseButton>,
+
+    peek_timeout: Option<Duration>,
+}
+
+impl InputParser {
+    fn new(peek_timeout: Option<Duration>) -> Self {
+        InputParser {
+            last_button: None,
+            peek_timeout,
+        }
+    }
+
+    fn peek(&mut self) -> Option<Event> {
+        let timeout = self.peek_timeout.unwrap_or(Duration::from_millis(0));
+
+        match cevent::poll(timeout) {
+            Ok(true) => Some(self.next_event()),
+            _ => None,
+        }
+    }
+
+    fn next_event(&mut self) -> Event {
+        match cevent::read() {
+            Ok(event) => self.map_event(event),
+            Err(e) => {
+                warn!("Error reading input: {}", e);
+                Event::Unknown(vec![])
+            }
+        }
+    }
+
+    fn map_event(&mut self, event: CEvent) -> Event {
+        match event {
+            CEvent::Key(key) => map_key(key),
+            CEvent::Mouse(mouse) => self.map_mouse(mouse),
+            CEvent::Paste(text) => Event::Paste(text),
+            CEvent::Resize(_, _) => Event::WindowResize,
+            CEvent::FocusGained => Event::WindowFocus(true),
+            CEvent::FocusLost => Event::WindowFocus(false),
+        }
+    }
+
+    fn map_mouse(&mut self, mouse: CMouseEvent) -> Event {
+        let shift = mouse.modifiers.contains(CKeyModifiers::SHIFT);
+        let event = match mouse.kind {
+            CMouseEventKind::Down(btn) => {
+                let btn = map_mouse_button(btn);
+                self.last_button = Some(btn);
+                MouseEvent::Press(btn)
+            }
+            CMouseEventKind::Up(btn) => {
+                self.last_button = None;
+                MouseEvent::Release(map_mouse_button(btn))
+            }
+            CMouseEventKind::Drag(btn) => {
+                MouseEvent::Hold(map_mouse_button(btn))
+            }
+            // Shift + wheel is commonly used to scroll horizontally.
+            CMouseEventKind::ScrollUp if shift => MouseEvent::WheelLeft,
+            CMouseEventKind::ScrollDown if shift => MouseEvent::WheelRight,
+            CMouseEventKind::ScrollUp => MouseEvent::WheelUp,
+            CMouseEventKind::ScrollDown => MouseEvent::WheelDown,
+            CMouseEventKind::ScrollLeft => MouseEvent::WheelLeft,
+            CMouseEventKind::ScrollRight => MouseEvent::WheelRight,
+            CMouseEventKind::Moved => MouseEvent::Hover,
+        };
+
+        Event::Mouse {
+            event,
+            position: (mouse.column, mouse.row).into(),
+            offset: Vec2::zero(),
+        }
+    }
+}
+
+fn map_mouse_button(button: CMouseButton) -> MouseButton {
+    match button {
+        CMouseButton::Left => MouseButton::Left,
+        CMouseButton::Middle => MouseButton::Middle,
+        CMouseButton::Right => MouseButton::Right,
+    }
+}
+
+fn map_key(key: CKeyEvent) -> Event {
+    // Some platforms (Windows) also report key releases.
+    if key.kind == CKeyEventKind::Release {
+        return Event::Refresh;
+    }
+
+    let ctrl = key.modifiers.contains(CKeyModifiers::CONTROL);
+    let alt = key.modifiers.contains(CKeyModifiers::ALT);
+    let shift = key.modifiers.contains(CKeyModifiers::SHIFT);
+
+    let key = match key.code {
+        CKeyCode::Char(c) if ctrl => return Event::CtrlChar(c),
+        CKeyCode::Char(c) if alt => return Event::AltChar(c),
+        CKeyCode::Char(c) => return Event::Char(c),
+        CKeyCode::BackTab => return Event::Shift(Key::Tab),
+        CKeyCode::Enter => Key::Enter,
+        CKeyCode::Tab => Key::Tab,
+        CKeyCode::Backspace => Key::Backspace,
+        CKeyCode::Esc => Key::Esc,
+        CKeyCode::Left => Key::Left,
+        CKeyCode::Right => Key::Right,
+        CKeyCode::Up => Key::Up,
+        CKeyCode::Down => Key::Down,
+        CKeyCode::Insert => Key::Ins,
+        CKeyCode::Delete => Key::Del,
+        CKeyCode::Home => Key::Home,
+        CKeyCode::End => Key::End,
+        CKeyCode::PageUp => Key::PageUp,
+        CKeyCode::PageDown => Key::PageDown,
+        CKeyCode::Pause => Key::PauseBreak,
+        CKeyCode::KeypadBegin => Key::NumpadCenter,
+        CKeyCode::F(n) if n <= 24 => Key::from_f(n),
+        _ => return Event::Unknown(vec![]),
+    };
+
+    match (ctrl, alt, shift) {
+        (false, false, false) => Event::Key(key),
+        (false, false, true) => Event::Shift(key),
+        (false, true, false) => Event::Alt(key),
+        (false, true, true) => Event::AltShift(key),
+        (true, false, false) => Event::Ctrl(key),
+        (true, false, true) => Event::CtrlShift(key),
+        (true, true, _) => Event::CtrlAlt(key),
+    }
+}
+
+fn effect_attributes(
+    effect: theme::Effect,
+) -> Option<(CAttribute, CAttribute)> {
+    Some(match effect {
+        theme::Effect::Simple => return None,
+        theme::Effect::Reverse => (CAttribute::Reverse, CAttribute::NoReverse),
+        theme::Effect::Bold => (CAttribute::Bold, CAttribute::NormalIntensity),
+        theme::Effect::Italic => (CAttribute::Italic, CAttribute::NoItalic),
+        theme::Effect::Underline => {
+            (CAttribute::Underlined, CAttribute::NoUnderline)
+        }
+        theme::Effect::Strikethrough => {
+            (CAttribute::CrossedOut, CAttribute::NotCrossedOut)
+        }
+        theme::Effect::Blink => (CAttribute::SlowBlink, CAttribute::NoBlink),
+        theme::Effect::Dim => (CAttribute::Dim, CAttribute::NormalIntensity),
+    })
+}
+
+impl Backend {
+    /// Creates a new crossterm-based backend.
+    pub fn init() -> Box<backend::Backend> {
+        Self::init_with_peek_timeout(DEFAULT_PEEK_TIMEOUT)
+    }
+
+    /// Creates a new crossterm-based backend with a custom peek timeout.
+    ///
+    /// See `termion::Options::peek_timeout` for the tradeoff.
+    ///
+    /// `init()` uses `DEFAULT_PEEK_TIMEOUT`.
+    pub fn init_with_peek_timeout(
+        peek_timeout: Option<Duration>,
+    ) -> Box<backend::Backend> {
+        terminal::enable_raw_mode().unwrap();
+
+        let c = Backend {
+            buffer: RefCell::new(Vec::new()),
+            current_style: Cell::new(theme::ColorPair::from_256colors(0, 0)),
+            current_effects: Cell::new(EnumSet::new()),
+            color_support: backend::detect_color_support(),
+            title_saved: Cell::new(false),
+            peek_timeout,
+        };
+
+        c.queue(terminal::EnterAlternateScreen);
+        c.queue(cevent::EnableMouseCapture);
+        c.queue(cevent::EnableBracketedPaste);
+        c.queue(cevent::EnableFocusChange);
+        c.queue(cursor::Hide);
+
+        backend::set_panic_restore(|| {
+            let _ = crossterm::execute!(
+                io::stdout(),
+                SetAttribute(CAttribute::Reset),
+                cursor::Show,
+                cevent::DisableFocusChange,
+                cevent::DisableBracketedPaste,
+                cevent::DisableMouseCapture,
+                terminal::LeaveAlternateScreen
+            );
+            let _ = terminal::disable_raw_mode();
+        });
+
+        Box::new(c)
+    }
+
+    /// Adds the given command to the frame buffer.
+    ///
+    /// Nothing reaches the terminal until `flush()` is called.
+    fn queue<C>(&self, command: C)
+    where
+        C: Command,
+    {
+        crossterm::queue!(self.buffer.borrow_mut(), command).unwrap();
+    }
+
+    /// Sends the frame buffer to the terminal in a single write.
+    fn flush(&mut self) {
+        let buffer = self.buffer.get_mut();
+        let stdout = io::stdout();
+        let mut stdout = stdout.lock();
+        stdout.write_all(buffer).unwrap();
+        stdout.flush().unwrap();
+        buffer.clear();
+    }
+
+    fn apply_colors(&self, colors: theme::ColorPair) {
+        if let Some((front, back)) = map_colors(colors, self.color_support) {
+            self.queue(SetForegroundColor(front));
+            self.queue(SetBackgroundColor(back));
+        }
+    }
+}
+
+impl backend::Backend for Backend {
+    fn finish(&mut self) {
+        if !backend::unset_panic_restore() {
+            return;
+        }
+        if self.title_saved.get() {
+            // Restores the title saved on the terminal's title stack (xterm).
+            self.buffer.borrow_mut().extend_from_slice(b"\x1B[23;0t");
+        }
+        self.queue(cursor::MoveTo(0, 0));
+        self.queue(SetAttribute(CAttribute::Reset));
+        self.queue(terminal::Clear(terminal::ClearType::All));
+        self.queue(cursor::Show);
+        self.queue(cevent::DisableFocusChange);
+        self.queue(cevent::DisableBracketedPaste);
+        self.queue(cevent::DisableMouseCapture);
+        self.queue(terminal::LeaveAlternateScreen);
+        self.flush();
+
+        terminal::disable_raw_mode().unwrap();
+    }
+
+    fn set_title(&self, title: &str) {
+        if !self.title_saved.replace(true) {
+            // Saves the current title on the terminal's title stack (xterm).
+            self.buffer.borrow_mut().extend_from_slice(b"\x1B[22;0t");
+        }
+        self.queue(terminal::SetTitle(title));
+    }
+
+    fn suspend(&mut self) {
+        self.queue(cursor::Show);
+        self.queue(cevent::DisableFocusChange);
+        self.queue(cevent::DisableBracketedPaste);
+        self.queue(cevent::DisableMouseCapture);
+        self.queue(terminal::LeaveAlternateScreen);
+        self.flush();
+
+        terminal::disable_raw_mode().unwrap();
+    }
+
+    fn resume(&mut self) {
+        terminal::enable_raw_mode().unwrap();
+
+        self.queue(terminal::EnterAlternateScreen);
+        self.queue(cevent::EnableMouseCapture);
+        self.queue(cevent::EnableBracketedPaste);
+        self.queue(cevent::EnableFocusChange);
+        self.queue(cursor::Hide);
+        self.flush();
+    }
+
+    fn set_color(&self, color: theme::ColorPair) -> theme::ColorPair {
+        let current_style = self.current_style.get();
+
+        if current_style != color {
+            self.apply_colors(color);
+            self.current_style.set(color);
+        }
+
+        current_style
+    }
+
+    fn set_effect(&self, effect: theme::Effect) {
+        if let Some((on, _)) = effect_attributes(effect) {
+            self.queue(SetAttribute(on));
+        }
+
+        let mut effects = self.current_effects.get();
+        effects.insert(effect);
+        self.current_effects.set(effects);
+    }
+
+    fn unset_effect(&self, effect: theme::Effect) {
+        if let Some((_, off)) = effect_attributes(effect) {
+            self.queue(SetAttribute(off));
+        }
+
+        let mut effects = self.current_effects.get();
+        effects.remove(effect);
+        self.current_effects.set(effects);
+
+        // Bold and faint text are disabled by the same code,
+        // so bring back the other one if it's still active.
+        let other = match effect {
+            theme::Effect::Bold => theme::Effect::Dim,
+            theme::Effect::Dim => theme::Effect::Bold,
+            _ => return,
+        };
+        if effects.contains(other) {
+            self.set_effect(other);
+        }
+    }
+
+    fn has_colors(&self) -> bool {
+        self.color_support != backend::ColorSupport::None
+    }
+
+    fn color_support(&self) -> backend::ColorSupport {
+        self.color_support
+    }
+
+    fn screen_size(&self) -> Vec2 {
+        let (x, y) = terminal::size().unwrap_or((1, 1));
+        (x, y).into()
+    }
+
+    fn clear(&self, color: theme::Color) {
+        self.apply_colors(theme::ColorPair {
+            front: color,
+            back: color,
+        });
+        self.queue(terminal::Clear(terminal::ClearType::All));
+    }
+
+    fn refresh(&mut self) {
+        self.flush();
+    }
+
+    fn print_at(&self, pos: Vec2, text: &str) {
+        self.queue(cursor::MoveTo(pos.x as u16, pos.y as u16));
+        self.queue(crossterm::style::Print(text));
+    }
+
+    fn start_input_thread(
+        &mut self, event_sink: Sender<Option<Event>>,
+        input_request: Receiver<backend::InputRequest>,
+    ) {
+        // Crossterm reports resizes itself, on every platform,
+        // so we don't need a separate resize thread.
+        let mut parser = InputParser::new(self.peek_timeout);
+        thread::spawn(move || {
+            for req in input_request {
+                let event = match req {
+                    backend::InputRequest::Peek => parser.peek(),
+                    backend::InputRequest::Block => Some(parser.next_event()),
+                };
+
+                if event_sink.send(event).is_err() {
+                    return;
+                }
+            }
+        });
+    }
+}
+
+/// Returns the crossterm colors closest to `colors`.
+///
+/// Returns `None` if the terminal has no colors.
+fn map_colors(
+    colors: theme::ColorPair, support: backend::ColorSupport,
+) -> Option<(CColor, CColor)> {
+    if support == backend::ColorSupport::None {
+        return None;
+    }
+
+    Some((
+        map_color(colors.front, support),
+        map_color(colors.back, support),
+    ))
+}
+
+/// Returns the crossterm color closest to `clr`.
+///
+/// Colors the terminal cannot display are downgraded according to `support`.
+fn map_color(clr: theme::Color, support: backend::ColorSupport) -> CColor {
+    match clr {
+        theme::Color::Rgb(..) | theme::Color::RgbLowRes(..)
+            if support == backend::ColorSupport::Ansi16 =>
+        {
+            map_color(clr.to_16colors(), support)
+        }
+        theme::Color::Rgb(..) if support == backend::ColorSupport::Ansi256 => {
+            // `to_256colors` only returns `None` for `TerminalDefault`.
+            CColor::AnsiValue(clr.to_256colors().unwrap())
+        }
+
+        theme::Color::TerminalDefault => CColor::Reset,
+        theme::Color::Dark(theme::BaseColor::Black) => CColor::Black,
+        theme::Color::Dark(theme::BaseColor::Red) => CColor::DarkRed,
+        theme::Color::Dark(theme::BaseColor::Green) => CColor::DarkGreen,
+        theme::Color::Dark(theme::BaseColor::Yellow) => CColor::DarkYellow,
+        theme::Color::Dark(theme::BaseColor::Blue) => CColor::DarkBlue,
+        theme::Color::Dark(theme::BaseColor::Magenta) => CColor::DarkMagenta,
+        theme::Color::Dark(theme::BaseColor::Cyan) => CColor::DarkCyan,
+        theme::Color::Dark(theme::BaseColor::White) => CColor::Grey,
+
+        theme::Color::Light(theme::BaseColor::Black) => CColor::DarkGrey,
+        theme::Color::Light(theme::BaseColor::Red) => CColor::Red,
+        theme::Color::Light(theme::BaseColor::Green) => CColor::Green,
+        theme::Color::Light(theme::BaseColor::Yellow) => CColor::Yellow,
+        theme::Color::Light(theme::BaseColor::Blue) => CColor::Blue,
+        theme::Color::Light(theme::BaseColor::Magenta) => CColor::Magenta,
+        theme::Color::Light(theme::BaseColor::Cyan) => CColor::Cyan,
+        theme::Color::Light(theme::BaseColor::White) => CColor::White,
+
+        theme::Color::Rgb(r, g, b) => CColor::Rgb { r, g, b },
+        theme::Color::RgbLowRes(..) => {
+            CColor::AnsiValue(clr.to_256colors().unwrap())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: CKeyCode, modifiers: CKeyModifiers) -> Event {
+        map_key(CKeyEvent::new(code, modifiers))
+    }
+
+    #[test]
+    fn test_map_colors() {
+        use backend::ColorSupport;
+        use theme::{BaseColor, Color, ColorPair};
+
+        let colors = ColorPair {
+            front: Color::Rgb(255, 0, 0),
+            back: Color::Light(BaseColor::Blue),
+        };
+        assert_eq!(
+            map_colors(colors, ColorSupport::TrueColor),
+            Some((CColor::Rgb { r: 255, g: 0, b: 0 }, CColor::Blue))
+        );
+        assert_eq!(
+            map_colors(colors, ColorSupport::Ansi256),
+            Some((CColor::AnsiValue(196), CColor::Blue))
+        );
+        assert_eq!(
+            map_colors(colors, ColorSupport::Ansi16),
+            Some((CColor::Red, CColor::Blue))
+        );
+        assert_eq!(map_colors(colors, ColorSupport::None), None);
+    }
+
+    #[test]
+    fn test_map_mouse() {
+        let mut parser = InputParser::new(None);
+        let mut mouse = |kind| {
+            parser.map_mouse(CMouseEvent {
+                kind,
+                column: 9,
+                row: 4,
+                modifiers: CKeyModifiers::NONE,
+            })
+        };
+        let event = |event| Event::Mouse {
+            event,
+            position: Vec2::new(9, 4),
+            offset: Vec2::zero(),
+        };
+
+        assert_eq!(mouse(CMouseEventKind::Moved), event(MouseEvent::Hover));
+        assert_eq!(
+            mouse(CMouseEventKind::Drag(CMouseButton::Left)),
+            event(MouseEvent::Hold(MouseButton::Left))
+        );
+    }
+
+    #[test]
+    fn test_map_key() {
+        assert_eq!(
+            key(CKeyCode::Char('a'), CKeyModifiers::NONE),
+            Event::Char('a')
+        );
+        assert_eq!(
+            key(CKeyCode::Char('c'), CKeyModifiers::CONTROL),
+            Event::CtrlChar('c')
+        );
+        assert_eq!(
+            key(CKeyCode::Left, CKeyModifiers::CONTROL | CKeyModifiers::SHIFT),
+            Event::CtrlShift(Key::Left)
+        );
+        assert_eq!(
+            key(CKeyCode::BackTab, CKeyModifiers::SHIFT),
+            Event::Shift(Key::Tab)
+        );
+        assert_eq!(
+            key(CKeyCode::F(13), CKeyModifiers::NONE),
+            Event::Key(Key::F13)
+        );
+    }
+}
@@ -7,7 +7,8 @@ extern crate term_size;
 
 use std::collections::HashMap;
 
-use event::{Event, Key};
+use backend::ColorSupport;
+use event::{Event, Key, MouseEvent};
 use theme::{BaseColor, Color, ColorPair};
 use vec::Vec2;
 
@@ -26,6 +27,17 @@ fn terminal_size() -> Vec2 {
     term_size::dimensions().unwrap_or((0, 0)).into()
 }
 
+/// Turns a vertical wheel event into a horizontal one.
+///
+/// Terminals usually report Shift + wheel this way.
+fn shift_wheel(event: MouseEvent) -> MouseEvent {
+    match event {
+        MouseEvent::WheelUp => MouseEvent::WheelLeft,
+        MouseEvent::WheelDown => MouseEvent::WheelRight,
+        event => event,
+    }
+}
+
 fn split_i32(code: i32) -> Vec<u8> {
     (0..4).map(|i| ((code >> (8 * i)) & 0xFF) as u8).collect()
 }
@@ -74,6 +86,15 @@ where
     }
 }
 
+/// Returns the colors the terminal can display, from its number of colors.
+fn color_support(has_colors: bool, max_colors: i16) -> ColorSupport {
+    match max_colors {
+        _ if !has_colors => ColorSupport::None,
+        n if n >= 256 => ColorSupport::Ansi256,
+        _ => ColorSupport::Ansi16,
+    }
+}
+
 fn find_closest_pair(pair: ColorPair, max_colors: i16) -> (i16, i16) {
     (
         find_closest(pair.front, max_colors),
@@ -123,8 +144,8 @@ fn find_closest(color: Color, max_colors: i16) -> i16 {
             let b = if b > 127 { 1 } else { 0 };
             (r + 2 * g + 4 * b) as i16
         }
-        Color::RgbLowRes(r, g, b) if max_colors >= 256 => {
-            i16::from(16 + 36 * r + 6 * g + b)
+        Color::RgbLowRes(..) if max_colors >= 256 => {
+            i16::from(color.to_256colors().unwrap())
         }
         Color::RgbLowRes(r, g, b) => {
             let r = if r > 2 { 1 } else { 0 };
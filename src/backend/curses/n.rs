@@ -21,7 +21,7 @@ use theme::{Color, ColorPair, Effect};
 use utf8;
 use vec::Vec2;
 
-use self::super::split_i32;
+use self::super::{shift_wheel, split_i32};
 use self::ncurses::mmask_t;
 
 /// Backend using ncurses.
@@ -37,6 +37,9 @@ pub struct Backend {
 
     // The signal hook to receive SIGWINCH (window resize)
     signals: Option<Signals>,
+
+    // Set when the original window title was saved, to restore it later.
+    title_saved: Cell<bool>,
 }
 
 struct InputParser {
@@ -103,10 +106,10 @@ impl InputParser {
         if ncurses::getmouse(&mut mevent as *mut ncurses::MEVENT)
             == ncurses::OK
         {
+            let shift =
+                (mevent.bstate & ncurses::BUTTON_SHIFT as mmask_t) != 0;
             // Currently unused
             let _ctrl = (mevent.bstate & ncurses::BUTTON_CTRL as mmask_t) != 0;
-            let _shift =
-                (mevent.bstate & ncurses::BUTTON_SHIFT as mmask_t) != 0;
             let _alt = (mevent.bstate & ncurses::BUTTON_ALT as mmask_t) != 0;
 
             // Keep the base state, without the modifiers
@@ -153,6 +156,7 @@ impl InputParser {
 
                     // Process single_event
                     on_mouse_event(single_event as i32, |e| {
+                        let e = if shift { shift_wheel(e) } else { e };
                         // Keep one event for later,
                         // send the rest through the channel.
                         if event.is_none() {
@@ -243,11 +247,17 @@ impl Backend {
         // Replacing 1002 with 1003 would give us ANY mouse move.
         write_to_tty(b"\x1B[?1002h").unwrap();
 
+        backend::set_panic_restore(|| {
+            let _ = write_to_tty(b"\x1B[?1002l");
+            ncurses::endwin();
+        });
+
         let c = Backend {
             current_style: Cell::new(ColorPair::from_256colors(0, 0)),
             pairs: RefCell::new(HashMap::new()),
             needs_resize: Arc::new(AtomicBool::new(false)),
             signals,
+            title_saved: Cell::new(false),
         };
 
         Box::new(c)
@@ -320,6 +330,10 @@ impl backend::Backend for Backend {
         ncurses::has_colors()
     }
 
+    fn color_support(&self) -> backend::ColorSupport {
+        super::color_support(ncurses::has_colors(), ncurses::COLORS() as i16)
+    }
+
     fn start_input_thread(
         &mut self, event_sink: Sender<Option<Event>>,
         input_request: Receiver<backend::InputRequest>,
@@ -361,10 +375,36 @@ impl backend::Backend for Backend {
     }
 
     fn finish(&mut self) {
+        if !backend::unset_panic_restore() {
+            return;
+        }
+        if self.title_saved.get() {
+            // Restores the title saved on the terminal's title stack (xterm).
+            write_to_tty(b"\x1B[23;0t").unwrap();
+        }
+        write_to_tty(b"\x1B[?1002l").unwrap();
+        ncurses::endwin();
+    }
+
+    fn set_title(&self, title: &str) {
+        if !self.title_saved.replace(true) {
+            // Saves the current title on the terminal's title stack (xterm).
+            write_to_tty(b"\x1B[22;0t").unwrap();
+        }
+        write_to_tty(format!("\x1B]0;{}\x07", title).as_bytes()).unwrap();
+    }
+
+    fn suspend(&mut self) {
         write_to_tty(b"\x1B[?1002l").unwrap();
         ncurses::endwin();
     }
 
+    fn resume(&mut self) {
+        // Refreshing after `endwin()` restores the program mode.
+        ncurses::refresh();
+        write_to_tty(b"\x1B[?1002h").unwrap();
+    }
+
     fn set_color(&self, colors: ColorPair) -> ColorPair {
         // eprintln!("Color used: {:?}", colors);
         let current = self.current_style.get();
@@ -382,6 +422,10 @@ impl backend::Backend for Backend {
             Effect::Bold => ncurses::A_BOLD(),
             Effect::Italic => ncurses::A_ITALIC(),
             Effect::Underline => ncurses::A_UNDERLINE(),
+            // ncurses has no strikethrough attribute.
+            Effect::Strikethrough => ncurses::A_NORMAL(),
+            Effect::Blink => ncurses::A_BLINK(),
+            Effect::Dim => ncurses::A_DIM(),
         };
         ncurses::attron(style);
     }
@@ -393,6 +437,10 @@ impl backend::Backend for Backend {
             Effect::Bold => ncurses::A_BOLD(),
             Effect::Italic => ncurses::A_ITALIC(),
             Effect::Underline => ncurses::A_UNDERLINE(),
+            // ncurses has no strikethrough attribute.
+            Effect::Strikethrough => ncurses::A_NORMAL(),
+            Effect::Blink => ncurses::A_BLINK(),
+            Effect::Dim => ncurses::A_DIM(),
         };
         ncurses::attroff(style);
     }
@@ -21,7 +21,7 @@ use theme::{Color, ColorPair, Effect};
 use vec::Vec2;
 
 use self::pancurses::mmask_t;
-use super::split_i32;
+use super::{shift_wheel, split_i32};
 
 /// Backend using pancurses.
 pub struct Backend {
@@ -229,7 +229,7 @@ impl InputParser {
             Ok(event) => event,
         };
 
-        let _shift = (mevent.bstate & pancurses::BUTTON_SHIFT as mmask_t) != 0;
+        let shift = (mevent.bstate & pancurses::BUTTON_SHIFT as mmask_t) != 0;
         let _alt = (mevent.bstate & pancurses::BUTTON_ALT as mmask_t) != 0;
         let _ctrl = (mevent.bstate & pancurses::BUTTON_CTRL as mmask_t) != 0;
 
@@ -264,6 +264,7 @@ impl InputParser {
 
                 // Process single_event
                 on_mouse_event(single_event, |e| {
+                    let e = if shift { shift_wheel(e) } else { e };
                     if event.is_none() {
                         event = Some(e);
                     } else {
@@ -317,6 +318,12 @@ impl Backend {
         print!("\x1B[?1002h");
         stdout().flush().expect("could not flush stdout");
 
+        backend::set_panic_restore(|| {
+            print!("\x1B[?1002l");
+            let _ = stdout().flush();
+            pancurses::endwin();
+        });
+
         let c = Backend {
             current_style: Cell::new(ColorPair::from_256colors(0, 0)),
             pairs: RefCell::new(HashMap::new()),
@@ -396,12 +403,38 @@ impl backend::Backend for Backend {
         pancurses::has_colors()
     }
 
+    fn color_support(&self) -> backend::ColorSupport {
+        let colors = pancurses::COLORS() as i16;
+        super::color_support(pancurses::has_colors(), colors)
+    }
+
     fn finish(&mut self) {
+        if !backend::unset_panic_restore() {
+            return;
+        }
         print!("\x1B[?1002l");
         stdout().flush().expect("could not flush stdout");
         pancurses::endwin();
     }
 
+    fn set_title(&self, title: &str) {
+        // Only supported on Windows.
+        pancurses::set_title(title);
+    }
+
+    fn suspend(&mut self) {
+        print!("\x1B[?1002l");
+        stdout().flush().expect("could not flush stdout");
+        pancurses::endwin();
+    }
+
+    fn resume(&mut self) {
+        // Refreshing after `endwin()` restores the program mode.
+        self.window.refresh();
+        print!("\x1B[?1002h");
+        stdout().flush().expect("could not flush stdout");
+    }
+
     fn set_color(&self, colors: ColorPair) -> ColorPair {
         let current = self.current_style.get();
 
@@ -419,6 +452,9 @@ impl backend::Backend for Backend {
             Effect::Bold => pancurses::Attribute::Bold,
             Effect::Italic => pancurses::Attribute::Italic,
             Effect::Underline => pancurses::Attribute::Underline,
+            Effect::Strikethrough => pancurses::Attribute::Strikeout,
+            Effect::Blink => pancurses::Attribute::Blink,
+            Effect::Dim => pancurses::Attribute::Dim,
         };
         self.window.attron(style);
     }
@@ -430,6 +466,9 @@ impl backend::Backend for Backend {
             Effect::Bold => pancurses::Attribute::Bold,
             Effect::Italic => pancurses::Attribute::Italic,
             Effect::Underline => pancurses::Attribute::Underline,
+            Effect::Strikethrough => pancurses::Attribute::Strikeout,
+            Effect::Blink => pancurses::Attribute::Blink,
+            Effect::Dim => pancurses::Attribute::Dim,
         };
         self.window.attroff(style);
     }
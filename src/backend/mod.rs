@@ -13,15 +13,85 @@ use event::Event;
 use theme;
 use vec::Vec2;
 
-#[cfg(unix)]
-mod resize;
+pub mod resize;
+
+pub(crate) mod buffer;
+mod restore;
 
 pub mod dummy;
+pub mod puppet;
 
 pub mod blt;
+pub mod crossterm;
 pub mod curses;
 pub mod termion;
 
+pub use self::restore::install_panic_hook;
+pub(crate) use self::restore::{set_panic_restore, unset_panic_restore};
+
+/// Colors a backend can display.
+///
+/// See [`Backend::color_support()`](trait.Backend.html#method.color_support).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ColorSupport {
+    /// No color at all.
+    None,
+    /// The 16 base colors.
+    ///
+    /// Light colors are drawn as bold dark colors, the usual convention of
+    /// 16 colors terminals like the Linux console. Other colors are
+    /// replaced by the closest base color.
+    Ansi16,
+    /// The 256 colors palette.
+    Ansi256,
+    /// 24-bit colors.
+    TrueColor,
+}
+
+/// Detects color support from the environment.
+///
+/// This looks at `NO_COLOR`, `COLORTERM` and `TERM`.
+#[cfg(any(feature = "termion", feature = "crossterm"))]
+pub(crate) fn detect_color_support() -> ColorSupport {
+    use std::env::var;
+
+    let no_color = var("NO_COLOR").map(|v| !v.is_empty()).unwrap_or(false);
+    let term = var("TERM").ok();
+
+    // The Windows console doesn't set `TERM`, but has true colors since
+    // Windows 10.
+    if cfg!(windows) && term.is_none() && !no_color {
+        return ColorSupport::TrueColor;
+    }
+
+    color_support_from_env(
+        term.as_deref(),
+        var("COLORTERM").ok().as_deref(),
+        no_color,
+    )
+}
+
+#[cfg(any(feature = "termion", feature = "crossterm"))]
+fn color_support_from_env(
+    term: Option<&str>, colorterm: Option<&str>, no_color: bool,
+) -> ColorSupport {
+    if no_color {
+        return ColorSupport::None;
+    }
+
+    let term = match term {
+        None | Some("") | Some("dumb") => return ColorSupport::None,
+        Some(term) => term,
+    };
+
+    match colorterm {
+        Some("truecolor") | Some("24bit") => ColorSupport::TrueColor,
+        _ if term.ends_with("-direct") => ColorSupport::TrueColor,
+        _ if term.contains("256color") => ColorSupport::Ansi256,
+        _ => ColorSupport::Ansi16,
+    }
+}
+
 /// A request for input, sent to the backend.
 pub enum InputRequest {
     /// The backend should respond immediately with an answer, possibly empty.
@@ -39,9 +109,25 @@ pub trait Backend {
     /// This should clear any state in the terminal.
     fn finish(&mut self);
 
+    /// Temporarily gives the terminal back, for example to run a subprocess.
+    ///
+    /// This should restore the terminal to its original state, like
+    /// `finish()`, but in a way that `resume()` can undo.
+    fn suspend(&mut self) {}
+
+    /// Takes the terminal back after a call to `suspend()`.
+    fn resume(&mut self) {}
+
     /// Starts a thread to collect input and send it to the given channel.
     ///
     /// `event_trigger` will receive a value before any event is needed.
+    ///
+    /// The event loop waits on `event_sink` together with callbacks sent
+    /// from other threads, like with [`CbSink`]. When a callback comes
+    /// first, no new request is sent: the event being read is received on a
+    /// later step.
+    ///
+    /// [`CbSink`]: ../struct.CbSink.html
     fn start_input_thread(
         &mut self, event_sink: Sender<Option<Event>>,
         input_request: Receiver<InputRequest>,
@@ -61,12 +147,39 @@ pub trait Backend {
         let _ = input_request;
     }
 
+    /// Sets the title of the terminal window.
+    fn set_title(&self, title: &str) {
+        // Dummy implementation for backends that can't do it.
+        let _ = title;
+    }
+
+    /// Returns the position of the cursor before the backend started.
+    ///
+    /// Returns `None` if the position is unknown.
+    fn cursor_position(&self) -> Option<Vec2> {
+        None
+    }
+
     /// Refresh the screen.
     fn refresh(&mut self);
 
     /// Should return `true` if this backend supports colors.
     fn has_colors(&self) -> bool;
 
+    /// Returns the colors this backend can display.
+    ///
+    /// Colors are adapted to it before they are given to
+    /// [`set_color()`](#method.set_color). The default implementation
+    /// returns `TrueColor` if `has_colors()` is `true`, which leaves the
+    /// colors unchanged.
+    fn color_support(&self) -> ColorSupport {
+        if self.has_colors() {
+            ColorSupport::TrueColor
+        } else {
+            ColorSupport::None
+        }
+    }
+
     /// Returns the screen size.
     fn screen_size(&self) -> Vec2;
 
@@ -87,3 +200,28 @@ pub trait Backend {
     /// Disables the given effect.
     fn unset_effect(&self, effect: theme::Effect);
 }
+
+#[cfg(all(test, any(feature = "termion", feature = "crossterm")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_support_from_env() {
+        let support = color_support_from_env;
+        assert_eq!(support(None, None, false), ColorSupport::None);
+        assert_eq!(support(Some("dumb"), None, false), ColorSupport::None);
+        assert_eq!(support(Some("xterm"), None, false), ColorSupport::Ansi16);
+        assert_eq!(
+            support(Some("xterm-256color"), None, false),
+            ColorSupport::Ansi256
+        );
+        assert_eq!(
+            support(Some("xterm"), Some("truecolor"), false),
+            ColorSupport::TrueColor
+        );
+        assert_eq!(
+            support(Some("xterm-256color"), None, true),
+            ColorSupport::None
+        );
+    }
+}
@@ -0,0 +1,480 @@
+//! Puppet backend, rendering to an in-memory buffer.
+//!
+//! Useful to write tests for views: input events are pushed through a
+//! channel, and the rendered screen can be read back after each refresh.
+use std::cell::{Cell, RefCell};
+use std::cmp::min;
+use std::mem;
+use std::thread;
+
+use crossbeam_channel::{self, Receiver, Sender};
+use enumset::EnumSet;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use backend;
+use event::Event;
+use theme;
+use vec::Vec2;
+
+/// Default size of the puppet screen.
+pub const DEFAULT_SIZE: Vec2 = Vec2 { x: 80, y: 24 };
+
+/// Style of a single cell, as observed by the puppet backend.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ObservedStyle {
+    /// Colors used to print the cell.
+    pub colors: theme::ColorPair,
+    /// Effects active when the cell was printed.
+    pub effects: EnumSet<theme::Effect>,
+}
+
+/// A single cell on the puppet screen.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ObservedCell {
+    /// Grapheme printed in this cell.
+    ///
+    /// This is empty for cells covered by the end of a wide grapheme.
+    pub letter: String,
+    /// Style used to print this cell.
+    pub style: ObservedStyle,
+}
+
+/// Content of the puppet screen.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ObservedScreen {
+    size: Vec2,
+    cells: Vec<ObservedCell>,
+}
+
+impl ObservedScreen {
+    /// Creates a new blank screen of the given size.
+    pub fn new(size: Vec2) -> Self {
+        let blank = ObservedCell {
+            letter: " ".to_string(),
+            style: ObservedStyle {
+                colors: theme::ColorPair {
+                    front: theme::Color::TerminalDefault,
+                    back: theme::Color::TerminalDefault,
+                },
+                effects: EnumSet::new(),
+            },
+        };
+
+        ObservedScreen {
+            size,
+            cells: vec![blank; size.x * size.y],
+        }
+    }
+
+    /// Returns the size of this screen.
+    pub fn size(&self) -> Vec2 {
+        self.size
+    }
+
+    /// Returns the cell at the given position, if it is on the screen.
+    pub fn cell(&self, pos: Vec2) -> Option<&ObservedCell> {
+        self.index(pos).map(|i| &self.cells[i])
+    }
+
+    /// Returns the text content of the given row.
+    pub fn row(&self, y: usize) -> String {
+        let start = y * self.size.x;
+        self.cells[start..start + self.size.x]
+            .iter()
+            .map(|cell| &cell.letter[..])
+            .collect()
+    }
+
+    /// Returns the text content of every row.
+    pub fn rows(&self) -> Vec<String> {
+        (0..self.size.y).map(|y| self.row(y)).collect()
+    }
+
+    fn index(&self, pos: Vec2) -> Option<usize> {
+        if pos.x < self.size.x && pos.y < self.size.y {
+            Some(pos.y * self.size.x + pos.x)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn clear(&mut self, style: ObservedStyle) {
+        for cell in &mut self.cells {
+            cell.letter = " ".to_string();
+            cell.style = style;
+        }
+    }
+
+    pub(crate) fn print(
+        &mut self, pos: Vec2, text: &str, style: ObservedStyle,
+    ) {
+        let mut x = pos.x;
+        for g in text.graphemes(true) {
+            let width = g.width();
+            if pos.y >= self.size.y || x >= self.size.x {
+                return;
+            }
+            if width == 0 {
+                continue;
+            }
+            // Like a terminal, don't print half a double-width character.
+            let g = if x + width > self.size.x { " " } else { g };
+
+            self.erase_wide(Vec2::new(x, pos.y), width);
+            for offset in 0..min(width, self.size.x - x) {
+                let i = pos.y * self.size.x + x + offset;
+                let letter = if offset == 0 { g } else { "" };
+                self.cells[i] = ObservedCell {
+                    letter: letter.to_string(),
+                    style,
+                };
+            }
+            x += width;
+        }
+    }
+
+    // Blanks what remains of double-width characters about to be partly
+    // overwritten by `width` cells at `pos`.
+    fn erase_wide(&mut self, pos: Vec2, width: usize) {
+        let row = pos.y * self.size.x;
+
+        // We start on the second half of a character.
+        if self.cells[row + pos.x].letter.is_empty() && pos.x > 0 {
+            self.cells[row + pos.x - 1].letter = " ".to_string();
+        }
+
+        // We end on the first half of a character.
+        let end = pos.x + width;
+        if end < self.size.x && self.cells[row + end].letter.is_empty() {
+            self.cells[row + end].letter = " ".to_string();
+        }
+    }
+}
+
+/// Backend rendering to an in-memory buffer.
+///
+/// Events pushed to the sender returned by [`input()`] are fed to cursive,
+/// and a copy of the screen is sent to the receiver returned by
+/// [`stream()`] after each refresh, and when the backend is finished or
+/// suspended.
+///
+/// Like a terminal, it draws on an alternate screen by default, see
+/// [`set_alternate_screen()`].
+///
+/// Note that each call to `Cursive::step()` waits for at least one event:
+/// push an `Event::Refresh` to trigger a redraw.
+///
+/// [`input()`]: #method.input
+/// [`stream()`]: #method.stream
+/// [`set_alternate_screen()`]: #method.set_alternate_screen
+///
+/// # Examples
+///
+/// ```rust
+/// # use cursive::{Cursive, backend::puppet, event::Event, views::TextView};
+/// let backend = puppet::Backend::init(Some((20, 5).into()));
+/// let input = backend.input();
+/// let frames = backend.stream();
+///
+/// let mut siv = Cursive::new(move || backend);
+/// siv.add_fullscreen_layer(TextView::new("Hello"));
+///
+/// input.send(Some(Event::Refresh)).unwrap();
+/// siv.step();
+///
+/// let screen = frames.try_iter().last().unwrap();
+/// assert!(screen.row(0).starts_with("Hello"));
+/// ```
+pub struct Backend {
+    screen: RefCell<ObservedScreen>,
+    // The screen not shown: the normal one while cursive is in control.
+    hidden_screen: ObservedScreen,
+    alternate_screen: bool,
+    suspended: bool,
+    current_style: Cell<theme::ColorPair>,
+    current_effects: Cell<EnumSet<theme::Effect>>,
+
+    input_sender: Sender<Option<Event>>,
+    input_receiver: Receiver<Option<Event>>,
+
+    output_sender: Sender<ObservedScreen>,
+    output_receiver: Receiver<ObservedScreen>,
+}
+
+impl Backend {
+    /// Creates a new puppet backend with the given screen size.
+    ///
+    /// Uses `DEFAULT_SIZE` if `size` is `None`.
+    pub fn init(size: Option<Vec2>) -> Box<Backend> {
+        let size = size.unwrap_or(DEFAULT_SIZE);
+        let (input_sender, input_receiver) = crossbeam_channel::unbounded();
+        let (output_sender, output_receiver) = crossbeam_channel::unbounded();
+
+        Box::new(Backend {
+            screen: RefCell::new(ObservedScreen::new(size)),
+            hidden_screen: ObservedScreen::new(size),
+            alternate_screen: true,
+            suspended: false,
+            current_style: Cell::new(theme::ColorPair {
+                front: theme::Color::TerminalDefault,
+                back: theme::Color::TerminalDefault,
+            }),
+            current_effects: Cell::new(EnumSet::new()),
+            input_sender,
+            input_receiver,
+            output_sender,
+            output_receiver,
+        })
+    }
+
+    /// Sets whether to draw on an alternate screen.
+    ///
+    /// Like `termion::Options::alternate_screen`, this is `true` by default:
+    /// when the backend is finished or suspended, the screen shows again
+    /// what it had before cursive, which is blank. Otherwise, the last frame
+    /// stays.
+    pub fn set_alternate_screen(&mut self, alternate_screen: bool) {
+        self.alternate_screen = alternate_screen;
+    }
+
+    /// Returns a sender to push input events to cursive.
+    pub fn input(&self) -> Sender<Option<Event>> {
+        self.input_sender.clone()
+    }
+
+    /// Returns a receiver getting a copy of the screen after each refresh.
+    pub fn stream(&self) -> Receiver<ObservedScreen> {
+        self.output_receiver.clone()
+    }
+
+    /// Returns a copy of the current screen content.
+    pub fn screen(&self) -> ObservedScreen {
+        self.screen.borrow().clone()
+    }
+
+    // Goes to the other screen, if using the alternate one.
+    fn switch_screen(&mut self) {
+        if self.alternate_screen {
+            mem::swap(self.screen.get_mut(), &mut self.hidden_screen);
+        }
+    }
+
+    fn current_style(&self) -> ObservedStyle {
+        ObservedStyle {
+            colors: self.current_style.get(),
+            effects: self.current_effects.get(),
+        }
+    }
+}
+
+impl backend::Backend for Backend {
+    fn finish(&mut self) {
+        if !self.suspended {
+            self.switch_screen();
+        }
+        self.output_sender.send(self.screen()).unwrap();
+    }
+
+    fn suspend(&mut self) {
+        self.suspended = true;
+        self.switch_screen();
+        self.output_sender.send(self.screen()).unwrap();
+    }
+
+    fn resume(&mut self) {
+        self.suspended = false;
+        self.switch_screen();
+    }
+
+    fn refresh(&mut self) {
+        self.output_sender.send(self.screen()).unwrap();
+    }
+
+    fn has_colors(&self) -> bool {
+        true
+    }
+
+    fn screen_size(&self) -> Vec2 {
+        self.screen.borrow().size()
+    }
+
+    fn start_input_thread(
+        &mut self, event_sink: Sender<Option<Event>>,
+        input_requests: Receiver<backend::InputRequest>,
+    ) {
+        let receiver = self.input_receiver.clone();
+
+        thread::spawn(move || {
+            for req in input_requests {
+                let event = match req {
+                    backend::InputRequest::Peek => {
+                        receiver.try_recv().unwrap_or(None)
+                    }
+                    backend::InputRequest::Block => match receiver.recv() {
+                        Ok(event) => event,
+                        Err(_) => return,
+                    },
+                };
+
+                if event_sink.send(event).is_err() {
+                    return;
+                }
+            }
+        });
+    }
+
+    fn print_at(&self, pos: Vec2, text: &str) {
+        let style = self.current_style();
+        self.screen.borrow_mut().print(pos, text, style);
+    }
+
+    fn clear(&self, color: theme::Color) {
+        self.screen.borrow_mut().clear(ObservedStyle {
+            colors: theme::ColorPair {
+                front: color,
+                back: color,
+            },
+            effects: EnumSet::new(),
+        });
+    }
+
+    fn set_color(&self, colors: theme::ColorPair) -> theme::ColorPair {
+        self.current_style.replace(colors)
+    }
+
+    fn set_effect(&self, effect: theme::Effect) {
+        let mut effects = self.current_effects.get();
+        effects.insert(effect);
+        self.current_effects.set(effects);
+    }
+
+    fn unset_effect(&self, effect: theme::Effect) {
+        let mut effects = self.current_effects.get();
+        effects.remove(effect);
+        self.current_effects.set(effects);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use event::Key;
+    use views::{SelectView, TextView};
+    use Cursive;
+    use Printer;
+
+    #[test]
+    fn test_print_wide() {
+        let mut screen = ObservedScreen::new(Vec2::new(4, 1));
+        let style = screen.cells[0].style;
+        screen.print(Vec2::new(0, 0), "a日bc", style);
+
+        assert_eq!(screen.row(0), "a日b");
+        assert_eq!(screen.cell(Vec2::new(2, 0)).unwrap().letter, "");
+        assert_eq!(screen.cell(Vec2::new(4, 0)), None);
+    }
+
+    #[test]
+    fn test_overwrite_wide() {
+        let mut screen = ObservedScreen::new(Vec2::new(5, 1));
+        let style = screen.cells[0].style;
+        screen.print(Vec2::new(0, 0), "日本", style);
+
+        // Overwriting half of a character blanks the other half.
+        screen.print(Vec2::new(1, 0), "a", style);
+        assert_eq!(screen.row(0), " a本 ");
+        screen.print(Vec2::new(2, 0), "b", style);
+        assert_eq!(screen.row(0), " ab  ");
+
+        // No room for the second half.
+        screen.print(Vec2::new(3, 0), "c語", style);
+        assert_eq!(screen.row(0), " abc ");
+        assert_eq!(screen.row(0).width(), 5);
+    }
+
+    #[test]
+    fn test_print_cropped_wide() {
+        let backend = Backend::init(Some(Vec2::new(6, 2)));
+        let theme = theme::load_default();
+        let printer = Printer::new((6, 2), &theme, &*backend);
+
+        printer.print((0, 0), "xxxxxx");
+        printer.print((0, 1), "xxxxxx");
+
+        // "日" is cut by the left edge, "本" by the right one.
+        printer.offset((1, 0)).content_offset((1, 0)).print((0, 0), "日本");
+        printer.cropped((4, 2)).print((0, 1), "a日本");
+
+        let screen = backend.screen();
+        assert_eq!(screen.row(0), "x 本xx");
+        assert_eq!(screen.row(1), "a日 xx");
+    }
+
+    #[test]
+    fn test_dump_screen() {
+        let backend = Backend::init(Some(Vec2::new(10, 2)));
+        let mut siv = Cursive::new(move || backend);
+        siv.add_fullscreen_layer(TextView::new("日本 ok"));
+
+        let rows = siv.dump_screen();
+        assert_eq!(rows.len(), 2);
+        assert!(rows[0].starts_with("日本 ok"));
+        assert_eq!(rows[0].width(), 10);
+    }
+
+    // Returns the rows shown while suspended, then after the exit.
+    fn shown_outside(alternate_screen: bool) -> (String, String) {
+        let mut backend = Backend::init(Some(Vec2::new(5, 1)));
+        backend.set_alternate_screen(alternate_screen);
+        let frames = backend.stream();
+        let mut siv = Cursive::new(move || backend);
+        siv.add_fullscreen_layer(TextView::new("Hi"));
+        siv.refresh();
+
+        let suspended = siv.suspend(|| frames.try_iter().last().unwrap());
+        siv.refresh();
+        assert_eq!(frames.try_iter().last().unwrap().row(0), "Hi   ");
+
+        drop(siv);
+        let exited = frames.try_iter().last().unwrap();
+        (suspended.row(0), exited.row(0))
+    }
+
+    #[test]
+    fn test_alternate_screen() {
+        let blank = "     ".to_string();
+        assert_eq!(shown_outside(true), (blank.clone(), blank));
+    }
+
+    #[test]
+    fn test_inline_screen() {
+        // The last frame stays visible.
+        let hi = "Hi   ".to_string();
+        assert_eq!(shown_outside(false), (hi.clone(), hi));
+    }
+
+    #[test]
+    fn test_select_down() {
+        let backend = Backend::init(Some(Vec2::new(10, 4)));
+        let input = backend.input();
+        let frames = backend.stream();
+
+        let mut siv = Cursive::new(move || backend);
+        siv.add_fullscreen_layer(
+            SelectView::new().with_all_str(vec!["a", "b", "c"]),
+        );
+
+        input.send(Some(Event::Key(Key::Down))).unwrap();
+        input.send(Some(Event::Key(Key::Down))).unwrap();
+        siv.step();
+        input.send(Some(Event::Refresh)).unwrap();
+        siv.step();
+
+        let screen = frames.try_iter().last().unwrap();
+        assert!(screen.row(2).starts_with('c'));
+        let first = screen.cell(Vec2::new(0, 0)).unwrap().style;
+        let third = screen.cell(Vec2::new(0, 2)).unwrap().style;
+        assert_ne!(first, third);
+    }
+}
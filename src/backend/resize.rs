@@ -1,12 +1,21 @@
+//! Helpers to detect terminal resizes, for backends.
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::thread;
+use std::time::Duration;
 
 use crossbeam_channel::{Receiver, Sender};
+#[cfg(unix)]
 use signal_hook::iterator::Signals;
 
 use backend::InputRequest;
 use event::Event;
+use vec::Vec2;
+
+/// Default interval between two checks of the screen size.
+///
+/// Used by `start_resize_poll_thread` when resize signals are unavailable.
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(200);
 
 /// This starts a new thread to listen for SIGWINCH signals
 ///
@@ -25,31 +34,105 @@ pub fn start_resize_thread(
         while resize_running.load(Ordering::Relaxed) {
             // We know it will only contain SIGWINCH signals, so no need to check.
             if signals.wait().count() > 0 {
-                // Tell ncurses about the new terminal size.
-                // Well, do the actual resizing later on, in the main thread.
-                // Ncurses isn't really thread-safe so calling resize_term() can crash
-                // other calls like clear() or refresh().
-                if let Some(ref needs_resize) = needs_resize {
-                    needs_resize.store(true, Ordering::Relaxed);
-                }
-
-                resize_sender.send(Some(Event::WindowResize)).unwrap();
-                // We've sent the message.
-                // This means Cursive was listening, and will now soon be sending a new request.
-                // This means the input thread accepted a request, but hasn't sent a message yet.
-                // So we KNOW the input thread is not waiting for a new request.
-
-                // We sent an event for free, so pay for it now by consuming a request
-                while let Ok(InputRequest::Peek) = resize_requests.recv() {
-                    // At this point Cursive will now listen for input.
-                    // There is a chance the input thread will send his event before us.
-                    // But without some extra atomic flag, it'd be hard to know.
-                    // So instead, keep sending `None`
-
-                    // Repeat until we receive a blocking call
-                    resize_sender.send(None).unwrap();
-                }
+                notify_resize(&resize_sender, &resize_requests, &needs_resize);
             }
         }
     });
 }
+
+/// This starts a new thread to detect resizes by polling the screen size.
+///
+/// This is a fallback for when resize signals are unavailable.
+///
+/// As long as `resize_running` is true, it will call `screen_size` every
+/// `interval`, and behave like `start_resize_thread` when the size changes.
+pub fn start_resize_poll_thread<F>(
+    interval: Duration, screen_size: F, resize_sender: Sender<Option<Event>>,
+    resize_requests: Receiver<InputRequest>, resize_running: Arc<AtomicBool>,
+    needs_resize: Option<Arc<AtomicBool>>,
+) where
+    F: Fn() -> Vec2 + Send + 'static,
+{
+    thread::spawn(move || {
+        let mut size = screen_size();
+
+        while resize_running.load(Ordering::Relaxed) {
+            thread::sleep(interval);
+
+            let new_size = screen_size();
+            if new_size != size {
+                size = new_size;
+                notify_resize(&resize_sender, &resize_requests, &needs_resize);
+            }
+        }
+    });
+}
+
+/// Reports a resize to cursive.
+fn notify_resize(
+    resize_sender: &Sender<Option<Event>>,
+    resize_requests: &Receiver<InputRequest>,
+    needs_resize: &Option<Arc<AtomicBool>>,
+) {
+    // Tell ncurses about the new terminal size.
+    // Well, do the actual resizing later on, in the main thread.
+    // Ncurses isn't really thread-safe so calling resize_term() can crash
+    // other calls like clear() or refresh().
+    if let Some(ref needs_resize) = *needs_resize {
+        needs_resize.store(true, Ordering::Relaxed);
+    }
+
+    resize_sender.send(Some(Event::WindowResize)).unwrap();
+    // We've sent the message.
+    // This means Cursive was listening, and will now soon be sending a new request.
+    // This means the input thread accepted a request, but hasn't sent a message yet.
+    // So we KNOW the input thread is not waiting for a new request.
+
+    // We sent an event for free, so pay for it now by consuming a request
+    while let Ok(InputRequest::Peek) = resize_requests.recv() {
+        // At this point Cursive will now listen for input.
+        // There is a chance the input thread will send his event before us.
+        // But without some extra atomic flag, it'd be hard to know.
+        // So instead, keep sending `None`
+
+        // Repeat until we receive a blocking call
+        resize_sender.send(None).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossbeam_channel;
+    use std::sync::atomic::AtomicUsize;
+
+    #[test]
+    fn test_poll_resize() {
+        let running = Arc::new(AtomicBool::new(true));
+        let (event_sink, events) = crossbeam_channel::unbounded();
+        let (requests, request_source) = crossbeam_channel::unbounded();
+
+        // The screen grows after the first call.
+        let calls = AtomicUsize::new(0);
+        let screen_size = move || match calls.fetch_add(1, Ordering::Relaxed) {
+            0 => Vec2::new(80, 24),
+            _ => Vec2::new(100, 24),
+        };
+
+        start_resize_poll_thread(
+            Duration::from_millis(1),
+            screen_size,
+            event_sink,
+            request_source,
+            Arc::clone(&running),
+            None,
+        );
+
+        let event = events.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(event, Some(Event::WindowResize));
+
+        // Let the thread go back to polling, then stop it.
+        requests.send(InputRequest::Block).unwrap();
+        running.store(false, Ordering::Relaxed);
+    }
+}
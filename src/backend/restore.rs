@@ -0,0 +1,144 @@
+//! Restores the terminal when the application panics.
+use std::panic;
+use std::sync::Mutex;
+use std::thread::{self, ThreadId};
+
+type Restore = Fn() + Send;
+
+lazy_static! {
+    // Set by the running backend to give the terminal back, with the thread
+    // running the UI.
+    //
+    // The panic hook takes it, so it only runs once.
+    static ref PANIC_RESTORE: Mutex<Option<(ThreadId, Box<Restore>)>> =
+        Mutex::new(None);
+}
+
+/// Sets how to restore the terminal if the application panics.
+///
+/// Backends call this when they initialize the terminal, from the thread
+/// running the UI.
+pub(crate) fn set_panic_restore<F>(restore: F)
+where
+    F: Fn() + Send + 'static,
+{
+    if let Ok(mut current) = PANIC_RESTORE.lock() {
+        *current = Some((thread::current().id(), Box::new(restore)));
+    }
+}
+
+/// Forgets the function given to `set_panic_restore()`.
+///
+/// Backends call this when they finish. Returns `false` if the panic hook
+/// already restored the terminal, in which case there is nothing left to
+/// clean up: clearing the screen now would erase the panic message.
+pub(crate) fn unset_panic_restore() -> bool {
+    PANIC_RESTORE
+        .lock()
+        .ok()
+        .and_then(|mut current| current.take())
+        .is_some()
+}
+
+/// Installs a panic hook that gives the terminal back before the panic is
+/// reported.
+///
+/// Without it, a panic prints its message while the terminal is still in
+/// raw mode, usually on the alternate screen: the message is garbled, then
+/// lost when `Cursive` is dropped and the backend clears the screen.
+///
+/// When the thread running the UI panics, the new hook shows the cursor,
+/// resets the colors, leaves the alternate screen and raw mode, then calls
+/// the previous hook. This doesn't rely on `Cursive` being dropped. Once the
+/// terminal is restored, dropping `Cursive` leaves it as it is.
+///
+/// Panics on other threads only call the previous hook: the UI keeps
+/// running.
+///
+/// This is opt-in: call it once, after setting any other panic hook, and
+/// before creating the `Cursive` root.
+///
+/// Note that the hook runs when the panic happens, before it is caught: with
+/// `std::panic::catch_unwind`, the terminal is already restored when the
+/// panic is caught. The `Cursive` root can't draw anymore, so it should only
+/// be dropped.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use cursive::Cursive;
+/// cursive::backend::install_panic_hook();
+///
+/// let mut siv = Cursive::default();
+/// siv.run();
+/// ```
+pub fn install_panic_hook() {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if let Some(restore) = take_restore() {
+            restore();
+        }
+        previous(info);
+    }));
+}
+
+// Takes the function given to `set_panic_restore()`, if it was given by the
+// current thread.
+fn take_restore() -> Option<Box<Restore>> {
+    // Another thread may hold the lock if it's panicking too.
+    let mut current = PANIC_RESTORE.try_lock().ok()?;
+    match *current {
+        Some((id, _)) if id == thread::current().id() => (),
+        _ => return None,
+    }
+    current.take().map(|(_, restore)| restore)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_panic_hook() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let default = Arc::new(panic::take_hook());
+        {
+            // Other tests may panic meanwhile: only record this thread.
+            let events = Arc::clone(&events);
+            let default = Arc::clone(&default);
+            let id = thread::current().id();
+            panic::set_hook(Box::new(move |info| {
+                if thread::current().id() == id {
+                    events.lock().unwrap().push("previous hook");
+                } else {
+                    default(info);
+                }
+            }));
+        }
+        install_panic_hook();
+        {
+            let events = Arc::clone(&events);
+            set_panic_restore(move || {
+                events.lock().unwrap().push("restore");
+            });
+        }
+
+        // Other threads leave the terminal alone.
+        assert!(thread::spawn(|| panic!("elsewhere")).join().is_err());
+        assert!(events.lock().unwrap().is_empty());
+
+        assert!(panic::catch_unwind(|| panic!("oops")).is_err());
+        assert!(panic::catch_unwind(|| panic!("again")).is_err());
+
+        let _ = panic::take_hook();
+        panic::set_hook(Box::new(move |info| default(info)));
+
+        // The terminal is only restored once, before the previous hook.
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec!["restore", "previous hook", "previous hook"]
+        );
+        assert!(!unset_panic_restore());
+    }
+}
@@ -10,9 +10,8 @@ use self::termion::event::Event as TEvent;
 use self::termion::event::Key as TKey;
 use self::termion::event::MouseButton as TMouseButton;
 use self::termion::event::MouseEvent as TMouseEvent;
-use self::termion::input::{MouseTerminal, TermRead};
+use self::termion::input::{MouseTerminal, TermReadEventsAndRaw};
 use self::termion::raw::{IntoRawMode, RawTerminal};
-use self::termion::screen::AlternateScreen;
 use self::termion::style as tstyle;
 use crossbeam_channel::{self, Receiver, Sender};
 use libc;
@@ -20,47 +19,348 @@ use libc;
 #[cfg(unix)]
 use signal_hook::iterator::Signals;
 
-use backend;
+use backend::{self, ColorSupport};
 use event::{Event, Key, MouseButton, MouseEvent};
 use theme;
 use vec::Vec2;
 
-use std::cell::Cell;
-use std::io::{Stdout, Write};
+use std::cell::{Cell, RefCell};
+use std::fmt::Display;
+use std::io::{self, Read, Stdout, Write};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
 use std::thread;
+use std::time::{Duration, Instant};
+
+type Terminal = MouseTerminal<RawTerminal<Stdout>>;
+
+/// Where the backend writes its output.
+trait Output: Write {
+    /// Leaves raw mode, until `activate_raw_mode()` is called.
+    fn suspend_raw_mode(&self) -> io::Result<()>;
+
+    /// Enters raw mode again.
+    fn activate_raw_mode(&self) -> io::Result<()>;
+}
+
+impl Output for Terminal {
+    fn suspend_raw_mode(&self) -> io::Result<()> {
+        (**self).suspend_raw_mode()
+    }
+
+    fn activate_raw_mode(&self) -> io::Result<()> {
+        (**self).activate_raw_mode()
+    }
+}
 
 /// Backend using termion
 pub struct Backend {
-    terminal: AlternateScreen<MouseTerminal<RawTerminal<Stdout>>>,
+    terminal: Box<Output>,
+
+    // Output for the current frame, written to the terminal on `refresh()`.
+    buffer: RefCell<Vec<u8>>,
+
     current_style: Cell<theme::ColorPair>,
+    current_effects: Cell<theme::EffectSet>,
+    color_support: ColorSupport,
+
+    // Set when the original window title was saved, to restore it later.
+    title_saved: Cell<bool>,
+
+    options: Options,
+
+    // Position of the cursor before we took over, restored on exit when
+    // using the alternate screen.
+    initial_cursor: Option<Vec2>,
+
+    // Stops the input thread from reading stdin while suspended.
+    input_pause: InputPause,
+}
+
+/// Sequence sent by the terminal before pasted text.
+const PASTE_START: &[u8] = b"\x1B[200~";
+/// Sequence sent by the terminal after pasted text.
+const PASTE_END: &[u8] = b"\x1B[201~";
+/// Maximum delay to wait for the rest of a paste.
+const PASTE_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Enables mouse input, including drag events and other mouse moves (`1003`).
+///
+/// Includes SGR extended coordinates (`1006`), so clicks past column 223 are
+/// reported correctly.
+const MOUSE_ENABLE: &str =
+    "\x1B[?1000h\x1B[?1002h\x1B[?1003h\x1B[?1015h\x1B[?1006h";
+/// Disables mouse input enabled with `MOUSE_ENABLE`.
+const MOUSE_DISABLE: &str =
+    "\x1B[?1006l\x1B[?1015l\x1B[?1003l\x1B[?1002l\x1B[?1000l";
+
+/// Asks the terminal to report focus changes.
+const FOCUS_ENABLE: &str = "\x1B[?1004h";
+/// Disables focus reporting enabled with `FOCUS_ENABLE`.
+const FOCUS_DISABLE: &str = "\x1B[?1004l";
+
+/// Saves the window title on the terminal's title stack (xterm).
+const TITLE_PUSH: &str = "\x1B[22;0t";
+/// Restores the window title saved with `TITLE_PUSH`.
+const TITLE_POP: &str = "\x1B[23;0t";
+
+/// Options for the termion backend.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Options {
+    /// How long to wait for more input after an event.
+    ///
+    /// After each event, cursive looks for more pending input before
+    /// redrawing the screen, waiting up to this long for it. A longer
+    /// timeout batches more input on slow links (like SSH), at the cost of
+    /// a slower redraw. With `None`, only input already available is
+    /// batched; cursive then sleeps until the next event or resize, which
+    /// uses the least CPU.
+    ///
+    /// Defaults to 10ms.
+    pub peek_timeout: Option<Duration>,
+
+    /// If set, check the screen size this often to detect resizes.
+    ///
+    /// This is a fallback for when `SIGWINCH` is not available, or doesn't
+    /// arrive (as in some containers). Shorter intervals detect resizes
+    /// faster, but use more CPU.
+    ///
+    /// Defaults to `None` on unix, and to
+    /// `resize::DEFAULT_POLL_INTERVAL` elsewhere.
+    pub resize_poll_interval: Option<Duration>,
+
+    /// If `true`, draw on the alternate screen.
+    ///
+    /// The alternate screen is restored with its original content on exit,
+    /// as with most full-screen applications. Without it, cursive draws on
+    /// the normal screen, and the last frame stays visible after exit, with
+    /// the cursor moved below it.
+    ///
+    /// Defaults to `true`.
+    pub alternate_screen: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        let resize_poll_interval = if cfg!(unix) {
+            None
+        } else {
+            Some(backend::resize::DEFAULT_POLL_INTERVAL)
+        };
+
+        Options {
+            peek_timeout: Some(Duration::from_millis(10)),
+            resize_poll_interval,
+            alternate_screen: true,
+        }
+    }
+}
+
+/// Maximum delay to wait for the cursor position report.
+#[cfg(unix)]
+const CURSOR_QUERY_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Maximum delay between two presses to count as a double click.
+pub const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+
+/// Counts consecutive clicks to detect double and triple clicks.
+struct ClickCounter {
+    // Button, position and time of the last press.
+    last_press: Option<(MouseButton, Vec2, Instant)>,
+    count: usize,
+    interval: Duration,
+}
+
+impl ClickCounter {
+    fn new(interval: Duration) -> Self {
+        ClickCounter {
+            last_press: None,
+            count: 0,
+            interval,
+        }
+    }
+
+    /// Registers a new press, and returns the number of consecutive clicks.
+    ///
+    /// This goes from 1 to 3, then starts again from 1.
+    fn press(
+        &mut self, button: MouseButton, position: Vec2, now: Instant,
+    ) -> usize {
+        let consecutive = match self.last_press {
+            Some((last_button, last_position, last_time)) => {
+                last_button == button
+                    && now.duration_since(last_time) <= self.interval
+                    && position.x.max(last_position.x)
+                        - position.x.min(last_position.x)
+                        <= 1
+                    && position.y == last_position.y
+            }
+            None => false,
+        };
+
+        self.count = if consecutive && self.count < 3 {
+            self.count + 1
+        } else {
+            1
+        };
+        self.last_press = Some((button, position, now));
+
+        self.count
+    }
+}
+
+/// Lets the input thread stop reading stdin, for example while a child
+/// process uses the terminal.
+#[derive(Clone)]
+struct InputPause {
+    state: Arc<(Mutex<bool>, Condvar)>,
+}
+
+impl InputPause {
+    fn new() -> Self {
+        InputPause {
+            state: Arc::new((Mutex::new(false), Condvar::new())),
+        }
+    }
+
+    /// Locks the state, `true` while reading is paused.
+    ///
+    /// The input thread holds the lock while it reads.
+    fn lock(&self) -> MutexGuard<'_, bool> {
+        self.state.0.lock().unwrap()
+    }
+
+    /// Pauses reading.
+    ///
+    /// If the input thread is reading an event, this waits until it's done.
+    fn pause(&self) {
+        *self.lock() = true;
+    }
+
+    fn resume(&self) {
+        *self.lock() = false;
+        self.state.1.notify_all();
+    }
+
+    /// Blocks while reading is paused.
+    fn wait(&self) {
+        let mut paused = self.lock();
+        while *paused {
+            paused = self.state.1.wait(paused).unwrap();
+        }
+    }
+}
+
+/// Unbuffered stdin.
+///
+/// `io::stdin()` reads as much as it can, so input already read would not
+/// show up when polling stdin.
+struct RawStdin;
+
+impl Read for RawStdin {
+    #[cfg(unix)]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        // termion tells a lone `Esc` from an escape sequence by reading two
+        // bytes at once. Read more only after an `Esc`, so no event is left
+        // in termion's own buffer.
+        let mut read = read_stdin(&mut buf[..1])?;
+        if read == 1 && buf[0] == 0x1B && buf.len() > 1 && stdin_ready(0) {
+            read += read_stdin(&mut buf[1..2])?;
+        }
+        Ok(read)
+    }
+
+    #[cfg(not(unix))]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        io::stdin().read(buf)
+    }
+}
+
+/// Returns `true` if stdin has input within `timeout` milliseconds.
+///
+/// A negative `timeout` waits forever.
+#[cfg(unix)]
+fn stdin_ready(timeout: libc::c_int) -> bool {
+    let mut fd = libc::pollfd {
+        fd: libc::STDIN_FILENO,
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    unsafe { libc::poll(&mut fd, 1, timeout) > 0 }
+}
+
+#[cfg(not(unix))]
+fn stdin_ready(_: i32) -> bool {
+    true
+}
+
+/// Reads directly from the stdin file descriptor.
+#[cfg(unix)]
+fn read_stdin(buf: &mut [u8]) -> io::Result<usize> {
+    let read = unsafe {
+        libc::read(
+            libc::STDIN_FILENO,
+            buf.as_mut_ptr() as *mut libc::c_void,
+            buf.len(),
+        )
+    };
+    if read < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(read as usize)
+    }
 }
 
 struct InputParser {
     // Inner state required to parse input
     last_button: Option<MouseButton>,
+    clicks: ClickCounter,
+
+    // Event to send before reading more input.
+    input_buffer: Option<Event>,
 
     event_due: bool,
     requests: Sender<()>,
-    input: Receiver<TEvent>,
+    // Events, with the raw bytes they were parsed from.
+    input: Receiver<(TEvent, Vec<u8>)>,
+
+    peek_timeout: Option<Duration>,
+
+    // Set when the terminal is resized.
+    resized: Arc<AtomicBool>,
 }
 
 impl InputParser {
     // Creates a non-blocking abstraction over the usual blocking input
-    fn new() -> Self {
+    fn new(
+        peek_timeout: Option<Duration>, pause: InputPause,
+        resized: Arc<AtomicBool>,
+    ) -> Self {
         let (input_sender, input_receiver) = crossbeam_channel::bounded(0);
         let (request_sender, request_receiver) = crossbeam_channel::bounded(0);
 
         // This thread will stop after an event when `InputParser` is dropped.
         thread::spawn(move || {
-            let stdin = ::std::io::stdin();
-            let stdin = stdin.lock();
-            let mut events = stdin.events();
+            let mut events = RawStdin.events_and_raw();
 
             for _ in request_receiver {
-                let event: Result<TEvent, ::std::io::Error> =
-                    events.next().unwrap();
+                let event: Result<(TEvent, Vec<u8>), io::Error> = loop {
+                    pause.wait();
+                    if !stdin_ready(-1) {
+                        continue;
+                    }
+
+                    // We may have been paused while waiting: the input is
+                    // then for whoever has the terminal now.
+                    let paused = pause.lock();
+                    if !*paused {
+                        break events.next().unwrap();
+                    }
+                };
 
                 if input_sender.send(event.unwrap()).is_err() {
                     return;
@@ -68,11 +368,30 @@ impl InputParser {
             }
         });
 
+        InputParser::with_input(
+            input_receiver,
+            request_sender,
+            peek_timeout,
+            resized,
+        )
+    }
+
+    /// Creates a parser for the events sent on `input`.
+    ///
+    /// `requests` is notified each time an event is wanted.
+    fn with_input(
+        input: Receiver<(TEvent, Vec<u8>)>, requests: Sender<()>,
+        peek_timeout: Option<Duration>, resized: Arc<AtomicBool>,
+    ) -> Self {
         InputParser {
             last_button: None,
-            input: input_receiver,
-            requests: request_sender,
+            clicks: ClickCounter::new(DOUBLE_CLICK_INTERVAL),
+            input_buffer: None,
+            input,
+            requests,
             event_due: false,
+            peek_timeout,
+            resized,
         }
     }
 
@@ -87,33 +406,87 @@ impl InputParser {
     }
 
     fn peek(&mut self) -> Option<Event> {
-        self.request();
+        if let Some(event) = self.input_buffer.take() {
+            return Some(event);
+        }
 
-        let timeout = ::std::time::Duration::from_millis(10);
+        self.request();
 
-        let input = select! {
-            recv(self.input) -> input => {
-                input
-            }
-            recv(crossbeam_channel::after(timeout)) -> _ => return None,
+        let input = match self.peek_timeout {
+            Some(timeout) => select! {
+                recv(self.input) -> input => {
+                    input.unwrap()
+                }
+                recv(crossbeam_channel::after(timeout)) -> _ => return None,
+            },
+            None => match self.input.try_recv() {
+                Ok(input) => input,
+                Err(_) => return None,
+            },
         };
 
         // We got what we came for.
         self.event_due = false;
-        Some(self.map_key(input.unwrap()))
+        Some(self.map_key(input.0, input.1))
     }
 
     fn next_event(&mut self) -> Event {
+        if let Some(event) = self.input_buffer.take() {
+            return event;
+        }
+
         self.request();
 
-        let input = self.input.recv().unwrap();
+        let (input, raw) = self.input.recv().unwrap();
         self.event_due = false;
-        self.map_key(input)
+        self.map_key(input, raw)
+    }
+
+    /// Reads pasted text, until the end of the bracketed paste.
+    ///
+    /// The terminal may not send the end of a paste interrupted by a
+    /// resize: the paste then ends with the input received before the
+    /// resize, or when the input stops for `PASTE_TIMEOUT`. Any input left
+    /// is then read as usual.
+    fn read_paste(&mut self) -> Event {
+        let mut text = Vec::new();
+
+        while !self.resized.load(Ordering::Relaxed) {
+            self.request();
+
+            let (input, raw) = select! {
+                recv(self.input) -> input => input.unwrap(),
+                recv(crossbeam_channel::after(PASTE_TIMEOUT)) -> _ => break,
+            };
+            self.event_due = false;
+
+            match input {
+                TEvent::Unsupported(ref bytes) if bytes == PASTE_END => break,
+                // Starting over, maybe after an interrupted paste.
+                TEvent::Unsupported(ref bytes) if bytes == PASTE_START => (),
+                // termion reads `\r` as `\n`.
+                TEvent::Key(TKey::Char(c)) => {
+                    let mut buf = [0; 4];
+                    text.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+                }
+                // Control characters and escape sequences are kept as is.
+                _ => text.extend_from_slice(&raw),
+            }
+        }
+
+        Event::Paste(String::from_utf8_lossy(&text).into_owned())
     }
 
-    fn map_key(&mut self, event: TEvent) -> Event {
+    /// Converts a termion event, parsed from `raw`.
+    fn map_key(&mut self, event: TEvent, raw: Vec<u8>) -> Event {
         match event {
-            TEvent::Unsupported(bytes) => Event::Unknown(bytes),
+            TEvent::Unsupported(ref bytes) if bytes == PASTE_START => {
+                self.resized.store(false, Ordering::Relaxed);
+                self.read_paste()
+            }
+            TEvent::Unsupported(bytes) => {
+                parse_unsupported(&bytes).unwrap_or(Event::Unknown(bytes))
+            }
             TEvent::Key(TKey::Esc) => Event::Key(Key::Esc),
             TEvent::Key(TKey::Backspace) => Event::Key(Key::Backspace),
             TEvent::Key(TKey::Left) => Event::Key(Key::Left),
@@ -126,12 +499,11 @@ impl InputParser {
             TEvent::Key(TKey::PageDown) => Event::Key(Key::PageDown),
             TEvent::Key(TKey::Delete) => Event::Key(Key::Del),
             TEvent::Key(TKey::Insert) => Event::Key(Key::Ins),
-            TEvent::Key(TKey::F(i)) if i < 12 => Event::Key(Key::from_f(i)),
-            TEvent::Key(TKey::F(j)) => Event::Unknown(vec![j]),
+            TEvent::Key(TKey::F(i)) if i <= 24 => Event::Key(Key::from_f(i)),
+            TEvent::Key(TKey::BackTab) => Event::Shift(Key::Tab),
             TEvent::Key(TKey::Char('\n')) => Event::Key(Key::Enter),
             TEvent::Key(TKey::Char('\t')) => Event::Key(Key::Tab),
             TEvent::Key(TKey::Char(c)) => Event::Char(c),
-            TEvent::Key(TKey::Ctrl('c')) => Event::Exit,
             TEvent::Key(TKey::Ctrl(c)) => Event::CtrlChar(c),
             TEvent::Key(TKey::Alt(c)) => Event::AltChar(c),
             TEvent::Mouse(TMouseEvent::Press(btn, x, y)) => {
@@ -151,6 +523,24 @@ impl InputParser {
 
                 if let MouseEvent::Press(btn) = event {
                     self.last_button = Some(btn);
+
+                    // The press itself is sent first, followed by the
+                    // multi-click event if any.
+                    let multi_click = match self.clicks.press(
+                        btn,
+                        position,
+                        Instant::now(),
+                    ) {
+                        2 => Some(MouseEvent::DoubleClick(btn)),
+                        3 => Some(MouseEvent::TripleClick(btn)),
+                        _ => None,
+                    };
+
+                    self.input_buffer = multi_click.map(|event| Event::Mouse {
+                        event,
+                        position,
+                        offset: Vec2::zero(),
+                    });
                 }
 
                 Event::Mouse {
@@ -181,95 +571,337 @@ impl InputParser {
                     offset: Vec2::zero(),
                 }
             }
-            _ => Event::Unknown(vec![]),
+            _ => Event::Unknown(raw),
         }
     }
 }
 
-trait Effectable {
-    fn on(&self);
-    fn off(&self);
+/// Returns the escape sequence changing the `from` colors into `to`.
+///
+/// Only the colors that changed are written.
+fn color_transition(
+    from: theme::ColorPair, to: theme::ColorPair, support: ColorSupport,
+) -> String {
+    let mut codes = String::new();
+    if support == ColorSupport::None {
+        return codes;
+    }
+
+    if from.front != to.front {
+        let fg = with_color(&to.front, support, |c| tcolor::Fg(c).to_string());
+        codes.push_str(&fg);
+    }
+    if from.back != to.back {
+        let bg = with_color(&to.back, support, |c| tcolor::Bg(c).to_string());
+        codes.push_str(&bg);
+    }
+    codes
+}
+
+/// Returns the SGR parameters enabling and disabling `effect`.
+fn sgr_codes(effect: theme::Effect) -> Option<(u8, u8)> {
+    Some(match effect {
+        theme::Effect::Simple => return None,
+        theme::Effect::Bold => (1, 22),
+        theme::Effect::Dim => (2, 22),
+        theme::Effect::Italic => (3, 23),
+        theme::Effect::Underline => (4, 24),
+        theme::Effect::Blink => (5, 25),
+        theme::Effect::Reverse => (7, 27),
+        theme::Effect::Strikethrough => (9, 29),
+    })
 }
 
-impl Effectable for theme::Effect {
-    fn on(&self) {
-        match *self {
-            theme::Effect::Simple => (),
-            theme::Effect::Reverse => print!("{}", tstyle::Invert),
-            theme::Effect::Bold => print!("{}", tstyle::Bold),
-            theme::Effect::Italic => print!("{}", tstyle::Italic),
-            theme::Effect::Underline => print!("{}", tstyle::Underline),
+/// Returns the escape sequence changing the `from` effects into `to`.
+///
+/// Bold and faint text are disabled by the same code: when only one of them
+/// is removed, the other one is enabled again.
+fn effect_transition(from: theme::EffectSet, to: theme::EffectSet) -> String {
+    let mut codes = Vec::new();
+    let mut push = |code: u8| {
+        if !codes.contains(&code) {
+            codes.push(code);
         }
-    }
+    };
 
-    fn off(&self) {
-        match *self {
-            theme::Effect::Simple => (),
-            theme::Effect::Reverse => print!("{}", tstyle::NoInvert),
-            theme::Effect::Bold => print!("{}", tstyle::NoBold),
-            theme::Effect::Italic => print!("{}", tstyle::NoItalic),
-            theme::Effect::Underline => print!("{}", tstyle::NoUnderline),
+    let mut on = to.difference(from);
+    for effect in from.difference(to).iter() {
+        if let Some((_, off)) = sgr_codes(effect) {
+            push(off);
+            if off == 22 {
+                let intensity = theme::Effect::Bold | theme::Effect::Dim;
+                on = on.union(to.intersection(intensity));
+            }
+        }
+    }
+    for effect in on.iter() {
+        if let Some((code, _)) = sgr_codes(effect) {
+            push(code);
         }
     }
+
+    if codes.is_empty() {
+        return String::new();
+    }
+    let codes: Vec<_> = codes.iter().map(u8::to_string).collect();
+    format!("\x1B[{}m", codes.join(";"))
 }
 
 impl Backend {
     /// Creates a new termion-based backend.
     pub fn init() -> Box<backend::Backend> {
-        print!("{}", termion::cursor::Hide);
+        Self::init_with_options(Options::default())
+    }
 
-        // TODO: lock stdout
-        let terminal = AlternateScreen::from(MouseTerminal::from(
-            ::std::io::stdout().into_raw_mode().unwrap(),
-        ));
+    /// Creates a new termion-based backend with the given options.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use cursive::Cursive;
+    /// use cursive::backend::termion::{Backend, Options};
+    /// use std::time::Duration;
+    ///
+    /// let siv = Cursive::new(|| {
+    ///     Backend::init_with_options(Options {
+    ///         peek_timeout: Some(Duration::from_millis(50)),
+    ///         ..Options::default()
+    ///     })
+    /// });
+    /// ```
+    pub fn init_with_options(options: Options) -> Box<backend::Backend> {
+        let color_support = backend::detect_color_support();
 
-        let c = Backend {
-            terminal: terminal,
-            current_style: Cell::new(theme::ColorPair::from_256colors(0, 0)),
-        };
+        #[cfg(unix)]
+        let termios = original_termios();
+
+        let mut raw = ::std::io::stdout().into_raw_mode().unwrap();
+
+        // Ask before the input thread starts reading stdin.
+        #[cfg(unix)]
+        let initial_cursor = query_cursor_position(&mut raw);
+        #[cfg(not(unix))]
+        let initial_cursor = None;
+
+        let terminal = MouseTerminal::from(raw);
+
+        let c = Backend::new(
+            Box::new(terminal),
+            color_support,
+            options,
+            initial_cursor,
+        );
+
+        if c.options.alternate_screen {
+            c.write(termion::screen::ToAlternateScreen);
+        }
+        c.write(termion::cursor::Hide);
+        // `MouseTerminal` already does this, but we rely on SGR coordinates.
+        c.write(MOUSE_ENABLE);
+        c.write(FOCUS_ENABLE);
+        // Enable bracketed paste
+        c.write("\x1B[?2004h");
+
+        let alternate_screen = c.options.alternate_screen;
+        backend::set_panic_restore(move || {
+            let mut restore = format!(
+                "\x1B[?2004l{}{}{}{}",
+                FOCUS_DISABLE,
+                MOUSE_DISABLE,
+                tstyle::Reset,
+                termion::cursor::Show
+            );
+            if alternate_screen {
+                restore.push_str(&termion::screen::ToMainScreen.to_string());
+            } else {
+                restore.push_str("\r\n");
+            }
+            let stdout = ::std::io::stdout();
+            let mut stdout = stdout.lock();
+            let _ = stdout.write_all(restore.as_bytes());
+            let _ = stdout.flush();
+
+            // `RawTerminal` only leaves raw mode when dropped.
+            #[cfg(unix)]
+            {
+                if let Some(ref termios) = termios {
+                    unsafe {
+                        libc::tcsetattr(
+                            libc::STDOUT_FILENO,
+                            libc::TCSANOW,
+                            termios,
+                        );
+                    }
+                }
+            }
+        });
 
         Box::new(c)
     }
 
+    fn new(
+        terminal: Box<Output>, color_support: ColorSupport, options: Options,
+        initial_cursor: Option<Vec2>,
+    ) -> Self {
+        Backend {
+            terminal,
+            buffer: RefCell::new(Vec::new()),
+            current_style: Cell::new(theme::ColorPair {
+                front: theme::Color::TerminalDefault,
+                back: theme::Color::TerminalDefault,
+            }),
+            current_effects: Cell::new(theme::EffectSet::new()),
+            color_support,
+            title_saved: Cell::new(false),
+            options,
+            initial_cursor,
+            input_pause: InputPause::new(),
+        }
+    }
+
+    /// Writes the given content to the frame buffer.
+    ///
+    /// Nothing reaches the terminal until `flush()` is called.
+    fn write<T>(&self, content: T)
+    where
+        T: Display,
+    {
+        write!(self.buffer.borrow_mut(), "{}", content).unwrap();
+    }
+
+    /// Sends the frame buffer to the terminal in a single write.
+    fn flush(&mut self) {
+        let buffer = self.buffer.get_mut();
+        self.terminal.write_all(buffer).unwrap();
+        self.terminal.flush().unwrap();
+        buffer.clear();
+    }
+
+    /// Gives the terminal back, restoring the cursor.
+    ///
+    /// On the alternate screen, this goes back to the normal screen.
+    /// Otherwise, the content stays, and the cursor moves below it.
+    fn leave_screen(&self) {
+        self.write(tstyle::Reset);
+        self.current_style.set(theme::ColorPair {
+            front: theme::Color::TerminalDefault,
+            back: theme::Color::TerminalDefault,
+        });
+        self.current_effects.set(theme::EffectSet::new());
+        self.write(termion::cursor::Show);
+
+        if self.options.alternate_screen {
+            self.write(termion::screen::ToMainScreen);
+            if let Some(pos) = self.initial_cursor {
+                self.write(termion::cursor::Goto(
+                    pos.x as u16 + 1,
+                    pos.y as u16 + 1,
+                ));
+            }
+        } else {
+            let (_, height) = termion::terminal_size().unwrap_or((1, 1));
+            self.write(termion::cursor::Goto(1, height));
+            self.write("\r\n");
+        }
+    }
+
+    /// Switches to the given colors.
     fn apply_colors(&self, colors: theme::ColorPair) {
-        with_color(&colors.front, |c| print!("{}", tcolor::Fg(c)));
-        with_color(&colors.back, |c| print!("{}", tcolor::Bg(c)));
+        let current = self.current_style.replace(colors);
+        self.write(color_transition(current, colors, self.color_support));
     }
 }
 
 impl backend::Backend for Backend {
     fn finish(&mut self) {
-        print!("{}{}", termion::cursor::Show, termion::cursor::Goto(1, 1));
-        print!(
-            "{}[49m{}[39m{}",
-            27 as char,
-            27 as char,
-            termion::clear::All
-        );
+        if !backend::unset_panic_restore() {
+            return;
+        }
+        if self.title_saved.get() {
+            self.write(TITLE_POP);
+        }
+        // Disable bracketed paste, focus reporting and mouse input
+        self.write("\x1B[?2004l");
+        self.write(FOCUS_DISABLE);
+        self.write(MOUSE_DISABLE);
+        if self.options.alternate_screen {
+            self.write(format_args!(
+                "{}[49m{}[39m{}",
+                27 as char,
+                27 as char,
+                termion::clear::All
+            ));
+        }
+        self.leave_screen();
+        self.flush();
     }
 
-    fn set_color(&self, color: theme::ColorPair) -> theme::ColorPair {
-        let current_style = self.current_style.get();
+    fn set_title(&self, title: &str) {
+        if !self.title_saved.replace(true) {
+            self.write(TITLE_PUSH);
+        }
+        self.write(format_args!("\x1B]0;{}\x07", title));
+    }
+
+    fn suspend(&mut self) {
+        // Leave the input to the next program.
+        self.input_pause.pause();
+
+        // Disable bracketed paste, focus reporting, then mouse input
+        self.write("\x1B[?2004l");
+        self.write(FOCUS_DISABLE);
+        self.write(MOUSE_DISABLE);
+        self.leave_screen();
+        self.flush();
 
-        if current_style != color {
-            self.apply_colors(color);
-            self.current_style.set(color);
+        self.terminal.suspend_raw_mode().unwrap();
+    }
+
+    fn resume(&mut self) {
+        self.terminal.activate_raw_mode().unwrap();
+
+        if self.options.alternate_screen {
+            self.write(termion::screen::ToAlternateScreen);
         }
+        self.write(termion::cursor::Hide);
+        self.write(MOUSE_ENABLE);
+        self.write(FOCUS_ENABLE);
+        self.write("\x1B[?2004h");
+        self.flush();
 
-        return current_style;
+        self.input_pause.resume();
+    }
+
+    fn set_color(&self, color: theme::ColorPair) -> theme::ColorPair {
+        let current_style = self.current_style.get();
+        self.apply_colors(color);
+        current_style
     }
 
     fn set_effect(&self, effect: theme::Effect) {
-        effect.on();
+        let current = self.current_effects.get();
+        let effects = current.union(effect.into());
+        self.write(effect_transition(current, effects));
+        self.current_effects.set(effects);
     }
 
     fn unset_effect(&self, effect: theme::Effect) {
-        effect.off();
+        let current = self.current_effects.get();
+        let effects = current.difference(effect.into());
+        self.write(effect_transition(current, effects));
+        self.current_effects.set(effects);
+    }
+
+    fn cursor_position(&self) -> Option<Vec2> {
+        self.initial_cursor
     }
 
     fn has_colors(&self) -> bool {
-        // TODO: color support detection?
-        true
+        self.color_support != ColorSupport::None
+    }
+
+    fn color_support(&self) -> ColorSupport {
+        self.color_support
     }
 
     fn screen_size(&self) -> Vec2 {
@@ -282,19 +914,19 @@ impl backend::Backend for Backend {
             front: color,
             back: color,
         });
-        print!("{}", termion::clear::All);
+        self.write(termion::clear::All);
     }
 
     fn refresh(&mut self) {
-        self.terminal.flush().unwrap();
+        self.flush();
     }
 
     fn print_at(&self, pos: Vec2, text: &str) {
-        print!(
+        self.write(format_args!(
             "{}{}",
             termion::cursor::Goto(1 + pos.x as u16, 1 + pos.y as u16),
             text
-        );
+        ));
     }
 
     fn start_input_thread(
@@ -302,6 +934,7 @@ impl backend::Backend for Backend {
         input_request: Receiver<backend::InputRequest>,
     ) {
         let running = Arc::new(AtomicBool::new(true));
+        let resized = Arc::new(AtomicBool::new(false));
 
         #[cfg(unix)]
         {
@@ -310,11 +943,29 @@ impl backend::Backend for Backend {
                 event_sink.clone(),
                 input_request.clone(),
                 Arc::clone(&running),
-                None,
+                Some(Arc::clone(&resized)),
             );
         }
 
-        let mut parser = InputParser::new();
+        if let Some(interval) = self.options.resize_poll_interval {
+            backend::resize::start_resize_poll_thread(
+                interval,
+                || {
+                    let (x, y) = termion::terminal_size().unwrap_or((1, 1));
+                    (x, y).into()
+                },
+                event_sink.clone(),
+                input_request.clone(),
+                Arc::clone(&running),
+                Some(Arc::clone(&resized)),
+            );
+        }
+
+        let mut parser = InputParser::new(
+            self.options.peek_timeout,
+            self.input_pause.clone(),
+            resized,
+        );
         thread::spawn(move || {
             for req in input_request {
                 match req {
@@ -331,11 +982,249 @@ impl backend::Backend for Backend {
     }
 }
 
-fn with_color<F, R>(clr: &theme::Color, f: F) -> R
+/// Returns the terminal settings, to restore them after a panic.
+#[cfg(unix)]
+fn original_termios() -> Option<libc::termios> {
+    let mut termios = unsafe { ::std::mem::zeroed() };
+    if unsafe { libc::tcgetattr(libc::STDOUT_FILENO, &mut termios) } == 0 {
+        Some(termios)
+    } else {
+        None
+    }
+}
+
+/// Asks the terminal for the cursor position (0-based).
+///
+/// The terminal must be in raw mode, and nothing else may be reading stdin.
+/// Returns `None` if no reply came within `CURSOR_QUERY_TIMEOUT`.
+#[cfg(unix)]
+fn query_cursor_position<W: Write>(terminal: &mut W) -> Option<Vec2> {
+    // Device Status Report
+    terminal.write_all(b"\x1B[6n").ok()?;
+    terminal.flush().ok()?;
+
+    let deadline = Instant::now() + CURSOR_QUERY_TIMEOUT;
+    let mut reply = Vec::new();
+
+    // Read byte per byte, directly from the file descriptor:
+    // we don't want to buffer (and lose) any following input.
+    while reply.last() != Some(&b'R') {
+        let now = Instant::now();
+        if now >= deadline {
+            return None;
+        }
+        let remaining = deadline - now;
+        let remaining = remaining.as_secs() * 1000
+            + u64::from(remaining.subsec_millis());
+
+        if !stdin_ready(remaining as libc::c_int) {
+            return None;
+        }
+
+        let mut byte = [0u8];
+        if read_stdin(&mut byte).ok()? != 1 {
+            return None;
+        }
+        reply.push(byte[0]);
+    }
+
+    parse_cursor_position(&reply)
+}
+
+/// Parses a cursor position report, `ESC [ row ; col R`.
+///
+/// Returns a 0-based position.
+fn parse_cursor_position(bytes: &[u8]) -> Option<Vec2> {
+    let start = bytes.windows(2).rposition(|w| w == b"\x1B[")?;
+    let report = ::std::str::from_utf8(&bytes[start + 2..]).ok()?;
+    let report = report.trim_end_matches('R');
+
+    let mut coords = report.split(';').map(|n| n.parse::<usize>());
+    match (coords.next(), coords.next(), coords.next()) {
+        (Some(Ok(row)), Some(Ok(col)), None) if row > 0 && col > 0 => {
+            Some(Vec2::new(col - 1, row - 1))
+        }
+        _ => None,
+    }
+}
+
+/// Parses a sequence termion reported as unsupported.
+///
+/// Termion only knows about the most basic keys; this handles the common
+/// CSI (`ESC [`) and SS3 (`ESC O`) sequences it doesn't, like keys with
+/// modifiers, `F13` to `F20`, the keypad, focus reports or horizontal
+/// scrolling.
+fn parse_unsupported(bytes: &[u8]) -> Option<Event> {
+    match *bytes {
+        [27, b'[', b'<', ref rest @ ..] => parse_sgr_mouse(rest),
+        [27, b'[', ref rest @ ..] => parse_csi(rest),
+        [27, b'O', key] => parse_ss3(key),
+        _ => None,
+    }
+}
+
+/// Parses a CSI sequence, `ESC [ <params> <final>`, without the `ESC [`.
+fn parse_csi(bytes: &[u8]) -> Option<Event> {
+    let (&last, params) = bytes.split_last()?;
+    let params = ::std::str::from_utf8(params).ok()?;
+    let params = if params.is_empty() {
+        Vec::new()
+    } else {
+        params
+            .split(';')
+            .map(|p| p.parse::<u8>().ok())
+            .collect::<Option<Vec<u8>>>()?
+    };
+
+    let (key, modifier) = match (last, &params[..]) {
+        (b'I', []) => return Some(Event::WindowFocus(true)),
+        (b'O', []) => return Some(Event::WindowFocus(false)),
+        (b'~', &[code]) => (tilde_key(code)?, 1),
+        (b'~', &[code, modifier]) => (tilde_key(code)?, modifier),
+        // Keys like `Enter` or `Tab` with modifiers, for terminals using
+        // xterm's `modifyOtherKeys`, or the `CSI u` encoding.
+        (b'~', &[27, modifier, code]) => (code_key(code)?, modifier),
+        (b'u', &[code]) => (code_key(code)?, 1),
+        (b'u', &[code, modifier]) => (code_key(code)?, modifier),
+        (_, []) => (letter_key(last)?, 1),
+        (_, &[1, modifier]) => (letter_key(last)?, modifier),
+        _ => return None,
+    };
+
+    // The modifier is 1 + a bitmask: 1 for shift, 2 for alt, 4 for ctrl.
+    Some(match modifier {
+        1 => Event::Key(key),
+        2 => Event::Shift(key),
+        3 => Event::Alt(key),
+        4 => Event::AltShift(key),
+        5 => Event::Ctrl(key),
+        6 => Event::CtrlShift(key),
+        7 => Event::CtrlAlt(key),
+        _ => return None,
+    })
+}
+
+/// Parses an SGR mouse report, `ESC [ < <button> ; <x> ; <y> M`, without the
+/// `ESC [ <`.
+///
+/// Only events not handled by termion are parsed here: horizontal wheels,
+/// and moves without a button pressed.
+fn parse_sgr_mouse(bytes: &[u8]) -> Option<Event> {
+    let (&last, params) = bytes.split_last()?;
+    if last != b'M' {
+        return None;
+    }
+
+    let params = ::std::str::from_utf8(params).ok()?;
+    let params = params
+        .split(';')
+        .map(|p| p.parse::<usize>().ok())
+        .collect::<Option<Vec<usize>>>()?;
+    let (button, x, y) = match params[..] {
+        [button, x, y] if x > 0 && y > 0 => (button, x, y),
+        _ => return None,
+    };
+
+    // Bits 4, 8 and 16 are the Shift, Alt and Ctrl modifiers.
+    let shift = button & 4 != 0;
+    let event = match (button & !(4 | 8 | 16), shift) {
+        // Shift + wheel is commonly used to scroll horizontally.
+        (64, true) | (66, _) => MouseEvent::WheelLeft,
+        (65, true) | (67, _) => MouseEvent::WheelRight,
+        (64, false) => MouseEvent::WheelUp,
+        (65, false) => MouseEvent::WheelDown,
+        (35, _) => MouseEvent::Hover,
+        _ => return None,
+    };
+
+    Some(Event::Mouse {
+        event,
+        position: Vec2::new(x - 1, y - 1),
+        offset: Vec2::zero(),
+    })
+}
+
+/// Parses an SS3 sequence, `ESC O <key>`, sent by the keypad.
+fn parse_ss3(key: u8) -> Option<Event> {
+    Some(match key {
+        b'M' => Event::Key(Key::Enter),
+        // Keypad characters, in application mode
+        b'j' => Event::Char('*'),
+        b'k' => Event::Char('+'),
+        b'l' => Event::Char(','),
+        b'm' => Event::Char('-'),
+        b'n' => Event::Char('.'),
+        b'o' => Event::Char('/'),
+        b'p'..=b'y' => Event::Char((key - b'p' + b'0') as char),
+        _ => Event::Key(letter_key(key)?),
+    })
+}
+
+/// Returns the key for `ESC [ <code> ~` sequences.
+fn tilde_key(code: u8) -> Option<Key> {
+    Some(match code {
+        1 | 7 => Key::Home,
+        2 => Key::Ins,
+        3 => Key::Del,
+        4 | 8 => Key::End,
+        5 => Key::PageUp,
+        6 => Key::PageDown,
+        11..=15 => Key::from_f(code - 10),
+        17..=21 => Key::from_f(code - 11),
+        23..=26 => Key::from_f(code - 12),
+        28 | 29 => Key::from_f(code - 13),
+        31..=34 => Key::from_f(code - 14),
+        _ => return None,
+    })
+}
+
+/// Returns the key sending the given character code.
+fn code_key(code: u8) -> Option<Key> {
+    Some(match code {
+        9 => Key::Tab,
+        13 => Key::Enter,
+        27 => Key::Esc,
+        127 => Key::Backspace,
+        _ => return None,
+    })
+}
+
+/// Returns the key for sequences ending with a letter, like `ESC [ A`.
+fn letter_key(letter: u8) -> Option<Key> {
+    Some(match letter {
+        b'A' => Key::Up,
+        b'B' => Key::Down,
+        b'C' => Key::Right,
+        b'D' => Key::Left,
+        b'E' => Key::NumpadCenter,
+        b'H' => Key::Home,
+        b'F' => Key::End,
+        b'P' => Key::F1,
+        b'Q' => Key::F2,
+        b'R' => Key::F3,
+        b'S' => Key::F4,
+        _ => return None,
+    })
+}
+
+/// Runs `f` with the termion color closest to `clr`.
+///
+/// Colors the terminal cannot display are downgraded according to `support`.
+fn with_color<F, R>(clr: &theme::Color, support: ColorSupport, f: F) -> R
 where
     F: FnOnce(&tcolor::Color) -> R,
 {
     match *clr {
+        theme::Color::Rgb(..) | theme::Color::RgbLowRes(..)
+            if support == ColorSupport::Ansi16 =>
+        {
+            with_color(&clr.to_16colors(), support, f)
+        }
+        theme::Color::Rgb(..) if support == ColorSupport::Ansi256 => {
+            // `to_256colors` only returns `None` for `TerminalDefault`.
+            f(&tcolor::AnsiValue(clr.to_256colors().unwrap()))
+        }
+
         theme::Color::TerminalDefault => f(&tcolor::Reset),
         theme::Color::Dark(theme::BaseColor::Black) => f(&tcolor::Black),
         theme::Color::Dark(theme::BaseColor::Red) => f(&tcolor::Red),
@@ -360,8 +1249,465 @@ where
         theme::Color::Light(theme::BaseColor::White) => f(&tcolor::LightWhite),
 
         theme::Color::Rgb(r, g, b) => f(&tcolor::Rgb(r, g, b)),
-        theme::Color::RgbLowRes(r, g, b) => {
-            f(&tcolor::AnsiValue::rgb(r, g, b))
+        theme::Color::RgbLowRes(..) => {
+            f(&tcolor::AnsiValue(clr.to_256colors().unwrap()))
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::termion::event::{parse_event, Event as TEvent};
+    use super::termion::color as tcolor;
+    use super::{
+        color_transition, effect_transition, parse_cursor_position,
+        parse_unsupported, Backend, ClickCounter, ColorSupport, InputParser,
+        InputPause, Options, Output, PASTE_TIMEOUT,
+    };
+    use backend::Backend as _;
+    use crossbeam_channel::{self, Receiver, Sender};
+    use event::{Event, Key, MouseButton, MouseEvent};
+    use std::cell::RefCell;
+    use std::io::{self, Write};
+    use std::rc::Rc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::{Duration, Instant};
+    use theme::{BaseColor, Color, ColorPair, Effect, EffectSet};
+    use vec::Vec2;
+
+    fn parser() -> InputParser {
+        InputParser::new(
+            None,
+            InputPause::new(),
+            Arc::new(AtomicBool::new(false)),
+        )
+    }
+
+    #[test]
+    fn test_click_counter() {
+        let mut clicks = ClickCounter::new(Duration::from_millis(400));
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        let left = MouseButton::Left;
+        let pos = Vec2::new(3, 4);
+
+        assert_eq!(clicks.press(left, pos, at(0)), 1);
+        assert_eq!(clicks.press(left, pos, at(200)), 2);
+        assert_eq!(clicks.press(left, pos + (1, 0), at(400)), 3);
+        // After a triple click, we start over.
+        assert_eq!(clicks.press(left, pos, at(500)), 1);
+
+        // Too slow
+        assert_eq!(clicks.press(left, pos, at(1000)), 1);
+
+        // Different button
+        assert_eq!(clicks.press(MouseButton::Right, pos, at(1100)), 1);
+        assert_eq!(clicks.press(MouseButton::Right, pos, at(1200)), 2);
+
+        // Too far
+        let far = Vec2::new(8, 4);
+        assert_eq!(clicks.press(MouseButton::Right, far, at(1300)), 1);
+    }
+
+    #[test]
+    fn test_wide_mouse_click() {
+        // SGR encoding of a left click at column 250, row 5 (1-based).
+        let bytes = b"\x1B[<0;251;5M";
+        let mut iter = bytes[1..].iter().map(|&b| Ok(b));
+        let event = parse_event(bytes[0], &mut iter).unwrap();
+
+        let mut parser = parser();
+        assert_eq!(
+            parser.map_key(event, bytes.to_vec()),
+            Event::Mouse {
+                offset: Vec2::zero(),
+                position: Vec2::new(250, 4),
+                event: MouseEvent::Press(MouseButton::Left),
+            }
+        );
+    }
+
+    #[test]
+    fn test_cursor_position() {
+        assert_eq!(
+            parse_cursor_position(b"\x1B[12;40R"),
+            Some(Vec2::new(39, 11))
+        );
+        // Input typed before the reply is ignored.
+        assert_eq!(
+            parse_cursor_position(b"ab\x1B[1;1R"),
+            Some(Vec2::new(0, 0))
+        );
+        assert_eq!(parse_cursor_position(b"\x1B[12R"), None);
+        assert_eq!(parse_cursor_position(b"\x1B[0;0R"), None);
+    }
+
+    #[test]
+    fn test_modified_keys() {
+        let cases: &[(&[u8], Event)] = &[
+            (b"\x1B[1;5C", Event::Ctrl(Key::Right)),
+            (b"\x1B[1;5D", Event::Ctrl(Key::Left)),
+            (b"\x1B[1;3A", Event::Alt(Key::Up)),
+            (b"\x1B[1;2B", Event::Shift(Key::Down)),
+            (b"\x1B[1;6H", Event::CtrlShift(Key::Home)),
+            (b"\x1B[1;7F", Event::CtrlAlt(Key::End)),
+            (b"\x1B[1;2P", Event::Shift(Key::F1)),
+            (b"\x1B[3;5~", Event::Ctrl(Key::Del)),
+            (b"\x1B[6;3~", Event::Alt(Key::PageDown)),
+            (b"\x1B[15;2~", Event::Shift(Key::F5)),
+        ];
+
+        for &(bytes, ref event) in cases {
+            assert_eq!(parse_unsupported(bytes).as_ref(), Some(event));
+        }
+    }
+
+    #[test]
+    fn test_color_transition() {
+        let pair = |front, back| ColorPair { front, back };
+        let blue = Color::Dark(BaseColor::Blue);
+        let from = pair(Color::Dark(BaseColor::Red), blue);
+        let support = ColorSupport::Ansi16;
+
+        // Same background: only the foreground is written.
+        let to = pair(Color::Dark(BaseColor::Green), blue);
+        assert_eq!(
+            color_transition(from, to, support),
+            tcolor::Fg(tcolor::Green).to_string()
+        );
+
+        let to = pair(Color::Dark(BaseColor::Red), Color::TerminalDefault);
+        assert_eq!(
+            color_transition(from, to, support),
+            tcolor::Bg(tcolor::Reset).to_string()
+        );
+
+        assert_eq!(color_transition(from, from, support), "");
+        let to = pair(Color::Light(BaseColor::Red), Color::TerminalDefault);
+        assert_eq!(color_transition(from, to, ColorSupport::None), "");
+    }
+
+    #[test]
+    fn test_effect_transition() {
+        let none = EffectSet::new();
+        let cases = [
+            (none, Effect::Bold.into(), "\x1B[1m"),
+            (Effect::Bold.into(), Effect::Bold | Effect::Underline, "\x1B[4m"),
+            (Effect::Bold | Effect::Dim, Effect::Dim.into(), "\x1B[22;2m"),
+            (Effect::Bold | Effect::Dim, Effect::Bold.into(), "\x1B[22;1m"),
+            (Effect::Bold | Effect::Dim, none, "\x1B[22m"),
+            (
+                Effect::Bold | Effect::Italic,
+                Effect::Underline.into(),
+                "\x1B[22;23;4m",
+            ),
+            (Effect::Reverse.into(), Effect::Reverse.into(), ""),
+            (none, Effect::Simple.into(), ""),
+        ];
+
+        for &(from, to, expected) in &cases {
+            assert_eq!(effect_transition(from, to), expected);
+        }
+    }
+
+    // Parses `bytes` like the input thread does.
+    fn parse(bytes: &[u8]) -> Event {
+        let mut iter = bytes[1..].iter().map(|&b| Ok(b));
+        let event = parse_event(bytes[0], &mut iter)
+            .unwrap_or_else(|_| TEvent::Unsupported(bytes.to_vec()));
+        let mut parser = parser();
+        parser.map_key(event, bytes.to_vec())
+    }
+
+    #[test]
+    fn test_shift_keys() {
+        assert_eq!(parse(b"\x1B[Z"), Event::Shift(Key::Tab));
+        assert_eq!(parse(b"\x1B[1;2A"), Event::Shift(Key::Up));
+        assert_eq!(parse(b"\x1B[1;2P"), Event::Shift(Key::F1));
+        assert_eq!(parse(b"\x1B[15;2~"), Event::Shift(Key::F5));
+        assert_eq!(parse(b"\x1B[13;2u"), Event::Shift(Key::Enter));
+        assert_eq!(parse(b"\x1B[9;2u"), Event::Shift(Key::Tab));
+        assert_eq!(parse(b"\x1B[27;2;13~"), Event::Shift(Key::Enter));
+        assert_eq!(parse(b"\x1B[27;5;9~"), Event::Ctrl(Key::Tab));
+    }
+
+    #[test]
+    fn test_keypad() {
+        assert_eq!(parse_unsupported(b"\x1BOA"), Some(Event::Key(Key::Up)));
+        assert_eq!(
+            parse_unsupported(b"\x1BOM"),
+            Some(Event::Key(Key::Enter))
+        );
+        assert_eq!(parse_unsupported(b"\x1BOk"), Some(Event::Char('+')));
+        assert_eq!(parse_unsupported(b"\x1BOr"), Some(Event::Char('2')));
+        assert_eq!(
+            parse_unsupported(b"\x1B[E"),
+            Some(Event::Key(Key::NumpadCenter))
+        );
+    }
+
+    #[test]
+    fn test_focus() {
+        assert_eq!(
+            parse_unsupported(b"\x1B[I"),
+            Some(Event::WindowFocus(true))
+        );
+        assert_eq!(
+            parse_unsupported(b"\x1B[O"),
+            Some(Event::WindowFocus(false))
+        );
+    }
+
+    #[test]
+    fn test_unknown_sequences() {
+        assert_eq!(parse_unsupported(b"\x1B[1;9C"), None);
+        assert_eq!(parse_unsupported(b"\x1B[1;5Z"), None);
+        assert_eq!(parse_unsupported(b"\x1B[5C"), None);
+        assert_eq!(parse_unsupported(b"\x1B[27~"), None);
+        assert_eq!(parse_unsupported(b"\x1B[~"), None);
+        assert_eq!(parse_unsupported(b"\x1B[1;x~"), None);
+        assert_eq!(parse_unsupported(b"\x1B["), None);
+        assert_eq!(parse_unsupported(b""), None);
+    }
+
+    #[test]
+    fn test_horizontal_wheel() {
+        let wheel = |event| Event::Mouse {
+            event,
+            position: Vec2::new(9, 4),
+            offset: Vec2::zero(),
+        };
+        assert_eq!(
+            parse_unsupported(b"\x1B[<68;10;5M"),
+            Some(wheel(MouseEvent::WheelLeft))
+        );
+        assert_eq!(
+            parse_unsupported(b"\x1B[<69;10;5M"),
+            Some(wheel(MouseEvent::WheelRight))
+        );
+        assert_eq!(
+            parse_unsupported(b"\x1B[<67;10;5M"),
+            Some(wheel(MouseEvent::WheelRight))
+        );
+        assert_eq!(
+            parse_unsupported(b"\x1B[<80;10;5M"),
+            Some(wheel(MouseEvent::WheelUp))
+        );
+        // Ctrl + click is not handled yet.
+        assert_eq!(parse_unsupported(b"\x1B[<16;10;5M"), None);
+        assert_eq!(parse_unsupported(b"\x1B[<68;0;5M"), None);
+    }
+
+    #[test]
+    fn test_mouse_move() {
+        let hover = Event::Mouse {
+            event: MouseEvent::Hover,
+            position: Vec2::new(9, 4),
+            offset: Vec2::zero(),
+        };
+        assert_eq!(parse(b"\x1B[<35;10;5M"), hover);
+        // Moving with Shift pressed.
+        assert_eq!(parse(b"\x1B[<39;10;5M"), hover);
+    }
+
+    #[test]
+    fn test_function_keys() {
+        assert_eq!(
+            parse_unsupported(b"\x1B[25~"),
+            Some(Event::Key(Key::F13))
+        );
+        assert_eq!(
+            parse_unsupported(b"\x1B[28~"),
+            Some(Event::Key(Key::F15))
+        );
+        assert_eq!(
+            parse_unsupported(b"\x1B[34~"),
+            Some(Event::Key(Key::F20))
+        );
+        assert_eq!(
+            parse_unsupported(b"\x1B[26;5~"),
+            Some(Event::Ctrl(Key::F14))
+        );
+
+        assert_eq!(Key::from_f(15), Key::F15);
+        assert_eq!(Key::from_f(24), Key::F24);
+    }
+
+    type Channels = (Sender<(TEvent, Vec<u8>)>, Receiver<()>);
+
+    // Creates a parser reading `bytes`, split into events like termion does.
+    //
+    // Also returns the other ends of the parser's channels, to keep them
+    // open.
+    fn paste_parser(bytes: &[u8]) -> (InputParser, Channels) {
+        let (sender, input) = crossbeam_channel::unbounded();
+        let mut rest = bytes;
+        while let Some((&first, tail)) = rest.split_first() {
+            let mut iter = tail.iter().map(|&b| Ok(b));
+            let result = parse_event(first, &mut iter);
+            let (raw, next) = rest.split_at(rest.len() - iter.len());
+            let event =
+                result.unwrap_or_else(|_| TEvent::Unsupported(raw.to_vec()));
+            sender.send((event, raw.to_vec())).unwrap();
+            rest = next;
+        }
+
+        // Nobody reads the requests: the input is already there.
+        let (requests, request_receiver) = crossbeam_channel::unbounded();
+        let parser = InputParser::with_input(
+            input,
+            requests,
+            None,
+            Arc::new(AtomicBool::new(false)),
+        );
+        (parser, (sender, request_receiver))
+    }
+
+    #[test]
+    fn test_paste() {
+        let (mut parser, _channels) =
+            paste_parser(b"\x1B[200~one\rtwo \xC3\xA9t\xC3\xA9\x1B[201~x");
+        assert_eq!(
+            parser.next_event(),
+            Event::Paste("one\ntwo \u{e9}t\u{e9}".to_string())
+        );
+        assert_eq!(parser.next_event(), Event::Char('x'));
+
+        // Control characters and escape sequences are part of the text.
+        let (mut parser, _channels) =
+            paste_parser(b"\x1B[200~a\tb\x01\x1B[A\xFFxyz\x1B[201~");
+        assert_eq!(
+            parser.next_event(),
+            Event::Paste("a\tb\x01\x1B[A\u{FFFD}xyz".to_string())
+        );
+    }
+
+    #[test]
+    fn test_interrupted_paste() {
+        // The end of the paste never comes.
+        let (mut parser, _channels) = paste_parser(b"\x1B[200~abc");
+        let start = Instant::now();
+        assert_eq!(parser.next_event(), Event::Paste("abc".to_string()));
+        assert!(start.elapsed() >= PASTE_TIMEOUT);
+
+        // Pasting again starts over.
+        let (mut parser, _channels) =
+            paste_parser(b"\x1B[200~ab\x1B[200~cd\x1B[201~");
+        assert_eq!(parser.next_event(), Event::Paste("abcd".to_string()));
+
+        // A resize ends the paste: the rest is read as usual.
+        let (mut parser, _channels) = paste_parser(b"ab");
+        parser.resized.store(true, Ordering::Relaxed);
+        assert_eq!(parser.read_paste(), Event::Paste(String::new()));
+        assert_eq!(parser.next_event(), Event::Char('a'));
+    }
+
+    #[test]
+    fn test_unknown_keeps_bytes() {
+        let mut parser = parser();
+        let bytes = b"\x1B[99~".to_vec();
+        assert_eq!(
+            parser.map_key(TEvent::Unsupported(bytes.clone()), bytes.clone()),
+            Event::Unknown(bytes)
+        );
+    }
+
+    #[test]
+    fn test_input_pause() {
+        let pause = InputPause::new();
+        pause.pause();
+
+        let (sender, receiver) = crossbeam_channel::bounded(1);
+        let waiting = pause.clone();
+        thread::spawn(move || {
+            waiting.wait();
+            sender.send(()).unwrap();
+        });
+
+        let timeout = Duration::from_millis(50);
+        assert!(receiver.recv_timeout(timeout).is_err());
+        pause.resume();
+        assert!(receiver.recv_timeout(Duration::from_secs(5)).is_ok());
+    }
+
+    // Terminal output, with the number of calls it took.
+    #[derive(Default)]
+    struct Recorded {
+        writes: usize,
+        flushes: usize,
+        bytes: Vec<u8>,
+    }
+
+    #[derive(Clone, Default)]
+    struct Recorder(Rc<RefCell<Recorded>>);
+
+    impl Write for Recorder {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            let mut recorded = self.0.borrow_mut();
+            recorded.writes += 1;
+            recorded.bytes.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            self.0.borrow_mut().flushes += 1;
+            Ok(())
+        }
+    }
+
+    impl Output for Recorder {
+        fn suspend_raw_mode(&self) -> io::Result<()> {
+            Ok(())
+        }
+
+        fn activate_raw_mode(&self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_frame_single_write() {
+        let recorder = Recorder::default();
+        let mut backend = Backend::new(
+            Box::new(recorder.clone()),
+            ColorSupport::Ansi16,
+            Options::default(),
+            None,
+        );
+
+        let black = Color::Dark(BaseColor::Black);
+        let left = ColorPair {
+            front: Color::Dark(BaseColor::Red),
+            back: black,
+        };
+        let right = ColorPair {
+            front: Color::Dark(BaseColor::Blue),
+            back: black,
+        };
+
+        for frame in 1..3 {
+            // Redraw every cell of a 80x24 screen, like a printer would.
+            for y in 0..24 {
+                for x in 0..80 {
+                    backend.set_color(if x < 40 { left } else { right });
+                    backend.print_at(Vec2::new(x, y), "x");
+                }
+            }
+            assert_eq!(recorder.0.borrow().writes, frame - 1);
+
+            backend.refresh();
+            assert_eq!(recorder.0.borrow().writes, frame);
+            assert_eq!(recorder.0.borrow().flushes, frame);
+        }
+
+        // The colors only change twice per line.
+        let recorded = recorder.0.borrow();
+        let output = String::from_utf8_lossy(&recorded.bytes);
+        let count = |code: String| output.matches(code.as_str()).count();
+        assert_eq!(count(tcolor::Fg(tcolor::Red).to_string()), 48);
+        assert_eq!(count(tcolor::Fg(tcolor::Blue).to_string()), 48);
+        assert_eq!(count(tcolor::Bg(tcolor::Black).to_string()), 1);
+    }
+}
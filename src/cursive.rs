@@ -1,18 +1,34 @@
 use std::any::Any;
+use std::cell::Cell;
 use std::collections::HashMap;
+use std::fs;
+#[cfg(feature = "async")]
+use std::future::Future;
 use std::path::Path;
-use std::time::Duration;
+#[cfg(feature = "async")]
+use std::pin::Pin;
+use std::rc::{Rc, Weak};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+#[cfg(feature = "async")]
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
 
 use crossbeam_channel::{self, Receiver, Sender};
 
-use backend;
+use align::Align;
+use backend::{self, ColorSupport};
 use direction;
-use event::{Callback, Event, EventResult};
+use event::{Callback, Event, EventResult, Key, MouseEvent};
 use printer::Printer;
+use replay::{self, EventLog};
 use theme;
+use utils::clipboard::{self, Clipboard};
+use utils::markup::StyledString;
 use vec::Vec2;
 use view::{self, Finder, IntoBoxedView, Position, View};
-use views::{self, LayerPosition};
+use views::{self, LayerHandle, LayerPosition, StaleLayerHandle};
+use XY;
 
 static DEBUG_VIEW_ID: &'static str = "_cursive_debug_view";
 
@@ -25,19 +41,69 @@ static DEBUG_VIEW_ID: &'static str = "_cursive_debug_view";
 /// It uses a list of screen, with one screen active at a time.
 pub struct Cursive {
     theme: theme::Theme,
+
+    // Most frames drawn per second, or 0 for no limit.
+    max_fps: u32,
+    last_draw: Option<Instant>,
+    // A step was not drawn to respect `max_fps`.
+    draw_pending: bool,
+    // Set by `Invalidator`s until the next frame is drawn.
+    dirty: Arc<AtomicBool>,
+
+    // Identical navigation keys closer than this are merged.
+    repeat_window: Option<Duration>,
+    // Last navigation key handled, and when.
+    last_repeat: Option<(Event, Instant)>,
+
     screens: Vec<views::StackView>,
     global_callbacks: HashMap<Event, Vec<Callback>>,
+    global_sequences: Vec<(Vec<Event>, Callback)>,
+    on_unhandled_event: Option<Rc<OnUnhandledEvent>>,
+    // Gets all the input instead of the views, while set.
+    raw_input: Option<Rc<RawInput>>,
+
+    timers: Vec<Timer>,
+    next_timer_id: usize,
+
+    notifications: Vec<Notification>,
+    notifications_align: Align,
+    tooltip: Option<Tooltip>,
+    // Set by the `TooltipView` under the mouse while dispatching a hover.
+    hovered: Option<views::Hovered>,
     menubar: views::Menubar,
+    clipboard: Box<Clipboard>,
+
+    // Events received so far that start a global sequence.
+    pending_events: Vec<Event>,
+    // When the pending events will be given up on.
+    sequence_deadline: Option<Instant>,
+    sequence_timeout: Duration,
 
     // Last layer sizes of the stack view.
     // If it changed, clear the screen.
     last_sizes: Vec<Vec2>,
+    // Rows taken by the menubar on the last frame.
+    // If it changed, clear the screen too.
+    last_menubar_height: usize,
+    menubar_trigger: Option<Event>,
 
     fps: u32,
 
+    // Quit on `Ctrl-C`, before any view sees it.
+    exit_on_ctrl_c: bool,
+
+    // Overrides the colors supported by the backend.
+    color_support: Option<ColorSupport>,
+
     active_screen: ScreenId,
 
     running: bool,
+    // Asked before quitting, if set.
+    on_quit: Option<Rc<OnQuit>>,
+    // Set while `on_quit` runs, so quitting from it doesn't ask again.
+    confirming_quit: bool,
+    // Set when `on_quit` refused an `Event::Exit`: the next one quits.
+    exit_refused: bool,
 
     backend: Box<backend::Backend>,
 
@@ -49,6 +115,23 @@ pub struct Cursive {
     // Sends true or false after each event.
     input_trigger: Sender<backend::InputRequest>,
     expecting_event: bool,
+
+    // Set while events are being recorded.
+    recorder: Option<replay::Recorder>,
+
+    // Runs the futures given to `spawn()`.
+    #[cfg(feature = "async")]
+    executor: Option<Box<Fn(Task)>>,
+}
+
+/// Result of comparing pending events with the global sequences.
+enum SequenceMatch {
+    /// The pending events form a complete sequence.
+    Complete(Callback),
+    /// The pending events start at least one sequence.
+    Prefix,
+    /// No sequence starts with the pending events.
+    None,
 }
 
 /// Describes one of the possible interruptions we should handle.
@@ -64,6 +147,57 @@ enum Interruption {
 /// Identifies a screen in the cursive root.
 pub type ScreenId = usize;
 
+type OnUnhandledEvent = Fn(&mut Cursive, &Event) -> EventResult;
+
+type RawInput = Fn(&mut Cursive, Event);
+
+type OnQuit = Fn(&mut Cursive) -> bool;
+
+/// Identifies a timer.
+///
+/// Returned by [`Cursive::add_timer`], and used to remove the timer.
+///
+/// [`Cursive::add_timer`]: struct.Cursive.html#method.add_timer
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TimerId(usize);
+
+struct Timer {
+    id: TimerId,
+    interval: Duration,
+    next: Instant,
+    callback: Callback,
+}
+
+/// Tooltip of the `TooltipView` under the mouse.
+struct Tooltip {
+    // Gone when the `TooltipView` is dropped.
+    owner: Weak<()>,
+    // Where the mouse stopped.
+    position: Vec2,
+    // When to show the tooltip, `None` once shown or after a click.
+    deadline: Option<Instant>,
+    shown: bool,
+    view: views::Layer<views::TextView>,
+    size: Vec2,
+}
+
+struct Notification {
+    // Timer removing this notification.
+    timer: TimerId,
+    view: views::Layer<views::Panel<views::TextView>>,
+    size: Vec2,
+}
+
+#[cfg(feature = "system-clipboard")]
+fn default_clipboard() -> Box<Clipboard> {
+    Box::new(clipboard::SystemClipboard::new())
+}
+
+#[cfg(not(feature = "system-clipboard"))]
+fn default_clipboard() -> Box<Clipboard> {
+    Box::new(clipboard::LocalClipboard::new())
+}
+
 /// Asynchronous callback function trait.
 ///
 /// Every `FnOnce(&mut Cursive) -> () + Send` automatically
@@ -82,6 +216,213 @@ impl<F: FnOnce(&mut Cursive) -> () + Send> CbFunc for F {
     }
 }
 
+/// Cloneable handle to update the UI from other threads.
+///
+/// Obtained with [`Cursive::update_sink()`]. Each update is a closure, run
+/// on the thread owning the `Cursive` root.
+///
+/// The event loop waits for input and for updates at the same time, so an
+/// update is run as soon as it is sent, without waiting for a key press or
+/// for the next frame from [`set_fps`]. Input keeps being read by the
+/// backend's own thread (see [`Backend::start_input_thread`]); a read
+/// started before an update is not lost, and is handled on a later step.
+///
+/// [`Cursive::update_sink()`]: struct.Cursive.html#method.update_sink
+/// [`set_fps`]: struct.Cursive.html#method.set_fps
+/// [`Backend::start_input_thread`]:
+/// backend/trait.Backend.html#method.start_input_thread
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate cursive;
+/// # use cursive::views::{Dialog, TextView};
+/// # use cursive::Cursive;
+/// # use std::thread;
+/// # fn main() {
+/// let mut siv = Cursive::dummy();
+/// let sink = siv.update_sink();
+///
+/// thread::spawn(move || {
+///     let answer = 6 * 7;
+///     sink.send_update(move |s| {
+///         s.add_layer(Dialog::around(TextView::new(answer.to_string())));
+///     });
+/// });
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct CbSink {
+    sender: Sender<Box<CbFunc>>,
+}
+
+impl CbSink {
+    /// Queues `f`, to be run on the next step of the event loop.
+    ///
+    /// The screen is redrawn after `f` runs.
+    ///
+    /// Returns `false` if the `Cursive` root was dropped, in which case `f`
+    /// will never run.
+    pub fn send_update<F>(&self, f: F) -> bool
+    where
+        F: FnOnce(&mut Cursive) + Send + 'static,
+    {
+        self.sender.send(Box::new(f)).is_ok()
+    }
+
+    /// Wakes the event loop up, to redraw the screen right away.
+    ///
+    /// Useful after changing some shared state read by views, like a
+    /// [`Counter`](utils/struct.Counter.html).
+    ///
+    /// Returns `false` if the `Cursive` root was dropped.
+    pub fn wake(&self) -> bool {
+        self.send_update(|_| ())
+    }
+
+    /// Queues `event`, to be processed on the next step of the event loop.
+    ///
+    /// See [`Cursive::send_event`](struct.Cursive.html#method.send_event).
+    ///
+    /// Returns `false` if the `Cursive` root was dropped.
+    pub fn send_event(&self, event: Event) -> bool {
+        self.send_update(move |s| s.on_event(event))
+    }
+
+    /// Wraps `future` to send its output to `cb`, on the UI thread.
+    ///
+    /// The returned future should be given to an executor. Once `future`
+    /// completes, `cb` is queued like with [`send_update`], and the
+    /// returned future completes too.
+    ///
+    /// Requires the `async` feature.
+    ///
+    /// [`send_update`]: #method.send_update
+    #[cfg(feature = "async")]
+    pub fn bridge<F, C>(&self, future: F, cb: C) -> Bridge<F, C>
+    where
+        F: Future,
+        F::Output: Send + 'static,
+        C: FnOnce(&mut Cursive, F::Output) + Send + 'static,
+    {
+        Bridge {
+            future: Box::pin(future),
+            cb: Some(cb),
+            sink: self.clone(),
+        }
+    }
+}
+
+/// Cloneable handle to ask for the screen to be redrawn.
+///
+/// Obtained with [`Cursive::invalidator()`]. Views with state changing
+/// outside of events, for example from a timer thread or an animation, can
+/// keep one and call [`invalidate()`] after each change.
+///
+/// The event loop wakes up on the first call after a frame is drawn; more
+/// calls before the next frame are free, and drawn only once. It still
+/// respects the limit from [`set_max_fps`].
+///
+/// [`Cursive::invalidator()`]: struct.Cursive.html#method.invalidator
+/// [`invalidate()`]: #method.invalidate
+/// [`set_max_fps`]: struct.Cursive.html#method.set_max_fps
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate cursive;
+/// # use cursive::views::TextView;
+/// # use cursive::Cursive;
+/// # use std::sync::atomic::{AtomicUsize, Ordering};
+/// # use std::sync::Arc;
+/// # use std::thread;
+/// # fn main() {
+/// let mut siv = Cursive::dummy();
+/// let invalidator = siv.invalidator();
+/// let frames = Arc::new(AtomicUsize::new(0));
+///
+/// let counter = Arc::clone(&frames);
+/// thread::spawn(move || {
+///     for _ in 0..100 {
+///         counter.fetch_add(1, Ordering::Relaxed);
+///         invalidator.invalidate();
+///     }
+/// });
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Invalidator {
+    dirty: Arc<AtomicBool>,
+    sender: Sender<Box<CbFunc>>,
+}
+
+impl Invalidator {
+    /// Marks the screen as needing a redraw.
+    ///
+    /// Returns `false` if the `Cursive` root was dropped.
+    pub fn invalidate(&self) -> bool {
+        if self.dirty.swap(true, Ordering::SeqCst) {
+            // Already waking up for the next frame.
+            return true;
+        }
+
+        self.sender.send(Box::new(|_: &mut Cursive| ())).is_ok()
+    }
+
+    /// Returns `true` if a redraw was asked since the last frame.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty.load(Ordering::SeqCst)
+    }
+}
+
+/// Future given to an executor by [`Cursive::spawn()`].
+///
+/// Requires the `async` feature.
+///
+/// [`Cursive::spawn()`]: struct.Cursive.html#method.spawn
+#[cfg(feature = "async")]
+pub type Task = Pin<Box<Future<Output = ()> + Send>>;
+
+/// Future sending the output of another one to the UI thread.
+///
+/// Created by [`CbSink::bridge()`]. Requires the `async` feature.
+///
+/// [`CbSink::bridge()`]: struct.CbSink.html#method.bridge
+#[cfg(feature = "async")]
+pub struct Bridge<F, C> {
+    future: Pin<Box<F>>,
+    // Taken when `future` completes.
+    cb: Option<C>,
+    sink: CbSink,
+}
+
+// `cb` is never pinned.
+#[cfg(feature = "async")]
+impl<F, C> Unpin for Bridge<F, C> {}
+
+#[cfg(feature = "async")]
+impl<F, C> Future for Bridge<F, C>
+where
+    F: Future,
+    F::Output: Send + 'static,
+    C: FnOnce(&mut Cursive, F::Output) + Send + 'static,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.get_mut();
+        let output = match this.future.as_mut().poll(cx) {
+            Poll::Ready(output) => output,
+            Poll::Pending => return Poll::Pending,
+        };
+
+        if let Some(cb) = this.cb.take() {
+            this.sink.send_update(move |s| cb(s, output));
+        }
+        Poll::Ready(())
+    }
+}
+
 #[cfg(feature = "termion-backend")]
 impl Default for Cursive {
     fn default() -> Self {
@@ -89,7 +430,21 @@ impl Default for Cursive {
     }
 }
 
-#[cfg(all(not(feature = "termion-backend"), feature = "pancurses-backend"))]
+#[cfg(all(
+    not(feature = "termion-backend"),
+    feature = "crossterm-backend"
+))]
+impl Default for Cursive {
+    fn default() -> Self {
+        Self::crossterm()
+    }
+}
+
+#[cfg(all(
+    not(feature = "termion-backend"),
+    not(feature = "crossterm-backend"),
+    feature = "pancurses-backend"
+))]
 impl Default for Cursive {
     fn default() -> Self {
         Self::pancurses()
@@ -98,6 +453,7 @@ impl Default for Cursive {
 
 #[cfg(all(
     not(feature = "termion-backend"),
+    not(feature = "crossterm-backend"),
     not(feature = "pancurses-backend"),
     feature = "blt-backend"
 ))]
@@ -109,6 +465,7 @@ impl Default for Cursive {
 
 #[cfg(all(
     not(feature = "termion-backend"),
+    not(feature = "crossterm-backend"),
     not(feature = "pancurses-backend"),
     not(feature = "blt-backend"),
     feature = "ncurses-backend"
@@ -127,6 +484,7 @@ impl Cursive {
     ///   * `Cursive::ncurses()` if the `ncurses-backend` feature is enabled (it is by default).
     ///   * `Cursive::pancurses()` if the `pancurses-backend` feature is enabled.
     ///   * `Cursive::termion()` if the `termion-backend` feature is enabled.
+    ///   * `Cursive::crossterm()` if the `crossterm-backend` feature is enabled.
     ///   * `Cursive::blt()` if the `blt-backend` feature is enabled.
     ///   * `Cursive::dummy()` for a dummy backend, mostly useful for tests.
     /// * If you want to use a third-party backend, then `Cursive::new` is indeed the way to go:
@@ -149,25 +507,61 @@ impl Cursive {
 
         let (input_sink, input_source) = crossbeam_channel::bounded(0);
 
-        let mut backend = backend_init();
+        // Only the cells that changed are sent to the actual backend.
+        let mut backend: Box<backend::Backend> =
+            Box::new(backend::buffer::Buffered::new(backend_init()));
         backend.start_input_thread(event_sink, input_source);
 
-        Cursive {
+        let mut siv = Cursive {
             fps: 0,
             theme,
+            exit_on_ctrl_c: false,
+            color_support: None,
+            max_fps: 0,
+            last_draw: None,
+            draw_pending: false,
+            dirty: Arc::new(AtomicBool::new(false)),
+            repeat_window: None,
+            last_repeat: None,
             screens: vec![views::StackView::new()],
             last_sizes: Vec::new(),
+            last_menubar_height: 0,
+            menubar_trigger: Some(Event::Key(Key::Esc)),
             global_callbacks: HashMap::new(),
+            global_sequences: Vec::new(),
+            on_unhandled_event: None,
+            raw_input: None,
+            timers: Vec::new(),
+            next_timer_id: 0,
+            notifications: Vec::new(),
+            notifications_align: Align::bot_right(),
+            tooltip: None,
+            hovered: None,
             menubar: views::Menubar::new(),
+            clipboard: default_clipboard(),
+            pending_events: Vec::new(),
+            sequence_deadline: None,
+            sequence_timeout: Duration::from_secs(1),
             active_screen: 0,
             running: true,
+            on_quit: None,
+            confirming_quit: false,
+            exit_refused: false,
             cb_source,
             cb_sink,
             event_source,
             backend,
             input_trigger: input_sink,
             expecting_event: false,
-        }
+            recorder: None,
+            #[cfg(feature = "async")]
+            executor: None,
+        };
+
+        // Views may use `Ctrl-C` first, for example to copy a selection.
+        siv.add_global_callback(Event::CtrlChar('c'), Cursive::quit);
+
+        siv
     }
 
     /// Creates a new Cursive root using a ncurses backend.
@@ -188,6 +582,12 @@ impl Cursive {
         Self::new(backend::termion::Backend::init)
     }
 
+    /// Creates a new Cursive root using a crossterm backend.
+    #[cfg(feature = "crossterm-backend")]
+    pub fn crossterm() -> Self {
+        Self::new(backend::crossterm::Backend::init)
+    }
+
     /// Creates a new Cursive root using a bear-lib-terminal backend.
     #[cfg(feature = "blt-backend")]
     pub fn blt() -> Self {
@@ -201,6 +601,13 @@ impl Cursive {
         Self::new(backend::dummy::Backend::init)
     }
 
+    /// Sets the title of the terminal window.
+    ///
+    /// This is not supported by every backend or terminal.
+    pub fn set_window_title(&mut self, title: &str) {
+        self.backend.set_title(title);
+    }
+
     /// Show the debug console.
     ///
     /// Currently, this will show logs if [`::logger::init()`] was called.
@@ -238,10 +645,11 @@ impl Cursive {
     /// callbacks to `self` from other threads.
     ///
     /// Callbacks will be executed in the order
-    /// of arrival on the next event cycle.
+    /// of arrival on the next event cycle. They wake the event loop up, so
+    /// [`set_fps`] is not needed to run them.
     ///
-    /// Note that you currently need to call [`set_fps`] to force cursive to
-    /// regularly check for messages.
+    /// [`update_sink`](#method.update_sink) gives a more convenient handle
+    /// to the same channel.
     ///
     /// # Examples
     ///
@@ -262,6 +670,189 @@ impl Cursive {
         &self.cb_sink
     }
 
+    /// Returns a handle to update the UI from other threads.
+    ///
+    /// Updates go through the same channel as [`cb_sink`](#method.cb_sink),
+    /// and are run in the order they are sent.
+    pub fn update_sink(&self) -> CbSink {
+        CbSink {
+            sender: self.cb_sink.clone(),
+        }
+    }
+
+    /// Queues `event`, as if it came from the user.
+    ///
+    /// It is processed by [`on_event`] on the next step of the event loop,
+    /// so global callbacks, sequences and the focused view all see it. This
+    /// can be called from a callback: the event waits until the callback
+    /// returns.
+    ///
+    /// Events go through the same channel as [`cb_sink`], in the order they
+    /// are sent. Input from the backend is handled in between, as it comes.
+    /// Use [`update_sink`] to send events from other threads.
+    ///
+    /// [`on_event`]: #method.on_event
+    /// [`cb_sink`]: #method.cb_sink
+    /// [`update_sink`]: #method.update_sink
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cursive::Cursive;
+    /// use cursive::event::{Event, Key};
+    ///
+    /// let mut siv = Cursive::dummy();
+    /// siv.add_global_callback('q', |s| s.quit());
+    ///
+    /// // A demo that moves around, then quits.
+    /// siv.send_event(Event::Key(Key::Down));
+    /// siv.send_event(Event::Char('q'));
+    /// siv.run();
+    /// ```
+    pub fn send_event(&self, event: Event) {
+        // We hold the receiver, so this can't fail.
+        self.cb_sink
+            .send(Box::new(move |s: &mut Cursive| s.on_event(event)))
+            .unwrap();
+    }
+
+    /// Returns the descriptions of the focused views, for assistive tools.
+    ///
+    /// Only views wrapped in an [`AccessibleView`], for example with
+    /// [`a11y_label()`], are described. They go from the outermost to the
+    /// innermost one.
+    ///
+    /// [`AccessibleView`]: views/struct.AccessibleView.html
+    /// [`a11y_label()`]: view/trait.Accessible.html#method.a11y_label
+    pub fn focused_description(&mut self) -> Vec<views::Description> {
+        let mut descriptions = Vec::new();
+        self.follow_focus(&mut |value, _| {
+            let description = value.downcast_ref::<views::Description>();
+            if let Some(description) = description {
+                descriptions.push(description.clone());
+            }
+        });
+        descriptions
+    }
+
+    /// Describes the views of the active screen, for debugging.
+    ///
+    /// Each view is given with its position and its size, and the size it
+    /// asked for if it remembers it. The result can be printed as an
+    /// indented tree, or serialized with the `serde` feature.
+    ///
+    /// Nothing is laid out or drawn: this describes the screen as it was
+    /// last drawn.
+    ///
+    /// Views only list their children if they implement
+    /// [`View::debug_layout()`], like the views in this crate do.
+    ///
+    /// [`View::debug_layout()`]: view/trait.View.html#method.debug_layout
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cursive::views::TextView;
+    /// # use cursive::Cursive;
+    /// # let mut siv = Cursive::dummy();
+    /// siv.add_layer(TextView::new("Hello"));
+    /// let layout = siv.dump_layout();
+    /// assert_eq!(layout.children[0].type_name, "TextView");
+    /// println!("{}", layout);
+    /// ```
+    pub fn dump_layout(&mut self) -> view::LayoutNode {
+        let offset = Vec2::new(0, self.menubar_height());
+        let size = self.screen_size().saturating_sub(offset);
+        let focused = !self.menubar.receive_events();
+        view::LayoutTree::describe(self.screen_mut(), offset, size, focused)
+    }
+
+    /// Returns a handle to ask for a redraw, from any thread.
+    ///
+    /// See [`Invalidator`](struct.Invalidator.html).
+    pub fn invalidator(&self) -> Invalidator {
+        Invalidator {
+            dirty: Arc::clone(&self.dirty),
+            sender: self.cb_sink.clone(),
+        }
+    }
+
+    /// Marks the screen as needing a redraw.
+    ///
+    /// The event loop draws the next frame without waiting for input, as
+    /// soon as [`set_max_fps`] allows it.
+    ///
+    /// [`set_max_fps`]: #method.set_max_fps
+    pub fn invalidate(&mut self) {
+        self.dirty.store(true, Ordering::SeqCst);
+    }
+
+    /// Starts recording the events given to [`on_event`].
+    ///
+    /// This includes the input from the backend. If a recording was
+    /// already started, it is dropped and a new one begins.
+    ///
+    /// See the [`replay`] module to play the events back.
+    ///
+    /// [`on_event`]: #method.on_event
+    /// [`replay`]: replay/index.html
+    pub fn start_recording(&mut self) {
+        self.recorder = Some(replay::Recorder::new());
+    }
+
+    /// Stops recording events, and returns them.
+    ///
+    /// Returns `None` if no recording was started.
+    pub fn stop_recording(&mut self) -> Option<EventLog> {
+        self.recorder.take().map(replay::Recorder::finish)
+    }
+
+    /// Returns `true` if events are being recorded.
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
+    }
+
+    /// Sets the executor running the futures given to [`spawn`].
+    ///
+    /// Cursive doesn't come with a runtime: `executor` should hand the task
+    /// to one, like `tokio::spawn`.
+    ///
+    /// Requires the `async` feature.
+    ///
+    /// [`spawn`]: #method.spawn
+    #[cfg(feature = "async")]
+    pub fn set_executor<E>(&mut self, executor: E)
+    where
+        E: Fn(Task) + 'static,
+    {
+        self.executor = Some(Box::new(executor));
+    }
+
+    /// Runs `future` on the executor, then gives its output to `cb`.
+    ///
+    /// `cb` runs on the event loop, so it can update the views.
+    ///
+    /// Requires the `async` feature.
+    ///
+    /// # Panics
+    ///
+    /// If no executor was set with [`set_executor`].
+    ///
+    /// [`set_executor`]: #method.set_executor
+    #[cfg(feature = "async")]
+    pub fn spawn<F, C>(&self, future: F, cb: C)
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+        C: FnOnce(&mut Cursive, F::Output) + Send + 'static,
+    {
+        let executor = self
+            .executor
+            .as_ref()
+            .expect("spawn() requires an executor; see set_executor()");
+        executor(Box::pin(self.update_sink().bridge(future, cb)));
+    }
+
     /// Selects the menubar.
     pub fn select_menubar(&mut self) {
         self.menubar.take_focus(direction::Direction::none());
@@ -270,11 +861,65 @@ impl Cursive {
     /// Sets the menubar autohide feature.
     ///
     /// * When enabled (default), the menu is only visible when selected.
+    ///   The views use the whole screen while it's hidden, and move down
+    ///   one row while it's shown. It hides again after a selection, or
+    ///   when it loses the focus.
     /// * When disabled, the menu is always visible and reserves the top row.
+    ///
+    /// In both cases, the menubar is selected by the [trigger event]. When
+    /// it's visible, clicking it selects it too; while hidden, clicks on the
+    /// top row go to the views.
+    ///
+    /// [trigger event]: #method.set_menubar_trigger
     pub fn set_autohide_menu(&mut self, autohide: bool) {
         self.menubar.autohide = autohide;
     }
 
+    /// Sets the event selecting the menubar.
+    ///
+    /// Defaults to `Key::Esc`. It only applies when no view and no global
+    /// callback used the event, and when the menubar has items. Use `None`
+    /// to disable it.
+    ///
+    /// Terminals don't report a lone `Alt` key press.
+    pub fn set_menubar_trigger(&mut self, trigger: Option<Event>) {
+        self.menubar_trigger = trigger;
+    }
+
+    /// Sets whether `Ctrl-C` always quits.
+    ///
+    /// Backends send `Ctrl-C` as `Event::CtrlChar('c')`. By default, it is
+    /// first given to the views, and quits through a global callback if
+    /// they ignore it. Clear this callback with
+    /// [`clear_global_callbacks()`] to handle `Ctrl-C` differently.
+    ///
+    /// When enabled, `Ctrl-C` quits right away instead, and no view nor
+    /// callback receives it. Defaults to `false`.
+    ///
+    /// [`clear_global_callbacks()`]: #method.clear_global_callbacks
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cursive::Cursive;
+    /// # use cursive::event::Event;
+    /// # use cursive::views::Dialog;
+    /// let mut siv = Cursive::dummy();
+    ///
+    /// // Ask before quitting.
+    /// siv.clear_global_callbacks(Event::CtrlChar('c'));
+    /// siv.add_global_callback(Event::CtrlChar('c'), |s| {
+    ///     s.add_layer(
+    ///         Dialog::text("Really quit?")
+    ///             .button("Yes", Cursive::quit)
+    ///             .dismiss_button("No"),
+    ///     );
+    /// });
+    /// ```
+    pub fn set_exit_on_ctrl_c(&mut self, exit: bool) {
+        self.exit_on_ctrl_c = exit;
+    }
+
     /// Access the menu tree used by the menubar.
     ///
     /// This allows to add menu items to the menubar.
@@ -340,12 +985,69 @@ impl Cursive {
         self.clear();
     }
 
+    /// Updates the current theme in place, then redraws the screen.
+    ///
+    /// This can be used to change a few colors of the palette, including
+    /// custom keys and namespaces, without rebuilding a whole theme.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cursive::Cursive;
+    /// # use cursive::theme::{BaseColor, Color, PaletteColor};
+    /// # let mut siv = Cursive::dummy();
+    /// siv.with_theme(|theme| {
+    ///     theme.palette[PaletteColor::View] = Color::Dark(BaseColor::Black);
+    ///     theme.palette.set_color("my_title", Color::Rgb(255, 0, 0));
+    /// });
+    /// ```
+    pub fn with_theme<F: FnOnce(&mut theme::Theme)>(&mut self, f: F) {
+        f(&mut self.theme);
+        // Layers only clear the screen when their size changes, so force a
+        // complete redraw with the new colors.
+        self.last_sizes.clear();
+        self.clear();
+    }
+
     /// Clears the screen.
     ///
     /// Users rarely have to call this directly.
     pub fn clear(&self) {
-        self.backend
-            .clear(self.theme.palette[theme::PaletteColor::Background]);
+        self.printer(&*self.backend).clear();
+    }
+
+    /// Overrides the colors the backend is assumed to display.
+    ///
+    /// By default, this comes from [`Backend::color_support()`]. For
+    /// example, with `ColorSupport::Ansi16`, light colors are drawn as bold
+    /// dark colors, and other colors are replaced by the closest base color.
+    /// Give `None` to go back to the backend's value.
+    ///
+    /// [`Backend::color_support()`]:
+    /// backend/trait.Backend.html#method.color_support
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cursive::Cursive;
+    /// use cursive::backend::ColorSupport;
+    ///
+    /// let mut siv = Cursive::dummy();
+    /// siv.set_color_support(Some(ColorSupport::Ansi16));
+    /// assert_eq!(siv.color_support(), ColorSupport::Ansi16);
+    /// ```
+    pub fn set_color_support(&mut self, support: Option<ColorSupport>) {
+        self.color_support = support;
+        self.last_sizes.clear();
+        self.clear();
+    }
+
+    /// Returns the colors used to draw the screen.
+    ///
+    /// See [`set_color_support()`](#method.set_color_support).
+    pub fn color_support(&self) -> ColorSupport {
+        self.color_support
+            .unwrap_or_else(|| self.backend.color_support())
     }
 
     /// Loads a theme from the given file.
@@ -360,27 +1062,184 @@ impl Cursive {
     /// Loads a theme from the given string content.
     ///
     /// Content must be valid toml.
+    ///
+    /// This is the same as [`set_theme_from_toml`].
+    ///
+    /// [`set_theme_from_toml`]: #method.set_theme_from_toml
     pub fn load_toml(&mut self, content: &str) -> Result<(), theme::Error> {
+        self.set_theme_from_toml(content)
+    }
+
+    /// Parses a theme from toml content and sets it as active.
+    ///
+    /// This does not touch the filesystem, so themes can be embedded in the
+    /// application, for example with `include_str!`. On error, the current
+    /// theme is kept.
+    pub fn set_theme_from_toml(
+        &mut self, content: &str,
+    ) -> Result<(), theme::Error> {
         theme::load_toml(content).map(|theme| self.set_theme(theme))
     }
 
-    /// Sets the refresh rate, in frames per second.
+    /// Loads a theme from the given file again, and redraws everything.
     ///
-    /// Regularly redraws everything, even when no input is given.
+    /// Unlike [`load_theme_file`], this forces a complete redraw, so every
+    /// view picks up the new colors. On error, the current theme is kept.
     ///
-    /// You currently need this to regularly check
-    /// for events sent using [`cb_sink`].
+    /// [`load_theme_file`]: #method.load_theme_file
+    pub fn reload_theme_file<P: AsRef<Path>>(
+        &mut self, filename: P,
+    ) -> Result<(), theme::Error> {
+        theme::load_theme_file(filename)
+            .map(|theme| self.with_theme(|current| *current = theme))
+    }
+
+    /// Reloads a theme file whenever it changes.
     ///
-    /// Between 0 and 1000. Call with `fps = 0` to disable (default value).
+    /// The modification time of `filename` is checked every `interval`.
+    /// This doesn't load the theme right away: call [`load_theme_file`]
+    /// first.
     ///
-    /// [`cb_sink`]: #method.cb_sink
-    pub fn set_fps(&mut self, fps: u32) {
-        // self.backend.set_refresh_rate(fps)
-        self.fps = fps;
-    }
+    /// If the new content is invalid, the error is shown in a notification
+    /// and the current theme is kept. Only `filename` is watched, not its
+    /// `parent` theme.
+    ///
+    /// Returns the id of the timer doing the checks: give it to
+    /// [`remove_timer`] to stop watching the file.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use cursive::Cursive;
+    /// # use std::time::Duration;
+    /// # let mut siv = Cursive::dummy();
+    /// siv.load_theme_file("assets/style.toml").unwrap();
+    /// siv.watch_theme_file("assets/style.toml", Duration::from_millis(500));
+    /// ```
+    ///
+    /// [`load_theme_file`]: #method.load_theme_file
+    /// [`remove_timer`]: #method.remove_timer
+    pub fn watch_theme_file<P: AsRef<Path>>(
+        &mut self, filename: P, interval: Duration,
+    ) -> TimerId {
+        let filename = filename.as_ref().to_path_buf();
+        let modified = move |filename: &Path| {
+            fs::metadata(filename).and_then(|m| m.modified()).ok()
+        };
+        let mut last = modified(&filename);
 
-    /// Returns a reference to the currently active screen.
-    pub fn screen(&self) -> &views::StackView {
+        self.add_timer(interval, move |s| {
+            // The file may be missing for a moment while it's saved.
+            let current = modified(&filename);
+            if current.is_none() || current == last {
+                return;
+            }
+            last = current;
+
+            if let Err(err) = s.reload_theme_file(&filename) {
+                s.notify(err.to_string(), Duration::from_secs(5));
+            }
+        })
+    }
+
+    /// Sets the refresh rate, in frames per second.
+    ///
+    /// Regularly redraws everything, even when no input is given.
+    ///
+    /// This is useful for views showing some state changed outside of
+    /// cursive. Callbacks sent using [`cb_sink`] don't need it.
+    ///
+    /// Between 0 and 1000. Call with `fps = 0` to disable (default value).
+    ///
+    /// To run some code at regular intervals, use [`add_timer`] instead.
+    /// To limit how often the screen is redrawn, see [`set_max_fps`].
+    ///
+    /// [`cb_sink`]: #method.cb_sink
+    /// [`add_timer`]: #method.add_timer
+    /// [`set_max_fps`]: #method.set_max_fps
+    pub fn set_fps(&mut self, fps: u32) {
+        // self.backend.set_refresh_rate(fps)
+        self.fps = fps;
+    }
+
+    /// Draws the screen at most `max_fps` times per second.
+    ///
+    /// By default, the screen is redrawn after every batch of events. With
+    /// a fast stream of input, like mouse drags, this can take a lot of
+    /// CPU. With a limit, events are still handled as soon as they arrive,
+    /// but drawing their result is delayed to the next allowed frame.
+    ///
+    /// This limit applies to every frame: the ones requested by
+    /// [`set_fps`], timers or animated layers may be delayed too.
+    ///
+    /// Call with `max_fps = 0` to remove the limit (default value).
+    ///
+    /// [`set_fps`]: #method.set_fps
+    pub fn set_max_fps(&mut self, max_fps: u32) {
+        self.max_fps = max_fps;
+    }
+
+    /// Merges bursts of identical navigation keys.
+    ///
+    /// When a key is held down, the terminal repeats it, possibly faster
+    /// than the views are drawn. The extra events pile up, and keep moving
+    /// the selection after the key is released. With a `window`, an arrow
+    /// or page key received less than `window` after the same key is
+    /// dropped. Other events, and different keys, are never dropped.
+    ///
+    /// Keys repeated at a normal rate are still handled: `window` should be
+    /// shorter than the repeat interval, like 20ms.
+    ///
+    /// Call with `None` to handle every event (default value).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cursive::Cursive;
+    /// # use cursive::views::SelectView;
+    /// # use std::time::Duration;
+    /// let mut siv = Cursive::dummy();
+    /// siv.add_layer(SelectView::new().with_all((0..1000).map(|i| {
+    ///     (i.to_string(), i)
+    /// })));
+    ///
+    /// // Holding `Down` stops when it is released.
+    /// siv.set_repeat_coalescing(Some(Duration::from_millis(20)));
+    /// ```
+    pub fn set_repeat_coalescing(&mut self, window: Option<Duration>) {
+        self.repeat_window = window;
+        self.last_repeat = None;
+    }
+
+    // Returns `true` if `event` repeats a key handled just before.
+    fn is_repeat(&mut self, event: &Event) -> bool {
+        let window = match self.repeat_window {
+            Some(window) if is_navigation(event) => window,
+            _ => return false,
+        };
+
+        let now = Instant::now();
+        if let Some((ref last, time)) = self.last_repeat {
+            if last == event && now < time + window {
+                return true;
+            }
+        }
+
+        self.last_repeat = Some((event.clone(), now));
+        false
+    }
+
+    // When the next frame can be drawn, if it has to wait.
+    fn next_draw(&self) -> Option<Instant> {
+        if self.max_fps == 0 {
+            return None;
+        }
+        let interval = Duration::from_secs(1) / self.max_fps;
+        self.last_draw.map(|last| last + interval)
+    }
+
+    /// Returns a reference to the currently active screen.
+    pub fn screen(&self) -> &views::StackView {
         let id = self.active_screen;
         &self.screens[id]
     }
@@ -496,6 +1355,56 @@ impl Cursive {
         self.call_on(&view::Selector::Id(id), callback)
     }
 
+    /// Runs a closure on the first focused view of type `V`.
+    ///
+    /// The focus is followed from the active screen down to the innermost
+    /// focused view, without drawing. Returns `None` if no view of this
+    /// type has the focus, for example while the menubar is active.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cursive::Cursive;
+    /// # use cursive::views::{EditView, LinearLayout, TextView};
+    /// # let mut siv = Cursive::dummy();
+    /// siv.add_layer(
+    ///     LinearLayout::vertical()
+    ///         .child(TextView::new("Name:"))
+    ///         .child(EditView::new()),
+    /// );
+    ///
+    /// siv.call_on_focused(|view: &mut EditView| view.set_content("Bob"));
+    /// ```
+    pub fn call_on_focused<V, F, R>(&mut self, callback: F) -> Option<R>
+    where
+        V: View + Any,
+        F: FnOnce(&mut V) -> R,
+    {
+        let mut callback = Some(callback);
+        let mut result = None;
+        self.follow_focus(&mut |view, _| {
+            if let Some(view) = view.downcast_mut::<V>() {
+                if let Some(callback) = callback.take() {
+                    result = Some(callback(view));
+                }
+            }
+        });
+        result
+    }
+
+    // Gives the focused views of the active screen to `visitor`, outermost
+    // first, with their position on the screen.
+    pub(crate) fn follow_focus(
+        &mut self, visitor: &mut FnMut(&mut Any, XY<isize>),
+    ) {
+        if self.menubar.receive_events() {
+            return;
+        }
+
+        let offset = Vec2::new(0, self.menubar_height()).signed();
+        view::FocusPath::follow(self.screen_mut(), offset, visitor);
+    }
+
     /// Convenient method to find a view wrapped in [`IdView`].
     ///
     /// This looks for a `IdView<V>` with the given ID, and return
@@ -607,6 +1516,431 @@ impl Cursive {
         self.global_callbacks.remove(&event);
     }
 
+    /// Adds a global callback triggered by a sequence of events.
+    ///
+    /// The callback runs when all `events` are received in order, each
+    /// within the [sequence timeout] of the previous one. This can be used
+    /// for prefix shortcuts like `Ctrl-X Ctrl-S`.
+    ///
+    /// Unlike other global callbacks, sequences are checked before the
+    /// focused view gets the events. Events that start a sequence are held
+    /// back until the sequence either completes, or fails because of a
+    /// different event or a timeout. The held back events are then
+    /// processed as usual, so they are not lost.
+    ///
+    /// A sequence that starts with another registered sequence will never
+    /// be triggered.
+    ///
+    /// [sequence timeout]: #method.set_sequence_timeout
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate cursive;
+    /// # use cursive::*;
+    /// # use cursive::event::Event;
+    /// # fn main() {
+    /// let mut siv = Cursive::dummy();
+    ///
+    /// siv.add_global_sequence(
+    ///     vec![Event::CtrlChar('x'), Event::CtrlChar('c')],
+    ///     |s| s.quit(),
+    /// );
+    /// # }
+    /// ```
+    pub fn add_global_sequence<F, I>(&mut self, events: I, cb: F)
+    where
+        F: FnMut(&mut Cursive) + 'static,
+        I: IntoIterator,
+        I::Item: Into<Event>,
+    {
+        let events: Vec<Event> = events.into_iter().map(Into::into).collect();
+        assert!(!events.is_empty(), "a sequence needs at least one event");
+
+        self.global_sequences
+            .push((events, Callback::from_fn_mut(cb)));
+    }
+
+    /// Removes any callback tied to the given sequence of events.
+    pub fn clear_global_sequence<I>(&mut self, events: I)
+    where
+        I: IntoIterator,
+        I::Item: Into<Event>,
+    {
+        let events: Vec<Event> = events.into_iter().map(Into::into).collect();
+        self.global_sequences
+            .retain(|(sequence, _)| *sequence != events);
+    }
+
+    /// Sets a callback for events that nothing else handled.
+    ///
+    /// It will be called with every event ignored by the views, for which
+    /// no global callback is registered. This includes `Event::Refresh`
+    /// and `Event::WindowResize`, which are still processed as usual
+    /// before reaching the views.
+    ///
+    /// The returned `EventResult` is processed like the one from a view.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate cursive;
+    /// # use cursive::*;
+    /// # use cursive::event::EventResult;
+    /// # fn main() {
+    /// let mut siv = Cursive::dummy();
+    ///
+    /// siv.set_on_unhandled_event(|_, event| {
+    ///     eprintln!("Unhandled event: {:?}", event);
+    ///     EventResult::Ignored
+    /// });
+    /// # }
+    /// ```
+    pub fn set_on_unhandled_event<F>(&mut self, f: F)
+    where
+        F: Fn(&mut Cursive, &Event) -> EventResult + 'static,
+    {
+        self.on_unhandled_event = Some(Rc::new(f));
+    }
+
+    /// Removes the callback set by [`set_on_unhandled_event`].
+    ///
+    /// [`set_on_unhandled_event`]: #method.set_on_unhandled_event
+    pub fn clear_on_unhandled_event(&mut self) {
+        self.on_unhandled_event = None;
+    }
+
+    /// Sends all the input to `f`, until [`exit_raw_input`] is called.
+    ///
+    /// Events skip everything else: the views, the menubar, the global
+    /// callbacks and sequences, even `Ctrl-C`. This can be used to forward
+    /// the keys to another program. The views stay on the screen, and are
+    /// still redrawn.
+    ///
+    /// `Event::WindowResize` is also processed as usual, so the views keep
+    /// fitting the screen. `Event::Refresh` and `Event::Exit` are not sent
+    /// to `f`.
+    ///
+    /// Events of an unfinished global sequence are processed first.
+    ///
+    /// [`exit_raw_input`]: #method.exit_raw_input
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cursive::event::{Event, Key};
+    /// # use cursive::Cursive;
+    /// # let mut siv = Cursive::dummy();
+    /// siv.enter_raw_input(|s, event| {
+    ///     if event == Event::Key(Key::Esc) {
+    ///         s.exit_raw_input();
+    ///     } else {
+    ///         // Forward the event...
+    ///     }
+    /// });
+    /// ```
+    pub fn enter_raw_input<F>(&mut self, f: F)
+    where
+        F: 'static + Fn(&mut Cursive, Event),
+    {
+        self.sequence_deadline = None;
+        let events: Vec<_> = self.pending_events.drain(..).collect();
+        for event in events {
+            self.dispatch_event(event);
+        }
+
+        self.raw_input = Some(Rc::new(f));
+    }
+
+    /// Gives the input back to the views, after [`enter_raw_input`].
+    ///
+    /// [`enter_raw_input`]: #method.enter_raw_input
+    pub fn exit_raw_input(&mut self) {
+        self.raw_input = None;
+    }
+
+    /// Returns `true` while the input is sent to the callback given to
+    /// [`enter_raw_input`].
+    ///
+    /// [`enter_raw_input`]: #method.enter_raw_input
+    pub fn is_raw_input(&self) -> bool {
+        self.raw_input.is_some()
+    }
+
+    /// Adds a callback called at regular intervals.
+    ///
+    /// The event loop wakes up in time to run the callback every
+    /// `interval`, then redraws the screen. This doesn't depend on
+    /// [`set_fps`]: it only controls how often the screen is redrawn when
+    /// nothing else happens.
+    ///
+    /// Ticks are scheduled from the previous one rather than from when the
+    /// callback actually ran, so timers don't drift. If the event loop was
+    /// busy for more than one interval, the missed ticks are skipped.
+    ///
+    /// Returns an id to give to [`remove_timer`].
+    ///
+    /// # Panics
+    ///
+    /// If `interval` is zero.
+    ///
+    /// [`set_fps`]: #method.set_fps
+    /// [`remove_timer`]: #method.remove_timer
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate cursive;
+    /// # use cursive::*;
+    /// # use std::time::Duration;
+    /// # fn main() {
+    /// let mut siv = Cursive::dummy();
+    ///
+    /// let timer = siv.add_timer(Duration::from_secs(1), |s| {
+    ///     s.call_on_id("clock", |view: &mut views::TextView| {
+    ///         view.set_content("Tick!");
+    ///     });
+    /// });
+    /// # }
+    /// ```
+    pub fn add_timer<F>(&mut self, interval: Duration, cb: F) -> TimerId
+    where
+        F: FnMut(&mut Cursive) + 'static,
+    {
+        assert!(
+            interval > Duration::from_secs(0),
+            "a timer interval cannot be zero"
+        );
+
+        let id = TimerId(self.next_timer_id);
+        self.next_timer_id += 1;
+
+        self.timers.push(Timer {
+            id,
+            interval,
+            next: Instant::now() + interval,
+            callback: Callback::from_fn_mut(cb),
+        });
+
+        id
+    }
+
+    /// Removes a timer added with [`add_timer`].
+    ///
+    /// Returns `false` if the timer was already removed.
+    ///
+    /// [`add_timer`]: #method.add_timer
+    pub fn remove_timer(&mut self, id: TimerId) -> bool {
+        let len = self.timers.len();
+        self.timers.retain(|timer| timer.id != id);
+        self.timers.len() != len
+    }
+
+    // Runs the callbacks of the timers that are due.
+    fn run_timers(&mut self) {
+        let now = Instant::now();
+        let mut callbacks = Vec::new();
+
+        for timer in &mut self.timers {
+            if timer.next <= now {
+                while timer.next <= now {
+                    timer.next += timer.interval;
+                }
+                callbacks.push((timer.id, timer.callback.clone()));
+            }
+        }
+
+        for (id, cb) in callbacks {
+            // An earlier callback may have removed this timer.
+            if self.timers.iter().any(|timer| timer.id == id) {
+                cb(self);
+            }
+        }
+    }
+
+    // Sends a `Hover` event to the views under the mouse.
+    fn hover(&mut self, position: Vec2) {
+        self.hovered = None;
+        if self.menubar.receive_events() {
+            return;
+        }
+
+        let offset = self.menubar_height();
+        let event = Event::Mouse {
+            offset: Vec2::zero(),
+            position,
+            event: MouseEvent::Hover,
+        };
+        self.screen_mut()
+            .on_event(event.relativized((0, offset)))
+            .process(self);
+    }
+
+    // Called by the `TooltipView` under the mouse, on `Hover` events.
+    pub(crate) fn set_hovered(&mut self, hovered: views::Hovered) {
+        self.hovered = Some(hovered);
+    }
+
+    // Follows the `TooltipView` under the mouse after a mouse event.
+    fn update_tooltip(&mut self, event: MouseEvent, position: Vec2) {
+        let hovered = match self.hovered.take() {
+            Some(hovered) => hovered,
+            None => return self.hide_tooltip(),
+        };
+
+        // After a click, wait for the mouse to move before showing it again.
+        let deadline = match event {
+            MouseEvent::Press(_)
+            | MouseEvent::DoubleClick(_)
+            | MouseEvent::TripleClick(_) => None,
+            _ => Some(Instant::now() + hovered.delay),
+        };
+        let same = self
+            .tooltip
+            .as_ref()
+            .filter(|tooltip| {
+                tooltip.position == position
+                    && Weak::ptr_eq(&tooltip.owner, &hovered.owner)
+            })
+            .is_some();
+        if same && deadline.is_some() {
+            return;
+        }
+
+        self.hide_tooltip();
+        self.tooltip = Some(Tooltip {
+            owner: hovered.owner,
+            position,
+            deadline,
+            shown: false,
+            view: views::Layer::new(views::TextView::new(hovered.text)),
+            size: Vec2::zero(),
+        });
+    }
+
+    // Shows the pending tooltip once its delay is over.
+    fn check_tooltip(&mut self) {
+        if let Some(ref mut tooltip) = self.tooltip {
+            let now = Instant::now();
+            if tooltip.deadline.filter(|&deadline| deadline <= now).is_some()
+            {
+                tooltip.deadline = None;
+                tooltip.shown = true;
+            }
+        }
+    }
+
+    fn hide_tooltip(&mut self) {
+        if let Some(tooltip) = self.tooltip.take() {
+            if tooltip.shown {
+                // Repaint what was behind the tooltip.
+                self.clear();
+            }
+        }
+    }
+
+    /// Shows a message for the given duration.
+    ///
+    /// The message is printed in a small panel, drawn above every layer in
+    /// the corner set with [`set_notifications_align`]. It never takes the
+    /// focus, so the user can keep using the application. Messages shown
+    /// at the same time are stacked.
+    ///
+    /// [`set_notifications_align`]: #method.set_notifications_align
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate cursive;
+    /// # use cursive::*;
+    /// # use std::time::Duration;
+    /// # fn main() {
+    /// let mut siv = Cursive::dummy();
+    ///
+    /// siv.add_global_callback('s', |s| {
+    ///     s.notify("Saved!", Duration::from_secs(2));
+    /// });
+    /// # }
+    /// ```
+    pub fn notify<S>(&mut self, text: S, duration: Duration)
+    where
+        S: Into<StyledString>,
+    {
+        // The callback needs the id, which we only get once it's added.
+        let id = Rc::new(Cell::new(None));
+        let timer = self.add_timer(duration, {
+            let id = Rc::clone(&id);
+            move |s| {
+                if let Some(id) = id.get() {
+                    s.remove_notification(id);
+                }
+            }
+        });
+        id.set(Some(timer));
+
+        self.notifications.push(Notification {
+            timer,
+            view: views::Layer::new(views::Panel::new(views::TextView::new(
+                text,
+            ))),
+            size: Vec2::zero(),
+        });
+    }
+
+    /// Sets where notifications are shown.
+    ///
+    /// Defaults to the bottom-right corner.
+    pub fn set_notifications_align(&mut self, align: Align) {
+        self.notifications_align = align;
+        self.clear();
+    }
+
+    /// Removes all notifications currently shown.
+    pub fn clear_notifications(&mut self) {
+        let timers: Vec<_> =
+            self.notifications.iter().map(|n| n.timer).collect();
+        for timer in timers {
+            self.remove_notification(timer);
+        }
+    }
+
+    fn remove_notification(&mut self, timer: TimerId) {
+        self.remove_timer(timer);
+        self.notifications.retain(|n| n.timer != timer);
+
+        // Repaint what was behind the notification.
+        self.clear();
+    }
+
+    /// Sets the clipboard used by views to copy and paste text.
+    ///
+    /// Defaults to a [`LocalClipboard`], only shared within the
+    /// application, or to a [`SystemClipboard`] with the
+    /// `system-clipboard` feature.
+    ///
+    /// [`LocalClipboard`]: utils/clipboard/struct.LocalClipboard.html
+    /// [`SystemClipboard`]: utils/clipboard/struct.SystemClipboard.html
+    pub fn set_clipboard<C: Clipboard + 'static>(&mut self, clipboard: C) {
+        self.clipboard = Box::new(clipboard);
+    }
+
+    /// Returns the clipboard used to copy and paste text.
+    pub fn clipboard(&self) -> &Clipboard {
+        &*self.clipboard
+    }
+
+    /// Returns a mutable reference to the clipboard.
+    pub fn clipboard_mut(&mut self) -> &mut Clipboard {
+        &mut *self.clipboard
+    }
+
+    /// Sets the maximum delay between two events of a global sequence.
+    ///
+    /// Defaults to one second.
+    pub fn set_sequence_timeout(&mut self, timeout: Duration) {
+        self.sequence_timeout = timeout;
+    }
+
     /// Add a layer to the current screen.
     ///
     /// # Examples
@@ -639,7 +1973,8 @@ impl Cursive {
 
     /// Convenient method to remove a layer from the current screen.
     pub fn pop_layer(&mut self) -> Option<Box<View>> {
-        self.screen_mut().pop_layer()
+        let id = self.active_screen;
+        self.screens[id].pop_layer_animated(&self.theme)
     }
 
     /// Convenient stub forwarding layer repositioning.
@@ -649,6 +1984,52 @@ impl Cursive {
         self.screen_mut().reposition_layer(layer, position);
     }
 
+    /// Returns a handle to the top-most layer of the current screen.
+    ///
+    /// Call it right after `add_layer()` to remove or move this layer later,
+    /// even if others were added on top of it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cursive::views::{Dialog, TextView};
+    /// # use cursive::Cursive;
+    /// # let mut siv = Cursive::dummy();
+    /// siv.add_layer(TextView::new("Loading..."));
+    /// let status = siv.top_layer_handle().unwrap();
+    /// siv.add_layer(Dialog::info("Hello!"));
+    ///
+    /// // Later, removes the status without touching the dialog.
+    /// siv.remove_layer_by_handle(status).unwrap();
+    /// ```
+    pub fn top_layer_handle(&self) -> Option<LayerHandle> {
+        self.screen().layer_handle(LayerPosition::FromFront(0))
+    }
+
+    /// Removes the layer with the given handle from the current screen.
+    ///
+    /// See [`StackView::remove_layer_by_handle()`].
+    ///
+    /// [`StackView::remove_layer_by_handle()`]:
+    /// views/struct.StackView.html#method.remove_layer_by_handle
+    pub fn remove_layer_by_handle(
+        &mut self, handle: LayerHandle,
+    ) -> Result<Box<View>, StaleLayerHandle> {
+        self.screen_mut().remove_layer_by_handle(handle)
+    }
+
+    /// Moves the layer with the given handle on the current screen.
+    ///
+    /// See [`StackView::reposition_layer_by_handle()`].
+    ///
+    /// [`StackView::reposition_layer_by_handle()`]:
+    /// views/struct.StackView.html#method.reposition_layer_by_handle
+    pub fn reposition_layer_by_handle(
+        &mut self, handle: LayerHandle, position: Position,
+    ) -> Result<(), StaleLayerHandle> {
+        self.screen_mut().reposition_layer_by_handle(handle, position)
+    }
+
     fn peek(&mut self) -> Option<Interruption> {
         // First, try a callback
         select! {
@@ -683,13 +2064,48 @@ impl Cursive {
             self.expecting_event = true;
         }
 
-        let timeout = if self.fps > 0 {
+        let mut timeout = if self.fps > 0 {
             Duration::from_millis(1000 / self.fps as u64)
         } else {
             // Defaults to 1 refresh per hour.
             Duration::from_secs(3600)
         };
 
+        // Wake up in time to give up on a pending sequence, or to run the
+        // next timer.
+        let deadline = self
+            .sequence_deadline
+            .into_iter()
+            .chain(self.timers.iter().map(|timer| timer.next))
+            .chain(self.tooltip.as_ref().and_then(|t| t.deadline))
+            .min();
+        if let Some(deadline) = deadline {
+            let now = Instant::now();
+            let remaining = if deadline > now {
+                deadline - now
+            } else {
+                Duration::from_secs(0)
+            };
+            timeout = timeout.min(remaining);
+        }
+
+        // Keep drawing frames while layers are moving.
+        if self.screen().is_animating() {
+            timeout = timeout.min(Duration::from_millis(1000 / 30));
+        }
+
+        // Draw the frame that was delayed by `max_fps`, or that was asked
+        // for with `invalidate()`.
+        if self.draw_pending || self.dirty.load(Ordering::SeqCst) {
+            let now = Instant::now();
+            let next_draw = self.next_draw().unwrap_or(now);
+            timeout = timeout.min(if next_draw > now {
+                next_draw - now
+            } else {
+                Duration::from_secs(0)
+            });
+        }
+
         select! {
             recv(self.event_source) -> event => {
                 // Ok, we processed the event.
@@ -709,7 +2125,18 @@ impl Cursive {
     // Handles a key event when it was ignored by the current view
     fn on_ignored_event(&mut self, event: Event) {
         let cb_list = match self.global_callbacks.get(&event) {
-            None => return,
+            None => {
+                if self.menubar_trigger.as_ref() == Some(&event)
+                    && !self.menubar.is_empty()
+                {
+                    self.select_menubar();
+                    return;
+                }
+                if let Some(f) = self.on_unhandled_event.clone() {
+                    f(self, &event).process(self);
+                }
+                return;
+            }
             Some(cb_list) => cb_list.clone(),
         };
         // Not from a view, so no viewpath here
@@ -720,12 +2147,106 @@ impl Cursive {
 
     /// Processes an event.
     ///
+    /// * Global sequences will be checked for this event.
     /// * If the menubar is active, it will be handled the event.
     /// * The view tree will be handled the event.
     /// * If ignored, global_callbacks will be checked for this event.
     pub fn on_event(&mut self, event: Event) {
+        if let Some(ref mut recorder) = self.recorder {
+            recorder.record(&event);
+        }
+
+        if let Some(f) = self.raw_input.clone() {
+            match event {
+                Event::Refresh | Event::Exit => self.dispatch_event(event),
+                Event::WindowResize => {
+                    self.dispatch_event(Event::WindowResize);
+                    f(self, event);
+                }
+                _ => f(self, event),
+            }
+            return;
+        }
+
+        self.check_sequence_timeout();
+
+        // Refresh events are not sent by the user and should not
+        // interrupt a sequence.
+        if self.global_sequences.is_empty() || event == Event::Refresh {
+            self.dispatch_event(event);
+            return;
+        }
+
+        self.pending_events.push(event);
+        self.sequence_deadline = Some(Instant::now() + self.sequence_timeout);
+
+        while !self.pending_events.is_empty() {
+            match self.match_sequence() {
+                SequenceMatch::Complete(cb) => {
+                    self.pending_events.clear();
+                    self.sequence_deadline = None;
+                    cb(self);
+                    return;
+                }
+                SequenceMatch::Prefix => return,
+                SequenceMatch::None => {
+                    // The oldest event can't start a sequence: process it
+                    // normally, and try again with the following ones.
+                    let event = self.pending_events.remove(0);
+                    self.dispatch_event(event);
+                }
+            }
+        }
+
+        self.sequence_deadline = None;
+    }
+
+    fn match_sequence(&self) -> SequenceMatch {
+        let pending = &self.pending_events[..];
+        let mut result = SequenceMatch::None;
+
+        for (sequence, cb) in &self.global_sequences {
+            if sequence[..] == *pending {
+                return SequenceMatch::Complete(cb.clone());
+            } else if sequence.starts_with(pending) {
+                result = SequenceMatch::Prefix;
+            }
+        }
+
+        result
+    }
+
+    // Processes the pending events if the sequence timed out.
+    fn check_sequence_timeout(&mut self) {
+        match self.sequence_deadline {
+            Some(deadline) if Instant::now() >= deadline => (),
+            _ => return,
+        }
+
+        self.sequence_deadline = None;
+        let events: Vec<_> = self.pending_events.drain(..).collect();
+        for event in events {
+            self.dispatch_event(event);
+        }
+    }
+
+    // Sends the event to the menubar or the views, then to the global
+    // callbacks.
+    fn dispatch_event(&mut self, event: Event) {
         if event == Event::Exit {
+            // Asking again may never end, for example when the input is
+            // closed.
+            if self.exit_refused {
+                self.force_quit();
+            } else {
+                self.quit();
+                self.exit_refused = self.running;
+            }
+        }
+
+        if self.exit_on_ctrl_c && event == Event::CtrlChar('c') {
             self.quit();
+            return;
         }
 
         if event == Event::WindowResize {
@@ -736,8 +2257,11 @@ impl Cursive {
             event, position, ..
         } = event
         {
+            // A hidden menubar leaves the top row to the views.
             if event.grabs_focus()
-                && !self.menubar.autohide
+                && self.menubar.visible()
+                && !self.menubar.is_empty()
+                && !self.menubar.receive_events()
                 && !self.menubar.has_submenu()
                 && position.y == 0
             {
@@ -750,10 +2274,19 @@ impl Cursive {
         //     * Menubar (if active)
         //     * Current screen (top layer)
         // * Global callbacks
-        if self.menubar.receive_events() {
+        let mouse = match event {
+            Event::Mouse {
+                event, position, ..
+            } => Some((event, position)),
+            _ => None,
+        };
+
+        if event.mouse_event() == Some(MouseEvent::Hover) {
+            // The mouse moved: only the views under it get the event, below.
+        } else if self.menubar.receive_events() {
             self.menubar.on_event(event).process(self);
         } else {
-            let offset = if self.menubar.autohide { 0 } else { 1 };
+            let offset = self.menubar_height();
             match self.screen_mut().on_event(event.relativized((0, offset))) {
                 // If the event was ignored,
                 // it is our turn to play with it.
@@ -762,6 +2295,11 @@ impl Cursive {
                 EventResult::Consumed(Some(cb)) => cb(self),
             }
         }
+
+        if let Some((event, position)) = mouse {
+            self.hover(position);
+            self.update_tooltip(event, position);
+        }
     }
 
     /// Returns the size of the screen, in characters.
@@ -769,27 +2307,75 @@ impl Cursive {
         self.backend.screen_size()
     }
 
+    // Rows taken by the menubar above the views.
+    //
+    // Positions on the screen are this much below positions in the stack.
+    pub(crate) fn menubar_height(&self) -> usize {
+        if self.menubar.visible() {
+            1
+        } else {
+            0
+        }
+    }
+
     fn layout(&mut self) {
         let size = self.screen_size();
-        let offset = if self.menubar.autohide { 0 } else { 1 };
+        let offset = self.menubar_height();
         let size = size.saturating_sub((0, offset));
         self.screen_mut().layout(size);
+
+        for notification in &mut self.notifications {
+            let view = &mut notification.view;
+            notification.size = view.required_size(size).or_min(size);
+            view.layout(notification.size);
+        }
+
+        // The `TooltipView` may be gone.
+        let owner = self.tooltip.as_ref().map(|t| t.owner.upgrade());
+        if let Some(None) = owner {
+            self.hide_tooltip();
+        }
+        if let Some(ref mut tooltip) = self.tooltip {
+            let size = self.backend.screen_size();
+            tooltip.size = tooltip.view.required_size(size).or_min(size);
+            tooltip.view.layout(tooltip.size);
+        }
     }
 
     fn draw(&mut self) {
         let sizes = self.screen().layer_sizes();
-        if self.last_sizes != sizes {
+        let menubar_height = self.menubar_height();
+        if self.last_sizes != sizes
+            || self.last_menubar_height != menubar_height
+        {
             self.clear();
             self.last_sizes = sizes;
+            self.last_menubar_height = menubar_height;
+        }
+
+        self.draw_on(&*self.backend);
+    }
+
+    // Returns a printer for the whole screen on the given backend.
+    fn printer<'a, 'b>(
+        &'a self, backend: &'b backend::Backend,
+    ) -> Printer<'a, 'b> {
+        let mut printer =
+            Printer::new(self.screen_size(), &self.theme, backend);
+        if let Some(support) = self.color_support {
+            printer.color_support = support;
         }
+        printer
+    }
 
-        let printer =
-            Printer::new(self.screen_size(), &self.theme, &*self.backend);
+    // Draws the views on the given backend.
+    fn draw_on(&self, backend: &backend::Backend) {
+        let printer = self.printer(backend);
 
         let selected = self.menubar.receive_events();
 
         // Print the stackview background before the menubar
-        let offset = if self.menubar.autohide { 0 } else { 1 };
+        let offset = self.menubar_height();
         let id = self.active_screen;
         let sv_printer = printer.offset((0, offset)).focused(!selected);
 
@@ -806,6 +2392,131 @@ impl Cursive {
         // finally draw stackview layers
         // using variables from above
         self.screens[id].draw_fg(&sv_printer);
+
+        self.draw_notifications(&sv_printer.focused(false));
+        self.draw_tooltip(&printer.focused(false));
+    }
+
+    // Draws the tooltip next to the mouse, if it's shown.
+    fn draw_tooltip(&self, printer: &Printer) {
+        let tooltip = match self.tooltip {
+            Some(ref tooltip) if tooltip.shown => tooltip,
+            _ => return,
+        };
+
+        // Below the mouse if there is room, above otherwise.
+        let (position, size) = (tooltip.position, tooltip.size);
+        let x = position.x.min(printer.size.x.saturating_sub(size.x));
+        let y = if position.y + 1 + size.y <= printer.size.y {
+            position.y + 1
+        } else {
+            position.y.saturating_sub(size.y)
+        };
+        tooltip.view.draw(&printer.offset((x, y)).cropped(size));
+    }
+
+    // Draws the notifications above everything else.
+    fn draw_notifications(&self, printer: &Printer) {
+        let align = &self.notifications_align;
+        let height = self.notifications.iter().map(|n| n.size.y).sum();
+
+        let mut y = align.v.get_offset(height, printer.size.y);
+
+        for notification in &self.notifications {
+            let size = notification.size;
+            let x = align.h.get_offset(size.x, printer.size.x);
+            notification
+                .view
+                .draw(&printer.offset((x, y)).cropped(size));
+            y += size.y;
+        }
+    }
+
+    /// Returns the text currently displayed on the screen, row by row.
+    ///
+    /// Styling is stripped. Wide graphemes count for as many columns as they
+    /// take on the terminal, so rows stay aligned.
+    ///
+    /// This is useful for tests, or to include the screen in bug reports.
+    pub fn dump_screen(&mut self) -> Vec<String> {
+        self.layout();
+
+        let backend = backend::puppet::Backend::init(Some(self.screen_size()));
+        let background = self.theme.palette[theme::PaletteColor::Background];
+        backend::Backend::clear(&*backend, background);
+        self.draw_on(&*backend);
+
+        backend.screen().rows()
+    }
+
+    /// Temporarily gives the terminal back and runs `f`.
+    ///
+    /// This can be used to run an external program, like a text editor.
+    /// The terminal is taken back and fully redrawn once `f` returns.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use cursive::Cursive;
+    /// # use std::process::Command;
+    /// # let mut siv = Cursive::dummy();
+    /// siv.add_global_callback('e', |s| {
+    ///     s.suspend(|| Command::new("vi").status().unwrap());
+    /// });
+    /// ```
+    pub fn suspend<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        self.backend.suspend();
+        let result = f();
+        self.backend.resume();
+
+        // Whatever ran may have drawn over us.
+        self.last_sizes.clear();
+        self.clear();
+
+        result
+    }
+
+    /// Lays out and draws the screen right away.
+    ///
+    /// The event loop draws after each step, but a callback doing a long
+    /// job doesn't return to it until it's done. This can be called from
+    /// there to show some progress in the meantime.
+    ///
+    /// This doesn't process any input or callback: views only show what
+    /// they are given. This ignores [`set_max_fps`], so calling it too often
+    /// may slow the job down.
+    ///
+    /// [`set_max_fps`]: #method.set_max_fps
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cursive::traits::*;
+    /// # use cursive::views::ProgressBar;
+    /// # use cursive::Cursive;
+    /// # let mut siv = Cursive::dummy();
+    /// siv.add_layer(ProgressBar::new().with_id("progress"));
+    ///
+    /// for i in 0..100 {
+    ///     // Some work...
+    ///     siv.call_on_id("progress", |bar: &mut ProgressBar| {
+    ///         bar.set_value(i);
+    ///     });
+    ///     siv.refresh();
+    /// }
+    /// ```
+    pub fn refresh(&mut self) {
+        // Cleared first, so changes made while drawing ask for another
+        // frame.
+        self.dirty.store(false, Ordering::SeqCst);
+        self.layout();
+        self.draw();
+        self.backend.refresh();
+        self.last_draw = Some(Instant::now());
+        self.draw_pending = false;
     }
 
     /// Returns `true` until [`quit(&mut self)`] is called.
@@ -829,6 +2540,7 @@ impl Cursive {
     /// [`quit(&mut self)`]: #method.quit
     pub fn run(&mut self) {
         self.running = true;
+        self.exit_refused = false;
 
         // And the big event loop begins!
         while self.running {
@@ -843,15 +2555,20 @@ impl Cursive {
     ///
     /// [`run(&mut self)`]: #method.run
     pub fn step(&mut self) {
-        // Do we need to redraw everytime?
-        // Probably, actually.
-        // TODO: Do we need to re-layout everytime?
-        self.layout();
-
-        // TODO: Do we need to redraw every view every time?
-        // (Is this getting repetitive? :p)
-        self.draw();
-        self.backend.refresh();
+        // Layers added or removed since the last step may have moved the
+        // focus.
+        let result = self.screen_mut().notify_focus_change();
+        result.process(self);
+
+        // Too soon for another frame: `poll()` wakes up when it's time.
+        let now = Instant::now();
+        if self.next_draw().filter(|&next| next > now).is_some() {
+            self.draw_pending = true;
+        } else {
+            // Do we need to redraw everytime?
+            // Probably, actually.
+            self.refresh();
+        }
 
         if let Some(interruption) = self.poll() {
             self.handle_interruption(interruption);
@@ -860,6 +2577,12 @@ impl Cursive {
             }
         }
 
+        self.run_timers();
+        if !self.running {
+            return;
+        }
+        self.check_tooltip();
+
         // Don't block, but try to read any other pending event.
         // This lets us batch-process chunks of events, like big copy-paste or mouse drags.
         while let Some(interruption) = self.peek() {
@@ -872,20 +2595,119 @@ impl Cursive {
 
     fn handle_interruption(&mut self, interruption: Interruption) {
         match interruption {
+            Interruption::Event(ref event)
+                if self.raw_input.is_none() && self.is_repeat(event) =>
+            {
+            }
             Interruption::Event(event) => {
                 self.on_event(event);
             }
             Interruption::Callback(cb) => {
                 cb.call_box(self);
             }
-            Interruption::Timeout => {}
+            Interruption::Timeout => self.check_sequence_timeout(),
         }
     }
 
     /// Stops the event loop.
+    ///
+    /// If a callback was set with [`set_on_quit`], it is called first, and
+    /// can cancel the quit. This includes `Event::Exit` and `Ctrl-C`.
+    ///
+    /// Only the first `Event::Exit` asks: a second one quits anyway, like
+    /// when the window is closed again. Backends without input, like the
+    /// dummy one, send it on each step, so [`run`] still returns.
+    ///
+    /// [`set_on_quit`]: #method.set_on_quit
+    /// [`run`]: #method.run
     pub fn quit(&mut self) {
+        let on_quit = match self.on_quit {
+            Some(ref on_quit) if !self.confirming_quit => Rc::clone(on_quit),
+            _ => {
+                self.force_quit();
+                return;
+            }
+        };
+
+        self.confirming_quit = true;
+        let proceed = on_quit(self);
+        self.confirming_quit = false;
+
+        if proceed {
+            self.force_quit();
+        }
+    }
+
+    /// Stops the event loop, without calling the [`set_on_quit`] callback.
+    ///
+    /// Use it once the user confirmed, for example from a dialog shown by
+    /// the callback.
+    ///
+    /// [`set_on_quit`]: #method.set_on_quit
+    pub fn force_quit(&mut self) {
         self.running = false;
     }
+
+    /// Sets a callback to run before quitting.
+    ///
+    /// It is called by [`quit`], and returns `true` to quit, or `false` to
+    /// keep running. It can show a dialog to ask for confirmation, then
+    /// return `false`: the dialog should quit with [`force_quit`], or `quit`
+    /// would ask again. Calling `quit` from the callback itself doesn't call
+    /// it again, and the event loop stops.
+    ///
+    /// [`quit`]: #method.quit
+    /// [`force_quit`]: #method.force_quit
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cursive::Cursive;
+    /// # use cursive::views::Dialog;
+    /// let mut siv = Cursive::dummy();
+    ///
+    /// siv.set_on_quit(|s| {
+    ///     s.add_layer(
+    ///         Dialog::text("Really quit?")
+    ///             .button("Yes", Cursive::force_quit)
+    ///             .dismiss_button("No"),
+    ///     );
+    ///     false
+    /// });
+    ///
+    /// siv.quit();
+    /// assert!(siv.is_running());
+    /// ```
+    pub fn set_on_quit<F>(&mut self, f: F)
+    where
+        F: Fn(&mut Cursive) -> bool + 'static,
+    {
+        self.on_quit = Some(Rc::new(f));
+    }
+
+    /// Removes the callback set by [`set_on_quit`].
+    ///
+    /// [`set_on_quit`]: #method.set_on_quit
+    pub fn clear_on_quit(&mut self) {
+        self.on_quit = None;
+    }
+}
+
+// Returns `true` for the keys moving a selection, repeated when held down.
+fn is_navigation(event: &Event) -> bool {
+    let key = match *event {
+        Event::Key(key)
+        | Event::Shift(key)
+        | Event::Alt(key)
+        | Event::AltShift(key)
+        | Event::Ctrl(key)
+        | Event::CtrlShift(key)
+        | Event::CtrlAlt(key) => key,
+        _ => return false,
+    };
+
+    [Key::Up, Key::Down, Key::Left, Key::Right, Key::PageUp, Key::PageDown]
+        .contains(&key)
 }
 
 impl Drop for Cursive {
@@ -893,3 +2715,637 @@ impl Drop for Cursive {
         self.backend.finish();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use event::MouseButton;
+    use std::cell::{Cell, RefCell};
+    use std::path::PathBuf;
+    use std::thread;
+    use std::time::UNIX_EPOCH;
+    use traits::*;
+    use views::{BoxView, EditView, SelectView, TextView};
+
+    fn setup() -> (Cursive, Rc<Cell<usize>>) {
+        let backend = backend::puppet::Backend::init(Some(Vec2::new(20, 5)));
+        let mut siv = Cursive::new(move || backend);
+        siv.add_layer(BoxView::with_full_width(
+            EditView::new().with_id("edit"),
+        ));
+
+        let counter = Rc::new(Cell::new(0));
+        let c = Rc::clone(&counter);
+        siv.add_global_sequence(vec!['x', 's'], move |_| c.set(c.get() + 1));
+
+        (siv, counter)
+    }
+
+    fn content(siv: &mut Cursive) -> String {
+        siv.call_on_id("edit", |edit: &mut EditView| {
+            edit.get_content().to_string()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_sequence() {
+        let (mut siv, counter) = setup();
+
+        siv.on_event(Event::Char('x'));
+        assert_eq!(counter.get(), 0);
+        assert_eq!(content(&mut siv), "");
+
+        siv.on_event(Event::Char('s'));
+        assert_eq!(counter.get(), 1);
+        assert_eq!(content(&mut siv), "");
+
+        // A broken sequence lets the events reach the view.
+        siv.on_event(Event::Char('x'));
+        siv.on_event(Event::Char('y'));
+        assert_eq!(counter.get(), 1);
+        assert_eq!(content(&mut siv), "xy");
+    }
+
+    #[test]
+    fn test_sequence_timeout() {
+        let (mut siv, counter) = setup();
+        siv.set_sequence_timeout(Duration::from_millis(1));
+
+        siv.on_event(Event::Char('x'));
+        thread::sleep(Duration::from_millis(10));
+        siv.on_event(Event::Char('s'));
+
+        assert_eq!(counter.get(), 0);
+        assert_eq!(content(&mut siv), "xs");
+    }
+
+    #[test]
+    fn test_timers() {
+        let (mut siv, _) = setup();
+
+        let ticks = Rc::new(Cell::new(0));
+        let t = Rc::clone(&ticks);
+        siv.add_timer(Duration::from_millis(5), move |_| t.set(t.get() + 1));
+
+        let removed = siv.add_timer(Duration::from_millis(5), |_| {
+            panic!("removed timers should not run")
+        });
+        assert!(siv.remove_timer(removed));
+        assert!(!siv.remove_timer(removed));
+
+        // Each step waits for the next tick.
+        for _ in 0..3 {
+            siv.step();
+        }
+        assert_eq!(ticks.get(), 3);
+    }
+
+    #[test]
+    fn test_timer_removed_during_tick() {
+        let (mut siv, _) = setup();
+
+        // Timers are due in the order they were added.
+        let next = Rc::new(Cell::new(None));
+        let n = Rc::clone(&next);
+        siv.add_timer(Duration::from_millis(5), move |s| {
+            s.remove_timer(n.get().unwrap());
+        });
+        next.set(Some(siv.add_timer(Duration::from_millis(5), |_| {
+            panic!("removed timers should not run")
+        })));
+
+        siv.step();
+        assert_eq!(siv.timers.len(), 1);
+    }
+
+    #[test]
+    fn test_call_on_focused() {
+        let (mut siv, _) = setup();
+        siv.add_layer(views::Dialog::around(
+            views::LinearLayout::vertical()
+                .child(EditView::new())
+                .child(EditView::new().with_id("second")),
+        ));
+        siv.dump_screen();
+
+        siv.on_event(Event::Key(Key::Down));
+        siv.call_on_focused(|view: &mut EditView| view.set_content("b"));
+        let content = siv
+            .call_on_id("second", |view: &mut EditView| view.get_content())
+            .unwrap();
+        assert_eq!(&*content, "b");
+
+        // No focused view of this type.
+        assert!(siv.call_on_focused(|_: &mut TextView| ()).is_none());
+    }
+
+    #[test]
+    fn test_notifications() {
+        let backend = backend::puppet::Backend::init(Some(Vec2::new(20, 8)));
+        let mut siv = Cursive::new(move || backend);
+        siv.add_layer(BoxView::with_full_width(
+            EditView::new().with_id("edit"),
+        ));
+
+        siv.notify("Saved!", Duration::from_millis(5));
+        siv.notify("Sent", Duration::from_millis(50));
+
+        let screen = siv.dump_screen();
+        assert_eq!(screen[1].trim(), "");
+        assert!(screen[2].ends_with("┌──────┐"));
+        assert!(screen[3].ends_with("│Saved!│"));
+        assert!(screen[4].ends_with("└──────┘"));
+        assert!(screen[5].ends_with("┌────┐"));
+        assert!(screen[6].ends_with("│Sent│"));
+
+        // The focused view still gets the events.
+        siv.on_event(Event::Char('a'));
+        assert_eq!(content(&mut siv), "a");
+
+        // Wait for the first notification to expire.
+        siv.step();
+        let screen = siv.dump_screen();
+        assert!(screen[6].ends_with("│Sent│"));
+        assert!(!screen.iter().any(|row| row.contains("Saved")));
+
+        siv.clear_notifications();
+        assert!(siv.timers.is_empty());
+        assert!(!siv.dump_screen().iter().any(|row| row.contains("Sent")));
+    }
+
+    #[test]
+    fn test_watch_theme_file() {
+        // Removes the file even if the test fails.
+        struct TempFile(PathBuf);
+
+        impl Drop for TempFile {
+            fn drop(&mut self) {
+                fs::remove_file(&self.0).ok();
+            }
+        }
+
+        let (mut siv, _) = setup();
+        let file = TempFile(::std::env::temp_dir().join(format!(
+            "cursive-watch-theme-{}.toml",
+            ::std::process::id()
+        )));
+        let path = file.0.clone();
+        fs::write(&path, "shadow = false").unwrap();
+        siv.load_theme_file(&path).unwrap();
+        siv.watch_theme_file(&path, Duration::from_millis(1));
+
+        // Each write gets a new modification time, even if the filesystem
+        // only keeps whole seconds.
+        let mut seconds = 0;
+        let mut touch = |siv: &mut Cursive, content: &str| {
+            fs::write(&path, content).unwrap();
+            seconds += 1;
+            let modified = UNIX_EPOCH + Duration::from_secs(seconds);
+            let file = fs::OpenOptions::new().write(true).open(&path);
+            file.unwrap().set_modified(modified).unwrap();
+            // Lets the timer run.
+            thread::sleep(Duration::from_millis(2));
+            siv.step();
+        };
+
+        touch(&mut siv, "shadow = true");
+        assert!(siv.current_theme().shadow);
+
+        // Errors are shown, and the theme stays the same.
+        touch(&mut siv, "shadow = 3");
+        assert!(siv.current_theme().shadow);
+        assert!(siv
+            .dump_screen()
+            .iter()
+            .any(|row| row.contains("invalid value")));
+    }
+
+    #[test]
+    fn test_menubar_autohide() {
+        let backend = backend::puppet::Backend::init(Some(Vec2::new(20, 5)));
+        let mut siv = Cursive::new(move || backend);
+        let quit = Rc::new(Cell::new(false));
+        let q = Rc::clone(&quit);
+        siv.menubar().add_leaf("Quit", move |_| q.set(true));
+        siv.add_fullscreen_layer(BoxView::with_full_screen(
+            views::TextView::new("top"),
+        ));
+
+        // Hidden, the menubar leaves the whole screen to the views.
+        assert_eq!(siv.dump_screen()[0].trim(), "top");
+        assert_eq!(siv.screen().layer_sizes(), vec![Vec2::new(20, 5)]);
+
+        siv.on_event(Event::Key(Key::Esc));
+        let screen = siv.dump_screen();
+        assert_eq!(screen[0].trim(), "Quit");
+        assert_eq!(screen[1].trim(), "top");
+        assert_eq!(siv.screen().layer_sizes(), vec![Vec2::new(20, 4)]);
+
+        // It hides again after a selection.
+        siv.on_event(Event::Key(Key::Enter));
+        assert!(quit.get());
+        assert_eq!(siv.dump_screen()[0].trim(), "top");
+
+        // While hidden, clicks on the top row are for the views.
+        let clicked = Rc::new(Cell::new(false));
+        let c = Rc::clone(&clicked);
+        siv.pop_layer();
+        siv.add_fullscreen_layer(BoxView::with_full_screen(
+            views::Button::new("top", move |_| c.set(true)),
+        ));
+        let left = MouseButton::Left;
+        for &event in &[MouseEvent::Press(left), MouseEvent::Release(left)] {
+            siv.on_event(Event::Mouse {
+                offset: Vec2::zero(),
+                position: Vec2::new(1, 0),
+                event,
+            });
+        }
+        assert!(clicked.get());
+        assert_eq!(siv.dump_screen()[0].trim(), "<top>");
+
+        siv.set_menubar_trigger(Some(Event::Key(Key::F10)));
+        siv.on_event(Event::Key(Key::Esc));
+        assert_eq!(siv.dump_screen()[0].trim(), "<top>");
+        siv.on_event(Event::Key(Key::F10));
+        assert_eq!(siv.dump_screen()[0].trim(), "Quit");
+    }
+
+    #[test]
+    fn test_unhandled_event() {
+        let (mut siv, _) = setup();
+        siv.add_global_callback(Key::F1, |_| ());
+
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let e = Rc::clone(&events);
+        siv.set_on_unhandled_event(move |_, event| {
+            e.borrow_mut().push(event.clone());
+            EventResult::Ignored
+        });
+
+        // Consumed by the edit view.
+        siv.on_event(Event::Char('a'));
+        // Handled by a global callback.
+        siv.on_event(Event::Key(Key::F1));
+        // Ignored by everything.
+        siv.on_event(Event::Key(Key::F5));
+        siv.on_event(Event::WindowResize);
+
+        assert_eq!(content(&mut siv), "a");
+        assert_eq!(
+            &events.borrow()[..],
+            &[Event::Key(Key::F5), Event::WindowResize]
+        );
+    }
+
+    #[test]
+    fn test_ctrl_c() {
+        let (mut siv, _) = setup();
+        siv.on_event(Event::CtrlChar('c'));
+        assert!(!siv.is_running());
+
+        // The default callback can be replaced.
+        let (mut siv, _) = setup();
+        siv.clear_global_callbacks(Event::CtrlChar('c'));
+        siv.on_event(Event::CtrlChar('c'));
+        assert!(siv.is_running());
+
+        siv.set_exit_on_ctrl_c(true);
+        siv.on_event(Event::CtrlChar('c'));
+        assert!(!siv.is_running());
+    }
+
+    #[test]
+    fn test_on_quit() {
+        let (mut siv, asked) = setup();
+        let a = Rc::clone(&asked);
+        siv.set_on_quit(move |s| {
+            a.set(a.get() + 1);
+            s.add_layer(
+                views::Dialog::text("Quit?")
+                    .button("Yes", Cursive::force_quit)
+                    .dismiss_button("No"),
+            );
+            false
+        });
+
+        // Both `Ctrl-C` and `Event::Exit` ask first.
+        siv.on_event(Event::CtrlChar('c'));
+        assert!(siv.is_running());
+        siv.refresh();
+        siv.on_event(Event::Key(Key::Right));
+        siv.on_event(Event::Key(Key::Enter));
+        siv.on_event(Event::Exit);
+        assert!(siv.is_running());
+        assert_eq!((asked.get(), siv.screen().len()), (2, 2));
+        siv.refresh();
+
+        // The dialog quits for good.
+        siv.on_event(Event::Key(Key::Enter));
+        assert!(!siv.is_running());
+        assert_eq!(asked.get(), 2);
+
+        // A second `Event::Exit` doesn't ask.
+        siv.running = true;
+        siv.on_event(Event::Exit);
+        assert!(!siv.is_running());
+        assert_eq!(asked.get(), 2);
+
+        // Quitting from the callback doesn't ask again.
+        siv.set_on_quit(|s| {
+            s.quit();
+            false
+        });
+        siv.running = true;
+        siv.quit();
+        assert!(!siv.is_running());
+
+        siv.clear_on_quit();
+        siv.running = true;
+        siv.quit();
+        assert!(!siv.is_running());
+    }
+
+    #[test]
+    fn test_on_quit_without_input() {
+        // The dummy backend sends `Event::Exit` on each step.
+        let mut siv = Cursive::dummy();
+        let asked = Rc::new(Cell::new(0));
+        let a = Rc::clone(&asked);
+        siv.set_on_quit(move |_| {
+            a.set(a.get() + 1);
+            false
+        });
+
+        siv.run();
+        assert_eq!(asked.get(), 1);
+    }
+
+    #[test]
+    fn test_update_sink() {
+        let (mut siv, _) = setup();
+        let sink = siv.update_sink();
+
+        // The update wakes `step()` up, even without timers or input.
+        let worker = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(10));
+            sink.send_update(|s| {
+                s.call_on_id("edit", |edit: &mut EditView| {
+                    edit.set_content("done");
+                });
+            })
+        });
+        siv.step();
+        assert_eq!(content(&mut siv), "done");
+        assert!(worker.join().unwrap());
+
+        // Once the root is dropped, updates are refused.
+        let sink = siv.update_sink();
+        drop(siv);
+        assert!(!sink.wake());
+    }
+
+    #[test]
+    fn test_raw_input() {
+        let (mut siv, calls) = setup();
+        let events = Rc::new(RefCell::new(Vec::new()));
+
+        // The unfinished sequence is given up on.
+        siv.on_event(Event::Char('x'));
+        {
+            let events = Rc::clone(&events);
+            siv.enter_raw_input(move |s, event| {
+                if event == Event::Key(Key::Esc) {
+                    s.exit_raw_input();
+                }
+                events.borrow_mut().push(event);
+            });
+        }
+        assert!(siv.is_raw_input());
+        assert_eq!(content(&mut siv), "x");
+
+        // Nothing else sees the input.
+        let input = vec![
+            Event::Char('x'),
+            Event::Char('s'),
+            Event::CtrlChar('c'),
+            Event::Refresh,
+            Event::WindowResize,
+            Event::Key(Key::Esc),
+        ];
+        for event in input {
+            siv.on_event(event);
+        }
+        let expected = vec![
+            Event::Char('x'),
+            Event::Char('s'),
+            Event::CtrlChar('c'),
+            Event::WindowResize,
+            Event::Key(Key::Esc),
+        ];
+        assert_eq!(*events.borrow(), expected);
+        assert_eq!(content(&mut siv), "x");
+        assert_eq!(calls.get(), 0);
+        assert!(siv.is_running());
+
+        // Back to normal.
+        assert!(!siv.is_raw_input());
+        for c in "xs".chars() {
+            siv.on_event(Event::Char(c));
+        }
+        assert_eq!(calls.get(), 1);
+        assert_eq!(events.borrow().len(), 5);
+    }
+
+    #[test]
+    fn test_refresh() {
+        let backend = backend::puppet::Backend::init(Some(Vec2::new(20, 5)));
+        let input = backend.input();
+        let frames = backend.stream();
+        let mut siv = Cursive::new(move || backend);
+        siv.add_layer(TextView::new("0").with_id("text"));
+
+        // Each call draws a frame, even from a callback.
+        siv.add_global_callback('a', |s| {
+            for i in 1..4 {
+                s.call_on_id("text", |text: &mut TextView| {
+                    text.set_content(i.to_string())
+                });
+                s.refresh();
+            }
+        });
+        input.send(Some(Event::Char('a'))).unwrap();
+        siv.step();
+
+        let rows: Vec<_> = frames.try_iter().map(|s| s.row(2)).collect();
+        assert_eq!(rows.len(), 4);
+        for (row, digit) in rows.iter().zip("0123".chars()) {
+            assert_eq!(row.trim(), digit.to_string());
+        }
+    }
+
+    #[test]
+    fn test_max_fps() {
+        let backend = backend::puppet::Backend::init(Some(Vec2::new(20, 5)));
+        let input = backend.input();
+        let frames = backend.stream();
+        let mut siv = Cursive::new(move || backend);
+        siv.add_layer(EditView::new().with_id("edit").fixed_width(10));
+        siv.set_max_fps(10);
+
+        input.send(Some(Event::Char('a'))).unwrap();
+        siv.step();
+        assert_eq!(frames.try_iter().count(), 1);
+
+        // Events are handled right away, but not drawn yet.
+        for c in "bcd".chars() {
+            input.send(Some(Event::Char(c))).unwrap();
+            siv.step();
+        }
+        assert_eq!(content(&mut siv), "abcd");
+        assert_eq!(frames.try_iter().count(), 0);
+
+        // Without input, `step()` wakes up in time for the delayed frame.
+        siv.step();
+        input.send(Some(Event::Refresh)).unwrap();
+        siv.step();
+        let screen = frames.try_iter().last().unwrap();
+        assert!(screen.row(2).contains("abcd"));
+    }
+
+    #[test]
+    fn test_invalidate() {
+        let backend = backend::puppet::Backend::init(Some(Vec2::new(20, 5)));
+        let input = backend.input();
+        let frames = backend.stream();
+        let mut siv = Cursive::new(move || backend);
+        siv.set_max_fps(10);
+
+        // Many calls only wake the event loop up once.
+        let invalidator = siv.invalidator();
+        let worker = thread::spawn(move || {
+            for _ in 0..10 {
+                assert!(invalidator.invalidate());
+            }
+            invalidator
+        });
+        let invalidator = worker.join().unwrap();
+        assert_eq!(siv.cb_source.len(), 1);
+        siv.step();
+        assert_eq!(frames.try_iter().count(), 1);
+        assert!(!invalidator.is_dirty());
+
+        // Too soon for another frame: it waits for `max_fps`.
+        for _ in 0..10 {
+            invalidator.invalidate();
+        }
+        siv.step();
+        assert_eq!(frames.try_iter().count(), 0);
+        assert!(invalidator.is_dirty());
+
+        // Without input, `step()` wakes up in time for it.
+        siv.step();
+        input.send(Some(Event::Refresh)).unwrap();
+        siv.step();
+        assert_eq!(frames.try_iter().count(), 1);
+        assert!(!invalidator.is_dirty());
+    }
+
+    #[test]
+    fn test_repeat_coalescing() {
+        let backend = backend::puppet::Backend::init(Some(Vec2::new(20, 5)));
+        let input = backend.input();
+        let mut siv = Cursive::new(move || backend);
+        let items = (0..20).map(|i| (i.to_string(), i));
+        siv.add_layer(SelectView::new().with_all(items).with_id("select"));
+
+        let selection = |siv: &mut Cursive| {
+            siv.call_on_id("select", |s: &mut SelectView<i32>| s.selected_id())
+                .unwrap()
+        };
+        let send = |key: Key, count: usize| {
+            for _ in 0..count {
+                input.send(Some(Event::Key(key))).unwrap();
+            }
+        };
+
+        // Without coalescing, every repeat moves the selection.
+        send(Key::Down, 3);
+        siv.step();
+        assert_eq!(selection(&mut siv), Some(3));
+
+        // A burst only counts once, but other keys are kept.
+        siv.set_repeat_coalescing(Some(Duration::from_millis(50)));
+        send(Key::Down, 5);
+        send(Key::Up, 1);
+        send(Key::Down, 5);
+        siv.step();
+        assert_eq!(selection(&mut siv), Some(4));
+
+        // Slower repeats are handled.
+        thread::sleep(Duration::from_millis(60));
+        send(Key::Down, 1);
+        siv.step();
+        assert_eq!(selection(&mut siv), Some(5));
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_spawn() {
+        use std::future;
+        use std::sync::Arc;
+        use std::task::{Wake, Waker};
+
+        struct NoWake;
+
+        impl Wake for NoWake {
+            fn wake(self: Arc<Self>) {}
+        }
+
+        let (mut siv, _) = setup();
+        let tasks = Rc::new(RefCell::new(Vec::new()));
+        let t = Rc::clone(&tasks);
+        siv.set_executor(move |task| t.borrow_mut().push(task));
+
+        siv.spawn(future::ready(42), |s, answer: usize| {
+            s.call_on_id("edit", |edit: &mut EditView| {
+                edit.set_content(answer.to_string());
+            });
+        });
+
+        // Nothing happens until the executor runs the task.
+        let mut task = tasks.borrow_mut().pop().unwrap();
+        assert_eq!(content(&mut siv), "");
+
+        let waker = Waker::from(Arc::new(NoWake));
+        let mut cx = Context::from_waker(&waker);
+        assert!(task.as_mut().poll(&mut cx).is_ready());
+        siv.step();
+        assert_eq!(content(&mut siv), "42");
+    }
+
+    #[test]
+    fn test_send_event() {
+        let (mut siv, counter) = setup();
+        siv.add_global_callback(Key::F1, |s| s.send_event(Event::Char('z')));
+
+        siv.send_event(Event::Char('x'));
+        siv.send_event(Event::Char('s'));
+        siv.send_event(Event::Char('a'));
+        siv.send_event(Event::Key(Key::F1));
+        assert_eq!((counter.get(), content(&mut siv)), (0, String::new()));
+
+        // Events from a callback are handled right after it.
+        siv.step();
+        assert_eq!(counter.get(), 1);
+        assert_eq!(content(&mut siv), "az");
+
+        let sink = siv.update_sink();
+        thread::spawn(move || sink.send_event(Event::Char('b')))
+            .join()
+            .unwrap();
+        siv.step();
+        assert_eq!(content(&mut siv), "azb");
+    }
+}
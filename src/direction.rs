@@ -25,6 +25,13 @@
 //!   This is mostly relevant when referring to change of focus. Hitting the
 //!   `Tab` key would usually cycle focus in the "front" direction, while
 //!   using the arrow keys would use absolute directions instead.
+//!
+//! ### Text direction
+//!
+//! [`TextDirection`] tells text views whether their content reads
+//! left-to-right or right-to-left.
+//!
+//! [`TextDirection`]: direction::TextDirection
 
 use vec::Vec2;
 use XY;
@@ -252,3 +259,30 @@ impl Absolute {
         }
     }
 }
+
+/// Base direction in which text flows.
+///
+/// Right-to-left text starts at the right edge of a view, and each line is
+/// shown in reverse order.
+///
+/// There is no bidirectional reordering: left-to-right runs inside
+/// right-to-left text, like numbers, are reversed as well.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextDirection {
+    /// Left-to-right text, like English.
+    LeftToRight,
+    /// Right-to-left text, like Arabic or Hebrew.
+    RightToLeft,
+}
+
+impl TextDirection {
+    /// Returns the column where to draw `width` cells found at column `x`.
+    ///
+    /// `x` is counted in reading order, in a line of `length` cells.
+    pub fn mirror(self, x: usize, width: usize, length: usize) -> usize {
+        match self {
+            TextDirection::LeftToRight => x,
+            TextDirection::RightToLeft => length.saturating_sub(x + width),
+        }
+    }
+}
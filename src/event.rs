@@ -312,6 +312,30 @@ pub enum Key {
     F11,
     /// F12 key
     F12,
+    /// F13 key
+    F13,
+    /// F14 key
+    F14,
+    /// F15 key
+    F15,
+    /// F16 key
+    F16,
+    /// F17 key
+    F17,
+    /// F18 key
+    F18,
+    /// F19 key
+    F19,
+    /// F20 key
+    F20,
+    /// F21 key
+    F21,
+    /// F22 key
+    F22,
+    /// F23 key
+    F23,
+    /// F24 key
+    F24,
 }
 
 impl Key {
@@ -321,7 +345,7 @@ impl Key {
     ///
     /// # Panics
     ///
-    /// If `n == 0 || n > 12`
+    /// If `n > 24`
     pub fn from_f(n: u8) -> Key {
         match n {
             0 => Key::F0,
@@ -337,6 +361,18 @@ impl Key {
             10 => Key::F10,
             11 => Key::F11,
             12 => Key::F12,
+            13 => Key::F13,
+            14 => Key::F14,
+            15 => Key::F15,
+            16 => Key::F16,
+            17 => Key::F17,
+            18 => Key::F18,
+            19 => Key::F19,
+            20 => Key::F20,
+            21 => Key::F21,
+            22 => Key::F22,
+            23 => Key::F23,
+            24 => Key::F24,
             _ => panic!("unknown function key: F{}", n),
         }
     }
@@ -371,33 +407,62 @@ pub enum MouseEvent {
     Release(MouseButton),
     /// A button is being held.
     Hold(MouseButton),
+    /// A button was pressed twice in a short interval.
+    ///
+    /// This is sent right after the second `Press` event.
+    DoubleClick(MouseButton),
+    /// A button was pressed three times in a short interval.
+    ///
+    /// This is sent right after the third `Press` event.
+    TripleClick(MouseButton),
     /// The wheel was moved up.
     WheelUp,
     /// The wheel was moved down.
     WheelDown,
+    /// The wheel was moved left.
+    ///
+    /// Sent for horizontal wheels, or `Shift` + `WheelUp` by some backends.
+    WheelLeft,
+    /// The wheel was moved right.
+    ///
+    /// Sent for horizontal wheels, or `Shift` + `WheelDown` by some backends.
+    WheelRight,
+    /// The mouse is over the position.
+    ///
+    /// Sent by `Cursive` after each other mouse event. Unlike other events,
+    /// layouts give it to the child under the mouse, even if it doesn't have
+    /// the focus.
+    ///
+    /// Backends also send it when the mouse moves without a button pressed,
+    /// if the terminal reports it.
+    Hover,
 }
 
 impl MouseEvent {
     /// Returns the button used by this event, if any.
     ///
-    /// Returns `None` if `self` is `WheelUp` or `WheelDown`.
+    /// Returns `None` if `self` is a wheel or `Hover` event.
     pub fn button(self) -> Option<MouseButton> {
         match self {
             MouseEvent::Press(btn)
             | MouseEvent::Release(btn)
-            | MouseEvent::Hold(btn) => Some(btn),
+            | MouseEvent::Hold(btn)
+            | MouseEvent::DoubleClick(btn)
+            | MouseEvent::TripleClick(btn) => Some(btn),
             _ => None,
         }
     }
 
     /// Returns `true` if `self` is an event that can grab focus.
     ///
-    /// This includes `Press`, `WheelUp` and `WheelDown`.
+    /// This includes `Press` and wheel events.
     pub fn grabs_focus(self) -> bool {
         match self {
             MouseEvent::Press(_)
             | MouseEvent::WheelUp
-            | MouseEvent::WheelDown => true,
+            | MouseEvent::WheelDown
+            | MouseEvent::WheelLeft
+            | MouseEvent::WheelRight => true,
             _ => false,
         }
     }
@@ -409,9 +474,27 @@ pub enum Event {
     /// Event fired when the window is resized.
     WindowResize,
 
+    /// Event fired when the window gains (`true`) or loses (`false`) focus.
+    ///
+    /// Only sent if the terminal supports focus reporting.
+    WindowFocus(bool),
+
     /// Event fired regularly when a auto-refresh is set.
     Refresh,
 
+    /// Event sent to a view when it gets the focus from a sibling.
+    ///
+    /// Layouts send it to their newly focused child when the focus moves
+    /// after a key press or a mouse click, and `StackView` to its new front
+    /// layer. It then reaches the views along the new focus path.
+    FocusGained,
+
+    /// Event sent to a view when the focus moves to one of its siblings.
+    ///
+    /// This is the counterpart of `Event::FocusGained`, sent to the child
+    /// that had the focus before.
+    FocusLost,
+
     // TODO: have Char(modifier, char) and Key(modifier, key) enums?
     /// A character was entered (includes numbers, punctuation, ...).
     Char(char),
@@ -445,8 +528,16 @@ pub enum Event {
         event: MouseEvent,
     },
 
+    /// Some text was pasted in the terminal.
+    ///
+    /// Only sent by backends supporting bracketed paste.
+    Paste(String),
+
     // TODO: use a backend-dependent type for the unknown values?
     /// An unknown event was received.
+    ///
+    /// Contains the raw bytes of the event, when the backend knows them.
+    /// Logging them helps adding support for new keys.
     Unknown(Vec<u8>),
 
     // Maybe add a `Custom(Rc<Any>)` ?
@@ -468,6 +559,15 @@ impl Event {
         }
     }
 
+    /// Returns the kind of mouse event, if `self` is a mouse event.
+    pub fn mouse_event(&self) -> Option<MouseEvent> {
+        if let Event::Mouse { event, .. } = *self {
+            Some(event)
+        } else {
+            None
+        }
+    }
+
     /// Returns a mutable reference to the position of the mouse/
     ///
     /// Returns `None` if `self` is not a mouse event.
@@ -85,6 +85,8 @@ extern crate chrono;
 extern crate libc;
 extern crate num;
 extern crate owning_ref;
+#[cfg(feature = "serde")]
+extern crate serde;
 extern crate toml;
 extern crate unicode_segmentation;
 extern crate unicode_width;
@@ -114,6 +116,7 @@ pub mod direction;
 pub mod logger;
 pub mod menu;
 pub mod rect;
+pub mod replay;
 pub mod theme;
 pub mod vec;
 pub mod views;
@@ -129,7 +132,9 @@ mod utf8;
 
 pub mod backend;
 
-pub use cursive::{CbFunc, Cursive, ScreenId};
+pub use cursive::{CbFunc, CbSink, Cursive, Invalidator, ScreenId, TimerId};
+#[cfg(feature = "async")]
+pub use cursive::{Bridge, Task};
 pub use printer::Printer;
 pub use vec::Vec2;
 pub use with::With;
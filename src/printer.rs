@@ -1,10 +1,14 @@
 //! Provide higher-level abstraction to draw things on backends.
 
-use backend::Backend;
+use align::HAlign;
+use backend::{Backend, ColorSupport};
 use direction::Orientation;
-use enumset::EnumSet;
+use rect::Rect;
 use std::cmp::min;
-use theme::{BorderStyle, ColorStyle, Effect, PaletteColor, Style, Theme};
+use theme::{
+    BorderStyle, Color, ColorPair, ColorStyle, Effect, EffectSet,
+    PaletteColor, Style, Theme,
+};
 use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 use utils::lines::simple::{prefix, suffix};
@@ -49,6 +53,21 @@ pub struct Printer<'a, 'b> {
     /// Currently used theme
     pub theme: &'a Theme,
 
+    /// Colors the backend can display.
+    ///
+    /// Colors are adapted to it before they are used. It comes from
+    /// the backend, unless overridden with
+    /// [`Cursive::set_color_support()`].
+    ///
+    /// [`Cursive::set_color_support()`]:
+    /// struct.Cursive.html#method.set_color_support
+    pub color_support: ColorSupport,
+
+    // Set while a light color is shown as a bold dark color.
+    bold_color: bool,
+    // Set inside `with_effect(Effect::Bold)`.
+    bold_effect: bool,
+
     /// Backend used to actually draw things
     backend: &'b Backend,
 }
@@ -70,6 +89,9 @@ impl<'a, 'b> Printer<'a, 'b> {
             focused: true,
             enabled: true,
             theme,
+            color_support: backend.color_support(),
+            bold_color: false,
+            bold_effect: false,
             backend,
         }
     }
@@ -80,8 +102,41 @@ impl<'a, 'b> Printer<'a, 'b> {
     ///
     /// Users rarely need to call this directly.
     pub fn clear(&self) {
-        self.backend
-            .clear(self.theme.palette[PaletteColor::Background]);
+        let background = self.theme.palette[PaletteColor::Background];
+        self.backend.clear(self.adapt_color(background).0);
+    }
+
+    /// Returns the closest color the backend can display.
+    ///
+    /// The boolean is `true` when the color should be drawn in bold.
+    fn adapt_color(&self, color: Color) -> (Color, bool) {
+        if self.color_support != ColorSupport::Ansi16 {
+            return (color, false);
+        }
+
+        match color.to_16colors() {
+            Color::Light(base) => (Color::Dark(base), true),
+            color => (color, false),
+        }
+    }
+
+    /// Returns `true` if the backend is currently drawing in bold.
+    fn is_bold(&self) -> bool {
+        self.bold_color || self.bold_effect
+    }
+
+    /// Sets or unsets bold on the backend.
+    fn set_bold(&self, bold: bool) {
+        if bold {
+            self.backend.set_effect(Effect::Bold);
+        } else {
+            self.backend.unset_effect(Effect::Bold);
+        }
+    }
+
+    /// Returns `true` if the backend supports colors.
+    pub fn has_colors(&self) -> bool {
+        self.backend.has_colors()
     }
 
     /// Prints some styled text at the given position.
@@ -144,9 +199,12 @@ impl<'a, 'b> Printer<'a, 'b> {
         // character preventing us from splitting perfectly.
         assert!(skipped_width >= hidden_part.x);
 
+        // The visible half of a split double-width character is blank.
+        let left_padding = skipped_width - hidden_part.x;
+
         // Drop part of the text, and move the cursor correspondingly.
         let text = &text[skipped_len..];
-        let start = start + (skipped_width, 0);
+        let start = start + (hidden_part.x, 0);
         assert!(start.fits(self.content_offset));
 
         // What we did before should guarantee that this won't overflow.
@@ -154,16 +212,34 @@ impl<'a, 'b> Printer<'a, 'b> {
 
         // Do we have enough room for the entire line?
         let room = self.output_size.x - start.x;
+        if left_padding >= room {
+            return;
+        }
 
         // Drop the end of the text if it's too long
         // We want the number of CHARACTERS, not bytes.
         // (Actually we want the "width" of the string, see unicode-width)
-        let prefix_len = prefix(text.graphemes(true), room, "").length;
-        let text = &text[..prefix_len];
-        assert!(text.width() <= room);
+        let text_room = room - left_padding;
+        let kept = prefix(text.graphemes(true), text_room, "");
+        let text_width = text.width();
+        let text = &text[..kept.length];
+        assert!(kept.width <= text_room);
+
+        // Again, blank the cell of a double-width character cut in half.
+        let right_padding = min(text_width, text_room) - kept.width;
 
         let start = start + self.offset;
-        self.backend.print_at(start, text);
+        if left_padding == 0 && right_padding == 0 {
+            self.backend.print_at(start, text);
+        } else {
+            let padded = format!(
+                "{}{}{}",
+                " ".repeat(left_padding),
+                text,
+                " ".repeat(right_padding)
+            );
+            self.backend.print_at(start, &padded);
+        }
     }
 
     /// Prints a vertical line using the given character.
@@ -267,8 +343,21 @@ impl<'a, 'b> Printer<'a, 'b> {
     where
         F: FnOnce(&Printer),
     {
-        let old = self.backend.set_color(c.resolve(&self.theme.palette));
-        f(self);
+        let colors = c.resolve(&self.theme.palette);
+        let (front, bold) = self.adapt_color(colors.front);
+        let back = self.adapt_color(colors.back).0;
+        let old = self.backend.set_color(ColorPair { front, back });
+
+        let printer = self.clone().with(|s| s.bold_color = bold);
+        if printer.is_bold() == self.is_bold() {
+            f(&printer);
+        } else {
+            // Toggle bold, and back when done, for the nested colors.
+            self.set_bold(printer.is_bold());
+            f(&printer);
+            self.set_bold(self.is_bold());
+        }
+
         self.backend.set_color(old);
     }
 
@@ -295,10 +384,28 @@ impl<'a, 'b> Printer<'a, 'b> {
 
     /// Call the given closure with a modified printer
     /// that will apply the given effect on prints.
+    ///
+    /// `Effect::Blink` is skipped if the theme disabled it.
     pub fn with_effect<F>(&self, effect: Effect, f: F)
     where
         F: FnOnce(&Printer),
     {
+        if effect == Effect::Blink && !self.theme.blink {
+            return f(self);
+        }
+
+        if effect == Effect::Bold {
+            // Bold may already be set, by a light color or an outer call.
+            let printer = self.clone().with(|s| s.bold_effect = true);
+            if self.is_bold() {
+                return f(&printer);
+            }
+            self.set_bold(true);
+            f(&printer);
+            self.set_bold(false);
+            return;
+        }
+
         self.backend.set_effect(effect);
         f(self);
         self.backend.unset_effect(effect);
@@ -313,6 +420,21 @@ impl<'a, 'b> Printer<'a, 'b> {
         f(&self.theme(theme));
     }
 
+    /// Call the given closure with a modified printer
+    /// that will draw borders with the given style.
+    ///
+    /// The rest of the theme is unchanged.
+    pub fn with_borders<F>(&self, borders: BorderStyle, f: F)
+    where
+        F: FnOnce(&Printer),
+    {
+        let theme = Theme {
+            borders,
+            ..self.theme.clone()
+        };
+        self.with_theme(&theme, f);
+    }
+
     /// Create a new sub-printer with the given theme.
     pub fn theme<'c>(&self, theme: &'c Theme) -> Printer<'c, 'b>
     where
@@ -326,7 +448,7 @@ impl<'a, 'b> Printer<'a, 'b> {
 
     /// Call the given closure with a modified printer
     /// that will apply each given effect on prints.
-    pub fn with_effects<F>(&self, effects: EnumSet<Effect>, f: F)
+    pub fn with_effects<F>(&self, effects: EffectSet, f: F)
     where
         F: FnOnce(&Printer),
     {
@@ -346,6 +468,8 @@ impl<'a, 'b> Printer<'a, 'b> {
     /// If `invert` is `true`, and the theme uses `Outset` borders, then the
     /// box will use an "inset" style instead.
     ///
+    /// The corners and edges follow the theme's border style.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -368,21 +492,178 @@ impl<'a, 'b> Printer<'a, 'b> {
         }
         let size = size - (1, 1);
 
+        let glyphs = self.theme.glyphs.borders(self.theme.borders);
+
         self.with_high_border(invert, |s| {
-            s.print(start, "┌");
-            s.print(start + size.keep_y(), "└");
-            s.print_hline(start + (1, 0), size.x - 1, "─");
-            s.print_vline(start + (0, 1), size.y - 1, "│");
+            s.print(start, &glyphs.top_left);
+            s.print(start + size.keep_y(), &glyphs.bottom_left);
+            s.print_hline(start + (1, 0), size.x - 1, &glyphs.horizontal);
+            s.print_vline(start + (0, 1), size.y - 1, &glyphs.vertical);
         });
 
         self.with_low_border(invert, |s| {
-            s.print(start + size.keep_x(), "┐");
-            s.print(start + size, "┘");
-            s.print_hline(start + (1, 0) + size.keep_y(), size.x - 1, "─");
-            s.print_vline(start + (0, 1) + size.keep_x(), size.y - 1, "│");
+            s.print(start + size.keep_x(), &glyphs.top_right);
+            s.print(start + size, &glyphs.bottom_right);
+            s.print_hline(
+                start + (1, 0) + size.keep_y(),
+                size.x - 1,
+                &glyphs.horizontal,
+            );
+            s.print_vline(
+                start + (0, 1) + size.keep_x(),
+                size.y - 1,
+                &glyphs.vertical,
+            );
+        });
+    }
+
+    /// Prints a title on the top edge of a box.
+    ///
+    /// The box starts at `start` and is `width` columns wide. The title is
+    /// placed according to `align`, between `┤ ` and ` ├`, and keeps some
+    /// room from the corners. Nothing is printed if it doesn't fit.
+    ///
+    /// # Examples
+    ///
+    /// A custom view drawing a titled box around its content:
+    ///
+    /// ```rust
+    /// # use cursive::align::HAlign;
+    /// # use cursive::view::View;
+    /// # use cursive::Printer;
+    /// struct Frame;
+    ///
+    /// impl View for Frame {
+    ///     fn draw(&self, printer: &Printer) {
+    ///         printer.print_box((0, 0), printer.size, false);
+    ///         let width = printer.size.x;
+    ///         printer.print_box_title((0, 0), width, "Frame", HAlign::Left);
+    ///         printer.offset((1, 1)).print((0, 0), "Content");
+    ///     }
+    /// }
+    /// ```
+    pub fn print_box_title<T: Into<Vec2>>(
+        &self, start: T, width: usize, title: &str, align: HAlign,
+    ) {
+        let start = start.into();
+        let len = title.width();
+        // Minimum distance to the borders.
+        let spacing = 3;
+        if title.is_empty() || len + 2 * spacing > width {
+            return;
+        }
+
+        let x = start.x + spacing + align.get_offset(len, width - 2 * spacing);
+        let glyphs = self.theme.glyphs.borders(self.theme.borders);
+        self.with_high_border(false, |printer| {
+            printer.print((x - 2, start.y), &glyphs.right_tee);
+            printer.print((x - 1, start.y), " ");
+            printer.print((x + len, start.y), " ");
+            printer.print((x + len + 1, start.y), &glyphs.left_tee);
+        });
+
+        self.with_color(ColorStyle::title_primary(), |printer| {
+            printer.print((x, start.y), title)
         });
     }
 
+    /// Prints the outline of `rect` with the glyphs of the given style.
+    ///
+    /// Unlike [`print_box`], this uses the current color and effects, and
+    /// ignores the theme's border style. Nothing is printed if `rect` is
+    /// less than 2 cells wide or high.
+    ///
+    /// [`print_box`]: #method.print_box
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cursive::Printer;
+    /// # use cursive::rect::Rect;
+    /// # use cursive::theme::{self, BorderStyle, ColorStyle};
+    /// # use cursive::backend;
+    /// # let b = backend::dummy::Backend::init();
+    /// # let t = theme::load_default();
+    /// # let printer = Printer::new((6,4), &t, &*b);
+    /// printer.with_color(ColorStyle::secondary(), |printer| {
+    ///     let rect = Rect::from_size((0, 0), (6, 4));
+    ///     printer.print_outline(rect, BorderStyle::Double);
+    /// });
+    /// ```
+    pub fn print_outline(&self, rect: Rect, borders: BorderStyle) {
+        let (start, size) = (rect.top_left(), rect.size());
+        if size.x < 2 || size.y < 2 {
+            return;
+        }
+        let end = rect.bottom_right();
+
+        let glyphs = self.theme.glyphs.borders(borders);
+        self.print(start, &glyphs.top_left);
+        self.print(rect.top_right(), &glyphs.top_right);
+        self.print(rect.bottom_left(), &glyphs.bottom_left);
+        self.print(end, &glyphs.bottom_right);
+        for &y in &[start.y, end.y] {
+            self.print_hline((start.x + 1, y), size.x - 2, &glyphs.horizontal);
+        }
+        for &x in &[start.x, end.x] {
+            self.print_vline((x, start.y + 1), size.y - 2, &glyphs.vertical);
+        }
+    }
+
+    /// Prints arrows on the visible edges that hide some content.
+    ///
+    /// The content is [`size`] big, and the visible part starts at
+    /// [`content_offset`], like in a printer given to a scrolled view. For
+    /// example, `▼` is printed in the middle of the last visible row if
+    /// more content is below it.
+    ///
+    /// The arrows are printed over the content, with the current color.
+    /// They come from the theme's [`Glyphs`], which have an ASCII variant.
+    ///
+    /// [`size`]: #structfield.size
+    /// [`content_offset`]: #structfield.content_offset
+    /// [`Glyphs`]: theme/struct.Glyphs.html
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cursive::Printer;
+    /// # use cursive::theme;
+    /// # use cursive::backend;
+    /// # let b = backend::dummy::Backend::init();
+    /// # let t = theme::load_default();
+    /// # let printer = Printer::new((6,4), &t, &*b);
+    /// // Shows the 4 first rows of a 10 rows long content.
+    /// let printer = printer.inner_size((6, 10));
+    /// for y in 0..10 {
+    ///     printer.print((0, y), &format!("Row {}", y));
+    /// }
+    /// printer.print_overflow_markers();
+    /// ```
+    pub fn print_overflow_markers(&self) {
+        let (offset, visible) = (self.content_offset, self.output_size);
+        if visible.x == 0 || visible.y == 0 {
+            return;
+        }
+
+        let glyphs = &self.theme.glyphs;
+        let hidden_after = self.size.saturating_sub(offset + visible);
+        let middle = offset + visible / 2;
+        let last = offset + visible - (1, 1);
+        if offset.y > 0 {
+            self.print((middle.x, offset.y), &glyphs.overflow_up);
+        }
+        if hidden_after.y > 0 {
+            self.print((middle.x, last.y), &glyphs.overflow_down);
+        }
+        if offset.x > 0 {
+            self.print((offset.x, middle.y), &glyphs.overflow_left);
+        }
+        if hidden_after.x > 0 {
+            self.print((last.x, middle.y), &glyphs.overflow_right);
+        }
+    }
+
     /// Runs the given function using a color depending on the theme.
     ///
     /// * If the theme's borders is `None`, return without calling `f`.
@@ -444,14 +725,25 @@ impl<'a, 'b> Printer<'a, 'b> {
     }
 
     /// Prints a horizontal delimiter with side border `├` and `┤`.
+    ///
+    /// The characters follow the theme's border style. Nothing is printed
+    /// if `len` is less than 2.
     pub fn print_hdelim<T>(&self, start: T, len: usize)
     where
         T: Into<Vec2>,
     {
+        if len < 2 {
+            return;
+        }
         let start = start.into();
-        self.print(start, "├");
-        self.print_hline(start + (1, 0), len.saturating_sub(2), "─");
-        self.print(start + (len.saturating_sub(1), 0), "┤");
+        let glyphs = self.theme.glyphs.borders(self.theme.borders);
+        self.print(start, &glyphs.left_tee);
+        self.print_hline(
+            start + (1, 0),
+            len.saturating_sub(2),
+            &glyphs.horizontal,
+        );
+        self.print(start + (len.saturating_sub(1), 0), &glyphs.right_tee);
     }
 
     /// Returns a sub-printer with the given offset.
@@ -548,3 +840,123 @@ impl<'a, 'b> Printer<'a, 'b> {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backend::puppet;
+    use theme;
+
+    fn render<F>(size: (usize, usize), f: F) -> Vec<String>
+    where
+        F: FnOnce(&Printer),
+    {
+        let size = Vec2::from(size);
+        let backend = puppet::Backend::init(Some(size));
+        let theme = theme::load_default();
+        f(&Printer::new(size, &theme, &*backend));
+        backend.screen().rows()
+    }
+
+    #[test]
+    fn test_box_helpers() {
+        let rows = render((12, 4), |printer| {
+            printer.print_outline(
+                Rect::from_size((0, 0), (12, 4)),
+                BorderStyle::Double,
+            );
+            printer.print_box_title((0, 0), 12, "Hi", HAlign::Left);
+            printer.print_hdelim((0, 2), 12);
+        });
+        assert_eq!(
+            rows,
+            vec![
+                "╔┤ Hi ├════╗",
+                "║          ║",
+                "├──────────┤",
+                "╚══════════╝",
+            ]
+        );
+
+        // Too small to draw anything.
+        let rows = render((3, 2), |printer| {
+            let rect = Rect::from_size((0, 0), (1, 2));
+            printer.print_outline(rect, BorderStyle::Simple);
+            printer.print_box_title((0, 0), 5, "Hi", HAlign::Center);
+            printer.print_hdelim((0, 1), 1);
+            printer.print_hline((0, 0), 0, "-");
+            printer.print_vline((0, 0), 0, "|");
+        });
+        assert_eq!(rows, vec!["   ", "   "]);
+    }
+
+    #[test]
+    fn test_16_colors() {
+        use theme::BaseColor::{Blue, Red};
+
+        let light = ColorStyle::new(Color::Light(Red), Color::Dark(Blue));
+        let dark = ColorStyle::new(Color::Dark(Red), Color::Dark(Blue));
+        let draw = |support| {
+            let size = Vec2::new(3, 1);
+            let backend = puppet::Backend::init(Some(size));
+            let theme = theme::load_default();
+            let mut printer = Printer::new(size, &theme, &*backend);
+            printer.color_support = support;
+            printer.with_color(light, |printer| {
+                printer.print((0, 0), "a");
+                printer.with_color(dark, |printer| printer.print((1, 0), "b"));
+                printer.print((2, 0), "c");
+            });
+            let screen = backend.screen();
+            (0..3)
+                .map(|x| screen.cell(Vec2::new(x, 0)).unwrap().style)
+                .map(|style| {
+                    (style.colors.front, style.effects.contains(Effect::Bold))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Light colors become bold, but not the nested dark color.
+        assert_eq!(
+            draw(ColorSupport::Ansi16),
+            vec![
+                (Color::Dark(Red), true),
+                (Color::Dark(Red), false),
+                (Color::Dark(Red), true),
+            ]
+        );
+        assert_eq!(draw(ColorSupport::Ansi256)[0], (Color::Light(Red), false));
+    }
+
+    #[test]
+    fn test_nested_bold() {
+        use theme::BaseColor::{Blue, Red};
+
+        let light = ColorStyle::new(Color::Light(Red), Color::Dark(Blue));
+        let dark = ColorStyle::new(Color::Dark(Red), Color::Dark(Blue));
+        let size = Vec2::new(5, 1);
+        let backend = puppet::Backend::init(Some(size));
+        let theme = theme::load_default();
+        let mut printer = Printer::new(size, &theme, &*backend);
+        printer.color_support = ColorSupport::Ansi16;
+        printer.with_effect(Effect::Bold, |printer| {
+            printer.with_color(light, |printer| {
+                printer.with_effect(Effect::Bold, |printer| {
+                    printer.print((0, 0), "a");
+                });
+                printer.print((1, 0), "b");
+            });
+            // Bold was asked for, even with a dark color.
+            printer.with_color(dark, |printer| printer.print((2, 0), "c"));
+            printer.print((3, 0), "d");
+        });
+        printer.print((4, 0), "e");
+
+        let screen = backend.screen();
+        let bold = (0..5)
+            .map(|x| screen.cell(Vec2::new(x, 0)).unwrap().style.effects)
+            .map(|effects| effects.contains(Effect::Bold))
+            .collect::<Vec<_>>();
+        assert_eq!(bold, vec![true, true, true, true, false]);
+    }
+}
@@ -0,0 +1,790 @@
+//! Record events, and play them back later.
+//!
+//! [`Cursive::start_recording()`] captures every event given to the root,
+//! with the time it was received. The resulting [`EventLog`] can be saved
+//! as text, then fed back to a [puppet backend] with a [`Player`], or
+//! directly with [`EventLog::replay()`].
+//!
+//! Each line of the text form holds the time of the event in milliseconds,
+//! followed by the event:
+//!
+//! ```text
+//! # Lines starting with `#` are ignored.
+//! 0 Char 'h'
+//! 85 Key Enter
+//! 130 Ctrl Left
+//! 740 Mouse Press Left 12,3
+//! 790 Paste "two\nlines"
+//! ```
+//!
+//! This form is stable, so logs can be kept as test fixtures. With the
+//! `serde` feature, an `EventLog` can also be (de)serialized as a list of
+//! such lines.
+//!
+//! [`Cursive::start_recording()`]:
+//! ../struct.Cursive.html#method.start_recording
+//! [`EventLog`]: struct.EventLog.html
+//! [puppet backend]: ../backend/puppet/index.html
+//! [`Player`]: struct.Player.html
+//! [`EventLog::replay()`]: struct.EventLog.html#method.replay
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use cursive::replay::EventLog;
+//! # use cursive::views::EditView;
+//! # use cursive::Cursive;
+//! let log: EventLog = "0 Char 'h'\n10 Char 'i'".parse().unwrap();
+//!
+//! let mut siv = Cursive::dummy();
+//! siv.add_layer(EditView::new());
+//! log.replay(&mut siv);
+//! ```
+use crossbeam_channel::Sender;
+use event::{Event, Key, MouseButton, MouseEvent};
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+use std::thread;
+use std::time::{Duration, Instant};
+use vec::Vec2;
+use Cursive;
+use With;
+
+/// An event, with the time it was received.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecordedEvent {
+    /// Time since the start of the recording.
+    pub time: Duration,
+    /// The event itself.
+    pub event: Event,
+}
+
+/// Sequence of recorded events.
+///
+/// Obtained from [`Cursive::stop_recording()`], or parsed from its text
+/// form.
+///
+/// [`Cursive::stop_recording()`]:
+/// ../struct.Cursive.html#method.stop_recording
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EventLog {
+    /// Events, ordered by time.
+    pub events: Vec<RecordedEvent>,
+}
+
+impl EventLog {
+    /// Creates an empty log.
+    pub fn new() -> Self {
+        EventLog::default()
+    }
+
+    /// Adds an event at the end of the log.
+    pub fn push(&mut self, time: Duration, event: Event) {
+        self.events.push(RecordedEvent { time, event });
+    }
+
+    /// Gives every event to `siv` right away, ignoring their times.
+    ///
+    /// This is the most deterministic way to replay a log in tests.
+    pub fn replay(&self, siv: &mut Cursive) {
+        for recorded in &self.events {
+            siv.on_event(recorded.event.clone());
+        }
+    }
+}
+
+/// Records the events received by a `Cursive` root.
+pub(crate) struct Recorder {
+    start: Instant,
+    log: EventLog,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Recorder {
+            start: Instant::now(),
+            log: EventLog::new(),
+        }
+    }
+
+    pub fn record(&mut self, event: &Event) {
+        self.log.push(self.start.elapsed(), event.clone());
+    }
+
+    pub fn finish(self) -> EventLog {
+        self.log
+    }
+}
+
+/// Sends the events of a log to an input channel, at their recorded times.
+///
+/// Meant for the sender returned by [`puppet::Backend::input()`].
+///
+/// [`puppet::Backend::input()`]:
+/// ../backend/puppet/struct.Backend.html#method.input
+pub struct Player {
+    log: EventLog,
+    speed: f64,
+}
+
+impl Player {
+    /// Creates a player for the given log, at real speed.
+    pub fn new(log: EventLog) -> Self {
+        Player { log, speed: 1.0 }
+    }
+
+    /// Sets how much faster than recorded the events are sent.
+    ///
+    /// `2.0` sends them twice as fast, and `f64::INFINITY` sends them
+    /// without waiting. Defaults to `1.0`.
+    ///
+    /// # Panics
+    ///
+    /// If `speed` is not positive.
+    pub fn set_speed(&mut self, speed: f64) {
+        assert!(speed > 0.0, "the speed must be positive");
+        self.speed = speed;
+    }
+
+    /// Sets how much faster than recorded the events are sent.
+    ///
+    /// Chainable variant.
+    pub fn speed(self, speed: f64) -> Self {
+        self.with(|s| s.set_speed(speed))
+    }
+
+    /// Starts sending the events to `input` from a new thread.
+    ///
+    /// The thread stops after the last event, or when `input` is closed.
+    pub fn play(self, input: Sender<Option<Event>>) -> thread::JoinHandle<()> {
+        thread::spawn(move || {
+            let start = Instant::now();
+            for recorded in self.log.events {
+                let time = recorded.time.as_secs_f64() / self.speed;
+                let deadline = start + Duration::from_secs_f64(time);
+                let now = Instant::now();
+                if deadline > now {
+                    thread::sleep(deadline - now);
+                }
+
+                if input.send(Some(recorded.event)).is_err() {
+                    return;
+                }
+            }
+        })
+    }
+}
+
+impl fmt::Display for EventLog {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for recorded in &self.events {
+            writeln!(f, "{}", recorded)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for EventLog {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut log = EventLog::new();
+        for (i, line) in value.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let recorded = line.parse().map_err(|err: ParseError| {
+                ParseError {
+                    line: i + 1,
+                    ..err
+                }
+            })?;
+            log.events.push(recorded);
+        }
+        Ok(log)
+    }
+}
+
+impl fmt::Display for RecordedEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ", self.time.as_millis())?;
+        write_event(f, &self.event)
+    }
+}
+
+impl FromStr for RecordedEvent {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        let (time, event) = split_word(value);
+        let time = time
+            .parse()
+            .map_err(|_| ParseError::new(format!("invalid time `{}`", time)))?;
+        Ok(RecordedEvent {
+            time: Duration::from_millis(time),
+            event: parse_event(event)?,
+        })
+    }
+}
+
+/// Error returned when parsing an [`EventLog`] from a string.
+///
+/// [`EventLog`]: struct.EventLog.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line with the error, starting at 1.
+    ///
+    /// This is 0 when parsing a single `RecordedEvent`.
+    pub line: usize,
+    /// What went wrong.
+    pub message: String,
+}
+
+impl ParseError {
+    fn new(message: String) -> Self {
+        ParseError { line: 0, message }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.line > 0 {
+            write!(f, "line {}: ", self.line)?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+impl error::Error for ParseError {}
+
+// Returns the first word of `value`, and what follows it.
+fn split_word(value: &str) -> (&str, &str) {
+    match value.find(char::is_whitespace) {
+        Some(i) => (&value[..i], value[i..].trim_start()),
+        None => (value, ""),
+    }
+}
+
+fn write_event(f: &mut fmt::Formatter, event: &Event) -> fmt::Result {
+    match *event {
+        Event::WindowResize => write!(f, "WindowResize"),
+        Event::WindowFocus(focused) => write!(f, "WindowFocus {}", focused),
+        Event::Refresh => write!(f, "Refresh"),
+        Event::FocusGained => write!(f, "FocusGained"),
+        Event::FocusLost => write!(f, "FocusLost"),
+        Event::Char(c) => write!(f, "Char '{}'", c.escape_default()),
+        Event::CtrlChar(c) => write!(f, "CtrlChar '{}'", c.escape_default()),
+        Event::AltChar(c) => write!(f, "AltChar '{}'", c.escape_default()),
+        Event::Key(key) => write!(f, "Key {}", key_name(key)),
+        Event::Shift(key) => write!(f, "Shift {}", key_name(key)),
+        Event::Alt(key) => write!(f, "Alt {}", key_name(key)),
+        Event::AltShift(key) => write!(f, "AltShift {}", key_name(key)),
+        Event::Ctrl(key) => write!(f, "Ctrl {}", key_name(key)),
+        Event::CtrlShift(key) => write!(f, "CtrlShift {}", key_name(key)),
+        Event::CtrlAlt(key) => write!(f, "CtrlAlt {}", key_name(key)),
+        Event::Mouse {
+            offset,
+            position,
+            event,
+        } => {
+            match event.button() {
+                Some(button) => {
+                    write!(f, "Mouse {} {}", name(event), button_name(button))
+                }
+                None => write!(f, "Mouse {}", name(event)),
+            }?;
+            write!(f, " {},{}", position.x, position.y)?;
+            if offset != Vec2::zero() {
+                write!(f, " {},{}", offset.x, offset.y)?;
+            }
+            Ok(())
+        }
+        Event::Paste(ref text) => {
+            write!(f, "Paste \"{}\"", text.escape_default())
+        }
+        Event::Unknown(ref bytes) => {
+            write!(f, "Unknown")?;
+            for byte in bytes {
+                write!(f, " {:02x}", byte)?;
+            }
+            Ok(())
+        }
+        Event::Exit => write!(f, "Exit"),
+    }
+}
+
+// Name of a mouse event, without its button.
+fn name(event: MouseEvent) -> &'static str {
+    match event {
+        MouseEvent::Press(_) => "Press",
+        MouseEvent::Release(_) => "Release",
+        MouseEvent::Hold(_) => "Hold",
+        MouseEvent::DoubleClick(_) => "DoubleClick",
+        MouseEvent::TripleClick(_) => "TripleClick",
+        MouseEvent::WheelUp => "WheelUp",
+        MouseEvent::WheelDown => "WheelDown",
+        MouseEvent::WheelLeft => "WheelLeft",
+        MouseEvent::WheelRight => "WheelRight",
+        MouseEvent::Hover => "Hover",
+    }
+}
+
+// Name of a key, as written in logs.
+//
+// Names are spelled out, so they do not change with the `Debug` output.
+fn key_name(key: Key) -> &'static str {
+    match key {
+        Key::Enter => "Enter",
+        Key::Tab => "Tab",
+        Key::Backspace => "Backspace",
+        Key::Esc => "Esc",
+        Key::Left => "Left",
+        Key::Right => "Right",
+        Key::Up => "Up",
+        Key::Down => "Down",
+        Key::Ins => "Ins",
+        Key::Del => "Del",
+        Key::Home => "Home",
+        Key::End => "End",
+        Key::PageUp => "PageUp",
+        Key::PageDown => "PageDown",
+        Key::PauseBreak => "PauseBreak",
+        Key::NumpadCenter => "NumpadCenter",
+        Key::F0 => "F0",
+        Key::F1 => "F1",
+        Key::F2 => "F2",
+        Key::F3 => "F3",
+        Key::F4 => "F4",
+        Key::F5 => "F5",
+        Key::F6 => "F6",
+        Key::F7 => "F7",
+        Key::F8 => "F8",
+        Key::F9 => "F9",
+        Key::F10 => "F10",
+        Key::F11 => "F11",
+        Key::F12 => "F12",
+        Key::F13 => "F13",
+        Key::F14 => "F14",
+        Key::F15 => "F15",
+        Key::F16 => "F16",
+        Key::F17 => "F17",
+        Key::F18 => "F18",
+        Key::F19 => "F19",
+        Key::F20 => "F20",
+        Key::F21 => "F21",
+        Key::F22 => "F22",
+        Key::F23 => "F23",
+        Key::F24 => "F24",
+    }
+}
+
+// Name of a mouse button, as written in logs.
+fn button_name(button: MouseButton) -> &'static str {
+    match button {
+        MouseButton::Left => "Left",
+        MouseButton::Middle => "Middle",
+        MouseButton::Right => "Right",
+        MouseButton::Button4 => "Button4",
+        MouseButton::Button5 => "Button5",
+        MouseButton::Other => "Other",
+    }
+}
+
+fn parse_event(value: &str) -> Result<Event, ParseError> {
+    let (name, args) = split_word(value);
+    let no_args = |event| {
+        if args.is_empty() {
+            Ok(event)
+        } else {
+            Err(unexpected(args))
+        }
+    };
+
+    match name {
+        "WindowResize" => no_args(Event::WindowResize),
+        "Refresh" => no_args(Event::Refresh),
+        "FocusGained" => no_args(Event::FocusGained),
+        "FocusLost" => no_args(Event::FocusLost),
+        "Exit" => no_args(Event::Exit),
+        "WindowFocus" => match args {
+            "true" => Ok(Event::WindowFocus(true)),
+            "false" => Ok(Event::WindowFocus(false)),
+            _ => Err(unexpected(args)),
+        },
+        "Char" => parse_char(args).map(Event::Char),
+        "CtrlChar" => parse_char(args).map(Event::CtrlChar),
+        "AltChar" => parse_char(args).map(Event::AltChar),
+        "Key" => parse_key(args).map(Event::Key),
+        "Shift" => parse_key(args).map(Event::Shift),
+        "Alt" => parse_key(args).map(Event::Alt),
+        "AltShift" => parse_key(args).map(Event::AltShift),
+        "Ctrl" => parse_key(args).map(Event::Ctrl),
+        "CtrlShift" => parse_key(args).map(Event::CtrlShift),
+        "CtrlAlt" => parse_key(args).map(Event::CtrlAlt),
+        "Mouse" => parse_mouse(args),
+        "Paste" => parse_quoted(args, '"').map(Event::Paste),
+        "Unknown" => args
+            .split_whitespace()
+            .map(|byte| {
+                u8::from_str_radix(byte, 16).map_err(|_| unexpected(byte))
+            })
+            .collect::<Result<_, _>>()
+            .map(Event::Unknown),
+        _ => Err(ParseError::new(format!("unknown event `{}`", name))),
+    }
+}
+
+fn unexpected(value: &str) -> ParseError {
+    ParseError::new(format!("unexpected `{}`", value))
+}
+
+fn parse_char(value: &str) -> Result<char, ParseError> {
+    let text = parse_quoted(value, '\'')?;
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => Err(ParseError::new(format!("expected one char in {}", value))),
+    }
+}
+
+fn parse_key(value: &str) -> Result<Key, ParseError> {
+    Ok(match value {
+        "Enter" => Key::Enter,
+        "Tab" => Key::Tab,
+        "Backspace" => Key::Backspace,
+        "Esc" => Key::Esc,
+        "Left" => Key::Left,
+        "Right" => Key::Right,
+        "Up" => Key::Up,
+        "Down" => Key::Down,
+        "Ins" => Key::Ins,
+        "Del" => Key::Del,
+        "Home" => Key::Home,
+        "End" => Key::End,
+        "PageUp" => Key::PageUp,
+        "PageDown" => Key::PageDown,
+        "PauseBreak" => Key::PauseBreak,
+        "NumpadCenter" => Key::NumpadCenter,
+        "F0" => Key::F0,
+        "F1" => Key::F1,
+        "F2" => Key::F2,
+        "F3" => Key::F3,
+        "F4" => Key::F4,
+        "F5" => Key::F5,
+        "F6" => Key::F6,
+        "F7" => Key::F7,
+        "F8" => Key::F8,
+        "F9" => Key::F9,
+        "F10" => Key::F10,
+        "F11" => Key::F11,
+        "F12" => Key::F12,
+        "F13" => Key::F13,
+        "F14" => Key::F14,
+        "F15" => Key::F15,
+        "F16" => Key::F16,
+        "F17" => Key::F17,
+        "F18" => Key::F18,
+        "F19" => Key::F19,
+        "F20" => Key::F20,
+        "F21" => Key::F21,
+        "F22" => Key::F22,
+        "F23" => Key::F23,
+        "F24" => Key::F24,
+        _ => return Err(ParseError::new(format!("unknown key `{}`", value))),
+    })
+}
+
+fn parse_button(value: &str) -> Result<MouseButton, ParseError> {
+    Ok(match value {
+        "Left" => MouseButton::Left,
+        "Middle" => MouseButton::Middle,
+        "Right" => MouseButton::Right,
+        "Button4" => MouseButton::Button4,
+        "Button5" => MouseButton::Button5,
+        "Other" => MouseButton::Other,
+        _ => {
+            return Err(ParseError::new(format!(
+                "unknown mouse button `{}`",
+                value
+            )))
+        }
+    })
+}
+
+fn parse_vec2(value: &str) -> Result<Vec2, ParseError> {
+    let mut coords = value.split(',').map(str::parse::<usize>);
+    match (coords.next(), coords.next(), coords.next()) {
+        (Some(Ok(x)), Some(Ok(y)), None) => Ok(Vec2::new(x, y)),
+        _ => Err(ParseError::new(format!("invalid position `{}`", value))),
+    }
+}
+
+fn parse_mouse(value: &str) -> Result<Event, ParseError> {
+    let mut words = value.split_whitespace();
+    let mut next = || {
+        words
+            .next()
+            .ok_or_else(|| ParseError::new("incomplete mouse event".into()))
+    };
+
+    let name = next()?;
+    let event = match name {
+        "WheelUp" => MouseEvent::WheelUp,
+        "WheelDown" => MouseEvent::WheelDown,
+        "WheelLeft" => MouseEvent::WheelLeft,
+        "WheelRight" => MouseEvent::WheelRight,
+        "Hover" => MouseEvent::Hover,
+        "Press" => MouseEvent::Press(parse_button(next()?)?),
+        "Release" => MouseEvent::Release(parse_button(next()?)?),
+        "Hold" => MouseEvent::Hold(parse_button(next()?)?),
+        "DoubleClick" => MouseEvent::DoubleClick(parse_button(next()?)?),
+        "TripleClick" => MouseEvent::TripleClick(parse_button(next()?)?),
+        _ => {
+            return Err(ParseError::new(format!(
+                "unknown mouse event `{}`",
+                name
+            )))
+        }
+    };
+    let position = parse_vec2(next()?)?;
+    let offset = match words.next() {
+        Some(offset) => parse_vec2(offset)?,
+        None => Vec2::zero(),
+    };
+    if let Some(word) = words.next() {
+        return Err(unexpected(word));
+    }
+
+    Ok(Event::Mouse {
+        offset,
+        position,
+        event,
+    })
+}
+
+// Parses text between `quote`s, with escapes from `escape_default()`.
+fn parse_quoted(value: &str, quote: char) -> Result<String, ParseError> {
+    let invalid = || ParseError::new(format!("invalid quoted text {}", value));
+
+    let len = value.len();
+    if len < 2 || !value.starts_with(quote) || !value.ends_with(quote) {
+        return Err(invalid());
+    }
+
+    let mut text = String::new();
+    let mut chars = value[1..len - 1].chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+
+        text.push(match chars.next() {
+            Some('t') => '\t',
+            Some('r') => '\r',
+            Some('n') => '\n',
+            Some('0') => '\0',
+            Some('u') => {
+                if chars.next() != Some('{') {
+                    return Err(invalid());
+                }
+                let hex: String =
+                    chars.by_ref().take_while(|&c| c != '}').collect();
+                u32::from_str_radix(&hex, 16)
+                    .ok()
+                    .and_then(::std::char::from_u32)
+                    .ok_or_else(invalid)?
+            }
+            Some(c) => c,
+            None => return Err(invalid()),
+        });
+    }
+    Ok(text)
+}
+
+#[cfg(feature = "serde")]
+mod serialization {
+    use super::*;
+    use serde::de::{Deserialize, Deserializer, Error};
+    use serde::ser::{Serialize, Serializer};
+
+    // A list of lines, as in the text form.
+    impl Serialize for EventLog {
+        fn serialize<S: Serializer>(
+            &self, serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(self.events.iter().map(|e| e.to_string()))
+        }
+    }
+
+    impl<'de> Deserialize<'de> for EventLog {
+        fn deserialize<D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            let lines: Vec<String> = Vec::deserialize(deserializer)?;
+            let events = lines
+                .iter()
+                .map(|line| line.parse())
+                .collect::<Result<_, ParseError>>()
+                .map_err(D::Error::custom)?;
+            Ok(EventLog { events })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backend::puppet;
+    use traits::*;
+    use views::EditView;
+
+    fn events() -> Vec<Event> {
+        vec![
+            Event::WindowResize,
+            Event::WindowFocus(false),
+            Event::Char(' '),
+            Event::Char('\''),
+            Event::CtrlChar('é'),
+            Event::Key(Key::F12),
+            Event::CtrlShift(Key::PageDown),
+            Event::Mouse {
+                offset: Vec2::zero(),
+                position: Vec2::new(3, 14),
+                event: MouseEvent::Press(MouseButton::Right),
+            },
+            Event::Mouse {
+                offset: Vec2::new(1, 2),
+                position: Vec2::new(3, 4),
+                event: MouseEvent::WheelUp,
+            },
+            Event::Paste("a \"b\"\n\\c".to_string()),
+            Event::Unknown(vec![0x1b, 0x5b]),
+        ]
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut log = EventLog::new();
+        for (i, event) in events().into_iter().enumerate() {
+            log.push(Duration::from_millis(i as u64 * 10), event);
+        }
+
+        let text = log.to_string();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[2], "20 Char ' '");
+        assert_eq!(lines[3], "30 Char '\\''");
+        assert_eq!(lines[5], "50 Key F12");
+        assert_eq!(lines[7], "70 Mouse Press Right 3,14");
+        assert_eq!(lines[8], "80 Mouse WheelUp 3,4 1,2");
+        assert_eq!(lines[10], "100 Unknown 1b 5b");
+        assert_eq!(text.parse::<EventLog>(), Ok(log));
+    }
+
+    #[test]
+    fn test_key_names() {
+        let keys = [
+            Key::Enter,
+            Key::Tab,
+            Key::Backspace,
+            Key::Esc,
+            Key::Left,
+            Key::Right,
+            Key::Up,
+            Key::Down,
+            Key::Ins,
+            Key::Del,
+            Key::Home,
+            Key::End,
+            Key::PageUp,
+            Key::PageDown,
+            Key::PauseBreak,
+            Key::NumpadCenter,
+        ];
+        let f_keys = (0..25).map(Key::from_f);
+
+        for key in keys.iter().cloned().chain(f_keys) {
+            let recorded = RecordedEvent {
+                time: Duration::from_millis(0),
+                event: Event::CtrlAlt(key),
+            };
+            assert_eq!(recorded.to_string().parse(), Ok(recorded));
+        }
+        assert_eq!(key_name(Key::F0), "F0");
+        assert_eq!(key_name(Key::PauseBreak), "PauseBreak");
+
+        let buttons = [
+            MouseButton::Left,
+            MouseButton::Middle,
+            MouseButton::Right,
+            MouseButton::Button4,
+            MouseButton::Button5,
+            MouseButton::Other,
+        ];
+        for &button in &buttons {
+            assert_eq!(parse_button(button_name(button)), Ok(button));
+        }
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = "# Header\n0 Key Enter\n10 Key Foo"
+            .parse::<EventLog>()
+            .unwrap_err();
+        assert_eq!(err.to_string(), "line 3: unknown key `Foo`");
+
+        assert!("0 Mouse Press 1,2".parse::<EventLog>().is_err());
+        assert!("0 Char 'ab'".parse::<EventLog>().is_err());
+        assert!("0 Refresh now".parse::<EventLog>().is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let log: EventLog = "0 Char 'a'\n5 Key Enter".parse().unwrap();
+        let value = ::toml::Value::try_from(&log).unwrap();
+        assert_eq!(value.to_string(), r#"["0 Char 'a'", "5 Key Enter"]"#);
+        assert_eq!(value.try_into::<EventLog>().unwrap(), log);
+    }
+
+    fn content(siv: &mut Cursive) -> String {
+        siv.call_on_id("edit", |edit: &mut EditView| {
+            edit.get_content().to_string()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_record_and_play() {
+        let backend = puppet::Backend::init(Some(Vec2::new(20, 3)));
+        let input = backend.input();
+        let mut siv = Cursive::new(move || backend);
+        siv.add_layer(EditView::new().with_id("edit").fixed_width(10));
+
+        siv.start_recording();
+        for c in "hey".chars() {
+            siv.on_event(Event::Char(c));
+        }
+        siv.on_event(Event::Key(Key::Backspace));
+        let log = siv.stop_recording().unwrap();
+        assert!(!siv.is_recording());
+        assert_eq!(log.events.len(), 4);
+        assert_eq!(content(&mut siv), "he");
+
+        // Replaying directly.
+        log.replay(&mut siv);
+        assert_eq!(content(&mut siv), "hehe");
+
+        // Replaying through the input channel.
+        Player::new(log)
+            .speed(f64::INFINITY)
+            .play(input.clone())
+            .join()
+            .unwrap();
+        siv.step();
+        assert_eq!(content(&mut siv), "hehehe");
+    }
+}
@@ -9,18 +9,114 @@ pub enum BorderStyle {
     Simple,
     /// Outset borders with a simple 3d effect.
     Outset,
+    /// Simple borders with rounded corners.
+    Rounded,
+    /// Double-line borders.
+    Double,
+    /// Heavy borders.
+    Heavy,
     /// No borders.
     None,
 }
 
+/// Characters used to draw borders.
+///
+/// Themes can change them for each style, in [`Glyphs`].
+///
+/// [`Glyphs`]: struct.Glyphs.html
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BorderGlyphs {
+    /// Top-left corner.
+    pub top_left: String,
+    /// Top-right corner.
+    pub top_right: String,
+    /// Bottom-left corner.
+    pub bottom_left: String,
+    /// Bottom-right corner.
+    pub bottom_right: String,
+    /// Horizontal edge.
+    pub horizontal: String,
+    /// Vertical edge.
+    pub vertical: String,
+    /// Left edge, opening a horizontal line.
+    pub left_tee: String,
+    /// Right edge, closing a horizontal line.
+    pub right_tee: String,
+}
+
+impl BorderStyle {
+    /// Parses a border style: `"simple"`, `"outset"`, `"rounded"`,
+    /// `"double"`, `"heavy"` or `"none"`.
+    ///
+    /// Returns `None` for any other value.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "simple" => Some(BorderStyle::Simple),
+            "outset" => Some(BorderStyle::Outset),
+            "rounded" => Some(BorderStyle::Rounded),
+            "double" => Some(BorderStyle::Double),
+            "heavy" => Some(BorderStyle::Heavy),
+            "none" => Some(BorderStyle::None),
+            _ => None,
+        }
+    }
+
+    /// Returns the default characters used to draw borders in this style.
+    ///
+    /// `Outset` and `None` use the simple characters. The printer uses the
+    /// ones from the theme instead, see [`Glyphs::borders()`].
+    ///
+    /// [`Glyphs::borders()`]: struct.Glyphs.html#method.borders
+    pub fn glyphs(self) -> BorderGlyphs {
+        let chars = match self {
+            BorderStyle::Simple | BorderStyle::Outset | BorderStyle::None => {
+                "┌┐└┘─│├┤"
+            }
+            BorderStyle::Rounded => "╭╮╰╯─│├┤",
+            BorderStyle::Double => "╔╗╚╝═║╠╣",
+            BorderStyle::Heavy => "┏┓┗┛━┃┣┫",
+        };
+        BorderGlyphs::from_chars(chars)
+    }
+}
+
+impl BorderGlyphs {
+    // Takes one glyph from each character of `chars`, in field order.
+    pub(crate) fn from_chars(chars: &str) -> Self {
+        let mut chars = chars.chars().map(|c| c.to_string());
+        let mut next = || chars.next().unwrap();
+
+        // Fields are filled in order.
+        BorderGlyphs {
+            top_left: next(),
+            top_right: next(),
+            bottom_left: next(),
+            bottom_right: next(),
+            horizontal: next(),
+            vertical: next(),
+            left_tee: next(),
+            right_tee: next(),
+        }
+    }
+
+    // Returns the glyph with the given theme key.
+    pub(crate) fn get_mut(&mut self, key: &str) -> Option<&mut String> {
+        Some(match key {
+            "top_left" => &mut self.top_left,
+            "top_right" => &mut self.top_right,
+            "bottom_left" => &mut self.bottom_left,
+            "bottom_right" => &mut self.bottom_right,
+            "horizontal" => &mut self.horizontal,
+            "vertical" => &mut self.vertical,
+            "left_tee" => &mut self.left_tee,
+            "right_tee" => &mut self.right_tee,
+            _ => return None,
+        })
+    }
+}
+
 impl<S: Deref<Target = String>> From<S> for BorderStyle {
     fn from(s: S) -> Self {
-        if &*s == "simple" {
-            BorderStyle::Simple
-        } else if &*s == "outset" {
-            BorderStyle::Outset
-        } else {
-            BorderStyle::None
-        }
+        BorderStyle::parse(&s).unwrap_or(BorderStyle::None)
     }
 }
@@ -1,3 +1,8 @@
+use std::cmp::min;
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
 /// One of the 8 base colors.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum BaseColor {
@@ -84,7 +89,7 @@ pub enum Color {
 
     /// Low-resolution
     ///
-    /// Each value should be `<= 5` (you'll get panics otherwise).
+    /// Each value should be `<= 5`; larger values are treated as 5.
     ///
     /// These 216 possible colors are part of the default color palette.
     RgbLowRes(u8, u8, u8),
@@ -126,6 +131,125 @@ impl Color {
         }
     }
 
+    /// Returns the closest color in the 256 colors list.
+    ///
+    /// This is the reverse of [`Color::from_256colors`]:
+    ///
+    /// * Base colors are returned as-is (0-15).
+    /// * `RgbLowRes` colors map to the 6x6x6 cube (16-231).
+    /// * `Rgb` colors map to the closest entry in either the cube or the
+    ///   grayscale ramp (232-255).
+    ///
+    /// Returns `None` for `TerminalDefault`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cursive::theme::Color;
+    /// assert_eq!(Color::Rgb(255, 0, 0).to_256colors(), Some(196));
+    /// assert_eq!(Color::Rgb(128, 128, 128).to_256colors(), Some(244));
+    /// ```
+    ///
+    /// [`Color::from_256colors`]: #method.from_256colors
+    pub fn to_256colors(self) -> Option<u8> {
+        Some(match self {
+            Color::TerminalDefault => return None,
+            Color::Dark(base) => base as u8,
+            Color::Light(base) => 8 + base as u8,
+            Color::RgbLowRes(r, g, b) => {
+                16 + 36 * min(r, 5) + 6 * min(g, 5) + min(b, 5)
+            }
+            Color::Rgb(r, g, b) => {
+                // Closest color in the 6x6x6 cube
+                let (ri, gi, bi) =
+                    (cube_index(r), cube_index(g), cube_index(b));
+                let cube = (
+                    CUBE_LEVELS[ri as usize],
+                    CUBE_LEVELS[gi as usize],
+                    CUBE_LEVELS[bi as usize],
+                );
+
+                // Closest gray in the grayscale ramp: 8 + 10 * n
+                let average =
+                    (u16::from(r) + u16::from(g) + u16::from(b)) / 3;
+                let n = (average.saturating_sub(3) / 10).min(23) as u8;
+                let gray = 8 + 10 * n;
+
+                if distance((r, g, b), (gray, gray, gray))
+                    < distance((r, g, b), cube)
+                {
+                    232 + n
+                } else {
+                    16 + 36 * ri + 6 * gi + bi
+                }
+            }
+        })
+    }
+
+    /// Returns the closest color among the 16 base colors.
+    ///
+    /// `TerminalDefault`, `Dark` and `Light` colors are returned unchanged;
+    /// `Rgb` and `RgbLowRes` colors are replaced by the nearest base color.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cursive::theme::{BaseColor, Color};
+    /// assert_eq!(
+    ///     Color::Rgb(200, 50, 50).to_16colors(),
+    ///     Color::Dark(BaseColor::Red)
+    /// );
+    /// ```
+    pub fn to_16colors(self) -> Self {
+        let rgb = match self {
+            Color::Rgb(r, g, b) => (r, g, b),
+            Color::RgbLowRes(r, g, b) => {
+                (cube_level(r), cube_level(g), cube_level(b))
+            }
+            other => return other,
+        };
+
+        (0..16u8)
+            .min_by_key(|&i| distance(rgb, BASE_COLORS[i as usize]))
+            .map(|i| {
+                if i < 8 {
+                    Color::Dark(BaseColor::from(i))
+                } else {
+                    Color::Light(BaseColor::from(i))
+                }
+            })
+            .unwrap()
+    }
+
+    /// Returns approximate RGB values for this color.
+    ///
+    /// Base colors use common defaults, but the actual values depend on
+    /// the terminal configuration.
+    ///
+    /// Returns `None` for `TerminalDefault`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cursive::theme::{BaseColor, Color};
+    /// assert_eq!(Color::RgbLowRes(5, 0, 1).to_rgb(), Some((255, 0, 95)));
+    /// assert_eq!(
+    ///     Color::Light(BaseColor::White).to_rgb(),
+    ///     Some((255, 255, 255))
+    /// );
+    /// ```
+    pub fn to_rgb(self) -> Option<(u8, u8, u8)> {
+        Some(match self {
+            Color::TerminalDefault => return None,
+            Color::Dark(base) => BASE_COLORS[base as usize],
+            Color::Light(base) => BASE_COLORS[8 + base as usize],
+            Color::Rgb(r, g, b) => (r, g, b),
+            Color::RgbLowRes(r, g, b) => {
+                (cube_level(r), cube_level(g), cube_level(b))
+            }
+        })
+    }
+
     /// Parse a string into a color.
     ///
     /// Examples:
@@ -133,8 +257,54 @@ impl Color {
     /// * `"light green"` becomes `Color::Light(BaseColor::Green)`
     /// * `"default"` becomes `Color::TerminalDefault`
     /// * `"#123456"` becomes `Color::Rgb(0x12, 0x34, 0x56)`
+    /// * `"#f80"` becomes `Color::Rgb(0xff, 0x88, 0x00)`
+    /// * `"050"` becomes `Color::RgbLowRes(0, 5, 0)`
+    ///
+    /// Surrounding whitespace is ignored, and hex digits can be upper or
+    /// lowercase. Use `str::parse` to know why a string was rejected.
     pub fn parse(value: &str) -> Option<Self> {
-        Some(match value {
+        value.parse().ok()
+    }
+
+    fn parse_hex(value: &str) -> Result<Color, ParseColorError> {
+        let digits = &value[1..];
+        // Compute per-color length, and amplitude
+        let (l, multiplier) = match digits.len() {
+            6 => (2, 1),
+            3 => (1, 17),
+            _ => return Err(ParseColorError::InvalidHex(value.to_string())),
+        };
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(ParseColorError::InvalidHex(value.to_string()));
+        }
+
+        let r = load_hex(&digits[0..l]) * multiplier;
+        let g = load_hex(&digits[l..2 * l]) * multiplier;
+        let b = load_hex(&digits[2 * l..3 * l]) * multiplier;
+
+        Ok(Color::Rgb(r as u8, g as u8, b as u8))
+    }
+
+    fn parse_low_res(value: &str) -> Option<Color> {
+        // RGB values between 0 and 5 maybe?
+        // Like 050 for green
+        let rgb: Vec<_> =
+            value.chars().map(|c| c as i16 - '0' as i16).collect();
+
+        if rgb.len() == 3 && rgb.iter().all(|i| (0..6).contains(i)) {
+            Some(Color::RgbLowRes(rgb[0] as u8, rgb[1] as u8, rgb[2] as u8))
+        } else {
+            None
+        }
+    }
+}
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        Ok(match value {
             "black" => Color::Dark(BaseColor::Black),
             "red" => Color::Dark(BaseColor::Red),
             "green" => Color::Dark(BaseColor::Green),
@@ -152,46 +322,96 @@ impl Color {
             "light cyan" => Color::Light(BaseColor::Cyan),
             "light white" => Color::Light(BaseColor::White),
             "default" => Color::TerminalDefault,
-            value => return Color::parse_special(value),
+            value if value.starts_with('#') => return Color::parse_hex(value),
+            value => {
+                return Color::parse_low_res(value).ok_or_else(|| {
+                    ParseColorError::Unknown(value.to_string())
+                })
+            }
         })
     }
+}
+
+/// Error returned when parsing a [`Color`] from a string.
+///
+/// [`Color`]: enum.Color.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseColorError {
+    /// The string starts with `#`, but isn't followed by 3 or 6 hex digits.
+    InvalidHex(String),
+    /// The string is not a color name, a hex color or a low-resolution color.
+    Unknown(String),
+}
 
-    fn parse_special(value: &str) -> Option<Color> {
-        if value.starts_with('#') {
-            let value = &value[1..];
-            // Compute per-color length, and amplitude
-            let (l, multiplier) = match value.len() {
-                6 => (2, 1),
-                3 => (1, 17),
-                _ => panic!("Cannot parse color: {}", value),
-            };
-            let r = load_hex(&value[0..l]) * multiplier;
-            let g = load_hex(&value[l..2 * l]) * multiplier;
-            let b = load_hex(&value[2 * l..3 * l]) * multiplier;
-
-            Some(Color::Rgb(r as u8, g as u8, b as u8))
-        } else if value.len() == 3 {
-            // RGB values between 0 and 5 maybe?
-            // Like 050 for green
-            let rgb: Vec<_> =
-                value.chars().map(|c| c as i16 - '0' as i16).collect();
-
-            assert_eq!(rgb.len(), 3);
-            if rgb.iter().all(|&i| i >= 0 && i < 6) {
-                Some(Color::RgbLowRes(
-                    rgb[0] as u8,
-                    rgb[1] as u8,
-                    rgb[2] as u8,
-                ))
-            } else {
-                None
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseColorError::InvalidHex(ref value) => write!(
+                f,
+                "invalid hex color `{}`: expected 3 or 6 hex digits",
+                value
+            ),
+            ParseColorError::Unknown(ref value) => {
+                write!(f, "unknown color `{}`", value)
             }
-        } else {
-            None
         }
     }
 }
 
+impl error::Error for ParseColorError {}
+
+/// Value of each of the 6 levels used in the 256-colors cube.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+// Returns the RGB value of a level in the 6x6x6 cube, capped at 5.
+fn cube_level(level: u8) -> u8 {
+    CUBE_LEVELS[min(level, 5) as usize]
+}
+
+/// Approximate values of the 16 base colors.
+///
+/// Terminals are free to use different values, but these are common
+/// defaults (from xterm).
+const BASE_COLORS: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// Returns the index of the closest cube level for the given channel value.
+fn cube_index(value: u8) -> u8 {
+    if value < 48 {
+        0
+    } else if value < 115 {
+        1
+    } else {
+        (value - 35) / 40
+    }
+}
+
+/// Returns the squared distance between two colors.
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| {
+        let d = i32::from(x) - i32::from(y);
+        (d * d) as u32
+    };
+
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
 /// Loads a hexadecimal code
 fn load_hex(s: &str) -> u16 {
     let mut sum = 0;
@@ -221,4 +441,89 @@ mod tests {
             Color::from_256colors(i as u8);
         }
     }
+
+    #[test]
+    fn test_parse_hex() {
+        use super::{Color, ParseColorError};
+
+        assert_eq!("#000".parse(), Ok(Color::Rgb(0, 0, 0)));
+        assert_eq!("#FFFFFF".parse(), Ok(Color::Rgb(255, 255, 255)));
+        assert_eq!(" #Ff8800\n".parse(), Ok(Color::Rgb(255, 136, 0)));
+        assert_eq!(Color::parse("#f80"), Some(Color::Rgb(255, 136, 0)));
+
+        // Printing and parsing again gives the same color.
+        for &(r, g, b) in &[(0, 0, 0), (255, 255, 255), (18, 171, 205)] {
+            let hex = format!("#{:02x}{:02x}{:02x}", r, g, b);
+            assert_eq!(hex.parse(), Ok(Color::Rgb(r, g, b)));
+        }
+
+        assert_eq!(
+            "#12".parse::<Color>(),
+            Err(ParseColorError::InvalidHex("#12".to_string()))
+        );
+        assert_eq!(
+            "#12".parse::<Color>().unwrap_err().to_string(),
+            "invalid hex color `#12`: expected 3 or 6 hex digits"
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        use super::Color;
+
+        for value in &["#12", "#12345g", "#ééé", "€", "BLUE", "light"] {
+            assert_eq!(Color::parse(value), None);
+        }
+    }
+
+    #[test]
+    fn test_to_256_colors() {
+        use super::{BaseColor, Color};
+
+        // Base colors and the cube should round-trip.
+        for i in 0..232u16 {
+            let color = Color::from_256colors(i as u8);
+            assert_eq!(color.to_256colors(), Some(i as u8));
+        }
+
+        assert_eq!(Color::Dark(BaseColor::Blue).to_256colors(), Some(4));
+        assert_eq!(Color::Light(BaseColor::Blue).to_256colors(), Some(12));
+        assert_eq!(Color::TerminalDefault.to_256colors(), None);
+
+        // (200, 50, 50) is closest to (215, 95, 95) in the cube.
+        assert_eq!(Color::Rgb(200, 50, 50).to_256colors(), Some(167));
+        assert_eq!(Color::Rgb(0, 0, 0).to_256colors(), Some(16));
+        assert_eq!(Color::Rgb(255, 255, 255).to_256colors(), Some(231));
+        assert_eq!(Color::Rgb(18, 18, 18).to_256colors(), Some(233));
+
+        // Out of range values are capped.
+        assert_eq!(Color::RgbLowRes(9, 0, 200).to_256colors(), Some(201));
+        assert_eq!(Color::RgbLowRes(6, 0, 9).to_rgb(), Some((255, 0, 255)));
+        assert_eq!(
+            Color::RgbLowRes(9, 9, 9).to_16colors(),
+            Color::Light(BaseColor::White)
+        );
+    }
+
+    #[test]
+    fn test_to_16_colors() {
+        use super::{BaseColor, Color};
+
+        assert_eq!(
+            Color::Rgb(250, 250, 250).to_16colors(),
+            Color::Light(BaseColor::White)
+        );
+        assert_eq!(
+            Color::Rgb(10, 10, 10).to_16colors(),
+            Color::Dark(BaseColor::Black)
+        );
+        assert_eq!(
+            Color::RgbLowRes(0, 5, 0).to_16colors(),
+            Color::Light(BaseColor::Green)
+        );
+        assert_eq!(
+            Color::Dark(BaseColor::Cyan).to_16colors(),
+            Color::Dark(BaseColor::Cyan)
+        );
+    }
 }
@@ -1,3 +1,5 @@
+use enumset::EnumSet;
+
 enum_set_type! {
     /// Text effect
     pub enum Effect {
@@ -11,5 +13,18 @@ enum_set_type! {
         Italic,
         /// Prints foreground with underline
         Underline,
+        /// Prints foreground with a line through the text
+        Strikethrough,
+        /// Makes the foreground blink
+        ///
+        /// This can be disabled application-wide with `Theme::blink`.
+        Blink,
+        /// Prints foreground with a faint intensity
+        Dim,
     }
 }
+
+/// Any combination of text effects.
+///
+/// This is a bit set, cheap to copy and to combine.
+pub type EffectSet = EnumSet<Effect>;
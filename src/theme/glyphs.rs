@@ -0,0 +1,158 @@
+use theme::{BorderGlyphs, BorderStyle, Error};
+use toml;
+
+/// Characters some views draw to show their state.
+///
+/// Themes can set them in a `[glyphs]` table:
+///
+/// ```toml
+/// [glyphs]
+/// tree_collapsed = "+ "
+/// tree_expanded = "- "
+/// overflow_down = "v"
+///
+/// [glyphs.rounded_borders]
+/// top_left = "/"
+/// top_right = "\\"
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Glyphs {
+    /// Printed before `TreeView` nodes with hidden children.
+    pub tree_collapsed: String,
+    /// Printed before `TreeView` nodes with visible children.
+    pub tree_expanded: String,
+    /// Printed before `TreeView` nodes without children.
+    pub tree_leaf: String,
+    /// Used to draw `Simple`, `Outset` and `None` borders.
+    pub simple_borders: BorderGlyphs,
+    /// Used to draw `Rounded` borders.
+    pub rounded_borders: BorderGlyphs,
+    /// Used to draw `Double` borders.
+    pub double_borders: BorderGlyphs,
+    /// Used to draw `Heavy` borders.
+    pub heavy_borders: BorderGlyphs,
+    /// Shows that some content is hidden above.
+    pub overflow_up: String,
+    /// Shows that some content is hidden below.
+    pub overflow_down: String,
+    /// Shows that some content is hidden on the left.
+    pub overflow_left: String,
+    /// Shows that some content is hidden on the right.
+    pub overflow_right: String,
+}
+
+impl Default for Glyphs {
+    fn default() -> Self {
+        Glyphs {
+            tree_collapsed: "▸ ".to_string(),
+            tree_expanded: "▾ ".to_string(),
+            tree_leaf: "  ".to_string(),
+            simple_borders: BorderStyle::Simple.glyphs(),
+            rounded_borders: BorderStyle::Rounded.glyphs(),
+            double_borders: BorderStyle::Double.glyphs(),
+            heavy_borders: BorderStyle::Heavy.glyphs(),
+            overflow_up: "▲".to_string(),
+            overflow_down: "▼".to_string(),
+            overflow_left: "◀".to_string(),
+            overflow_right: "▶".to_string(),
+        }
+    }
+}
+
+impl Glyphs {
+    /// Returns glyphs using only ASCII characters.
+    ///
+    /// Use them for terminals or fonts without the default characters.
+    pub fn ascii() -> Self {
+        let borders = BorderGlyphs::from_chars("++++-|++");
+        Glyphs {
+            tree_collapsed: "+ ".to_string(),
+            tree_expanded: "- ".to_string(),
+            tree_leaf: "  ".to_string(),
+            simple_borders: borders.clone(),
+            rounded_borders: borders.clone(),
+            double_borders: BorderGlyphs::from_chars("++++=|++"),
+            heavy_borders: borders,
+            overflow_up: "^".to_string(),
+            overflow_down: "v".to_string(),
+            overflow_left: "<".to_string(),
+            overflow_right: ">".to_string(),
+        }
+    }
+
+    /// Returns the characters used to draw borders in the given style.
+    pub fn borders(&self, style: BorderStyle) -> &BorderGlyphs {
+        match style {
+            BorderStyle::Simple | BorderStyle::Outset | BorderStyle::None => {
+                &self.simple_borders
+            }
+            BorderStyle::Rounded => &self.rounded_borders,
+            BorderStyle::Double => &self.double_borders,
+            BorderStyle::Heavy => &self.heavy_borders,
+        }
+    }
+
+    // Returns the border glyphs with the given theme key.
+    fn borders_mut(&mut self, key: &str) -> Option<&mut BorderGlyphs> {
+        Some(match key {
+            "simple_borders" => &mut self.simple_borders,
+            "rounded_borders" => &mut self.rounded_borders,
+            "double_borders" => &mut self.double_borders,
+            "heavy_borders" => &mut self.heavy_borders,
+            _ => return None,
+        })
+    }
+
+    // Returns the glyph with the given theme key.
+    fn get_mut(&mut self, key: &str) -> Option<&mut String> {
+        Some(match key {
+            "tree_collapsed" => &mut self.tree_collapsed,
+            "tree_expanded" => &mut self.tree_expanded,
+            "tree_leaf" => &mut self.tree_leaf,
+            "overflow_up" => &mut self.overflow_up,
+            "overflow_down" => &mut self.overflow_down,
+            "overflow_left" => &mut self.overflow_left,
+            "overflow_right" => &mut self.overflow_right,
+            _ => return None,
+        })
+    }
+}
+
+/// Fills `glyphs` with the entries of a toml `[glyphs]` table.
+pub(crate) fn load_toml(
+    glyphs: &mut Glyphs, table: &toml::value::Table,
+) -> Result<(), Error> {
+    for (key, value) in table {
+        if let Some(borders) = glyphs.borders_mut(key) {
+            let table = value.as_table().ok_or_else(|| {
+                Error::invalid_value(&format!("glyphs.{}", key), value)
+            })?;
+            for (name, value) in table {
+                let key = format!("glyphs.{}.{}", key, name);
+                let glyph = borders
+                    .get_mut(name)
+                    .ok_or_else(|| Error::UnknownKey(key.clone()))?;
+                set_glyph(glyph, &key, value)?;
+            }
+        } else {
+            let glyph = glyphs
+                .get_mut(key)
+                .ok_or_else(|| Error::UnknownKey(format!("glyphs.{}", key)))?;
+            set_glyph(glyph, &format!("glyphs.{}", key), value)?;
+        }
+    }
+
+    Ok(())
+}
+
+// Sets `glyph` to a toml string.
+fn set_glyph(
+    glyph: &mut String, key: &str, value: &toml::Value,
+) -> Result<(), Error> {
+    let value = value
+        .as_str()
+        .ok_or_else(|| Error::invalid_value(key, value))?;
+    *glyph = value.to_string();
+
+    Ok(())
+}
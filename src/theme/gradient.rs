@@ -0,0 +1,148 @@
+use super::{Color, Style};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+use utils::markup::StyledString;
+
+/// Returns `text` with colors fading from `from` to `to`.
+///
+/// Each grapheme gets a color interpolated from its column: the first
+/// column uses `from`, and the last column of the widest line uses `to`.
+/// Each line of a multi-line text fades the same way, so columns keep the
+/// same color from one line to the next.
+///
+/// Colors are emitted as `Color::Rgb`; backends without true-color support
+/// print the closest color they have. If either color is
+/// `TerminalDefault`, the entire text uses `from`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use cursive::theme::{self, BaseColor, Color};
+/// # use cursive::views::TextView;
+/// let banner = theme::gradient(
+///     "Cursive",
+///     Color::Light(BaseColor::Red),
+///     Color::Rgb(0, 0, 255),
+/// );
+/// let view = TextView::new(banner);
+/// ```
+pub fn gradient(text: &str, from: Color, to: Color) -> StyledString {
+    let (from_rgb, to_rgb) = match (from.to_rgb(), to.to_rgb()) {
+        (Some(from), Some(to)) => (from, to),
+        _ => return StyledString::styled(text, from),
+    };
+
+    let width = text.lines().map(|line| line.width()).max().unwrap_or(0);
+
+    let mut result = StyledString::new();
+    for (i, line) in text.split('\n').enumerate() {
+        if i > 0 {
+            result.append_plain("\n");
+        }
+
+        let mut column = 0;
+        for grapheme in line.graphemes(true) {
+            let color = interpolate(from_rgb, to_rgb, column, width);
+            result.append_styled(grapheme, Style::from(color));
+            column += grapheme.width();
+        }
+    }
+
+    result
+}
+
+/// Returns the color at `column` in a gradient of `width` columns.
+fn interpolate(
+    from: (u8, u8, u8), to: (u8, u8, u8), column: usize, width: usize,
+) -> Color {
+    if width < 2 {
+        return Color::Rgb(from.0, from.1, from.2);
+    }
+
+    let t = column.min(width - 1) as f64 / (width - 1) as f64;
+    let channel = |a: u8, b: u8| {
+        let (a, b) = (f64::from(a), f64::from(b));
+        (a + (b - a) * t).round() as u8
+    };
+
+    Color::Rgb(
+        channel(from.0, to.0),
+        channel(from.1, to.1),
+        channel(from.2, to.2),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use theme::{BaseColor, ColorType};
+
+    fn colors(text: &StyledString) -> Vec<(&str, Option<Color>)> {
+        text.spans()
+            .map(|span| {
+                let color = span.attr.color.map(|color| match color.front {
+                    ColorType::Color(color) => color,
+                    _ => panic!("unexpected palette color"),
+                });
+                (span.content, color)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_gradient() {
+        let text =
+            gradient("abc", Color::Rgb(0, 0, 0), Color::Rgb(255, 100, 9));
+
+        assert_eq!(
+            colors(&text),
+            vec![
+                ("a", Some(Color::Rgb(0, 0, 0))),
+                ("b", Some(Color::Rgb(128, 50, 5))),
+                ("c", Some(Color::Rgb(255, 100, 9))),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_graphemes() {
+        // "é" is two code points, "日" is two cells wide.
+        let text = gradient(
+            "e\u{301}日x",
+            Color::Rgb(0, 0, 0),
+            Color::Rgb(30, 0, 0),
+        );
+
+        assert_eq!(
+            colors(&text),
+            vec![
+                ("e\u{301}", Some(Color::Rgb(0, 0, 0))),
+                ("日", Some(Color::Rgb(10, 0, 0))),
+                ("x", Some(Color::Rgb(30, 0, 0))),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lines() {
+        let from = Color::Dark(BaseColor::Black);
+        let to = Color::Light(BaseColor::White);
+        let text = gradient("ab\nabc", from, to);
+
+        assert_eq!(text.source(), "ab\nabc");
+        assert_eq!(
+            colors(&text),
+            vec![
+                ("a", Some(Color::Rgb(0, 0, 0))),
+                ("b", Some(Color::Rgb(128, 128, 128))),
+                ("\n", None),
+                ("a", Some(Color::Rgb(0, 0, 0))),
+                ("b", Some(Color::Rgb(128, 128, 128))),
+                ("c", Some(Color::Rgb(255, 255, 255))),
+            ]
+        );
+
+        let text = gradient("ab", Color::TerminalDefault, to);
+        assert_eq!(colors(&text), vec![("ab", Some(Color::TerminalDefault))]);
+    }
+}
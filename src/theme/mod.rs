@@ -129,9 +129,14 @@
 //! ```toml
 //! # Every field in a theme file is optional.
 //!
+//! # Starts from another theme file, relative to this one.
+//! # The other fields override the ones it sets.
+//! parent = "base.toml"
+//!
 //! # First come some various options
 //! shadow = false  # Don't draw shadows around stacked views
-//! borders = "simple"  # Alternatives are "none" and "outset"
+//! borders = "simple"  # Or "none", "outset", "rounded", "double", "heavy"
+//! blink = false  # Print blinking text without the blink effect
 //!
 //! # Here we define the color palette.
 //! [colors]
@@ -155,26 +160,43 @@
 //! 	# Lower precision values can use only 3 digits.
 //! 	highlight          = "#F00"
 //! 	highlight_inactive = "#5555FF"
+//!
+//! # Characters drawn by some views.
+//! [glyphs]
+//!     tree_collapsed = "+ "
+//!     tree_expanded  = "- "
+//!     overflow_up    = "^"
+//!
+//! # Characters drawn for `borders = "rounded"`.
+//! [glyphs.rounded_borders]
+//!     top_left  = "."
+//!     top_right = "."
 //! ```
 mod border_style;
 mod color;
 mod color_pair;
 mod color_style;
 mod effect;
+mod glyphs;
+mod gradient;
 mod palette;
 mod style;
 
-pub use self::border_style::BorderStyle;
-pub use self::color::{BaseColor, Color};
+pub use self::border_style::{BorderGlyphs, BorderStyle};
+pub use self::color::{BaseColor, Color, ParseColorError};
 pub use self::color_pair::ColorPair;
 pub use self::color_style::{ColorStyle, ColorType};
-pub use self::effect::Effect;
+pub use self::effect::{Effect, EffectSet};
+pub use self::glyphs::Glyphs;
+pub use self::gradient::gradient;
 pub use self::palette::{Palette, PaletteColor};
 pub use self::style::Style;
+use std::error;
+use std::fmt;
 use std::fs::File;
 use std::io;
 use std::io::Read;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use toml;
 
 /// Represents the style a Cursive application will use.
@@ -184,8 +206,14 @@ pub struct Theme {
     pub shadow: bool,
     /// How view borders should be drawn.
     pub borders: BorderStyle,
+    /// Whether `Effect::Blink` should be applied.
+    ///
+    /// When `false`, blinking text is printed without the effect.
+    pub blink: bool,
     /// What colors should be used through the application?
     pub palette: Palette,
+    /// Characters drawn by some views.
+    pub glyphs: Glyphs,
 }
 
 impl Default for Theme {
@@ -193,24 +221,107 @@ impl Default for Theme {
         Theme {
             shadow: true,
             borders: BorderStyle::Simple,
+            blink: true,
             palette: Palette::default(),
+            glyphs: Glyphs::default(),
         }
     }
 }
 
 impl Theme {
-    fn load_toml(&mut self, table: &toml::value::Table) {
-        if let Some(&toml::Value::Boolean(shadow)) = table.get("shadow") {
-            self.shadow = shadow;
+    /// Applies the options from toml content over this theme.
+    ///
+    /// Options missing from the content keep their current value, so a
+    /// base theme can be partially overridden. Palette namespaces are
+    /// merged with the existing ones, entry by entry.
+    ///
+    /// A `parent` file, relative to the current directory, is applied
+    /// before the content.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cursive::theme::{self, BaseColor, BorderStyle, Color};
+    /// # use cursive::theme::PaletteColor::View;
+    /// let base = theme::load_toml("borders = \"none\"").unwrap();
+    ///
+    /// let mut dark = base.clone();
+    /// dark.merge_toml("[colors]\nview = \"black\"").unwrap();
+    ///
+    /// assert_eq!(dark.borders, BorderStyle::None);
+    /// assert_eq!(dark.palette[View], Color::Dark(BaseColor::Black));
+    /// ```
+    pub fn merge_toml(&mut self, content: &str) -> Result<(), Error> {
+        let table = toml::de::from_str(content)?;
+        self.load_toml(&table, Path::new(""), &mut Vec::new())
+    }
+
+    // Applies a theme file and its parents.
+    //
+    // `parents` are the files being loaded, to detect loops.
+    fn load_file(
+        &mut self, filename: &Path, parents: &mut Vec<PathBuf>,
+    ) -> Result<(), Error> {
+        let content = {
+            let mut content = String::new();
+            let mut file = File::open(filename)?;
+            file.read_to_string(&mut content)?;
+            content
+        };
+        let table = toml::de::from_str(&content)?;
+
+        let canonical = filename.canonicalize()?;
+        if parents.contains(&canonical) {
+            return Err(Error::ParentLoop(filename.display().to_string()));
         }
+        parents.push(canonical);
+
+        let dir = filename.parent().unwrap_or_else(|| Path::new(""));
+        self.load_toml(&table, dir, parents)?;
+        parents.pop();
 
-        if let Some(&toml::Value::String(ref borders)) = table.get("borders") {
-            self.borders = BorderStyle::from(borders);
+        Ok(())
+    }
+
+    // `dir` is where `parent` files are looked for.
+    fn load_toml(
+        &mut self, table: &toml::value::Table, dir: &Path,
+        parents: &mut Vec<PathBuf>,
+    ) -> Result<(), Error> {
+        // The parent comes first, whatever the order of the keys.
+        if let Some(parent) = table.get("parent") {
+            let name = parent
+                .as_str()
+                .ok_or_else(|| Error::invalid_value("parent", parent))?;
+            self.load_file(&dir.join(name), parents)?;
         }
 
-        if let Some(&toml::Value::Table(ref table)) = table.get("colors") {
-            palette::load_toml(&mut self.palette, table);
+        for (key, value) in table {
+            match (key.as_str(), value) {
+                ("parent", _) => (),
+                ("shadow", toml::Value::Boolean(shadow)) => {
+                    self.shadow = *shadow
+                }
+                ("blink", toml::Value::Boolean(blink)) => self.blink = *blink,
+                ("borders", toml::Value::String(borders)) => {
+                    self.borders = BorderStyle::parse(borders)
+                        .ok_or_else(|| Error::invalid_value(key, value))?;
+                }
+                ("colors", toml::Value::Table(table)) => {
+                    palette::load_toml(&mut self.palette, table)?
+                }
+                ("glyphs", toml::Value::Table(table)) => {
+                    glyphs::load_toml(&mut self.glyphs, table)?
+                }
+                ("shadow", _) | ("blink", _) | ("borders", _)
+                | ("colors", _) | ("glyphs", _) => {
+                    return Err(Error::invalid_value(key, value))
+                }
+                _ => return Err(Error::UnknownKey(key.clone())),
+            }
         }
+
+        Ok(())
     }
 }
 
@@ -221,6 +332,66 @@ pub enum Error {
     Io(io::Error),
     /// An error occured when parsing the toml content.
     Parse(toml::de::Error),
+    /// The content has an option that doesn't exist.
+    UnknownKey(String),
+    /// An option has the wrong type, or isn't one of the allowed values.
+    InvalidValue {
+        /// Name of the option.
+        key: String,
+        /// Value found in the content.
+        value: String,
+    },
+    /// A palette entry is not a valid color.
+    ///
+    /// For arrays, this means that none of the colors is valid.
+    InvalidColor {
+        /// Name of the entry, like `colors.view`.
+        key: String,
+        /// Value found in the content.
+        value: String,
+    },
+    /// A theme file is its own parent, directly or not.
+    ParentLoop(String),
+}
+
+impl Error {
+    pub(crate) fn invalid_value(key: &str, value: &toml::Value) -> Self {
+        Error::InvalidValue {
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Io(ref err) => write!(f, "could not read theme: {}", err),
+            Error::Parse(ref err) => write!(f, "invalid theme: {}", err),
+            Error::UnknownKey(ref key) => {
+                write!(f, "unknown theme option `{}`", key)
+            }
+            Error::InvalidValue { ref key, ref value } => {
+                write!(f, "invalid value for `{}`: {}", key, value)
+            }
+            Error::InvalidColor { ref key, ref value } => {
+                write!(f, "invalid color for `{}`: {}", key, value)
+            }
+            Error::ParentLoop(ref filename) => {
+                write!(f, "theme `{}` is its own parent", filename)
+            }
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(error::Error + 'static)> {
+        match *self {
+            Error::Io(ref err) => Some(err),
+            Error::Parse(ref err) => Some(err),
+            _ => None,
+        }
+    }
 }
 
 impl From<io::Error> for Error {
@@ -236,23 +407,26 @@ impl From<toml::de::Error> for Error {
 }
 
 /// Loads a theme from file and sets it as active.
+///
+/// A `parent` file is looked for next to this one.
 pub fn load_theme_file<P: AsRef<Path>>(filename: P) -> Result<Theme, Error> {
-    let content = {
-        let mut content = String::new();
-        let mut file = File::open(filename)?;
-        file.read_to_string(&mut content)?;
-        content
-    };
-
-    load_toml(&content)
+    let mut theme = Theme::default();
+    theme.load_file(filename.as_ref(), &mut Vec::new())?;
+
+    Ok(theme)
 }
 
-/// Loads a theme string and sets it as active.
+/// Parses a theme from toml content.
+///
+/// Options missing from the content keep their default value. Unknown
+/// options and invalid colors are reported as errors.
+///
+/// See [`Theme::merge_toml()`] to start from another theme.
+///
+/// [`Theme::merge_toml()`]: struct.Theme.html#method.merge_toml
 pub fn load_toml(content: &str) -> Result<Theme, Error> {
-    let table = toml::de::from_str(content)?;
-
     let mut theme = Theme::default();
-    theme.load_toml(&table);
+    theme.merge_toml(content)?;
 
     Ok(theme)
 }
@@ -261,3 +435,162 @@ pub fn load_toml(content: &str) -> Result<Theme, Error> {
 pub fn load_default() -> Theme {
     Theme::default()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_toml() {
+        let theme = load_toml(
+            r##"
+            shadow = false
+            borders = "none"
+
+            [colors]
+            view = ["not a color", "red"]
+            highlight = "#F00"
+
+            [glyphs]
+            tree_leaf = "- "
+
+            [glyphs.rounded_borders]
+            top_left = "/"
+            "##,
+        )
+        .unwrap();
+
+        assert!(!theme.shadow);
+        assert_eq!(theme.borders, BorderStyle::None);
+        assert_eq!(
+            theme.palette[PaletteColor::View],
+            Color::Dark(BaseColor::Red)
+        );
+        assert_eq!(theme.glyphs.tree_leaf, "- ");
+        assert_eq!(theme.glyphs.tree_expanded, "▾ ");
+
+        let rounded = theme.glyphs.borders(BorderStyle::Rounded);
+        assert_eq!(rounded.top_left, "/");
+        assert_eq!(rounded.top_right, "╮");
+    }
+
+    #[test]
+    fn test_load_toml_errors() {
+        let error = |content| load_toml(content).unwrap_err().to_string();
+
+        assert_eq!(error("shadows = true"), "unknown theme option `shadows`");
+        assert_eq!(
+            error("borders = \"round\""),
+            "invalid value for `borders`: \"round\""
+        );
+        assert_eq!(
+            error("[colors]\nmy_app = { title = \"pink\" }"),
+            "invalid color for `colors.my_app.title`: \"pink\""
+        );
+        assert_eq!(
+            error("[glyphs]\ntree = \"+\""),
+            "unknown theme option `glyphs.tree`"
+        );
+        assert_eq!(
+            error("[glyphs.heavy_borders]\ncorner = \"+\""),
+            "unknown theme option `glyphs.heavy_borders.corner`"
+        );
+        assert_eq!(
+            error("[glyphs]\ndouble_borders = \"=\""),
+            "invalid value for `glyphs.double_borders`: \"=\""
+        );
+
+        match load_toml("shadow = ") {
+            Err(Error::Parse(_)) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_merge_toml() {
+        let mut theme = load_toml(
+            r##"
+            shadow = false
+            borders = "rounded"
+
+            [colors]
+            view = "black"
+            primary = "white"
+            my_app = { title = "red", menu = { bar = "blue" } }
+            "##,
+        )
+        .unwrap();
+
+        theme
+            .merge_toml(
+                r##"
+                borders = "double"
+
+                [colors]
+                primary = "green"
+                my_app = { menu = { item = "yellow" } }
+                "##,
+            )
+            .unwrap();
+
+        // Scalar options are replaced, or inherited if not set.
+        assert!(!theme.shadow);
+        assert_eq!(theme.borders, BorderStyle::Double);
+
+        let palette = &theme.palette;
+        assert_eq!(palette[PaletteColor::View], Color::Dark(BaseColor::Black));
+        assert_eq!(
+            palette[PaletteColor::Primary],
+            Color::Dark(BaseColor::Green)
+        );
+
+        // Namespaces are merged, at every level.
+        let my_app = palette.merge("my_app");
+        assert_eq!(my_app.custom("title"), Some(&Color::Dark(BaseColor::Red)));
+        let menu = my_app.merge("menu");
+        assert_eq!(menu.custom("bar"), Some(&Color::Dark(BaseColor::Blue)));
+        assert_eq!(
+            menu.custom("item"),
+            Some(&Color::Dark(BaseColor::Yellow))
+        );
+    }
+
+    #[test]
+    fn test_parent() {
+        struct TempDir(::std::path::PathBuf);
+
+        impl Drop for TempDir {
+            fn drop(&mut self) {
+                ::std::fs::remove_dir_all(&self.0).ok();
+            }
+        }
+
+        let dir =
+            TempDir(::std::env::temp_dir().join(format!(
+                "cursive-theme-parent-{}",
+                ::std::process::id()
+            )));
+        let dir = &dir.0;
+        ::std::fs::create_dir_all(dir.join("themes")).unwrap();
+        let write = |name: &str, content: &str| {
+            ::std::fs::write(dir.join(name), content).unwrap()
+        };
+
+        write("base.toml", "shadow = false\nborders = \"none\"");
+        write(
+            "themes/dark.toml",
+            "parent = \"../base.toml\"\nborders = \"heavy\"",
+        );
+        let theme = load_theme_file(dir.join("themes/dark.toml")).unwrap();
+        assert!(!theme.shadow);
+        assert_eq!(theme.borders, BorderStyle::Heavy);
+
+        // Loops are reported instead of followed.
+        write("base.toml", "parent = \"themes/dark.toml\"");
+        let error = load_theme_file(dir.join("base.toml")).unwrap_err();
+        match error {
+            Error::ParentLoop(_) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
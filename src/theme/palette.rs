@@ -1,4 +1,4 @@
-use super::Color;
+use super::{Color, Error};
 use enum_map::EnumMap;
 use toml;
 
@@ -167,58 +167,84 @@ impl Default for Palette {
     }
 }
 
-// Iterate over a toml
-fn iterate_toml<'a>(
-    table: &'a toml::value::Table,
-) -> impl Iterator<Item = (&'a str, PaletteNode)> + 'a {
-    table.iter().flat_map(|(key, value)| {
-        let node = match value {
-            toml::Value::Table(table) => {
-                // This should define a new namespace
-                // Treat basic colors as simple string.
-                // We'll convert them back in the merge method.
-                let map = iterate_toml(table)
-                    .map(|(key, value)| (key.to_string(), value))
-                    .collect();
-                // Should we only return something if it's non-empty?
-                Some(PaletteNode::Namespace(map))
-            }
-            toml::Value::Array(colors) => {
-                // This should be a list of colors - just pick the first valid one.
-                colors
-                    .iter()
-                    .flat_map(toml::Value::as_str)
-                    .flat_map(Color::parse)
-                    .map(PaletteNode::Color)
-                    .next()
-            }
-            toml::Value::String(color) => {
-                // This describe a new color - easy!
-                Color::parse(color).map(PaletteNode::Color)
-            }
-            other => {
-                // Other - error?
-                debug!(
-                    "Found unexpected value in theme: {} = {:?}",
-                    key, other
-                );
-                None
-            }
-        };
+// Parses the colors and namespaces of a toml table.
+//
+// `path` is the dotted name of the table, used in errors.
+fn parse_toml(
+    table: &toml::value::Table, path: &str,
+) -> Result<Vec<(String, PaletteNode)>, Error> {
+    table
+        .iter()
+        .map(|(key, value)| {
+            let path = format!("{}.{}", path, key);
+            let node = match value {
+                toml::Value::Table(table) => {
+                    // This should define a new namespace
+                    // Treat basic colors as simple string.
+                    // We'll convert them back in the merge method.
+                    let map = parse_toml(table, &path)?.into_iter().collect();
+                    Some(PaletteNode::Namespace(map))
+                }
+                toml::Value::Array(colors) => {
+                    // This should be a list of colors.
+                    // Just pick the first valid one.
+                    colors
+                        .iter()
+                        .flat_map(toml::Value::as_str)
+                        .flat_map(Color::parse)
+                        .map(PaletteNode::Color)
+                        .next()
+                }
+                toml::Value::String(color) => {
+                    // This describe a new color - easy!
+                    Color::parse(color).map(PaletteNode::Color)
+                }
+                _ => None,
+            };
 
-        node.map(|node| (key.as_str(), node))
-    })
+            match node {
+                Some(node) => Ok((key.clone(), node)),
+                None => Err(Error::InvalidColor {
+                    key: path,
+                    value: value.to_string(),
+                }),
+            }
+        })
+        .collect()
 }
 
 /// Fills `palette` with the colors from the given `table`.
-pub(crate) fn load_toml(palette: &mut Palette, table: &toml::value::Table) {
+pub(crate) fn load_toml(
+    palette: &mut Palette, table: &toml::value::Table,
+) -> Result<(), Error> {
     // TODO: use serde for that?
     // Problem: toml-rs doesn't do well with Enums...
 
-    for (key, value) in iterate_toml(table) {
+    for (key, value) in parse_toml(table, "colors")? {
         match value {
-            PaletteNode::Color(color) => palette.set_color(key, color),
-            PaletteNode::Namespace(map) => palette.add_namespace(key, map),
+            PaletteNode::Color(color) => palette.set_color(&key, color),
+            namespace => merge_node(&mut palette.custom, key, namespace),
+        }
+    }
+
+    Ok(())
+}
+
+// Inserts `node` in `map`, merging namespaces with the existing ones.
+fn merge_node(
+    map: &mut HashMap<String, PaletteNode>, key: String, node: PaletteNode,
+) {
+    match (map.get_mut(&key), node) {
+        (
+            Some(&mut PaletteNode::Namespace(ref mut existing)),
+            PaletteNode::Namespace(namespace),
+        ) => {
+            for (key, node) in namespace {
+                merge_node(existing, key, node);
+            }
+        }
+        (_, node) => {
+            map.insert(key, node);
         }
     }
 }
@@ -256,3 +282,42 @@ impl PaletteColor {
         palette[self]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use theme::BaseColor;
+
+    #[test]
+    fn test_custom_namespace() {
+        let mut palette = Palette::default();
+        palette.set_color("title", Color::Dark(BaseColor::Red));
+
+        let mut namespace = HashMap::new();
+        namespace.insert(
+            "title".to_string(),
+            PaletteNode::Color(Color::Light(BaseColor::Green)),
+        );
+        namespace.insert(
+            "view".to_string(),
+            PaletteNode::Color(Color::Dark(BaseColor::Black)),
+        );
+        palette.add_namespace("my_app", namespace);
+
+        assert_eq!(
+            palette.custom("title"),
+            Some(&Color::Dark(BaseColor::Red))
+        );
+        assert_eq!(palette.custom("my_app"), None);
+
+        let merged = palette.merge("my_app");
+        assert_eq!(
+            merged.custom("title"),
+            Some(&Color::Light(BaseColor::Green))
+        );
+        assert_eq!(
+            merged[PaletteColor::View],
+            Color::Dark(BaseColor::Black)
+        );
+    }
+}
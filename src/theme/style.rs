@@ -1,5 +1,4 @@
-use super::{Color, ColorStyle, ColorType, Effect, PaletteColor};
-use enumset::EnumSet;
+use super::{Color, ColorStyle, ColorType, Effect, EffectSet, PaletteColor};
 
 /// Combine a color and an effect.
 ///
@@ -9,7 +8,7 @@ pub struct Style {
     /// Effect to apply.
     ///
     /// `None` to keep using previous effects.
-    pub effects: EnumSet<Effect>,
+    pub effects: EffectSet,
 
     /// Color style to apply.
     ///
@@ -27,7 +26,7 @@ impl Style {
     /// Returns a new `Style` that doesn't apply anything.
     pub fn none() -> Self {
         Style {
-            effects: EnumSet::new(),
+            effects: EffectSet::new(),
             color: None,
         }
     }
@@ -37,7 +36,7 @@ impl Style {
     /// Will use the last non-`None` color, and will combine all effects.
     pub fn merge(styles: &[Style]) -> Self {
         let mut color = None;
-        let mut effects = EnumSet::new();
+        let mut effects = EffectSet::new();
 
         for style in styles {
             if style.color.is_some() {
@@ -71,7 +70,7 @@ impl From<Effect> for Style {
 impl From<ColorStyle> for Style {
     fn from(color: ColorStyle) -> Self {
         Style {
-            effects: EnumSet::new(),
+            effects: EffectSet::new(),
             color: Some(color),
         }
     }
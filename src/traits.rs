@@ -9,7 +9,7 @@
 //! ```
 
 #[doc(no_inline)]
-pub use view::{Boxable, Finder, Identifiable, Scrollable, View};
+pub use view::{Accessible, Boxable, Finder, Identifiable, Scrollable, View};
 
 #[doc(no_inline)]
 pub use With;
@@ -0,0 +1,215 @@
+//! Clipboard to copy and paste text.
+//!
+//! Views copying or pasting text use the clipboard installed with
+//! [`Cursive::set_clipboard`]:
+//!
+//! * A selectable [`TextView`] copies its selection with `<Ctrl-C>`.
+//! * [`EditView`] and [`TextArea`] paste with `<Ctrl-V>`.
+//!
+//! By default, this is a [`LocalClipboard`], only shared within the
+//! application. With the `system-clipboard` feature, it is a
+//! [`SystemClipboard`] instead. Other clipboards can implement
+//! [`Clipboard`].
+//!
+//! [`Cursive::set_clipboard`]: ../../struct.Cursive.html#method.set_clipboard
+//! [`TextView`]: ../../views/struct.TextView.html
+//! [`EditView`]: ../../views/struct.EditView.html
+//! [`TextArea`]: ../../views/struct.TextArea.html
+//! [`LocalClipboard`]: struct.LocalClipboard.html
+//! [`SystemClipboard`]: struct.SystemClipboard.html
+//! [`Clipboard`]: trait.Clipboard.html
+
+use event::{Event, EventResult};
+use Cursive;
+
+/// Stores some text to paste later.
+pub trait Clipboard {
+    /// Returns the text in the clipboard, if any.
+    fn get(&self) -> Option<String>;
+
+    /// Replaces the text in the clipboard.
+    ///
+    /// Slow clipboards can finish this in the background.
+    fn set(&mut self, text: &str);
+
+    /// Gives the text in the clipboard to `callback`, without blocking.
+    ///
+    /// Slow clipboards can call `callback` later, from another thread.
+    /// By default, it is called right away with the result of `get()`.
+    fn get_with(&self, mut callback: Box<FnMut(Option<String>) + Send>) {
+        callback(self.get());
+    }
+}
+
+/// Clipboard keeping its text in memory.
+///
+/// It is only available within the application.
+#[derive(Clone, Debug, Default)]
+pub struct LocalClipboard {
+    contents: Option<String>,
+}
+
+impl LocalClipboard {
+    /// Creates a new, empty clipboard.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Clipboard for LocalClipboard {
+    fn get(&self) -> Option<String> {
+        self.contents.clone()
+    }
+
+    fn set(&mut self, text: &str) {
+        self.contents = Some(text.to_string());
+    }
+}
+
+#[cfg(feature = "system-clipboard")]
+pub use self::system::SystemClipboard;
+
+#[cfg(feature = "system-clipboard")]
+mod system {
+    use super::{Clipboard, LocalClipboard};
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    // Tried in order, until one works.
+    static COPY_COMMANDS: &[(&str, &[&str])] = &[
+        ("pbcopy", &[]),
+        ("wl-copy", &[]),
+        ("xclip", &["-selection", "clipboard"]),
+        ("xsel", &["--clipboard", "--input"]),
+        ("clip", &[]),
+    ];
+
+    static PASTE_COMMANDS: &[(&str, &[&str])] = &[
+        ("pbpaste", &[]),
+        ("wl-paste", &["--no-newline"]),
+        ("xclip", &["-selection", "clipboard", "-out"]),
+        ("xsel", &["--clipboard", "--output"]),
+        ("powershell", &["-NoProfile", "-Command", "Get-Clipboard"]),
+    ];
+
+    /// Clipboard shared with other applications.
+    ///
+    /// It runs the clipboard tool available on the system: `pbcopy` on
+    /// macOS, `wl-copy` or `xclip` or `xsel` on Linux, `clip` on Windows.
+    /// Without any of these, it keeps the text in memory, like a
+    /// [`LocalClipboard`].
+    ///
+    /// The tools run on a separate thread, so copying and pasting with
+    /// `<Ctrl-V>` don't block the UI.
+    ///
+    /// [`LocalClipboard`]: struct.LocalClipboard.html
+    #[derive(Clone, Debug, Default)]
+    pub struct SystemClipboard {
+        // Last copied text, shared with the threads running the tools.
+        fallback: Arc<Mutex<LocalClipboard>>,
+        // Held while a copy command runs, so copies don't overlap.
+        writing: Arc<Mutex<()>>,
+    }
+
+    impl SystemClipboard {
+        /// Creates a new system clipboard.
+        pub fn new() -> Self {
+            Self::default()
+        }
+    }
+
+    impl Clipboard for SystemClipboard {
+        fn get(&self) -> Option<String> {
+            read().or_else(|| self.fallback.lock().unwrap().get())
+        }
+
+        fn set(&mut self, text: &str) {
+            self.fallback.lock().unwrap().set(text);
+
+            let fallback = Arc::clone(&self.fallback);
+            let writing = Arc::clone(&self.writing);
+            let text = text.to_string();
+            thread::spawn(move || {
+                let _writing = writing.lock().unwrap();
+                // A newer copy will be written instead.
+                let latest = fallback.lock().unwrap().get();
+                if latest.as_ref() == Some(&text) {
+                    write(&text);
+                }
+            });
+        }
+
+        fn get_with(&self, mut callback: Box<FnMut(Option<String>) + Send>) {
+            let fallback = Arc::clone(&self.fallback);
+            thread::spawn(move || {
+                let text = read().or_else(|| fallback.lock().unwrap().get());
+                callback(text);
+            });
+        }
+    }
+
+    // Returns the output of the first paste command that works.
+    fn read() -> Option<String> {
+        PASTE_COMMANDS
+            .iter()
+            .filter_map(|&(program, args)| {
+                let output = Command::new(program)
+                    .args(args)
+                    .stdin(Stdio::null())
+                    .stderr(Stdio::null())
+                    .output()
+                    .ok()?;
+                if output.status.success() {
+                    String::from_utf8(output.stdout).ok()
+                } else {
+                    None
+                }
+            })
+            .next()
+    }
+
+    // Gives `text` to the first copy command that works.
+    fn write(text: &str) {
+        for &(program, args) in COPY_COMMANDS {
+            let child = Command::new(program)
+                .args(args)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn();
+            let mut child = match child {
+                Ok(child) => child,
+                Err(_) => continue,
+            };
+
+            let written = match child.stdin.take() {
+                Some(mut stdin) => stdin.write_all(text.as_bytes()).is_ok(),
+                None => false,
+            };
+            // Closing stdin lets the command finish.
+            match child.wait() {
+                Ok(status) if written && status.success() => return,
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Returns a callback pasting the clipboard in the focused view.
+///
+/// The text is sent as an `Event::Paste`, once the clipboard gave it.
+pub(crate) fn paste() -> EventResult {
+    EventResult::with_cb(|s| {
+        let sink = s.cb_sink().clone();
+        s.clipboard().get_with(Box::new(move |text| {
+            if let Some(text) = text {
+                let paste = move |s: &mut Cursive| {
+                    s.on_event(Event::Paste(text));
+                };
+                sink.send(Box::new(paste)).ok();
+            }
+        }));
+    })
+}
@@ -0,0 +1,94 @@
+//! Moves through text one grapheme, or one word, at a time.
+//!
+//! A grapheme is what users see as a single character, even when it is made
+//! of several code points: an `e` followed by a combining accent, or emoji
+//! joined with zero-width joiners. Cursors should never stop inside one.
+//!
+//! Words follow the unicode word boundaries, which never split a grapheme.
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Returns the grapheme starting at byte `offset` of `text`, if any.
+pub fn next(text: &str, offset: usize) -> Option<&str> {
+    text[offset..].graphemes(true).next()
+}
+
+/// Returns the grapheme ending at byte `offset` of `text`, if any.
+pub fn previous(text: &str, offset: usize) -> Option<&str> {
+    text[..offset].graphemes(true).next_back()
+}
+
+/// Returns the byte offset of the end of the first word after `offset`.
+///
+/// Spaces and punctuation before the word are skipped. Returns the length
+/// of `text` if no word is left.
+pub fn next_word_end(text: &str, offset: usize) -> usize {
+    text[offset..]
+        .split_word_bound_indices()
+        .find(|&(_, segment)| is_word(segment))
+        .map(|(i, word)| offset + i + word.len())
+        .unwrap_or_else(|| text.len())
+}
+
+/// Returns the byte offset of the start of the last word before `offset`.
+///
+/// Spaces and punctuation after the word are skipped. Returns 0 if no word
+/// is left.
+pub fn previous_word_start(text: &str, offset: usize) -> usize {
+    text[..offset]
+        .split_word_bound_indices()
+        .rev()
+        .find(|&(_, segment)| is_word(segment))
+        .map(|(i, _)| i)
+        .unwrap_or(0)
+}
+
+// Spaces and punctuation are segments of their own.
+fn is_word(segment: &str) -> bool {
+    segment.chars().any(char::is_alphanumeric)
+}
+
+/// Returns `text` with its graphemes in reverse order.
+///
+/// Used to draw right-to-left text.
+pub fn reversed(text: &str) -> String {
+    text.graphemes(true).rev().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clusters() {
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        let text = format!("e\u{301}{}!", family);
+
+        assert_eq!(next(&text, 0), Some("e\u{301}"));
+        assert_eq!(next(&text, 3), Some(family));
+        assert_eq!(next(&text, text.len()), None);
+
+        assert_eq!(previous(&text, text.len() - 1), Some(family));
+        assert_eq!(previous(&text, 3), Some("e\u{301}"));
+        assert_eq!(previous(&text, 0), None);
+
+        assert_eq!(reversed(&text), format!("!{}e\u{301}", family));
+    }
+
+    #[test]
+    fn test_words() {
+        let text = "foo-bar,  e\u{301}t\u{e9}   don't!";
+        let ends: Vec<usize> = vec![0, 3, 4, 7, 16]
+            .into_iter()
+            .map(|offset| next_word_end(text, offset))
+            .collect();
+        assert_eq!(ends, vec![3, 7, 7, 16, 24]);
+        assert_eq!(next_word_end(text, 24), text.len());
+
+        let starts: Vec<usize> = vec![text.len(), 24, 16, 13, 7, 4, 3]
+            .into_iter()
+            .map(|offset| previous_word_start(text, offset))
+            .collect();
+        assert_eq!(starts, vec![19, 19, 10, 10, 4, 0, 0]);
+    }
+}
@@ -2,3 +2,4 @@
 
 pub mod simple;
 pub mod spans;
+pub mod tabs;
@@ -56,6 +56,16 @@ impl<'a> LinesIterator<'a> {
         let width = self.width;
         LinesIterator { iter, width }
     }
+
+    /// Sets the number of cells between two tab stops.
+    ///
+    /// Tabs expand to the next stop, counting from the start of each row.
+    /// Defaults to 4.
+    pub fn tab_width(self, tab_width: usize) -> Self {
+        let iter = self.iter.tab_width(tab_width);
+        let width = self.width;
+        LinesIterator { iter, width }
+    }
 }
 
 impl<'a> Iterator for LinesIterator<'a> {
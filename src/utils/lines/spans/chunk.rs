@@ -24,6 +24,9 @@ impl Chunk {
     /// Remove some text from the front.
     ///
     /// We're given the length (number of bytes) and the width.
+    ///
+    /// Widths may be off when the chunk has tabs: they are measured again
+    /// once we know where the chunk goes.
     pub fn remove_front(&mut self, mut to_remove: ChunkPart) {
         // Remove something from each segment until we've removed enough.
         for segment in &mut self.segments {
@@ -31,14 +34,15 @@ impl Chunk {
                 // This segment is bigger than what we need to remove
                 // So just trim the prefix and stop there.
                 segment.start += to_remove.length;
-                segment.width -= to_remove.width;
-                self.width -= to_remove.width;
+                segment.width = segment.width.saturating_sub(to_remove.width);
+                self.width = self.width.saturating_sub(to_remove.width);
                 break;
             } else {
                 // This segment is too small, so it'll disapear entirely.
                 to_remove.length -= segment.end - segment.start;
-                to_remove.width -= segment.width;
-                self.width -= segment.width;
+                to_remove.width =
+                    to_remove.width.saturating_sub(segment.width);
+                self.width = self.width.saturating_sub(segment.width);
 
                 // Empty this segment
                 segment.start = segment.end;
@@ -8,6 +8,7 @@ use std::iter::Peekable;
 use std::rc::Rc;
 use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
+use utils::lines::tabs::{self, DEFAULT_TAB_WIDTH};
 use utils::span::SpannedText;
 
 /// Generates rows of text in constrainted width.
@@ -30,6 +31,15 @@ where
     /// If `true`, keep a blank cell at the end of lines
     /// when a whitespace or newline should be.
     show_spaces: bool,
+
+    /// If `true`, words are split to fill each line entirely.
+    char_wrap: bool,
+
+    /// Number of cells between two tab stops.
+    tab_width: usize,
+
+    /// If `false`, widths from the `ChunkIterator` are already right.
+    has_tabs: bool,
 }
 
 impl<S> LinesIterator<S>
@@ -40,12 +50,16 @@ where
     pub fn new(source: S, width: usize) -> Self {
         let source = Rc::new(source);
         let chunk_source = source.clone();
+        let has_tabs = source.source().contains('\t');
         LinesIterator {
             iter: ChunkIterator::new(chunk_source).peekable(),
             source,
             width,
             chunk_offset: ChunkPart::default(),
             show_spaces: false,
+            char_wrap: false,
+            tab_width: DEFAULT_TAB_WIDTH,
+            has_tabs,
         }
     }
 
@@ -57,6 +71,104 @@ where
         self.show_spaces = true;
         self
     }
+
+    /// Break lines anywhere, rather than only between words.
+    ///
+    /// Each line is filled up to the available width, even if a word has
+    /// to be cut in pieces.
+    pub fn char_wrap(mut self) -> Self {
+        self.char_wrap = true;
+        self
+    }
+
+    /// Sets the number of cells between two tab stops.
+    ///
+    /// Tabs expand to the next stop, counting from the start of each row.
+    /// Defaults to 4.
+    pub fn tab_width(mut self, tab_width: usize) -> Self {
+        self.tab_width = tab_width;
+        self
+    }
+
+    /// Measures again the width of a chunk drawn at `column`.
+    ///
+    /// Widths from the `ChunkIterator` ignore tabs.
+    fn measure(
+        source: &S, tab_width: usize, chunk: &mut Chunk, column: usize,
+    ) {
+        let mut width = 0;
+        for segment in &mut chunk.segments {
+            let text = segment.resolve_plain(source);
+            segment.width = tabs::width(text, column + width, tab_width);
+            width += segment.width;
+        }
+        chunk.width = width;
+    }
+
+    /// Returns the start of the current chunk that fits in `width`.
+    ///
+    /// Each individual grapheme is considered as a valid chunk.
+    /// Note: it may not be the first time we try to fit this chunk,
+    /// so remember to trim the offset we may have stored.
+    fn split_chunk(&mut self, column: usize, width: usize) -> Vec<Chunk> {
+        let mut chunk = match self.iter.peek() {
+            None => return Vec::new(),
+            Some(chunk) => chunk.clone(),
+        };
+        chunk.remove_front(self.chunk_offset);
+
+        // Try to fit part of it?
+        let source = self.source.as_ref();
+        let graphemes = chunk.segments.iter().flat_map(move |seg| {
+            let mut offset = seg.start;
+
+            let text = seg.resolve_plain(source);
+
+            text.graphemes(true).map(move |g| {
+                let width = g.width();
+                let start = offset;
+                let end = offset + g.len();
+                offset = end;
+                Chunk {
+                    width,
+                    segments: vec![Segment {
+                        width,
+                        span_id: seg.span_id,
+                        start,
+                        end,
+                    }],
+                    hard_stop: false,
+                    ends_with_space: false, // should we?
+                }
+            })
+        });
+        let tab_width = self.tab_width;
+        let measure = |chunk: &mut Chunk, column| {
+            Self::measure(source, tab_width, chunk, column)
+        };
+        let chunks = prefix(
+            &mut graphemes.peekable(),
+            column,
+            width,
+            &mut ChunkPart::default(),
+            if self.has_tabs { Some(measure) } else { None },
+        );
+
+        // We are going to return a part of a chunk.
+        // So remember what we selected,
+        // so we can skip it next time.
+        let width: usize = chunks.iter().map(|chunk| chunk.width).sum();
+        let length: usize = chunks
+            .iter()
+            .flat_map(|chunk| chunk.segments.iter())
+            .map(|segment| segment.end - segment.start)
+            .sum();
+
+        self.chunk_offset.width += width;
+        self.chunk_offset.length += length;
+
+        chunks
+    }
 }
 
 impl<S> Iterator for LinesIterator<S>
@@ -76,75 +188,37 @@ where
             self.width
         };
 
-        let mut chunks =
-            prefix(&mut self.iter, allowed_width, &mut self.chunk_offset);
+        let (source, tab_width) = (&*self.source, self.tab_width);
+        let measure = |chunk: &mut Chunk, column| {
+            Self::measure(source, tab_width, chunk, column)
+        };
+        let mut chunks = prefix(
+            &mut self.iter,
+            0,
+            allowed_width,
+            &mut self.chunk_offset,
+            if self.has_tabs { Some(measure) } else { None },
+        );
 
         // println!("Chunks..: {:?}", chunks);
 
         if chunks.is_empty() {
             // Desperate action to make something fit:
             // Look at the current chunk. We'll try to return a part of it.
-            // So now, consider each individual grapheme as a valid chunk.
-            // Note: it may not be the first time we try to fit this chunk,
-            // so remember to trim the offset we may have stored.
-            match self.iter.peek() {
-                None => return None,
-                Some(chunk) => {
-                    let mut chunk = chunk.clone();
-                    chunk.remove_front(self.chunk_offset);
-
-                    // Try to fit part of it?
-                    let source = self.source.as_ref();
-                    let graphemes =
-                        chunk.segments.iter().flat_map(move |seg| {
-                            let mut offset = seg.start;
-
-                            let text = seg.resolve_plain(source);
-
-                            text.graphemes(true).map(move |g| {
-                                let width = g.width();
-                                let start = offset;
-                                let end = offset + g.len();
-                                offset = end;
-                                Chunk {
-                                    width,
-                                    segments: vec![Segment {
-                                        width,
-                                        span_id: seg.span_id,
-                                        start,
-                                        end,
-                                    }],
-                                    hard_stop: false,
-                                    ends_with_space: false, // should we?
-                                }
-                            })
-                        });
-                    chunks = prefix(
-                        &mut graphemes.peekable(),
-                        self.width,
-                        &mut ChunkPart::default(),
-                    );
-
-                    if chunks.is_empty() {
-                        // Seriously? After everything we did for you?
-                        return None;
-                    }
-
-                    // We are going to return a part of a chunk.
-                    // So remember what we selected,
-                    // so we can skip it next time.
-                    let width: usize =
-                        chunks.iter().map(|chunk| chunk.width).sum();
-                    let length: usize = chunks
-                        .iter()
-                        .flat_map(|chunk| chunk.segments.iter())
-                        .map(|segment| segment.end - segment.start)
-                        .sum();
-
-                    self.chunk_offset.width += width;
-                    self.chunk_offset.length += length;
-                }
+            chunks = self.split_chunk(0, self.width);
+
+            if chunks.is_empty() {
+                // Seriously? After everything we did for you?
+                return None;
             }
+        } else if self.char_wrap
+            && !chunks.last().map(|c| c.hard_stop).unwrap_or(false)
+        {
+            // Fill the rest of the row with the start of the next chunk.
+            let used: usize = chunks.iter().map(|c| c.width).sum();
+            let rest =
+                self.split_chunk(used, allowed_width.saturating_sub(used));
+            chunks.extend(rest);
         }
 
         // We can know text was wrapped if the stop was optional,
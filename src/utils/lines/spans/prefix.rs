@@ -2,11 +2,17 @@ use super::chunk::{Chunk, ChunkPart};
 use std::iter::Peekable;
 
 /// Concatenates chunks as long as they fit in the given width.
-pub fn prefix<I>(
-    tokens: &mut Peekable<I>, width: usize, offset: &mut ChunkPart,
+///
+/// The row starts at `column`. `measure` updates the width of a chunk
+/// drawn at the given column, for the tabs it may contain. Without tabs,
+/// give `None` to keep the widths of the chunks as they are.
+pub fn prefix<I, F>(
+    tokens: &mut Peekable<I>, column: usize, width: usize,
+    offset: &mut ChunkPart, measure: Option<F>,
 ) -> Vec<Chunk>
 where
     I: Iterator<Item = Chunk>,
+    F: Fn(&mut Chunk, usize),
 {
     let mut available = width;
     let mut chunks = Vec::new();
@@ -14,25 +20,21 @@ where
     // Accumulate chunks until it doesn't fit.
     loop {
         // Look at the next chunk and see if it would fit.
-        let result = {
-            let next_chunk = match tokens.peek() {
-                None => break,
-                Some(chunk) => chunk,
-            };
-
-            // When considering if the chunk fits, remember that we may
-            // already have processed part of it.
-            // So (chunk - width) fits available
-            // if chunks fits (available + width)
-            consider_chunk(available + offset.width, next_chunk)
+        let mut chunk = match tokens.peek() {
+            None => break,
+            Some(chunk) => chunk.clone(),
         };
 
-        match result {
+        // Remember to strip the prefix, in case we took some earlier.
+        chunk.remove_front(*offset);
+        if let Some(ref measure) = measure {
+            measure(&mut chunk, column + width - available);
+        }
+
+        match consider_chunk(available, &chunk) {
             ChunkFitResult::Fits => {
                 // It fits! Add it and move to the next one.
-                let mut chunk = tokens.next().unwrap();
-                // Remember to strip the prefix, in case we took some earlier.
-                chunk.remove_front(*offset);
+                tokens.next();
                 // And reset out offset.
                 offset.length = 0;
                 offset.width = 0;
@@ -43,8 +45,7 @@ where
             }
             ChunkFitResult::FitsBarely => {
                 // That's it, it's the last one and we're off.
-                let mut chunk = tokens.next().unwrap();
-                chunk.remove_front(*offset);
+                tokens.next();
                 offset.length = 0;
                 offset.width = 0;
 
@@ -63,3 +63,57 @@ fn test_line_breaks() {
         ]
     );
 }
+
+#[test]
+fn test_char_wrap() {
+    let input = input();
+
+    let iter = LinesIterator::new(&input, 10).char_wrap();
+
+    let rows: Vec<Vec<_>> = iter
+        .map(|row| {
+            row.resolve(&input)
+                .into_iter()
+                .map(|span| span.content)
+                .collect()
+        }).collect();
+
+    // Words are split across lines, keeping their style.
+    assert_eq!(
+        &rows[..],
+        &[
+            vec!["I ", "didn't", " s"],
+            vec!["ay ", "half", " th"],
+            vec!["e things p"],
+            vec!["eople say "],
+            vec!["I did."],
+            vec![],
+            vec!["    - A. E"],
+            vec!["instein"],
+        ]
+    );
+}
+
+#[test]
+fn test_tabs() {
+    let input = StyledString::plain("a\tbc\td\n  \te f");
+
+    let rows: Vec<_> = LinesIterator::new(&input, 8)
+        .tab_width(4)
+        .map(|row| {
+            let text: Vec<_> =
+                row.resolve(&input).into_iter().map(|s| s.content).collect();
+            (text.concat(), row.width)
+        })
+        .collect();
+
+    // Tabs expand to the next stop, and wrapping counts their width.
+    assert_eq!(
+        rows,
+        vec![
+            ("a\tbc\t".to_string(), 8),
+            ("d".to_string(), 1),
+            ("  \te f".to_string(), 7),
+        ]
+    );
+}
@@ -0,0 +1,159 @@
+//! Expand tab characters to the next tab stop.
+//!
+//! Tab stops are placed every `tab_width` cells. Since a tab fills the space
+//! up to the next stop, its width depends on the column where it is drawn:
+//! functions here take the column where the text starts.
+
+use std::borrow::Cow;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+use utils::lines::simple::Span;
+
+/// Default number of cells between two tab stops.
+pub const DEFAULT_TAB_WIDTH: usize = 4;
+
+/// Returns the width of a tab drawn at `column`.
+///
+/// A `tab_width` of 0 is treated like 1.
+pub fn tab_width_at(column: usize, tab_width: usize) -> usize {
+    let tab_width = tab_width.max(1);
+    tab_width - column % tab_width
+}
+
+/// Returns the width of the grapheme `g`, drawn at `column`.
+pub fn grapheme_width(g: &str, column: usize, tab_width: usize) -> usize {
+    if g == "\t" {
+        tab_width_at(column, tab_width)
+    } else {
+        g.width()
+    }
+}
+
+/// Returns the width of `text`, drawn from `column`.
+///
+/// `text` should be a single line.
+pub fn width(text: &str, column: usize, tab_width: usize) -> usize {
+    if !text.contains('\t') {
+        return text.width();
+    }
+
+    text.graphemes(true).fold(0, |width, g| {
+        width + grapheme_width(g, column + width, tab_width)
+    })
+}
+
+/// Replaces the tabs in `text`, drawn from `column`, with spaces.
+///
+/// Columns start again from 0 after each newline.
+///
+/// # Examples
+///
+/// ```rust
+/// # use cursive::utils::lines::tabs::expand;
+/// assert_eq!(expand("a\tb\n\tc", 0, 4), "a   b\n    c");
+/// assert_eq!(expand("a\tb", 2, 4), "a b");
+/// ```
+pub fn expand(
+    text: &str, column: usize, tab_width: usize,
+) -> Cow<'_, str> {
+    if !text.contains('\t') {
+        return Cow::Borrowed(text);
+    }
+
+    let mut column = column;
+    let mut expanded = String::with_capacity(text.len());
+    for g in text.graphemes(true) {
+        if g == "\t" {
+            let width = tab_width_at(column, tab_width);
+            expanded.extend((0..width).map(|_| ' '));
+            column += width;
+        } else if g.ends_with('\n') {
+            expanded.push_str(g);
+            column = 0;
+        } else {
+            expanded.push_str(g);
+            column += g.width();
+        }
+    }
+    Cow::Owned(expanded)
+}
+
+/// Computes the longest prefix of `text`, drawn from `column`, that fits in
+/// `width` cells.
+///
+/// Breaks between any two graphemes.
+pub fn prefix(
+    text: &str, column: usize, width: usize, tab_width: usize,
+) -> Span {
+    let mut span = Span {
+        length: 0,
+        width: 0,
+    };
+    for g in text.graphemes(true) {
+        let g_width = grapheme_width(g, column + span.width, tab_width);
+        if span.width + g_width > width {
+            break;
+        }
+        span.length += g.len();
+        span.width += g_width;
+    }
+    span
+}
+
+/// Computes the longest suffix of `text`, drawn from `column`, that fits in
+/// `width` cells.
+///
+/// Tabs keep the width they have when the whole text is drawn.
+pub fn suffix(
+    text: &str, column: usize, width: usize, tab_width: usize,
+) -> Span {
+    let mut x = column;
+    let widths: Vec<_> = text
+        .graphemes(true)
+        .map(|g| {
+            let g_width = grapheme_width(g, x, tab_width);
+            x += g_width;
+            (g.len(), g_width)
+        })
+        .collect();
+
+    let mut span = Span {
+        length: 0,
+        width: 0,
+    };
+    for &(length, g_width) in widths.iter().rev() {
+        if span.width + g_width > width {
+            break;
+        }
+        span.length += length;
+        span.width += g_width;
+    }
+    span
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_widths() {
+        assert_eq!(tab_width_at(0, 4), 4);
+        assert_eq!(tab_width_at(5, 4), 3);
+        assert_eq!(tab_width_at(3, 0), 1);
+
+        // Mixed tabs and spaces.
+        assert_eq!(width("a\tb", 0, 4), 5);
+        assert_eq!(width("  \tb", 0, 4), 5);
+        assert_eq!(width("a\t\tb", 0, 8), 17);
+        assert_eq!(width("\tb", 3, 4), 2);
+
+        let span = prefix("ab\tc", 0, 4, 4);
+        assert_eq!((span.length, span.width), (3, 4));
+        let span = prefix("ab\tc", 0, 3, 4);
+        assert_eq!((span.length, span.width), (2, 2));
+
+        // The tab after "ab" takes 2 cells.
+        let span = suffix("ab\tc", 0, 3, 4);
+        assert_eq!((span.length, span.width), (2, 3));
+    }
+}
@@ -0,0 +1,489 @@
+//! Parse text with ANSI escape sequences.
+//!
+//! Programs like `ls --color` or `git` color their output with SGR escape
+//! sequences (`ESC [ ... m`). This module turns such output into a
+//! `StyledString`, so it can be shown in a `TextView`.
+//!
+//! Supported SGR codes are:
+//!
+//! * `0` to reset the style.
+//! * Effects: `1` (bold), `2` (dim), `3` (italic), `4` (underline), `5` and
+//!   `6` (blink), `7` (reverse) and `9` (strikethrough), and `22` to `29`
+//!   to remove them.
+//! * Foreground colors: `30` to `37`, `90` to `97`, `38;5;n` for the 256
+//!   colors, `38;2;r;g;b` for true colors, and `39` for the default color.
+//! * Background colors: `40` to `47`, `100` to `107`, `48;5;n`,
+//!   `48;2;r;g;b`, and `49` for the default color.
+//!
+//! Colors can also use sub-parameters, like `38:5:n` or `38:2::r:g:b`.
+//! Underline styles, like `4:3` for curly underlines, are drawn as simple
+//! underlines.
+//!
+//! Other codes are ignored. Other escape sequences, like cursor movements
+//! or hyperlinks, are removed from the text.
+//!
+//! Use a [`Parser`] for text read in several chunks.
+//!
+//! [`Parser`]: struct.Parser.html
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use cursive::utils::markup::ansi;
+//! let text = ansi::parse("\x1b[01;34msrc\x1b[0m  README.md");
+//! assert_eq!(text.source(), "src  README.md");
+//! ```
+
+use std::mem;
+use theme::{BaseColor, Color, ColorStyle, ColorType, Effect};
+use theme::{EffectSet, PaletteColor, Style};
+use utils::markup::StyledString;
+
+const ESC: char = '\x1b';
+const BEL: char = '\x07';
+
+/// Parses the given string, styled with ANSI escape sequences.
+pub fn parse<S>(input: S) -> StyledString
+where
+    S: Into<String>,
+{
+    let mut parser = Parser::new();
+    parser.push(&input.into());
+    parser.take()
+}
+
+/// Parses text with ANSI escape sequences, given in several chunks.
+///
+/// Use it for the output of a running program: the style, and escape
+/// sequences cut between two chunks, carry over to the next chunk.
+///
+/// # Examples
+///
+/// ```rust
+/// # use cursive::utils::markup::ansi::Parser;
+/// let mut parser = Parser::new();
+/// parser.push("\x1b[3");
+/// parser.push("1mred\x1b[0m");
+/// assert_eq!(parser.take().source(), "red");
+/// ```
+#[derive(Default)]
+pub struct Parser {
+    result: StyledString,
+    // Text not yet added to `result`, using the current style.
+    text: String,
+    state: State,
+    // Start of an escape sequence, cut at the end of the last chunk.
+    pending: String,
+}
+
+impl Parser {
+    /// Creates a new parser, with the default style.
+    pub fn new() -> Self {
+        Parser::default()
+    }
+
+    /// Parses the next chunk of text.
+    pub fn push(&mut self, input: &str) {
+        if self.pending.is_empty() {
+            self.parse(input);
+        } else {
+            let input = mem::take(&mut self.pending) + input;
+            self.parse(&input);
+        }
+    }
+
+    /// Returns the text parsed so far, and starts again from an empty text.
+    ///
+    /// The current style is kept for the next chunks. An escape sequence
+    /// still incomplete is kept too.
+    pub fn take(&mut self) -> StyledString {
+        self.flush();
+        mem::take(&mut self.result)
+    }
+
+    fn parse(&mut self, input: &str) {
+        let mut input = input;
+        while let Some(start) = input.find(ESC) {
+            self.text.push_str(&input[..start]);
+            input = &input[start..];
+
+            match sequence_length(input) {
+                Some(length) => {
+                    self.escape(&input[..length]);
+                    input = &input[length..];
+                }
+                None => {
+                    // Wait for the end of the sequence.
+                    self.pending = input.to_string();
+                    return;
+                }
+            }
+        }
+        self.text.push_str(input);
+    }
+
+    /// Handles a complete escape sequence.
+    fn escape(&mut self, sequence: &str) {
+        // Private sequences, like `ESC [ ? 25 h`, are not SGR.
+        let is_sgr = sequence.starts_with("\x1b[")
+            && sequence.ends_with('m')
+            && sequence[2..sequence.len() - 1]
+                .chars()
+                .all(|c| c.is_ascii_digit() || c == ';' || c == ':');
+        if is_sgr {
+            self.apply(&sequence[2..sequence.len() - 1]);
+        }
+    }
+
+    fn flush(&mut self) {
+        if !self.text.is_empty() {
+            let style = self.state.style();
+            self.result.append_styled(&self.text[..], style);
+            self.text.clear();
+        }
+    }
+
+    /// Applies the parameters of an SGR sequence.
+    fn apply(&mut self, params: &str) {
+        let mut state = self.state;
+        state.apply(params);
+
+        if state != self.state {
+            // The text so far used the previous style.
+            self.flush();
+            self.state = state;
+        }
+    }
+}
+
+/// Returns the length of the escape sequence at the start of `input`.
+///
+/// Returns `None` if `input` ends before the sequence.
+fn sequence_length(input: &str) -> Option<usize> {
+    let mut chars = input.char_indices().skip(1);
+
+    match chars.next()?.1 {
+        // Control sequence: parameters, then a final byte.
+        '[' => {
+            for (i, c) in chars {
+                match c {
+                    '\x20'..='\x3f' => (),
+                    '\x40'..='\x7e' => return Some(i + 1),
+                    // Not a control sequence: keep the rest as text.
+                    _ => return Some(i),
+                }
+            }
+        }
+        // Operating system command, ended by `BEL` or `ESC \`.
+        ']' => {
+            let mut escaped = false;
+            for (i, c) in chars {
+                if c == BEL || (escaped && c == '\\') {
+                    return Some(i + 1);
+                }
+                escaped = c == ESC;
+            }
+        }
+        // Intermediate bytes, then a final byte, like `ESC ( B`.
+        '\x20'..='\x2f' => {
+            for (i, c) in chars {
+                if c >= '\x30' {
+                    return Some(i + c.len_utf8());
+                }
+            }
+        }
+        // Two-character sequence.
+        c => return Some(1 + c.len_utf8()),
+    }
+
+    None
+}
+
+/// Style set by SGR sequences.
+#[derive(Clone, Copy, Default, PartialEq)]
+struct State {
+    effects: EffectSet,
+    // `None` for the default colors.
+    front: Option<Color>,
+    back: Option<Color>,
+}
+
+impl State {
+    fn style(&self) -> Style {
+        let color = if self.front.is_some() || self.back.is_some() {
+            let color = |color: Option<Color>, default| {
+                color
+                    .map(ColorType::Color)
+                    .unwrap_or(ColorType::Palette(default))
+            };
+            Some(ColorStyle::new(
+                color(self.front, PaletteColor::Primary),
+                color(self.back, PaletteColor::View),
+            ))
+        } else {
+            None
+        };
+
+        Style {
+            effects: self.effects,
+            color,
+        }
+    }
+
+    /// Applies the parameters of an SGR sequence.
+    fn apply(&mut self, params: &str) {
+        // Each parameter can have sub-parameters, like `38:5:208`.
+        // An empty one is a 0.
+        let mut params = params.split(';').map(|param| {
+            param
+                .split(':')
+                .map(|code| {
+                    if code.is_empty() {
+                        0
+                    } else {
+                        code.parse().unwrap_or(u32::MAX)
+                    }
+                })
+                .collect::<Vec<u32>>()
+        });
+
+        while let Some(param) = params.next() {
+            let code = param[0];
+            match code {
+                0 => *self = State::default(),
+                // `4:0` removes the underline, `4:3` is a curly one.
+                4 if param.get(1) == Some(&0) => {
+                    self.effects.remove(Effect::Underline);
+                }
+                1..=9 => {
+                    if let Some(effect) = effect(code) {
+                        self.effects.insert(effect);
+                    }
+                }
+                21..=29 => {
+                    if let Some(effect) = effect(code - 20) {
+                        self.effects.remove(effect);
+                    }
+                    // Normal intensity removes both bold and dim.
+                    if code == 22 {
+                        self.effects.remove(Effect::Bold);
+                    }
+                }
+                30..=37 => self.front = Some(Color::Dark(base(code - 30))),
+                90..=97 => self.front = Some(Color::Light(base(code - 90))),
+                38 | 48 => {
+                    let color = if param.len() > 1 {
+                        sub_color(&param[1..])
+                    } else {
+                        // The color is in the next parameters.
+                        let mut codes = params.by_ref().map(|param| param[0]);
+                        match extended_color(&mut codes) {
+                            Some(color) => Some(color),
+                            None => break,
+                        }
+                    };
+                    if let Some(color) = color {
+                        if code == 38 {
+                            self.front = Some(color);
+                        } else {
+                            self.back = Some(color);
+                        }
+                    }
+                }
+                39 => self.front = None,
+                40..=47 => self.back = Some(Color::Dark(base(code - 40))),
+                100..=107 => self.back = Some(Color::Light(base(code - 100))),
+                49 => self.back = None,
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Returns the effect set by the given SGR code.
+fn effect(code: u32) -> Option<Effect> {
+    Some(match code {
+        1 => Effect::Bold,
+        2 => Effect::Dim,
+        3 => Effect::Italic,
+        4 => Effect::Underline,
+        5 | 6 => Effect::Blink,
+        7 => Effect::Reverse,
+        9 => Effect::Strikethrough,
+        _ => return None,
+    })
+}
+
+fn base(n: u32) -> BaseColor {
+    BaseColor::from(n as u8)
+}
+
+/// Reads the color in the sub-parameters of a `38` or `48` code.
+///
+/// True colors can start with a color space, like `38:2::r:g:b`.
+fn sub_color(codes: &[u32]) -> Option<Color> {
+    let codes = match codes {
+        [2, _, r, g, b] => &[2, *r, *g, *b][..],
+        codes => codes,
+    };
+    extended_color(&mut codes.iter().cloned())
+}
+
+/// Reads the color after a `38` or `48` code.
+fn extended_color<I>(codes: &mut I) -> Option<Color>
+where
+    I: Iterator<Item = u32>,
+{
+    let mut channel = || codes.next().filter(|&n| n < 256).map(|n| n as u8);
+
+    match channel()? {
+        5 => channel().map(Color::from_256colors),
+        2 => Some(Color::Rgb(channel()?, channel()?, channel()?)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spans(input: &str) -> Vec<(String, Style)> {
+        parse(input)
+            .spans()
+            .map(|span| (span.content.to_string(), *span.attr))
+            .collect()
+    }
+
+    #[test]
+    fn test_ls() {
+        let output =
+            "\x1b[0m\x1b[01;34msrc\x1b[0m  \x1b[01;32mrun.sh\x1b[0m\n";
+        let blue = Style::from(Color::Dark(BaseColor::Blue));
+        let green = Style::from(Color::Dark(BaseColor::Green));
+
+        assert_eq!(
+            spans(output),
+            vec![
+                ("src".to_string(), blue.combine(Effect::Bold)),
+                ("  ".to_string(), Style::none()),
+                ("run.sh".to_string(), green.combine(Effect::Bold)),
+                ("\n".to_string(), Style::none()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_git() {
+        let output = "\x1b[1mdiff --git a/x b/x\x1b[m\n\
+                      \x1b[31m-old\x1b[m\n\
+                      \x1b[32m+new\x1b[m\n";
+        let text = parse(output);
+        assert_eq!(text.source(), "diff --git a/x b/x\n-old\n+new\n");
+
+        let spans: Vec<_> = text.spans().map(|span| *span.attr).collect();
+        assert_eq!(spans[0], Style::from(Effect::Bold));
+        assert_eq!(spans[2], Style::from(Color::Dark(BaseColor::Red)));
+        assert_eq!(spans[4], Style::from(Color::Dark(BaseColor::Green)));
+    }
+
+    #[test]
+    fn test_colors() {
+        let input = "\x1b[38;5;208mA\x1b[48;2;0;0;255;4mB\x1b[39;24mC\
+                     \x1b[49;97;1;2mD\x1b[22mE";
+        let orange = Color::from_256colors(208);
+        let blue = Color::Rgb(0, 0, 255);
+
+        assert_eq!(
+            spans(input),
+            vec![
+                ("A".to_string(), Style::from(orange)),
+                (
+                    "B".to_string(),
+                    Style::from(ColorStyle::new(orange, blue))
+                        .combine(Effect::Underline)
+                ),
+                (
+                    "C".to_string(),
+                    Style::from(ColorStyle::new(PaletteColor::Primary, blue))
+                ),
+                (
+                    "D".to_string(),
+                    Style::from(Color::Light(BaseColor::White))
+                        .combine(Effect::Bold)
+                        .combine(Effect::Dim)
+                ),
+                ("E".to_string(), Style::from(Color::Light(BaseColor::White))),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown() {
+        // Cursor movements, hyperlinks and private modes are removed.
+        let input = "\x1b[2K\x1b[1Gdone \x1b]8;;https://example.org\x07link\
+                     \x1b]8;;\x1b\\\x1b[?25h\x1b[38;5m\x1b(B!\x1b";
+        let text = parse(input);
+        assert_eq!(text.source(), "done link!");
+        assert!(text.spans().all(|span| *span.attr == Style::none()));
+    }
+
+    #[test]
+    fn test_sub_parameters() {
+        let input = "\x1b[38:2::255:0:0mA\x1b[4:3;48:5:208mB\x1b[4:0mC\
+                     \x1b[38:2:0:0:255mD";
+        let red = Color::Rgb(255, 0, 0);
+        let orange = Color::from_256colors(208);
+        let blue = Color::Rgb(0, 0, 255);
+
+        assert_eq!(
+            spans(input),
+            vec![
+                ("A".to_string(), Style::from(red)),
+                (
+                    "B".to_string(),
+                    Style::from(ColorStyle::new(red, orange))
+                        .combine(Effect::Underline)
+                ),
+                ("C".to_string(), Style::from(ColorStyle::new(red, orange))),
+                ("D".to_string(), Style::from(ColorStyle::new(blue, orange))),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unfinished_sequence() {
+        // The newline is not part of a control sequence.
+        let text = parse("\x1b[1;31\nnext \x1b[1mline");
+        assert_eq!(text.source(), "\nnext line");
+        assert_eq!(
+            text.spans().map(|span| *span.attr).collect::<Vec<_>>(),
+            vec![Style::none(), Style::from(Effect::Bold)]
+        );
+    }
+
+    #[test]
+    fn test_chunks() {
+        let mut parser = Parser::new();
+        for chunk in &["a\x1b", "[3", "1mb\x1b]8;;ur", "l\x1b", "\\c"] {
+            parser.push(chunk);
+        }
+        let red = Style::from(Color::Dark(BaseColor::Red));
+        let spans: Vec<_> = parser
+            .take()
+            .spans()
+            .map(|span| (span.content.to_string(), *span.attr))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![("a".to_string(), Style::none()), ("bc".to_string(), red)]
+        );
+
+        // The style carries over.
+        parser.push("d\x1b[");
+        assert_eq!(parser.take().spans().next().unwrap().attr, &red);
+        parser.push("0me");
+        let text = parser.take();
+        assert_eq!(text.source(), "e");
+        assert_eq!(text.spans().next().unwrap().attr, &Style::none());
+    }
+}
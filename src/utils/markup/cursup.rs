@@ -0,0 +1,309 @@
+//! Parse cursup markup.
+//!
+//! Cursup is a compact syntax to style parts of a text: `/style{text}`
+//! prints `text` using `style`.
+//!
+//! A style is a list of tokens separated by `+`. Each token can be:
+//!
+//! * An effect: `bold`, `italic`, `underline`, `reverse`, `strikethrough`,
+//!   `blink`, `dim` or `simple`.
+//! * A foreground color, as accepted by [`Color::parse`]: `red`,
+//!   `light_blue`, `#ff8000`, ...
+//! * A background color, with the `back.` prefix: `back.blue`.
+//!
+//! Groups can be nested: `/bold{some /red{red} text}`. Inner groups keep
+//! the effects of outer groups, and a color only replaces the side
+//! (foreground or background) it applies to.
+//!
+//! A backslash escapes the next `\`, `/`, `{` or `}` character.
+//!
+//! Malformed markup is never an error: an unknown style is printed as
+//! plain text, along with its braces, and unbalanced braces are kept
+//! as-is. A group left open applies to the rest of the text.
+//!
+//! [`Color::parse`]: ../../theme/enum.Color.html#method.parse
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use cursive::utils::markup::cursup;
+//! let text = cursup::parse("Some /red+bold{important} text.");
+//! assert_eq!(text.source(), "Some important text.");
+//! ```
+
+use theme::{Color, ColorStyle, ColorType, Effect, PaletteColor, Style};
+use utils::markup::StyledString;
+
+/// Parses the given string as cursup markup.
+pub fn parse<S>(input: S) -> StyledString
+where
+    S: Into<String>,
+{
+    let input = input.into();
+    let mut parser = Parser::new();
+
+    let mut rest = &input[..];
+    while let Some(c) = rest.chars().next() {
+        let mut len = c.len_utf8();
+
+        match c {
+            '\\' => match rest[len..].chars().next() {
+                Some(next) if "\\/{}".contains(next) => {
+                    parser.text.push(next);
+                    len += next.len_utf8();
+                }
+                _ => parser.text.push(c),
+            },
+            '/' => {
+                let spec = rest[len..]
+                    .split(|c| "{}/\\\n".contains(c))
+                    .next()
+                    .unwrap_or("");
+                let opens = rest[len + spec.len()..].starts_with('{');
+
+                match parse_style(spec, &parser.current_style()) {
+                    Some(style) if opens => {
+                        parser.open(style);
+                        len += spec.len() + 1;
+                    }
+                    _ => parser.text.push(c),
+                }
+            }
+            '{' => {
+                parser.text.push(c);
+                parser.groups.push(false);
+            }
+            '}' => parser.close(),
+            c => parser.text.push(c),
+        }
+
+        rest = &rest[len..];
+    }
+
+    parser.flush();
+    parser.result
+}
+
+struct Parser {
+    result: StyledString,
+    // Text not yet added to `result`, using the current style.
+    text: String,
+    // Styles of the currently opened styled groups.
+    styles: Vec<Style>,
+    // Opened groups: `true` for styled ones, `false` for literal braces.
+    groups: Vec<bool>,
+}
+
+impl Parser {
+    fn new() -> Self {
+        Parser {
+            result: StyledString::new(),
+            text: String::new(),
+            styles: Vec::new(),
+            groups: Vec::new(),
+        }
+    }
+
+    fn current_style(&self) -> Style {
+        Style::merge(&self.styles)
+    }
+
+    fn flush(&mut self) {
+        if !self.text.is_empty() {
+            let style = self.current_style();
+            self.result.append_styled(&self.text[..], style);
+            self.text.clear();
+        }
+    }
+
+    fn open(&mut self, style: Style) {
+        self.flush();
+        self.styles.push(style);
+        self.groups.push(true);
+    }
+
+    fn close(&mut self) {
+        match self.groups.pop() {
+            Some(true) => {
+                self.flush();
+                self.styles.pop();
+            }
+            // Either a literal group, or an unbalanced brace.
+            _ => self.text.push('}'),
+        }
+    }
+}
+
+/// Parses a style specification, like `red+bold`.
+///
+/// Colors replace the corresponding side of the `parent` colors.
+fn parse_style(spec: &str, parent: &Style) -> Option<Style> {
+    if spec.is_empty() {
+        return None;
+    }
+
+    let (mut front, mut back) = match parent.color {
+        Some(color) => (color.front, color.back),
+        None => (
+            ColorType::Palette(PaletteColor::Primary),
+            ColorType::Palette(PaletteColor::View),
+        ),
+    };
+    let mut has_color = false;
+    let mut effects = parent.effects;
+
+    for token in spec.split('+') {
+        let token = token.trim().to_lowercase();
+
+        if let Some(effect) = parse_effect(&token) {
+            effects.insert(effect);
+            continue;
+        }
+
+        let mut parts = token.splitn(2, '.');
+        match (parts.next(), parts.next()) {
+            (Some("back"), Some(color)) => back = parse_color(color)?,
+            _ => front = parse_color(&token)?,
+        }
+        has_color = true;
+    }
+
+    let color = if has_color {
+        Some(ColorStyle::new(front, back))
+    } else {
+        None
+    };
+
+    Some(Style { effects, color })
+}
+
+fn parse_color(token: &str) -> Option<ColorType> {
+    Color::parse(&token.replace('_', " ")).map(ColorType::Color)
+}
+
+fn parse_effect(token: &str) -> Option<Effect> {
+    Some(match token {
+        "simple" => Effect::Simple,
+        "reverse" => Effect::Reverse,
+        "bold" => Effect::Bold,
+        "italic" => Effect::Italic,
+        "underline" => Effect::Underline,
+        "strikethrough" => Effect::Strikethrough,
+        "blink" => Effect::Blink,
+        "dim" => Effect::Dim,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use theme::BaseColor;
+    use utils::span::Span;
+
+    fn spans(input: &str) -> Vec<(String, Style)> {
+        parse(input)
+            .spans()
+            .map(|span| (span.content.to_string(), *span.attr))
+            .collect()
+    }
+
+    fn red() -> Style {
+        Style::from(Color::Dark(BaseColor::Red))
+    }
+
+    #[test]
+    fn test_parse() {
+        let text = parse("Some /red+bold{important} text.");
+        let spans: Vec<_> = text.spans().collect();
+
+        assert_eq!(
+            &spans[..],
+            &[
+                Span {
+                    content: "Some ",
+                    attr: &Style::none(),
+                },
+                Span {
+                    content: "important",
+                    attr: &red().combine(Effect::Bold),
+                },
+                Span {
+                    content: " text.",
+                    attr: &Style::none(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nested() {
+        let blue_on_red = Style::from(ColorStyle::new(
+            Color::Dark(BaseColor::Blue),
+            Color::Dark(BaseColor::Red),
+        ));
+
+        assert_eq!(
+            spans("/bold{a /red{b /back.red+blue{c}}}"),
+            vec![
+                ("a ".to_string(), Style::from(Effect::Bold)),
+                ("b ".to_string(), red().combine(Effect::Bold)),
+                ("c".to_string(), blue_on_red.combine(Effect::Bold)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_colors() {
+        let orange = Style::from(ColorStyle::new(
+            Color::Rgb(255, 128, 0),
+            Color::Light(BaseColor::Blue),
+        ));
+
+        assert_eq!(
+            spans("/#FF8000+back.light_blue+underline{x}"),
+            vec![("x".to_string(), orange.combine(Effect::Underline))]
+        );
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            spans(r"\{a\} \/red{b} \\/red{c}"),
+            vec![
+                ("{a} /red{b} \\".to_string(), Style::none()),
+                ("c".to_string(), red()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_malformed() {
+        let plain =
+            |input: &str| vec![(input.to_string(), Style::none())];
+
+        for input in &["/nope{a}", "a } b", "/{a}", "a/red", "/red b{c}"] {
+            assert_eq!(spans(input), plain(input));
+        }
+
+        // Literal braces don't close the outer group.
+        assert_eq!(
+            spans("/red{a /nope{b} c} d"),
+            vec![
+                ("a /nope{b} c".to_string(), red()),
+                (" d".to_string(), Style::none()),
+            ]
+        );
+
+        // Unclosed groups last until the end.
+        assert_eq!(
+            spans("a /red{b"),
+            vec![
+                ("a ".to_string(), Style::none()),
+                ("b".to_string(), red()),
+            ]
+        );
+
+        assert_eq!(spans(""), vec![]);
+    }
+}
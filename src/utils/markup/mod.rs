@@ -1,7 +1,10 @@
 //! Parse various text markup formats.
 //!
-//! Each module is optional and relies on a feature.
+//! Except for `ansi` and `cursup`, each module is optional and relies on a
+//! feature.
 
+pub mod ansi;
+pub mod cursup;
 #[cfg(feature = "markdown")]
 pub mod markdown;
 
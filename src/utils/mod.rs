@@ -1,12 +1,15 @@
 //! Toolbox to make text layout easier.
 
+pub mod clipboard;
 mod counter;
+pub(crate) mod graphemes;
 #[macro_use]
 mod immutify;
 pub mod lines;
 pub mod markup;
 mod reader;
 pub mod span;
+pub(crate) mod undo;
 
 pub use self::counter::Counter;
 pub use self::reader::ProgressReader;
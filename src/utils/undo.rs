@@ -0,0 +1,212 @@
+//! Undo history for text input views.
+
+use std::cmp::min;
+
+/// A single change: `removed` was replaced by `inserted` at `position`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Edit {
+    /// Byte offset of the change in the content.
+    position: usize,
+    removed: String,
+    inserted: String,
+}
+
+/// Undo and redo stacks of edits made to a string.
+///
+/// Consecutive single-character insertions are merged into one edit, so
+/// undoing removes a whole word at a time.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    undo: Vec<Edit>,
+    redo: Vec<Edit>,
+
+    // `true` if the next typed character can be merged with the last edit.
+    typing: bool,
+}
+
+impl History {
+    /// Creates a new, empty history.
+    pub fn new() -> Self {
+        History::default()
+    }
+
+    /// Removes every recorded edit.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.typing = false;
+    }
+
+    /// Records that `text` was inserted at `position`.
+    pub fn record_insert(&mut self, position: usize, text: &str) {
+        let single_char = text.chars().count() == 1;
+
+        if single_char && self.typing {
+            if let Some(last) = self.undo.last_mut() {
+                // Start a new step after each word.
+                let after_word = last
+                    .inserted
+                    .chars()
+                    .last()
+                    .map(char::is_whitespace)
+                    .unwrap_or(false)
+                    && !text.chars().all(char::is_whitespace);
+
+                if last.position + last.inserted.len() == position
+                    && !after_word
+                {
+                    last.inserted.push_str(text);
+                    self.redo.clear();
+                    return;
+                }
+            }
+        }
+
+        self.push(Edit {
+            position,
+            removed: String::new(),
+            inserted: text.to_string(),
+        });
+        self.typing = single_char;
+    }
+
+    /// Records that `text` was removed from `position`.
+    pub fn record_remove(&mut self, position: usize, text: &str) {
+        self.push(Edit {
+            position,
+            removed: text.to_string(),
+            inserted: String::new(),
+        });
+        self.typing = false;
+    }
+
+    /// Records that `removed` was replaced by `inserted` at `position`.
+    pub fn record_replace(
+        &mut self, position: usize, removed: &str, inserted: &str,
+    ) {
+        self.push(Edit {
+            position,
+            removed: removed.to_string(),
+            inserted: inserted.to_string(),
+        });
+        self.typing = false;
+    }
+
+    /// Records that `old` was replaced by `new`.
+    ///
+    /// Only the part that changed is kept, so typing is still merged.
+    pub fn record_change(&mut self, old: &str, new: &str) {
+        let prefix = old
+            .char_indices()
+            .zip(new.chars())
+            .find(|&((_, a), b)| a != b)
+            .map(|((i, _), _)| i)
+            .unwrap_or_else(|| min(old.len(), new.len()));
+        let suffix = old[prefix..]
+            .char_indices()
+            .rev()
+            .zip(new[prefix..].chars().rev())
+            .find(|&((_, a), b)| a != b)
+            .map(|((i, a), _)| old.len() - (prefix + i + a.len_utf8()))
+            .unwrap_or_else(|| min(old.len(), new.len()) - prefix);
+
+        let removed = &old[prefix..old.len() - suffix];
+        let inserted = &new[prefix..new.len() - suffix];
+
+        if removed.is_empty() {
+            if !inserted.is_empty() {
+                self.record_insert(prefix, inserted);
+            }
+        } else if inserted.is_empty() {
+            self.record_remove(prefix, removed);
+        } else {
+            self.record_replace(prefix, removed, inserted);
+        }
+    }
+
+    /// Reverts the last edit on `content`.
+    ///
+    /// Returns the new cursor position, or `None` if there is nothing to
+    /// undo.
+    pub fn undo(&mut self, content: &mut String) -> Option<usize> {
+        let edit = self.undo.pop()?;
+        self.typing = false;
+
+        let end = edit.position + edit.inserted.len();
+        content.replace_range(edit.position..end, &edit.removed);
+        let cursor = edit.position + edit.removed.len();
+
+        self.redo.push(edit);
+        Some(cursor)
+    }
+
+    /// Applies again the last undone edit on `content`.
+    ///
+    /// Returns the new cursor position, or `None` if there is nothing to
+    /// redo.
+    pub fn redo(&mut self, content: &mut String) -> Option<usize> {
+        let edit = self.redo.pop()?;
+        self.typing = false;
+
+        let end = edit.position + edit.removed.len();
+        content.replace_range(edit.position..end, &edit.inserted);
+        let cursor = edit.position + edit.inserted.len();
+
+        self.undo.push(edit);
+        Some(cursor)
+    }
+
+    fn push(&mut self, edit: Edit) {
+        self.undo.push(edit);
+        self.redo.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_words() {
+        let mut history = History::new();
+        let mut content = String::new();
+        for c in "hello big world".chars() {
+            history.record_insert(content.len(), &c.to_string());
+            content.push(c);
+        }
+
+        assert_eq!(history.undo(&mut content), Some(10));
+        assert_eq!(content, "hello big ");
+        assert_eq!(history.undo(&mut content), Some(6));
+        assert_eq!(content, "hello ");
+
+        assert_eq!(history.redo(&mut content), Some(10));
+        assert_eq!(content, "hello big ");
+
+        // A new edit drops the redo stack.
+        history.record_remove(0, "h");
+        content.remove(0);
+        assert_eq!(history.redo(&mut content), None);
+        assert_eq!(history.undo(&mut content), Some(1));
+        assert_eq!(content, "hello big ");
+    }
+
+    #[test]
+    fn test_record_change() {
+        let mut history = History::new();
+        let mut content = "abc".to_string();
+
+        history.record_change(&content, "abXc");
+        content = "abXc".to_string();
+        history.record_change(&content, "abXYc");
+        content = "abXYc".to_string();
+        history.record_change(&content, "aé");
+        content = "aé".to_string();
+
+        assert_eq!(history.undo(&mut content), Some(5));
+        assert_eq!(content, "abXYc");
+        // Both insertions were merged.
+        assert_eq!(history.undo(&mut content), Some(2));
+        assert_eq!(content, "abc");
+    }
+}
@@ -0,0 +1,23 @@
+use view::View;
+use views::{AccessibleView, Role};
+
+/// Makes a view wrappable in an [`AccessibleView`].
+///
+/// [`AccessibleView`]: ../views/struct.AccessibleView.html
+pub trait Accessible: View + Sized {
+    /// Wraps this view into an `AccessibleView` with the given label.
+    ///
+    /// This is just a shortcut for `AccessibleView::new(self, label)`
+    fn a11y_label<S: Into<String>>(self, label: S) -> AccessibleView<Self> {
+        AccessibleView::new(self, label)
+    }
+
+    /// Wraps this view into an unlabeled `AccessibleView` with the given
+    /// role.
+    fn a11y_role(self, role: Role) -> AccessibleView<Self> {
+        AccessibleView::new(self, "").a11y_role(role)
+    }
+}
+
+/// Any `View` implements this trait.
+impl<T: View> Accessible for T {}
@@ -26,6 +26,9 @@ pub trait AnyView {
     /// # }
     /// ```
     fn as_boxed_any(self: Box<Self>) -> Box<Any>;
+
+    /// Returns the name of the concrete type.
+    fn type_name(&self) -> &'static str;
 }
 
 impl<T: View> AnyView for T {
@@ -42,4 +45,8 @@ impl<T: View> AnyView for T {
     fn as_boxed_any(self: Box<Self>) -> Box<Any> {
         self
     }
+
+    fn type_name(&self) -> &'static str {
+        ::std::any::type_name::<T>()
+    }
 }
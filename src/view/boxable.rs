@@ -72,6 +72,48 @@ pub trait Boxable: View + Sized {
     fn min_height(self, min_height: usize) -> BoxView<Self> {
         BoxView::with_min_height(min_height, self)
     }
+
+    /// Wraps `self` in a `BoxView` sized between `min_size` and `max_size`.
+    fn bounded_size<S, U>(self, min_size: S, max_size: U) -> BoxView<Self>
+    where
+        S: Into<Vec2>,
+        U: Into<Vec2>,
+    {
+        BoxView::with_bounded_size(min_size, max_size, self)
+    }
+
+    /// Wraps `self` in a `BoxView` between `min_width` and `max_width` wide.
+    fn bounded_width(
+        self, min_width: usize, max_width: usize,
+    ) -> BoxView<Self> {
+        BoxView::with_bounded_width(min_width, max_width, self)
+    }
+
+    /// Wraps `self` in a `BoxView` between `min_height` and `max_height`
+    /// tall.
+    fn bounded_height(
+        self, min_height: usize, max_height: usize,
+    ) -> BoxView<Self> {
+        BoxView::with_bounded_height(min_height, max_height, self)
+    }
+
+    /// Wraps `self` in a `BoxView` taking a percentage of the available
+    /// size.
+    fn percentage_size(self, width: u8, height: u8) -> BoxView<Self> {
+        BoxView::with_percentage_size(width, height, self)
+    }
+
+    /// Wraps `self` in a `BoxView` taking a percentage of the available
+    /// width.
+    fn percentage_width(self, width: u8) -> BoxView<Self> {
+        BoxView::with_percentage_width(width, self)
+    }
+
+    /// Wraps `self` in a `BoxView` taking a percentage of the available
+    /// height.
+    fn percentage_height(self, height: u8) -> BoxView<Self> {
+        BoxView::with_percentage_height(height, self)
+    }
 }
 
 impl<T: View> Boxable for T {}
@@ -0,0 +1,48 @@
+use std::any::Any;
+use view::View;
+use XY;
+
+/// Follows the focus down the view tree, for [`View::follow_focus()`].
+///
+/// Unlike drawing, this only visits the views along the focus path, and
+/// gives each of them to a visitor with its position on the screen.
+///
+/// [`View::follow_focus()`]: trait.View.html#method.follow_focus
+pub struct FocusPath<'a> {
+    // Position of the current view.
+    position: XY<isize>,
+    visitor: &'a mut FnMut(&mut Any, XY<isize>),
+}
+
+impl<'a> FocusPath<'a> {
+    /// Follows the focus from `view`, drawn at `position`.
+    ///
+    /// `visitor` is given each focused view below `view`, outermost first.
+    pub(crate) fn follow<V: View + ?Sized>(
+        view: &mut V, position: XY<isize>,
+        visitor: &mut FnMut(&mut Any, XY<isize>),
+    ) {
+        view.follow_focus(&mut FocusPath { position, visitor });
+    }
+
+    /// Follows the focus to a child of the current view.
+    ///
+    /// `offset` is where the child is drawn, relative to the current view.
+    /// It can be negative for scrolled content.
+    pub fn child<V, O>(&mut self, view: &mut V, offset: O)
+    where
+        V: View + ?Sized,
+        O: Into<XY<isize>>,
+    {
+        let position = self.position + offset;
+        (self.visitor)(view.as_any_mut(), position);
+        FocusPath::follow(view, position, &mut *self.visitor);
+    }
+
+    /// Gives `value` to the visitor, at the position of the current view.
+    ///
+    /// Views can use this to share some of their state along the path.
+    pub fn visit(&mut self, value: &mut Any) {
+        (self.visitor)(value, self.position);
+    }
+}
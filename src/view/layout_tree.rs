@@ -0,0 +1,287 @@
+use std::fmt;
+use vec::Vec2;
+use view::View;
+
+/// Description of a view and its children, for debugging.
+///
+/// Returned by [`Cursive::dump_layout()`]. It can be printed as an indented
+/// tree, or serialized with the `serde` feature.
+///
+/// [`Cursive::dump_layout()`]: ../struct.Cursive.html#method.dump_layout
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LayoutNode {
+    /// Type of the view, without the module paths.
+    pub type_name: String,
+    /// Position of the view on the screen.
+    pub position: Vec2,
+    /// Size given to the view by its parent.
+    pub size: Vec2,
+    /// Size the view asked for in the last layout, if it remembers it.
+    pub required_size: Option<Vec2>,
+    /// `true` if the view has the focus.
+    pub focused: bool,
+    /// Details given by the view, if any.
+    pub info: Option<String>,
+    /// Children of the view, in drawing order.
+    pub children: Vec<LayoutNode>,
+}
+
+impl LayoutNode {
+    fn fmt_indented(
+        &self, f: &mut fmt::Formatter, depth: usize,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "{:indent$}{} at ({}, {}), size {}x{}",
+            "",
+            self.type_name,
+            self.position.x,
+            self.position.y,
+            self.size.x,
+            self.size.y,
+            indent = depth * 2
+        )?;
+        if let Some(required) = self.required_size {
+            write!(f, ", requires {}x{}", required.x, required.y)?;
+        }
+        if self.focused {
+            write!(f, ", focused")?;
+        }
+        if let Some(ref info) = self.info {
+            write!(f, ": {}", info)?;
+        }
+        writeln!(f)?;
+
+        for child in &self.children {
+            child.fmt_indented(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for LayoutNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+/// Collects the children of a view, for [`View::debug_layout()`].
+///
+/// [`View::debug_layout()`]: trait.View.html#method.debug_layout
+pub struct LayoutTree {
+    position: Vec2,
+    size: Vec2,
+    focused: bool,
+    required_size: Option<Vec2>,
+    info: Option<String>,
+    children: Vec<LayoutNode>,
+}
+
+impl LayoutTree {
+    /// Describes `view`, drawn at `position` with the given size.
+    pub(crate) fn describe<V: View + ?Sized>(
+        view: &mut V, position: Vec2, size: Vec2, focused: bool,
+    ) -> LayoutNode {
+        let mut tree = LayoutTree {
+            position,
+            size,
+            focused,
+            required_size: None,
+            info: None,
+            children: Vec::new(),
+        };
+        view.debug_layout(&mut tree);
+
+        LayoutNode {
+            type_name: short_type_name(view.type_name()),
+            position,
+            size,
+            required_size: tree.required_size,
+            focused,
+            info: tree.info,
+            children: tree.children,
+        }
+    }
+
+    /// Describes a child of the current view.
+    ///
+    /// `offset` is where the child is drawn, relative to the current view,
+    /// and `size` is the size it was given. `focused` tells if the current
+    /// view gives it the focus.
+    pub fn child<V: View + ?Sized>(
+        &mut self, view: &mut V, offset: Vec2, size: Vec2, focused: bool,
+    ) {
+        let position = self.position + offset;
+        let focused = self.focused && focused;
+        let node = LayoutTree::describe(view, position, size, focused);
+        self.children.push(node);
+    }
+
+    /// Gives the size the current view asked for in the last layout.
+    ///
+    /// Views which cache it can give it here: asking them again could change
+    /// their state.
+    pub fn set_required_size(&mut self, size: Vec2) {
+        self.required_size = Some(size);
+    }
+
+    /// Adds some details about the current view.
+    pub fn set_info<S: Into<String>>(&mut self, info: S) {
+        self.info = Some(info.into());
+    }
+
+    /// Returns the size given to the current view.
+    pub fn size(&self) -> Vec2 {
+        self.size
+    }
+}
+
+/// Removes the module paths from a type name.
+///
+/// `cursive::views::BoxView<cursive::views::TextView>` becomes
+/// `BoxView<TextView>`.
+fn short_type_name(name: &str) -> String {
+    let mut short = String::with_capacity(name.len());
+    // Where the current path starts in `short`.
+    let mut start = 0;
+    let mut chars = name.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == ':' && chars.peek() == Some(&':') {
+            chars.next();
+            short.truncate(start);
+        } else {
+            short.push(c);
+            if !c.is_alphanumeric() && c != '_' {
+                start = short.len();
+            }
+        }
+    }
+
+    short
+}
+
+#[cfg(feature = "serde")]
+mod serialization {
+    use super::*;
+    use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+    impl Serialize for LayoutNode {
+        fn serialize<S: Serializer>(
+            &self, serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            let mut node = serializer.serialize_struct("LayoutNode", 7)?;
+            node.serialize_field("type_name", &self.type_name)?;
+            node.serialize_field("position", &self.position.pair())?;
+            node.serialize_field("size", &self.size.pair())?;
+            node.serialize_field(
+                "required_size",
+                &self.required_size.map(Vec2::pair),
+            )?;
+            node.serialize_field("focused", &self.focused)?;
+            node.serialize_field("info", &self.info)?;
+            node.serialize_field("children", &self.children)?;
+            node.end()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backend::puppet;
+    use traits::*;
+    use views::{Dialog, EditView, LinearLayout, ListView, TabView, TextView};
+    use Cursive;
+
+    #[test]
+    fn test_short_type_name() {
+        let name = "cursive::views::BoxView<cursive::views::IdView<Foo>>";
+        assert_eq!(short_type_name(name), "BoxView<IdView<Foo>>");
+        assert_eq!(short_type_name("dyn cursive::View"), "dyn View");
+    }
+
+    #[test]
+    fn test_dump_layout() {
+        let backend = puppet::Backend::init(Some(Vec2::new(30, 10)));
+        let mut siv = Cursive::new(move || backend);
+        siv.add_layer(
+            Dialog::around(
+                LinearLayout::vertical()
+                    .child(TextView::new("Hello"))
+                    .child(EditView::new().fixed_width(10)),
+            )
+            .button("Ok", |_| ()),
+        );
+        siv.refresh();
+
+        let root = siv.dump_layout();
+        assert_eq!(root.type_name, "StackView");
+        assert_eq!(root.size, Vec2::new(30, 10));
+        let dialog = &root.children[0];
+        assert_eq!(dialog.type_name, "Dialog");
+        assert!(dialog.focused);
+        assert_eq!(dialog.children.len(), 2);
+
+        let (layout, button) = (&dialog.children[0], &dialog.children[1]);
+        assert_eq!(layout.type_name, "LinearLayout");
+        assert!(layout.focused);
+        assert_eq!(button.type_name, "Button");
+        assert!(!button.focused);
+
+        // The edit view is below the text, in its `BoxView`.
+        let (text, boxed) = (&layout.children[0], &layout.children[1]);
+        assert_eq!(text.position, layout.position);
+        assert_eq!(text.size, Vec2::new(10, 1));
+        assert_eq!(text.required_size, Some(Vec2::new(5, 1)));
+        assert_eq!(boxed.type_name, "BoxView<EditView>");
+        assert_eq!(boxed.position, text.position + (0, 1));
+        let edit = &boxed.children[0];
+        assert_eq!(edit.type_name, "EditView");
+        assert_eq!((edit.size, edit.focused), (Vec2::new(10, 1), true));
+
+        let dump = root.to_string();
+        assert!(dump.starts_with("StackView at (0, 0), size 30x10, focused"));
+        assert!(dump.contains(
+            "\n      TextView at (10, 3), size 10x1, requires 5x1\n"
+        ));
+        assert!(dump.contains("\n        EditView at "));
+    }
+
+    #[test]
+    fn test_dump_containers() {
+        let backend = puppet::Backend::init(Some(Vec2::new(20, 6)));
+        let mut siv = Cursive::new(move || backend);
+        let list = ListView::new()
+            .child("Name", EditView::new())
+            .child("Age", EditView::new());
+        siv.add_fullscreen_layer(
+            TabView::new().tab("Form", list).full_screen(),
+        );
+        siv.refresh();
+
+        let root = siv.dump_layout();
+        let tabs = &root.children[0].children[0];
+        assert_eq!(tabs.info, Some("tab \"Form\"".to_string()));
+        let list = &tabs.children[0];
+        assert_eq!(
+            (list.position, list.size),
+            (Vec2::new(0, 1), (20, 5).into())
+        );
+        let age = &list.children[1];
+        assert_eq!(age.position, Vec2::new(5, 2));
+        assert_eq!((age.size, age.focused), (Vec2::new(15, 1), false));
+        assert!(list.children[0].focused);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let mut text = TextView::new("Hi");
+        let (position, size) = (Vec2::new(1, 2), Vec2::new(2, 1));
+        let node = LayoutTree::describe(&mut text, position, size, false);
+        let value = ::toml::Value::try_from(&node).unwrap();
+        assert_eq!(value["type_name"].as_str(), Some("TextView"));
+        assert_eq!(value["position"].as_array().unwrap().len(), 2);
+    }
+}
@@ -2,7 +2,7 @@ use std::ops::{Add, Div, Mul, Sub};
 use vec::Vec2;
 
 /// Four values representing each direction.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Margins {
     /// Left margin
     pub left: usize,
@@ -49,6 +49,28 @@ impl Margins {
     pub fn bot_right(&self) -> Vec2 {
         Vec2::new(self.right, self.bottom)
     }
+
+    /// Returns these margins, shrunk so `content` fits in `available`.
+    ///
+    /// Along each axis, the room left by `content` is shared between both
+    /// sides in proportion to their original values.
+    pub fn shrunk_to_fit(&self, available: Vec2, content: Vec2) -> Margins {
+        let room = available.saturating_sub(content);
+        let (left, right) = shrink(self.left, self.right, room.x);
+        let (top, bottom) = shrink(self.top, self.bottom, room.y);
+
+        Margins::new(left, right, top, bottom)
+    }
+}
+
+// Shares `room` between `a` and `b`, if they don't fit.
+fn shrink(a: usize, b: usize, room: usize) -> (usize, usize) {
+    if a + b <= room {
+        (a, b)
+    } else {
+        let a = room * a / (a + b);
+        (a, room - a)
+    }
 }
 
 impl From<(usize, usize, usize, usize)> for Margins {
@@ -41,6 +41,8 @@ mod view_wrapper;
 // Essentials components
 mod any;
 mod finder;
+mod focus_path;
+mod layout_tree;
 mod margins;
 mod position;
 mod size_cache;
@@ -49,6 +51,7 @@ mod view;
 mod view_path;
 
 // Helper bases
+mod accessible;
 mod boxable;
 mod identifiable;
 mod scroll;
@@ -56,14 +59,20 @@ mod scrollable;
 
 mod into_boxed_view;
 
+pub use self::accessible::Accessible;
 pub use self::any::AnyView;
 pub use self::boxable::Boxable;
 pub use self::finder::{Finder, Selector};
+pub use self::focus_path::FocusPath;
 pub use self::identifiable::Identifiable;
 pub use self::into_boxed_view::IntoBoxedView;
+pub use self::layout_tree::{LayoutNode, LayoutTree};
 pub use self::margins::Margins;
 pub use self::position::{Offset, Position};
-pub use self::scroll::{ScrollBase, ScrollStrategy};
+pub use self::scroll::{
+    HorizontalScrollStrategy, RevealStrategy, ScrollBase, ScrollPosition,
+    ScrollStrategy,
+};
 pub use self::scrollable::Scrollable;
 pub use self::size_cache::SizeCache;
 pub use self::size_constraint::SizeConstraint;
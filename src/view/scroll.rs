@@ -36,14 +36,20 @@ pub struct ScrollBase {
 }
 
 /// Defines the scrolling behaviour on content or size change
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ScrollStrategy {
     /// Keeps the same row number
+    ///
+    /// The important area of the content is still revealed when it moves.
     KeepRow,
     /// Sticks to the top.
     StickToTop,
     /// Sticks to the bottom of the view.
     StickToBottom,
+    /// Keeps the important area of the content in view, like the cursor.
+    KeepCursor,
+    /// Never moves on its own, not even when the important area moves.
+    Manual,
 }
 
 impl Default for ScrollStrategy {
@@ -52,6 +58,42 @@ impl Default for ScrollStrategy {
     }
 }
 
+/// Defines how far to scroll to reveal the important area of the content.
+///
+/// The important area is usually the selection, or the cursor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevealStrategy {
+    /// Scrolls just enough to bring it in view.
+    Nearest,
+    /// Scrolls to show it in the middle of the view.
+    Center,
+}
+
+/// Scroll position reported by [`ScrollView::set_on_scroll()`].
+///
+/// [`ScrollView::set_on_scroll()`]:
+/// ../views/struct.ScrollView.html#method.set_on_scroll
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScrollPosition {
+    /// Offset of the viewport in the content.
+    pub offset: Vec2,
+    /// `true` if the first row of the content is visible.
+    pub at_top: bool,
+    /// `true` if the last row of the content is visible.
+    pub at_bottom: bool,
+}
+
+/// Defines the horizontal scrolling behaviour on content or size change
+#[derive(Debug)]
+pub enum HorizontalScrollStrategy {
+    /// Keeps the same column number
+    KeepColumn,
+    /// Sticks to the left.
+    StickToLeft,
+    /// Sticks to the right of the view.
+    StickToRight,
+}
+
 impl ScrollBase {
     /// Creates a new, uninitialized scrollbar.
     pub fn new() -> Self {
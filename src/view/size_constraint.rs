@@ -1,4 +1,4 @@
-use std::cmp::min;
+use std::cmp::{max, min};
 
 /// Single-dimensional constraint on a view size.
 ///
@@ -17,6 +17,15 @@ pub enum SizeConstraint {
     AtMost(usize),
     /// Returns the maximum of the included value and the child view's size.
     AtLeast(usize),
+    /// Clamps the child view's size between a minimum and a maximum.
+    ///
+    /// If the minimum exceeds the maximum or the available space, the
+    /// minimum wins: the parent may then need to scroll.
+    Between(usize, usize),
+    /// Takes the given percentage of the available space.
+    ///
+    /// Values above 100 are treated as 100.
+    Percentage(u8),
 }
 
 impl SizeConstraint {
@@ -29,8 +38,11 @@ impl SizeConstraint {
             | SizeConstraint::Full
             | SizeConstraint::AtLeast(_) => available,
             // If the available space is too small, always give in.
-            SizeConstraint::Fixed(value) | SizeConstraint::AtMost(value) => {
-                min(value, available)
+            SizeConstraint::Fixed(value)
+            | SizeConstraint::AtMost(value)
+            | SizeConstraint::Between(_, value) => min(value, available),
+            SizeConstraint::Percentage(percent) => {
+                percentage(percent, available)
             }
         }
     }
@@ -43,8 +55,54 @@ impl SizeConstraint {
             SizeConstraint::AtLeast(value) if result < value => value,
             SizeConstraint::AtMost(value) if result > value => value,
             SizeConstraint::Fixed(value) => value,
-            SizeConstraint::Full => available,
+            // `available` already is the percentage of the parent's space.
+            SizeConstraint::Full | SizeConstraint::Percentage(_) => available,
+            SizeConstraint::Between(low, high) => max(min(result, high), low),
             _ => result,
         }
     }
 }
+
+/// Returns `percent`% of `available`, rounded to the nearest cell.
+///
+/// Ties round toward the larger share, so two complementary percentages
+/// (like 30 and 70) always add up to `available`. An odd space split 50/50
+/// leaves one cell free, rather than overflowing.
+fn percentage(percent: u8, available: usize) -> usize {
+    let percent = min(percent, 100) as usize;
+    let cells = percent * available / 100;
+    let rest = percent * available % 100;
+
+    if rest > 50 || (rest == 50 && percent > 50) {
+        cells + 1
+    } else {
+        cells
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentage() {
+        let constraint = SizeConstraint::Percentage(30);
+        assert_eq!(constraint.available(10), 3);
+        assert_eq!(constraint.result((1, 3)), 3);
+
+        assert_eq!(SizeConstraint::Percentage(0).available(10), 0);
+        assert_eq!(SizeConstraint::Percentage(200).available(10), 10);
+
+        // Complementary percentages fill the whole space.
+        for available in 0..50 {
+            for percent in (0..101).filter(|&percent| percent != 50) {
+                let a = percentage(percent, available);
+                let b = percentage(100 - percent, available);
+                assert_eq!(a + b, available, "{}% of {}", percent, available);
+            }
+        }
+
+        assert_eq!(percentage(50, 10), 5);
+        assert_eq!(percentage(50, 11), 5);
+    }
+}
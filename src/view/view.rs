@@ -3,7 +3,7 @@ use event::{AnyCb, Event, EventResult};
 use rect::Rect;
 use std::any::Any;
 use vec::Vec2;
-use view::{AnyView, Selector};
+use view::{AnyView, FocusPath, LayoutTree, Selector};
 use Printer;
 
 /// Main trait defining a view behaviour.
@@ -118,4 +118,32 @@ pub trait View: Any + AnyView {
     fn important_area(&self, view_size: Vec2) -> Rect {
         Rect::from_size((0, 0), view_size)
     }
+
+    /// Describes the children of this view, for debugging.
+    ///
+    /// This is used by [`Cursive::dump_layout()`]. Views with children
+    /// should give each of them to `tree.child()`, with the geometry they
+    /// were given in the last layout. Any view can also add some details
+    /// with `tree.set_info()`.
+    ///
+    /// Default implementation describes no child.
+    ///
+    /// [`Cursive::dump_layout()`]: ../struct.Cursive.html#method.dump_layout
+    fn debug_layout(&mut self, tree: &mut LayoutTree) {
+        let _ = tree;
+    }
+
+    /// Gives the child that has the focus to `path`, if any.
+    ///
+    /// This follows the focus without drawing, for example in
+    /// [`Cursive::call_on_focused()`]. Views with children should give the
+    /// focused one to `path.child()`, with the offset where it is drawn.
+    ///
+    /// Default implementation has no focused child.
+    ///
+    /// [`Cursive::call_on_focused()`]:
+    /// ../struct.Cursive.html#method.call_on_focused
+    fn follow_focus(&mut self, path: &mut FocusPath) {
+        let _ = path;
+    }
 }
@@ -3,7 +3,7 @@ use event::{AnyCb, Event, EventResult};
 use rect::Rect;
 use std::any::Any;
 use vec::Vec2;
-use view::{Selector, View};
+use view::{FocusPath, LayoutTree, Selector, View};
 use Printer;
 
 /// Generic wrapper around a view.
@@ -100,6 +100,21 @@ pub trait ViewWrapper: 'static {
         self.with_view(|v| v.important_area(size))
             .unwrap_or_else(|| Rect::from((0, 0)))
     }
+
+    /// Wraps the `debug_layout` method.
+    ///
+    /// The wrapped view is described as a child with the same size.
+    fn wrap_debug_layout(&mut self, tree: &mut LayoutTree) {
+        let size = tree.size();
+        self.with_view_mut(|v| tree.child(v, Vec2::zero(), size, true));
+    }
+
+    /// Wraps the `follow_focus` method.
+    ///
+    /// The wrapped view is the focused child.
+    fn wrap_follow_focus(&mut self, path: &mut FocusPath) {
+        self.with_view_mut(|v| path.child(v, Vec2::zero()));
+    }
 }
 
 // The main point of implementing ViewWrapper is to have View for free.
@@ -141,6 +156,14 @@ impl<T: ViewWrapper> View for T {
     fn important_area(&self, size: Vec2) -> Rect {
         self.wrap_important_area(size)
     }
+
+    fn debug_layout(&mut self, tree: &mut LayoutTree) {
+        self.wrap_debug_layout(tree)
+    }
+
+    fn follow_focus(&mut self, path: &mut FocusPath) {
+        self.wrap_follow_focus(path)
+    }
 }
 
 /// Convenient macro to implement the [`ViewWrapper`] trait.
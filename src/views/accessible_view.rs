@@ -0,0 +1,172 @@
+use vec::Vec2;
+use view::{FocusPath, View, ViewWrapper};
+use With;
+
+/// What a view is, for assistive tools.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Role {
+    /// Triggers an action when pressed.
+    Button,
+    /// Can be checked or unchecked.
+    Checkbox,
+    /// Groups some content with buttons.
+    Dialog,
+    /// Shows items to choose from.
+    List,
+    /// Shows a menu.
+    Menu,
+    /// Chooses a value in a range.
+    Slider,
+    /// Shows text.
+    Text,
+    /// Edits text.
+    TextInput,
+    /// Any other kind of view.
+    Other(String),
+}
+
+/// Description of a view, for assistive tools.
+///
+/// Returned by [`Cursive::focused_description()`].
+///
+/// [`Cursive::focused_description()`]:
+/// ../struct.Cursive.html#method.focused_description
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Description {
+    /// Name of the view, like "Username".
+    pub label: String,
+    /// Kind of view, if set.
+    pub role: Option<Role>,
+}
+
+/// Describes the wrapped view for assistive tools.
+///
+/// Screen readers can't make sense of terminal cells. This view gives a
+/// label and a role to the wrapped view, which
+/// [`Cursive::focused_description()`] reports while it has the focus.
+///
+/// Nothing is done while drawing on the screen: the description is given
+/// to [`View::follow_focus()`] visitors instead.
+///
+/// [`Cursive::focused_description()`]:
+/// ../struct.Cursive.html#method.focused_description
+/// [`View::follow_focus()`]: ../view/trait.View.html#method.follow_focus
+///
+/// # Examples
+///
+/// ```rust
+/// # use cursive::views::{EditView, Role};
+/// use cursive::view::Accessible;
+///
+/// let name = EditView::new().a11y_label("Name").a11y_role(Role::TextInput);
+/// ```
+pub struct AccessibleView<T> {
+    view: T,
+    description: Description,
+}
+
+impl<T> AccessibleView<T> {
+    /// Wraps `view`, labeled with `label`.
+    pub fn new<S: Into<String>>(view: T, label: S) -> Self {
+        AccessibleView {
+            view,
+            description: Description {
+                label: label.into(),
+                role: None,
+            },
+        }
+    }
+
+    /// Sets the label of the view.
+    pub fn set_a11y_label<S: Into<String>>(&mut self, label: S) {
+        self.description.label = label.into();
+    }
+
+    /// Sets the label of the view.
+    ///
+    /// Chainable variant.
+    pub fn a11y_label<S: Into<String>>(self, label: S) -> Self {
+        self.with(|s| s.set_a11y_label(label))
+    }
+
+    /// Sets the role of the view.
+    pub fn set_a11y_role(&mut self, role: Role) {
+        self.description.role = Some(role);
+    }
+
+    /// Sets the role of the view.
+    ///
+    /// Chainable variant.
+    pub fn a11y_role(self, role: Role) -> Self {
+        self.with(|s| s.set_a11y_role(role))
+    }
+
+    /// Returns the description of the view.
+    pub fn get_description(&self) -> &Description {
+        &self.description
+    }
+
+    inner_getters!(self.view: T);
+}
+
+impl<T: View> ViewWrapper for AccessibleView<T> {
+    wrap_impl!(self.view: T);
+
+    fn wrap_follow_focus(&mut self, path: &mut FocusPath) {
+        path.visit(&mut self.description);
+        path.child(&mut self.view, Vec2::zero());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backend::puppet;
+    use event::{Event, Key};
+    use traits::*;
+    use vec::Vec2;
+    use views::{Dialog, EditView, LinearLayout};
+    use Cursive;
+
+    fn labels(siv: &mut Cursive) -> Vec<String> {
+        siv.focused_description()
+            .into_iter()
+            .map(|description| description.label)
+            .collect()
+    }
+
+    #[test]
+    fn test_focused_description() {
+        let backend = puppet::Backend::init(Some(Vec2::new(30, 10)));
+        let mut siv = Cursive::new(move || backend);
+        let name = EditView::new()
+            .a11y_label("Name")
+            .a11y_role(Role::TextInput);
+        siv.add_layer(
+            Dialog::around(
+                LinearLayout::vertical()
+                    .child(name)
+                    .child(EditView::new().a11y_label("Email")),
+            )
+            .button("Ok", |_| ())
+            .a11y_label("Sign up")
+            .a11y_role(Role::Dialog),
+        );
+
+        let description = siv.focused_description();
+        assert_eq!(description.len(), 2);
+        assert_eq!(description[0].role, Some(Role::Dialog));
+        let expected = Description {
+            label: "Name".to_string(),
+            role: Some(Role::TextInput),
+        };
+        assert_eq!(description[1], expected);
+
+        siv.on_event(Event::Key(Key::Down));
+        assert_eq!(labels(&mut siv), vec!["Sign up", "Email"]);
+
+        // On the button, only the dialog is described.
+        siv.on_event(Event::Key(Key::Down));
+        assert_eq!(labels(&mut siv), vec!["Sign up"]);
+    }
+}
@@ -0,0 +1,221 @@
+use direction::Direction;
+use event::{Callback, Event, EventResult, Key, MouseButton, MouseEvent};
+use std::cell::RefCell;
+use std::cmp::max;
+use std::rc::Rc;
+use theme::ColorStyle;
+use unicode_width::UnicodeWidthStr;
+use vec::Vec2;
+use view::{Position, View};
+use views::EditView;
+use Printer;
+
+/// Returns the completions for the given content.
+pub type SuggestionProvider = Fn(&str) -> Vec<String>;
+
+// Only the first suggestions are shown.
+const MAX_SUGGESTIONS: usize = 10;
+
+/// Completion support for [`EditView`].
+///
+/// Suggestions are shown in a popup layer. Since this layer takes the focus,
+/// it forwards anything it doesn't handle back to the `EditView`. Once a
+/// completion is accepted, the popup closes and gives it to the focused
+/// `EditView` through `accepted`.
+///
+/// [`EditView`]: struct.EditView.html
+pub struct Autocomplete {
+    provider: Rc<SuggestionProvider>,
+    // Completion accepted in the popup, not yet applied to the content.
+    //
+    // The popup keeps a handle on it while it's open.
+    accepted: Rc<RefCell<Option<String>>>,
+}
+
+impl Autocomplete {
+    pub fn new(provider: Rc<SuggestionProvider>) -> Self {
+        Autocomplete {
+            provider,
+            accepted: Rc::new(RefCell::new(None)),
+        }
+    }
+
+    /// Returns the completion accepted since the last call, if any.
+    pub fn take_accepted(&self) -> Option<String> {
+        self.accepted.borrow_mut().take()
+    }
+
+    /// Returns `true` while the popup is shown.
+    pub fn is_open(&self) -> bool {
+        Rc::strong_count(&self.accepted) > 1
+    }
+
+    /// Returns a callback showing the suggestions for `content`.
+    ///
+    /// The popup is shown below the focused `EditView`, and is at least
+    /// `width` wide. Returns `None` if there is nothing to suggest.
+    pub fn popup(&self, content: &str, width: usize) -> Option<Callback> {
+        if content.is_empty() {
+            return None;
+        }
+
+        let mut items = (self.provider)(content);
+        items.retain(|item| item != content);
+        items.truncate(MAX_SUGGESTIONS);
+        if items.is_empty() {
+            return None;
+        }
+
+        let width = items.iter().map(|item| item.width()).fold(width, max);
+        let accepted = Rc::clone(&self.accepted);
+
+        Some(Callback::from_fn(move |s| {
+            // The `EditView` that asked for the popup has the focus.
+            let mut position = None;
+            s.follow_focus(&mut |view, offset| {
+                if view.is::<EditView>() {
+                    position = Some(offset);
+                }
+            });
+            let position = match position {
+                Some(position) => position + (0, 1),
+                None => return,
+            };
+
+            let popup = SuggestionPopup {
+                items: items.clone(),
+                focus: 0,
+                width,
+                accepted: Rc::clone(&accepted),
+            };
+            // Stick to the parent layer, like `SelectView` popups.
+            let layer = s.screen().offset() + (0, s.menubar_height());
+            let offset = position - layer.signed();
+            s.screen_mut().add_layer_at(Position::parent(offset), popup);
+        }))
+    }
+}
+
+/// Suggests completions from a fixed list.
+///
+/// Items starting with the content are suggested, ignoring case.
+pub fn list_provider(items: Vec<String>) -> Rc<SuggestionProvider> {
+    Rc::new(move |content: &str| {
+        let content = content.to_lowercase();
+        items
+            .iter()
+            .filter(|item| item.to_lowercase().starts_with(&content))
+            .cloned()
+            .collect()
+    })
+}
+
+/// Popup layer listing the suggestions.
+struct SuggestionPopup {
+    items: Vec<String>,
+    focus: usize,
+    width: usize,
+    accepted: Rc<RefCell<Option<String>>>,
+}
+
+impl SuggestionPopup {
+    fn accept(&self) -> EventResult {
+        *self.accepted.borrow_mut() = Some(self.items[self.focus].clone());
+        EventResult::with_cb(|s| {
+            s.pop_layer();
+            // The `EditView` has the focus again: it takes the completion.
+            let cb = s.call_on_focused(EditView::apply_completion);
+            if let Some(cb) = cb {
+                cb(s);
+            }
+        })
+    }
+
+    fn size(&self) -> Vec2 {
+        Vec2::new(self.width, self.items.len())
+    }
+
+    /// Closes the popup, then sends `event` to the `EditView`.
+    fn forward(event: Event) -> EventResult {
+        EventResult::with_cb(move |s| {
+            s.pop_layer();
+            s.on_event(event.clone());
+        })
+    }
+}
+
+impl View for SuggestionPopup {
+    fn draw(&self, printer: &Printer) {
+        for (i, item) in self.items.iter().enumerate() {
+            let style = if i == self.focus {
+                ColorStyle::highlight()
+            } else {
+                ColorStyle::primary()
+            };
+            printer.with_color(style, |printer| {
+                printer.print_hline((0, i), self.width, " ");
+                printer.print((0, i), item);
+            });
+        }
+    }
+
+    fn required_size(&mut self, _: Vec2) -> Vec2 {
+        self.size()
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        match event {
+            Event::Key(Key::Up) if self.focus > 0 => self.focus -= 1,
+            Event::Key(Key::Down) if self.focus + 1 < self.items.len() => {
+                self.focus += 1
+            }
+            Event::Key(Key::Up) | Event::Key(Key::Down) => (),
+            Event::Key(Key::Tab) | Event::Key(Key::Enter) => {
+                return self.accept();
+            }
+            // Not meant for the `EditView`.
+            Event::Refresh
+            | Event::WindowResize
+            | Event::FocusGained
+            | Event::FocusLost
+            | Event::Mouse {
+                event: MouseEvent::Hover,
+                ..
+            } => {
+                return EventResult::Ignored;
+            }
+            Event::Key(Key::Esc) => {
+                return EventResult::with_cb(|s| {
+                    s.pop_layer();
+                });
+            }
+            Event::Mouse {
+                event: MouseEvent::Press(MouseButton::Left),
+                position,
+                offset,
+            } if position.fits_in_rect(offset, self.size()) => {
+                if let Some(position) = position.checked_sub(offset) {
+                    self.focus = position.y;
+                    return self.accept();
+                }
+            }
+            Event::Mouse {
+                event, position, ..
+            } => {
+                // Back to absolute coordinates.
+                return SuggestionPopup::forward(Event::Mouse {
+                    event,
+                    position,
+                    offset: Vec2::zero(),
+                });
+            }
+            event => return SuggestionPopup::forward(event),
+        }
+
+        EventResult::Consumed(None)
+    }
+
+    fn take_focus(&mut self, _: Direction) -> bool {
+        true
+    }
+}
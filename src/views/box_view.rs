@@ -11,6 +11,8 @@ use XY;
 /// * Use **all** available size
 /// * Use **at most** a given size
 /// * Use **at least** a given size
+/// * Stay **between** a minimum and a maximum size
+/// * Use a **percentage** of the available size
 /// * Let the wrapped view decide.
 ///
 /// # Examples
@@ -197,6 +199,80 @@ impl<T: View> BoxView<T> {
         )
     }
 
+    /// Wraps `view` in a `BoxView` sized between `min_size` and `max_size`.
+    ///
+    /// On each axis, the minimum wins if it exceeds the maximum.
+    pub fn with_bounded_size<S, U>(min_size: S, max_size: U, view: T) -> Self
+    where
+        S: Into<Vec2>,
+        U: Into<Vec2>,
+    {
+        let (min_size, max_size) = (min_size.into(), max_size.into());
+
+        BoxView::new(
+            SizeConstraint::Between(min_size.x, max_size.x),
+            SizeConstraint::Between(min_size.y, max_size.y),
+            view,
+        )
+    }
+
+    /// Wraps `view` in a `BoxView` with a bounded width.
+    ///
+    /// The resulting width will stay between `min_width` and `max_width`.
+    pub fn with_bounded_width(
+        min_width: usize, max_width: usize, view: T,
+    ) -> Self {
+        BoxView::new(
+            SizeConstraint::Between(min_width, max_width),
+            SizeConstraint::Free,
+            view,
+        )
+    }
+
+    /// Wraps `view` in a `BoxView` with a bounded height.
+    ///
+    /// The resulting height will stay between `min_height` and `max_height`.
+    pub fn with_bounded_height(
+        min_height: usize, max_height: usize, view: T,
+    ) -> Self {
+        BoxView::new(
+            SizeConstraint::Free,
+            SizeConstraint::Between(min_height, max_height),
+            view,
+        )
+    }
+
+    /// Wraps `view` in a `BoxView` taking a percentage of the available size.
+    ///
+    /// The size is computed again whenever the parent's size changes.
+    pub fn with_percentage_size(width: u8, height: u8, view: T) -> Self {
+        BoxView::new(
+            SizeConstraint::Percentage(width),
+            SizeConstraint::Percentage(height),
+            view,
+        )
+    }
+
+    /// Wraps `view` in a `BoxView` taking a percentage of the available
+    /// width.
+    pub fn with_percentage_width(width: u8, view: T) -> Self {
+        BoxView::new(
+            SizeConstraint::Percentage(width),
+            SizeConstraint::Free,
+            view,
+        )
+    }
+
+    /// Wraps `view` in a `BoxView` taking a percentage of the available
+    /// height.
+    pub fn with_percentage_height(height: u8, view: T) -> Self {
+        BoxView::new(
+            SizeConstraint::Free,
+            SizeConstraint::Percentage(height),
+            view,
+        )
+    }
+
     /// Should be called anytime something changes.
     fn invalidate(&mut self) {
         self.invalidated = true;
@@ -252,7 +328,7 @@ mod tests {
 
     use vec::Vec2;
     use view::{Boxable, View};
-    use views::DummyView;
+    use views::{DummyView, LinearLayout};
 
     // No need to test `draw()` method as it's directly forwarded.
 
@@ -304,6 +380,56 @@ mod tests {
         assert_eq!(Vec2::new(5, 5), max_s.required_size(Vec2::new(10, 10)));
     }
 
+    #[test]
+    fn bounded_size() {
+        use views::TextView;
+
+        let mut bounded_w = TextView::new("abcdefgh").bounded_width(3, 5);
+
+        // Wraps at the maximum width.
+        assert_eq!(Vec2::new(5, 2), bounded_w.required_size(Vec2::new(10, 5)));
+        assert_eq!(Vec2::new(4, 2), bounded_w.required_size(Vec2::new(4, 5)));
+        // Available space below the minimum: the minimum wins.
+        assert_eq!(Vec2::new(3, 4), bounded_w.required_size(Vec2::new(2, 5)));
+
+        let mut short_w = TextView::new("a").bounded_width(3, 5);
+        assert_eq!(Vec2::new(3, 1), short_w.required_size(Vec2::new(10, 5)));
+
+        let mut bounded_h = DummyView.full_screen().bounded_height(2, 4);
+
+        assert_eq!(Vec2::new(1, 2), bounded_h.required_size(Vec2::new(1, 1)));
+        assert_eq!(Vec2::new(1, 3), bounded_h.required_size(Vec2::new(1, 3)));
+        assert_eq!(Vec2::new(1, 4), bounded_h.required_size(Vec2::new(1, 9)));
+
+        let mut bounded_s =
+            DummyView.full_screen().bounded_size((2, 3), (5, 6));
+
+        assert_eq!(Vec2::new(2, 3), bounded_s.required_size(Vec2::new(1, 1)));
+        assert_eq!(Vec2::new(4, 6), bounded_s.required_size(Vec2::new(4, 9)));
+        assert_eq!(Vec2::new(5, 6), bounded_s.required_size(Vec2::new(9, 9)));
+
+        // The minimum wins over the maximum.
+        let mut inverted =
+            DummyView.full_screen().bounded_size((5, 5), (2, 2));
+        assert_eq!(Vec2::new(5, 5), inverted.required_size(Vec2::new(9, 9)));
+    }
+
+    #[test]
+    fn percentage_size() {
+        let mut view = DummyView.percentage_size(30, 100);
+        assert_eq!(Vec2::new(3, 5), view.required_size(Vec2::new(10, 5)));
+        assert_eq!(Vec2::new(6, 9), view.required_size(Vec2::new(20, 9)));
+
+        let mut row = LinearLayout::horizontal()
+            .child(DummyView.percentage_width(30))
+            .child(DummyView.percentage_width(70));
+
+        for width in 1..40 {
+            let size = row.required_size(Vec2::new(width, 1));
+            assert_eq!(size.x, width);
+        }
+    }
+
     #[test]
     fn full_screen() {
         let mut full = DummyView.full_screen();
@@ -8,12 +8,52 @@ use Cursive;
 use Printer;
 use With;
 
+/// State of a [`Checkbox`](struct.Checkbox.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckboxState {
+    /// The box is empty.
+    Unchecked,
+    /// The box is checked.
+    Checked,
+    /// Neither checked nor unchecked, like a "select all" box over a
+    /// partial selection.
+    Indeterminate,
+}
+
+impl CheckboxState {
+    /// Returns `true` for `CheckboxState::Checked`.
+    pub fn is_checked(self) -> bool {
+        self == CheckboxState::Checked
+    }
+}
+
+impl From<bool> for CheckboxState {
+    fn from(checked: bool) -> Self {
+        if checked {
+            CheckboxState::Checked
+        } else {
+            CheckboxState::Unchecked
+        }
+    }
+}
+
+type OnChange = Fn(&mut Cursive, CheckboxState);
+
 /// Checkable box.
+///
+/// Besides checked and unchecked, it can be indeterminate, shown as `[-]`.
+/// By default, only code can make it indeterminate, and toggling it checks
+/// it: see [`set_cycle_indeterminate()`] to cycle through it instead.
+///
+/// [`set_cycle_indeterminate()`]: #method.set_cycle_indeterminate
 pub struct Checkbox {
-    checked: bool,
+    state: CheckboxState,
     enabled: bool,
 
-    on_change: Option<Rc<Fn(&mut Cursive, bool)>>,
+    // Toggling goes through the indeterminate state.
+    cycle_indeterminate: bool,
+
+    on_change: Option<Rc<OnChange>>,
 }
 
 new_default!(Checkbox);
@@ -24,14 +64,15 @@ impl Checkbox {
     /// Creates a new, unchecked checkbox.
     pub fn new() -> Self {
         Checkbox {
-            checked: false,
+            state: CheckboxState::Unchecked,
             enabled: true,
+            cycle_indeterminate: false,
             on_change: None,
         }
     }
 
     /// Sets a callback to be used when the state changes.
-    pub fn set_on_change<F: 'static + Fn(&mut Cursive, bool)>(
+    pub fn set_on_change<F: 'static + Fn(&mut Cursive, CheckboxState)>(
         &mut self, on_change: F,
     ) {
         self.on_change = Some(Rc::new(on_change));
@@ -40,16 +81,41 @@ impl Checkbox {
     /// Sets a callback to be used when the state changes.
     ///
     /// Chainable variant.
-    pub fn on_change<F: 'static + Fn(&mut Cursive, bool)>(
+    pub fn on_change<F: 'static + Fn(&mut Cursive, CheckboxState)>(
         self, on_change: F,
     ) -> Self {
         self.with(|s| s.set_on_change(on_change))
     }
 
+    /// Makes toggling go from unchecked to checked to indeterminate.
+    ///
+    /// Defaults to `false`: toggling goes between unchecked and checked,
+    /// and checks an indeterminate box.
+    pub fn set_cycle_indeterminate(&mut self, cycle: bool) {
+        self.cycle_indeterminate = cycle;
+    }
+
+    /// Makes toggling go from unchecked to checked to indeterminate.
+    ///
+    /// Chainable variant.
+    pub fn cycle_indeterminate(self, cycle: bool) -> Self {
+        self.with(|s| s.set_cycle_indeterminate(cycle))
+    }
+
     /// Toggles the checkbox state.
     pub fn toggle(&mut self) -> EventResult {
-        let checked = !self.checked;
-        self.set_checked(checked)
+        let state = match self.state {
+            CheckboxState::Unchecked => CheckboxState::Checked,
+            CheckboxState::Checked if self.cycle_indeterminate => {
+                CheckboxState::Indeterminate
+            }
+            CheckboxState::Checked => CheckboxState::Unchecked,
+            CheckboxState::Indeterminate if self.cycle_indeterminate => {
+                CheckboxState::Unchecked
+            }
+            CheckboxState::Indeterminate => CheckboxState::Checked,
+        };
+        self.set_state(state)
     }
 
     /// Check the checkbox.
@@ -68,7 +134,7 @@ impl Checkbox {
 
     /// Returns `true` if the checkbox is checked.
     pub fn is_checked(&self) -> bool {
-        self.checked
+        self.state.is_checked()
     }
 
     /// Uncheck the checkbox.
@@ -85,21 +151,52 @@ impl Checkbox {
         })
     }
 
+    /// Makes the checkbox indeterminate.
+    pub fn set_indeterminate(&mut self) -> EventResult {
+        self.set_state(CheckboxState::Indeterminate)
+    }
+
+    /// Makes the checkbox indeterminate.
+    ///
+    /// Chainable variant.
+    pub fn indeterminate(self) -> Self {
+        self.with(|s| {
+            s.set_indeterminate();
+        })
+    }
+
+    /// Returns `true` if the checkbox is indeterminate.
+    pub fn is_indeterminate(&self) -> bool {
+        self.state == CheckboxState::Indeterminate
+    }
+
     /// Sets the checkbox state.
     pub fn set_checked(&mut self, checked: bool) -> EventResult {
-        self.checked = checked;
+        self.set_state(checked.into())
+    }
+
+    /// Sets the checkbox state.
+    pub fn set_state(&mut self, state: CheckboxState) -> EventResult {
+        self.state = state;
         if let Some(ref on_change) = self.on_change {
             let on_change = Rc::clone(on_change);
-            EventResult::with_cb(move |s| on_change(s, checked))
+            EventResult::with_cb(move |s| on_change(s, state))
         } else {
             EventResult::Consumed(None)
         }
     }
 
+    /// Returns the checkbox state.
+    pub fn get_state(&self) -> CheckboxState {
+        self.state
+    }
+
     fn draw_internal(&self, printer: &Printer) {
         printer.print((0, 0), "[ ]");
-        if self.checked {
-            printer.print((1, 0), "X");
+        match self.state {
+            CheckboxState::Unchecked => (),
+            CheckboxState::Checked => printer.print((1, 0), "X"),
+            CheckboxState::Indeterminate => printer.print((1, 0), "-"),
         }
     }
 }
@@ -126,6 +223,10 @@ impl View for Checkbox {
     }
 
     fn on_event(&mut self, event: Event) -> EventResult {
+        if !self.enabled {
+            return EventResult::Ignored;
+        }
+
         match event {
             Event::Key(Key::Enter) | Event::Char(' ') => self.toggle(),
             Event::Mouse {
@@ -137,3 +238,52 @@ impl View for Checkbox {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backend::puppet;
+    use std::cell::RefCell;
+    use traits::Identifiable;
+
+    #[test]
+    fn test_states() {
+        let backend = puppet::Backend::init(Some(Vec2::new(3, 1)));
+        let mut siv = Cursive::new(move || backend);
+        let states = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::clone(&states);
+        siv.add_fullscreen_layer(
+            Checkbox::new()
+                .on_change(move |_, state| log.borrow_mut().push(state))
+                .with_id("check"),
+        );
+
+        let mut rows = vec![siv.dump_screen().remove(0)];
+        siv.on_event(Event::Char(' '));
+        rows.push(siv.dump_screen().remove(0));
+        siv.call_on_id("check", |view: &mut Checkbox| {
+            assert!(!view.is_indeterminate());
+            view.set_indeterminate();
+            assert!(view.is_indeterminate());
+        });
+        rows.push(siv.dump_screen().remove(0));
+        assert_eq!(rows, vec!["[ ]", "[X]", "[-]"]);
+
+        // Toggling an indeterminate box checks it.
+        siv.on_event(Event::Char(' '));
+        siv.on_event(Event::Char(' '));
+
+        // Unless it cycles through it.
+        siv.call_on_id("check", |view: &mut Checkbox| {
+            view.set_cycle_indeterminate(true)
+        });
+        for _ in 0..3 {
+            siv.on_event(Event::Char(' '));
+        }
+
+        use self::CheckboxState::*;
+        let expected =
+            [Checked, Checked, Unchecked, Checked, Indeterminate, Unchecked];
+        assert_eq!(&states.borrow()[..], &expected);
+    }
+}
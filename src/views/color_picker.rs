@@ -0,0 +1,524 @@
+use backend::ColorSupport;
+use direction::{Absolute, Direction};
+use event::{Callback, Event, EventResult, Key, MouseButton, MouseEvent};
+use std::rc::Rc;
+use theme::{BaseColor, Color, ColorStyle};
+use vec::Vec2;
+use view::View;
+use views::EditView;
+use With;
+use {Cursive, Printer};
+
+type SubmitCallback = Fn(&mut Cursive, Color);
+
+// Each color is drawn on a cell this wide.
+const CELL_WIDTH: usize = 2;
+// Dark and light base colors.
+const BASE_ROWS: usize = 2;
+const BASE_COLUMNS: usize = 8;
+// The 6x6x6 color cube, as 6 blocks of 6x6 colors, 3 blocks per row.
+const CUBE_ROWS: usize = 12;
+const CUBE_COLUMNS: usize = 18;
+
+const WIDTH: usize = CUBE_COLUMNS * CELL_WIDTH;
+// Label in front of the hex field.
+const HEX_LABEL: &str = "Hex: ";
+
+/// View to pick a color from a palette.
+///
+/// It shows the 16 base colors and the 216 colors from the 256-color cube.
+/// The arrow keys or the mouse select a color, and `<Enter>` submits it.
+///
+/// A color can also be typed in the field at the bottom, using any format
+/// accepted by [`Color::parse`], like `#ff8000`.
+///
+/// The selected color is previewed below the palette.
+///
+/// Give it the terminal's [`color_support`] to only show the colors it
+/// can display: the cube is hidden on 16-color terminals, and the hex field
+/// without true colors.
+///
+/// [`Color::parse`]: ../theme/enum.Color.html#method.parse
+/// [`color_support`]: #method.set_color_support
+///
+/// # Examples
+///
+/// ```rust
+/// # use cursive::Cursive;
+/// # use cursive::views::{ColorPicker, Dialog};
+/// # let siv = Cursive::dummy();
+/// let picker = ColorPicker::new()
+///     .color_support(siv.color_support())
+///     .on_submit(|s, color| {
+///         s.pop_layer();
+///         s.add_layer(Dialog::info(format!("You picked {:?}", color)));
+///     });
+/// let dialog = Dialog::around(picker).title("Pick a color");
+/// ```
+pub struct ColorPicker {
+    // Selected cell in the palette.
+    cursor: Vec2,
+    color: Color,
+    edit: EditView,
+    edit_focused: bool,
+    on_submit: Option<Rc<SubmitCallback>>,
+    enabled: bool,
+    // Decides which parts of the palette are shown.
+    color_support: ColorSupport,
+}
+
+impl ColorPicker {
+    /// Creates a new `ColorPicker`, with black selected.
+    pub fn new() -> Self {
+        ColorPicker {
+            cursor: Vec2::zero(),
+            color: color_at(Vec2::zero()),
+            edit: EditView::new().content(hex(color_at(Vec2::zero()))),
+            edit_focused: false,
+            on_submit: None,
+            enabled: true,
+            color_support: ColorSupport::TrueColor,
+        }
+    }
+
+    impl_enabled!(self.enabled);
+
+    /// Sets a callback to run when a color is submitted.
+    pub fn set_on_submit<F>(&mut self, cb: F)
+    where
+        F: Fn(&mut Cursive, Color) + 'static,
+    {
+        self.on_submit = Some(Rc::new(cb));
+    }
+
+    /// Sets a callback to run when a color is submitted.
+    ///
+    /// Chainable variant.
+    pub fn on_submit<F>(self, cb: F) -> Self
+    where
+        F: Fn(&mut Cursive, Color) + 'static,
+    {
+        self.with(|s| s.set_on_submit(cb))
+    }
+
+    /// Selects the given color.
+    ///
+    /// If the color is in the palette, it also moves the cursor there.
+    pub fn set_color(&mut self, color: Color) {
+        if let Some(cursor) = self.find(color) {
+            self.cursor = cursor;
+        }
+        self.color = color;
+        self.edit.set_content(hex(color));
+    }
+
+    /// Selects the given color.
+    ///
+    /// Chainable variant.
+    pub fn color(self, color: Color) -> Self {
+        self.with(|s| s.set_color(color))
+    }
+
+    /// Returns the selected color.
+    pub fn get_color(&self) -> Color {
+        self.color
+    }
+
+    /// Sets the colors the terminal can display.
+    ///
+    /// Only the base colors are shown with `ColorSupport::Ansi16` or less,
+    /// and the hex field needs `ColorSupport::TrueColor`. Defaults to
+    /// `ColorSupport::TrueColor`: everything is shown.
+    ///
+    /// `Cursive::color_support()` gives the value for the current terminal.
+    pub fn set_color_support(&mut self, support: ColorSupport) {
+        self.color_support = support;
+
+        // The palette may have shrunk.
+        if self.cursor.y >= self.rows() {
+            self.select(Vec2::zero());
+        }
+        if !self.has_hex_field() {
+            self.edit_focused = false;
+        }
+    }
+
+    /// Sets the colors the terminal can display.
+    ///
+    /// Chainable variant.
+    pub fn color_support(self, support: ColorSupport) -> Self {
+        self.with(|s| s.set_color_support(support))
+    }
+
+    fn has_cube(&self) -> bool {
+        match self.color_support {
+            ColorSupport::Ansi256 | ColorSupport::TrueColor => true,
+            ColorSupport::None | ColorSupport::Ansi16 => false,
+        }
+    }
+
+    fn has_hex_field(&self) -> bool {
+        self.color_support == ColorSupport::TrueColor
+    }
+
+    /// Number of palette rows currently shown.
+    fn rows(&self) -> usize {
+        if self.has_cube() {
+            BASE_ROWS + CUBE_ROWS
+        } else {
+            BASE_ROWS
+        }
+    }
+
+    /// Row of the preview, after the palette.
+    fn preview_row(&self) -> usize {
+        if self.has_cube() {
+            BASE_ROWS + CUBE_ROWS + 2
+        } else {
+            BASE_ROWS + 1
+        }
+    }
+
+    fn find(&self, color: Color) -> Option<Vec2> {
+        (0..self.rows())
+            .flat_map(|y| (0..row_len(y)).map(move |x| Vec2::new(x, y)))
+            .find(|&cell| color_at(cell) == color)
+    }
+
+    /// Returns the palette cell at the given position, if any.
+    fn cell_at(&self, position: Vec2) -> Option<Vec2> {
+        let y = match position.y {
+            y if y < BASE_ROWS => y,
+            // The cube is one row lower.
+            y if y > BASE_ROWS && y <= self.rows() => y - 1,
+            _ => return None,
+        };
+
+        let x = position.x / CELL_WIDTH;
+        if x < row_len(y) {
+            Some(Vec2::new(x, y))
+        } else {
+            None
+        }
+    }
+
+    fn select(&mut self, cursor: Vec2) -> EventResult {
+        self.cursor = cursor;
+        self.color = color_at(cursor);
+        self.edit.set_content(hex(self.color));
+        EventResult::Consumed(None)
+    }
+
+    fn submit(&self) -> EventResult {
+        let color = self.color;
+        EventResult::Consumed(self.on_submit.clone().map(|cb| {
+            Callback::from_fn(move |s| {
+                cb(s, color);
+            })
+        }))
+    }
+
+    fn on_grid_event(&mut self, event: Event) -> EventResult {
+        let Vec2 { x, y } = self.cursor;
+
+        match event {
+            Event::Key(Key::Left) if x > 0 => {
+                self.select(Vec2::new(x - 1, y))
+            }
+            Event::Key(Key::Right) if x + 1 < row_len(y) => {
+                self.select(Vec2::new(x + 1, y))
+            }
+            Event::Key(Key::Up) if y > 0 => {
+                let x = x.min(row_len(y - 1) - 1);
+                self.select(Vec2::new(x, y - 1))
+            }
+            Event::Key(Key::Down) if y + 1 < self.rows() => {
+                let x = x.min(row_len(y + 1) - 1);
+                self.select(Vec2::new(x, y + 1))
+            }
+            Event::Key(Key::Down) if self.has_hex_field() => {
+                self.edit_focused = true;
+                EventResult::Consumed(None)
+            }
+            Event::Key(Key::Enter) => self.submit(),
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn on_edit_event(&mut self, event: Event) -> EventResult {
+        match event {
+            Event::Key(Key::Up) => {
+                self.edit_focused = false;
+                EventResult::Consumed(None)
+            }
+            Event::Key(Key::Enter) => match Color::parse(&self.content()) {
+                Some(color) => {
+                    self.color = color;
+                    self.submit()
+                }
+                None => EventResult::Consumed(None),
+            },
+            event => {
+                let result = self.edit.on_event(event);
+                if let Some(color) = Color::parse(&self.content()) {
+                    self.color = color;
+                }
+                result
+            }
+        }
+    }
+
+    fn content(&self) -> String {
+        self.edit.get_content().trim().to_string()
+    }
+}
+
+impl Default for ColorPicker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl View for ColorPicker {
+    fn draw(&self, printer: &Printer) {
+        let focused = printer.focused && self.enabled;
+        for y in 0..self.rows() {
+            // Leave an empty row between the base colors and the cube.
+            let row = if y < BASE_ROWS { y } else { y + 1 };
+            for x in 0..row_len(y) {
+                let cell = Vec2::new(x, y);
+                let color = color_at(cell);
+                let text = match (cell == self.cursor, focused) {
+                    (true, true) if !self.edit_focused => "[]",
+                    (true, _) => "()",
+                    (false, _) => "  ",
+                };
+                let style = ColorStyle::new(contrast(color), color);
+                printer.with_color(style, |printer| {
+                    printer.print((x * CELL_WIDTH, row), text);
+                });
+            }
+        }
+
+        let row = self.preview_row();
+        printer.print((0, row), "Preview: ");
+        let style = ColorStyle::new(self.color, self.color);
+        printer.with_color(style, |printer| {
+            printer.print((9, row), "      ");
+        });
+        printer.print((16, row), &hex(self.color));
+
+        if self.has_hex_field() {
+            printer.print((0, row + 1), HEX_LABEL);
+            self.edit.draw(
+                &printer
+                    .offset((HEX_LABEL.len(), row + 1))
+                    .cropped((WIDTH - HEX_LABEL.len(), 1))
+                    .focused(focused && self.edit_focused),
+            );
+        }
+    }
+
+    fn layout(&mut self, _: Vec2) {
+        self.edit.layout(Vec2::new(WIDTH - HEX_LABEL.len(), 1));
+    }
+
+    fn required_size(&mut self, _: Vec2) -> Vec2 {
+        let height = if self.has_hex_field() {
+            self.preview_row() + 2
+        } else {
+            self.preview_row() + 1
+        };
+
+        Vec2::new(WIDTH, height)
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        if !self.enabled {
+            return EventResult::Ignored;
+        }
+
+        let edit_row = self.preview_row() + 1;
+        match event {
+            Event::Mouse {
+                event: MouseEvent::Press(MouseButton::Left),
+                position,
+                offset,
+            } => match position.checked_sub(offset) {
+                Some(position) => match self.cell_at(position) {
+                    Some(cell) => {
+                        self.edit_focused = false;
+                        self.select(cell)
+                    }
+                    None if self.has_hex_field() && position.y == edit_row => {
+                        self.edit_focused = true;
+                        self.on_edit_event(
+                            event.relativized((HEX_LABEL.len(), edit_row)),
+                        )
+                    }
+                    None => EventResult::Ignored,
+                },
+                None => EventResult::Ignored,
+            },
+            Event::Mouse { .. } if self.edit_focused => self.on_edit_event(
+                event.relativized((HEX_LABEL.len(), edit_row)),
+            ),
+            event if self.edit_focused => self.on_edit_event(event),
+            event => self.on_grid_event(event),
+        }
+    }
+
+    fn take_focus(&mut self, source: Direction) -> bool {
+        if self.enabled {
+            // Coming from below: start with the hex field.
+            self.edit_focused = self.has_hex_field()
+                && source == Direction::Abs(Absolute::Down);
+        }
+        self.enabled
+    }
+}
+
+/// Number of colors in the given palette row.
+fn row_len(y: usize) -> usize {
+    if y < BASE_ROWS {
+        BASE_COLUMNS
+    } else {
+        CUBE_COLUMNS
+    }
+}
+
+/// Returns the color of the given palette cell.
+fn color_at(cell: Vec2) -> Color {
+    let Vec2 { x, y } = cell;
+
+    if y < BASE_ROWS {
+        let base = BaseColor::from(x as u8);
+        return if y == 0 { base.dark() } else { base.light() };
+    }
+
+    let y = y - BASE_ROWS;
+    let red = (y / 6) * 3 + x / 6;
+    let (green, blue) = (y % 6, x % 6);
+    Color::from_256colors((16 + red * 36 + green * 6 + blue) as u8)
+}
+
+/// Returns black or white, whichever is more readable on `color`.
+fn contrast(color: Color) -> Color {
+    match color.to_rgb() {
+        Some((r, g, b)) => {
+            let luma = 299 * u32::from(r) + 587 * u32::from(g)
+                + 114 * u32::from(b);
+            if luma > 128_000 {
+                Color::Dark(BaseColor::Black)
+            } else {
+                Color::Light(BaseColor::White)
+            }
+        }
+        None => Color::TerminalDefault,
+    }
+}
+
+fn hex(color: Color) -> String {
+    match color.to_rgb() {
+        Some((r, g, b)) => format!("#{:02x}{:02x}{:02x}", r, g, b),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backend;
+    use std::cell::RefCell;
+    use views::BoxView;
+
+    #[test]
+    fn test_palette() {
+        assert_eq!(color_at(Vec2::new(1, 0)), Color::Dark(BaseColor::Red));
+        assert_eq!(color_at(Vec2::new(7, 1)), Color::Light(BaseColor::White));
+        assert_eq!(color_at(Vec2::new(0, 2)), Color::RgbLowRes(0, 0, 0));
+        assert_eq!(color_at(Vec2::new(17, 7)), Color::RgbLowRes(2, 5, 5));
+        assert_eq!(color_at(Vec2::new(17, 13)), Color::RgbLowRes(5, 5, 5));
+
+        // Every cube color appears once.
+        let picker = ColorPicker::new();
+        for n in 16..232 {
+            let color = Color::from_256colors(n);
+            let cell = picker.find(color).unwrap();
+            assert_eq!(color_at(cell), color);
+        }
+    }
+
+    #[test]
+    fn test_select() {
+        let backend = backend::puppet::Backend::init(Some(Vec2::new(40, 20)));
+        let mut siv = Cursive::new(move || backend);
+
+        let picked = Rc::new(RefCell::new(Vec::new()));
+        let result = Rc::clone(&picked);
+        siv.add_fullscreen_layer(BoxView::with_full_screen(
+            ColorPicker::new().on_submit(move |_, color| {
+                result.borrow_mut().push(color);
+            }),
+        ));
+        siv.dump_screen();
+
+        siv.on_event(Event::Key(Key::Right));
+        siv.on_event(Event::Key(Key::Down));
+        siv.on_event(Event::Key(Key::Enter));
+
+        // Click on the cube.
+        siv.on_event(Event::Mouse {
+            offset: Vec2::zero(),
+            position: Vec2::new(35, 3),
+            event: MouseEvent::Press(MouseButton::Left),
+        });
+        siv.on_event(Event::Key(Key::Enter));
+
+        // Type a color in the hex field.
+        siv.on_event(Event::Mouse {
+            offset: Vec2::zero(),
+            position: Vec2::new(10, 17),
+            event: MouseEvent::Press(MouseButton::Left),
+        });
+        siv.on_event(Event::Key(Key::End));
+        for _ in 0..7 {
+            siv.on_event(Event::Key(Key::Backspace));
+        }
+        for c in "#ff8000".chars() {
+            siv.on_event(Event::Char(c));
+        }
+        let screen = siv.dump_screen();
+        assert!(screen[16].contains("#ff8000"));
+        siv.on_event(Event::Key(Key::Enter));
+
+        assert_eq!(
+            &picked.borrow()[..],
+            &[
+                Color::Light(BaseColor::Red),
+                Color::RgbLowRes(2, 0, 5),
+                Color::Rgb(255, 128, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_color_support() {
+        let mut picker = ColorPicker::new().color(Color::RgbLowRes(5, 5, 5));
+        assert_eq!(picker.required_size(Vec2::zero()), Vec2::new(WIDTH, 18));
+
+        // No hex field.
+        picker.set_color_support(ColorSupport::Ansi256);
+        assert_eq!(picker.required_size(Vec2::zero()), Vec2::new(WIDTH, 17));
+        assert_eq!(picker.cursor, Vec2::new(17, 13));
+
+        // Only the base colors.
+        picker.set_color_support(ColorSupport::Ansi16);
+        assert_eq!(picker.required_size(Vec2::zero()), Vec2::new(WIDTH, 4));
+        assert_eq!(picker.cursor, Vec2::zero());
+        picker.on_event(Event::Key(Key::Down));
+        picker.on_event(Event::Key(Key::Down));
+        assert_eq!(picker.cursor, Vec2::new(0, 1));
+        assert!(!picker.edit_focused);
+    }
+}
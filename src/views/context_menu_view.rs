@@ -0,0 +1,180 @@
+use direction::Direction;
+use event::{Event, EventResult, MouseButton, MouseEvent};
+use menu::MenuTree;
+use std::rc::Rc;
+use vec::Vec2;
+use view::{Position, View, ViewWrapper};
+use views::MenuPopup;
+
+/// Shows a menu when the wrapped view is right-clicked.
+///
+/// The menu opens at the mouse position, moved if needed to fit on the
+/// screen. Like the menubar, it closes when an item is selected, on `Esc`
+/// or when clicking outside.
+///
+/// # Examples
+///
+/// ```rust
+/// # use cursive::menu::MenuTree;
+/// # use cursive::views::{ContextMenuView, TextView};
+/// let view = ContextMenuView::new(
+///     TextView::new("Right-click me"),
+///     MenuTree::new()
+///         .leaf("Copy", |_| ())
+///         .subtree("Sort", MenuTree::new().leaf("By name", |_| ())),
+/// );
+/// ```
+pub struct ContextMenuView<T> {
+    view: T,
+    menu: Rc<MenuTree>,
+    last_size: Vec2,
+}
+
+impl<T> ContextMenuView<T> {
+    /// Wraps `view`, showing `menu` when it is right-clicked.
+    pub fn new(view: T, menu: MenuTree) -> Self {
+        ContextMenuView {
+            view,
+            menu: Rc::new(menu),
+            last_size: Vec2::zero(),
+        }
+    }
+
+    /// Replaces the menu shown on right-click.
+    pub fn set_menu(&mut self, menu: MenuTree) {
+        self.menu = Rc::new(menu);
+    }
+
+    /// Returns the menu shown on right-click.
+    pub fn get_menu(&self) -> &MenuTree {
+        &self.menu
+    }
+
+    fn open_menu(&self, position: Vec2) -> EventResult {
+        let menu = Rc::clone(&self.menu);
+        EventResult::with_cb(move |s| {
+            // The stack starts below the menubar, when it's shown.
+            let position = position.saturating_sub((0, s.menubar_height()));
+            s.screen_mut().add_layer_at(
+                Position::absolute(position),
+                MenuPopup::new(Rc::clone(&menu)),
+            );
+        })
+    }
+}
+
+impl<T: View> ViewWrapper for ContextMenuView<T> {
+    wrap_impl!(self.view: T);
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        match event {
+            Event::Mouse {
+                event: MouseEvent::Press(MouseButton::Right),
+                position,
+                offset,
+            } if !self.menu.is_empty()
+                && position.fits_in_rect(offset, self.last_size) =>
+            {
+                self.open_menu(position)
+            }
+            event => self.view.on_event(event),
+        }
+    }
+
+    fn wrap_take_focus(&mut self, source: Direction) -> bool {
+        // Clicks only reach focused views, so accept the focus they bring.
+        self.view.take_focus(source)
+            || (source == Direction::none() && !self.menu.is_empty())
+    }
+
+    fn wrap_layout(&mut self, size: Vec2) {
+        self.last_size = size;
+        self.view.layout(size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backend;
+    use event::Key;
+    use std::cell::Cell;
+    use views::{BoxView, EditView, LinearLayout, TextView};
+    use Cursive;
+
+    fn click(siv: &mut Cursive, button: MouseButton, x: usize, y: usize) {
+        for &event in &[MouseEvent::Press(button), MouseEvent::Release(button)]
+        {
+            siv.on_event(Event::Mouse {
+                offset: Vec2::zero(),
+                position: Vec2::new(x, y),
+                event,
+            });
+        }
+    }
+
+    #[test]
+    fn test_context_menu() {
+        let backend = backend::puppet::Backend::init(Some(Vec2::new(20, 6)));
+        let mut siv = Cursive::new(move || backend);
+
+        let copied = Rc::new(Cell::new(0));
+        let c = Rc::clone(&copied);
+        siv.add_fullscreen_layer(ContextMenuView::new(
+            BoxView::with_full_screen(TextView::new("Hello")),
+            MenuTree::new()
+                .leaf("Copy", move |_| c.set(c.get() + 1))
+                .leaf("Paste", |_| ()),
+        ));
+        siv.dump_screen();
+
+        click(&mut siv, MouseButton::Right, 2, 0);
+        let screen = siv.dump_screen();
+        assert_eq!(&screen[0][2..], "┌───────┐         ");
+        assert_eq!(&screen[1][2..], "│ Copy  │         ");
+
+        // Selecting an item closes the menu.
+        click(&mut siv, MouseButton::Left, 4, 1);
+        assert_eq!(copied.get(), 1);
+        assert_eq!(siv.screen().len(), 1);
+
+        // Near the corner, the menu is moved to fit on the screen.
+        click(&mut siv, MouseButton::Right, 18, 5);
+        let screen = siv.dump_screen();
+        assert!(screen[1].ends_with("┌───────┐ "));
+        assert!(screen[4].ends_with("└───────┘ "));
+
+        siv.on_event(Event::Key(Key::Esc));
+        assert_eq!(siv.screen().len(), 1);
+
+        // Clicking outside also closes it.
+        click(&mut siv, MouseButton::Right, 2, 0);
+        click(&mut siv, MouseButton::Left, 15, 0);
+        assert_eq!(siv.screen().len(), 1);
+        assert_eq!(copied.get(), 1);
+    }
+
+    #[test]
+    fn test_context_menu_without_focus() {
+        let backend = backend::puppet::Backend::init(Some(Vec2::new(20, 6)));
+        let mut siv = Cursive::new(move || backend);
+        siv.menubar().add_leaf("File", |_| ());
+        siv.set_autohide_menu(false);
+
+        // The edit view has the focus, and the menu opens anyway.
+        siv.add_fullscreen_layer(
+            LinearLayout::vertical().child(EditView::new()).child(
+                ContextMenuView::new(
+                    TextView::new("Hello"),
+                    MenuTree::new().leaf("Copy", |_| ()),
+                ),
+            ),
+        );
+        siv.dump_screen();
+
+        click(&mut siv, MouseButton::Right, 2, 2);
+        let screen = siv.dump_screen();
+        assert_eq!(screen[2].trim(), "He┌──────┐");
+        assert_eq!(screen[3].trim(), "│ Copy │");
+    }
+}
@@ -1,13 +1,13 @@
 use align::*;
 use direction::{Absolute, Direction, Relative};
-use event::{AnyCb, Event, EventResult, Key};
+use event::{AnyCb, Event, EventResult, Key, MouseEvent};
 use rect::Rect;
 use std::cell::Cell;
-use std::cmp::max;
-use theme::ColorStyle;
+use std::cmp::{max, min};
+use theme::BorderStyle;
 use unicode_width::UnicodeWidthStr;
 use vec::Vec2;
-use view::{Margins, Selector, View};
+use view::{FocusPath, LayoutTree, Margins, Selector, View};
 use views::{Button, DummyView, SizedView, TextView, ViewBox};
 use Cursive;
 use Printer;
@@ -27,6 +27,8 @@ pub enum DialogFocus {
 struct ChildButton {
     button: SizedView<Button>,
     offset: Cell<Vec2>,
+    // Label given to `add_button()`, without the brackets.
+    label: String,
 }
 
 impl ChildButton {
@@ -34,13 +36,21 @@ impl ChildButton {
     where
         F: 'static + Fn(&mut Cursive),
     {
+        let label = label.into();
         ChildButton {
-            button: SizedView::new(Button::new(label, cb)),
+            button: SizedView::new(Button::new(label.clone(), cb)),
             offset: Cell::new(Vec2::zero()),
+            label,
         }
     }
 }
 
+// Button focused first, and triggered by `Enter` from the content.
+enum DefaultButton {
+    Index(usize),
+    Label(String),
+}
+
 /// Popup-like view with a main content, and optional buttons under it.
 ///
 /// # Examples
@@ -67,12 +77,26 @@ pub struct Dialog {
     // Padding around the inner view.
     padding: Margins,
 
+    // Padding actually used, shrunk if the dialog is too small.
+    layout_padding: Margins,
+
+    // Where to put the content, if it doesn't fill the dialog.
+    content_align: Option<Align>,
+
+    // Position of the content, from the last layout.
+    content_offset: Vec2,
+
     // Borders around everything.
     borders: Margins,
 
+    // Overrides the theme's border style.
+    border_style: Option<BorderStyle>,
+
     // The current element in focus
     focus: DialogFocus,
 
+    default_button: Option<DefaultButton>,
+
     // How to align the buttons under the view.
     align: Align,
 
@@ -98,8 +122,13 @@ impl Dialog {
             title: String::new(),
             title_position: HAlign::Center,
             focus: DialogFocus::Content,
+            default_button: None,
             padding: Margins::new(1, 1, 0, 0),
+            layout_padding: Margins::new(1, 1, 0, 0),
+            content_align: None,
+            content_offset: Vec2::new(2, 1),
             borders: Margins::new(1, 1, 1, 1),
+            border_style: None,
             align: Align::top_right(),
             invalidated: true,
         }
@@ -173,6 +202,65 @@ impl Dialog {
         self.invalidate();
     }
 
+    /// Sets the default button, by index.
+    ///
+    /// When the content cannot take the focus, the dialog starts with the
+    /// default button focused, instead of the first one. While the content
+    /// is focused, `Enter` triggers the default button if the content
+    /// ignores it.
+    ///
+    /// This is kept when buttons are removed or added. It has no effect
+    /// while there is no button at this index.
+    pub fn set_default_button(&mut self, i: usize) {
+        self.default_button = Some(DefaultButton::Index(i));
+    }
+
+    /// Sets the default button, by index.
+    ///
+    /// Chainable variant.
+    pub fn default_button(self, i: usize) -> Self {
+        self.with(|s| s.set_default_button(i))
+    }
+
+    /// Sets the default button, by label.
+    ///
+    /// Like [`set_default_button()`], using the first button with this
+    /// label.
+    ///
+    /// [`set_default_button()`]: #method.set_default_button
+    pub fn set_default_button_label<S: Into<String>>(&mut self, label: S) {
+        self.default_button = Some(DefaultButton::Label(label.into()));
+    }
+
+    /// Sets the default button, by label.
+    ///
+    /// Chainable variant.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cursive::views::Dialog;
+    /// // Don't delete anything by mistake.
+    /// let dialog = Dialog::text("Delete this file?")
+    ///     .button("Delete", |s| s.quit())
+    ///     .dismiss_button("Cancel")
+    ///     .default_button_label("Cancel");
+    /// ```
+    pub fn default_button_label<S: Into<String>>(self, label: S) -> Self {
+        self.with(|s| s.set_default_button_label(label))
+    }
+
+    // Index of the default button, if it exists.
+    fn default_button_id(&self) -> Option<usize> {
+        match self.default_button {
+            Some(DefaultButton::Index(i)) if i < self.buttons.len() => Some(i),
+            Some(DefaultButton::Label(ref label)) => {
+                self.buttons.iter().position(|b| b.label == *label)
+            }
+            _ => None,
+        }
+    }
+
     /// Returns the number of buttons on this dialog.
     pub fn buttons_len(&self) -> usize {
         self.buttons.len()
@@ -248,6 +336,26 @@ impl Dialog {
         self.title_position = align;
     }
 
+    /// Sets the style of the border, instead of the theme's.
+    pub fn set_border(&mut self, style: BorderStyle) {
+        self.border_style = Some(style);
+    }
+
+    /// Sets the style of the border, instead of the theme's.
+    ///
+    /// Chainable variant.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cursive::theme::BorderStyle;
+    /// # use cursive::views::Dialog;
+    /// let dialog = Dialog::text("Hello!").border(BorderStyle::Rounded);
+    /// ```
+    pub fn border(self, style: BorderStyle) -> Self {
+        self.with(|s| s.set_border(style))
+    }
+
     /// Sets the padding in the dialog (around content and buttons).
     pub fn padding<T: Into<Margins>>(mut self, padding: T) -> Self {
         self.padding = padding.into();
@@ -279,6 +387,21 @@ impl Dialog {
         self
     }
 
+    /// Gives the content only the size it requires, placed with `align`.
+    ///
+    /// By default, the content takes all the space above the buttons.
+    pub fn set_content_align(&mut self, align: Align) {
+        self.content_align = Some(align);
+        self.invalidate();
+    }
+
+    /// Gives the content only the size it requires, placed with `align`.
+    ///
+    /// Chainable variant.
+    pub fn content_align(self, align: Align) -> Self {
+        self.with(|s| s.set_content_align(align))
+    }
+
     /// Returns an iterator on this buttons for this dialog.
     pub fn buttons_mut(&mut self) -> impl Iterator<Item = &mut Button> {
         self.invalidate();
@@ -294,9 +417,10 @@ impl Dialog {
 
     // An event is received while the content is in focus
     fn on_event_content(&mut self, event: Event) -> EventResult {
-        match self.content.on_event(
-            event.relativized((self.padding + self.borders).top_left()),
-        ) {
+        match self
+            .content
+            .on_event(event.relativized(self.content_offset))
+        {
             EventResult::Ignored => {
                 if !self.buttons.is_empty() {
                     match event {
@@ -305,6 +429,14 @@ impl Dialog {
                             self.focus = DialogFocus::Button(0);
                             EventResult::Consumed(None)
                         }
+                        Event::Key(Key::Enter) => {
+                            match self.default_button_id() {
+                                Some(i) => self.buttons[i]
+                                    .button
+                                    .on_event(Event::Key(Key::Enter)),
+                                None => EventResult::Ignored,
+                            }
+                        }
                         _ => EventResult::Ignored,
                     }
                 } else {
@@ -402,7 +534,7 @@ impl Dialog {
             .map(|button| button.button.size.x)
             .sum::<usize>()
             + self.buttons.len().saturating_sub(1);
-        let overhead = self.padding + self.borders;
+        let overhead = self.layout_padding + self.borders;
         if printer.size.x < overhead.horizontal() {
             return None;
         }
@@ -412,7 +544,8 @@ impl Dialog {
                 .h
                 .get_offset(width, printer.size.x - overhead.horizontal());
 
-        let overhead_bottom = self.padding.bottom + self.borders.bottom + 1;
+        let overhead_bottom =
+            self.layout_padding.bottom + self.borders.bottom + 1;
 
         let y = match printer.size.y.checked_sub(overhead_bottom) {
             Some(y) => y,
@@ -439,44 +572,56 @@ impl Dialog {
         Some(buttons_height)
     }
 
-    fn draw_content(&self, printer: &Printer, buttons_height: usize) {
-        // What do we have left?
-        let taken = Vec2::new(0, buttons_height)
-            + self.borders.combined()
-            + self.padding.combined();
-
-        let inner_size = match printer.size.checked_sub(taken) {
-            Some(s) => s,
-            None => return,
-        };
-
+    fn draw_content(&self, printer: &Printer) {
         self.content.draw(
             &printer
-                .offset(self.borders.top_left() + self.padding.top_left())
-                .cropped(inner_size)
+                .offset(self.content_offset)
+                .cropped(self.content.size)
                 .focused(self.focus == DialogFocus::Content),
         );
     }
 
     fn draw_title(&self, printer: &Printer) {
-        if !self.title.is_empty() {
-            let len = self.title.width();
-            if len + 4 > printer.size.x {
-                return;
+        printer.print_box_title(
+            (0, 0),
+            printer.size.x,
+            &self.title,
+            self.title_position,
+        );
+    }
+
+    // Tells the content if it gained or lost the focus.
+    fn notify_content_focus(&mut self, had_focus: bool) -> EventResult {
+        match (had_focus, self.focus == DialogFocus::Content) {
+            (true, false) => self.content.on_event(Event::FocusLost),
+            (false, true) => self.content.on_event(Event::FocusGained),
+            _ => EventResult::Ignored,
+        }
+    }
+
+    // Gives a `Hover` event to the button or content under the mouse.
+    fn hover(&mut self, event: Event) -> EventResult {
+        let position = match event {
+            Event::Mouse {
+                offset, position, ..
+            } => match position.checked_sub(offset) {
+                Some(position) => position,
+                None => return EventResult::Ignored,
+            },
+            _ => return EventResult::Ignored,
+        };
+
+        let button = self.buttons.iter_mut().find(|btn| {
+            position.fits_in_rect(btn.offset.get(), btn.button.size)
+        });
+        match button {
+            Some(btn) => {
+                let offset = btn.offset.get();
+                btn.button.on_event(event.relativized(offset))
             }
-            let spacing = 3; //minimum distance to borders
-            let x = spacing
-                + self
-                    .title_position
-                    .get_offset(len, printer.size.x - 2 * spacing);
-            printer.with_high_border(false, |printer| {
-                printer.print((x - 2, 0), "┤ ");
-                printer.print((x + len, 0), " ├");
-            });
-
-            printer.with_color(ColorStyle::title_primary(), |p| {
-                p.print((x, 0), &self.title)
-            });
+            None => self
+                .content
+                .on_event(event.relativized(self.content_offset)),
         }
     }
 
@@ -504,10 +649,9 @@ impl Dialog {
                 position.fits_in_rect(btn.offset.get(), btn.button.size)
             }) {
                 self.focus = DialogFocus::Button(i);
-            } else if position.fits_in_rect(
-                (self.padding + self.borders).top_left(),
-                self.content.size,
-            ) && self.content.take_focus(Direction::none())
+            } else if position
+                .fits_in_rect(self.content_offset, self.content.size)
+                && self.content.take_focus(Direction::none())
             {
                 // Or did we click the content?
                 self.focus = DialogFocus::Content;
@@ -522,18 +666,21 @@ impl Dialog {
 
 impl View for Dialog {
     fn draw(&self, printer: &Printer) {
-        // This will be the buttons_height used by the buttons.
-        let buttons_height = match self.draw_buttons(printer) {
-            Some(height) => height,
-            None => return,
-        };
+        if self.draw_buttons(printer).is_none() {
+            return;
+        }
 
-        self.draw_content(printer, buttons_height);
+        self.draw_content(printer);
 
         // Print the borders
-        printer.print_box(Vec2::new(0, 0), printer.size, false);
-
-        self.draw_title(printer);
+        let draw_border = |printer: &Printer| {
+            printer.print_box(Vec2::new(0, 0), printer.size, false);
+            self.draw_title(printer);
+        };
+        match self.border_style {
+            Some(style) => printer.with_borders(style, draw_border),
+            None => draw_border(printer),
+        }
     }
 
     fn required_size(&mut self, req: Vec2) -> Vec2 {
@@ -579,42 +726,72 @@ impl View for Dialog {
         inner_size
     }
 
-    fn layout(&mut self, mut size: Vec2) {
-        // Padding and borders are taken, sorry.
+    fn layout(&mut self, size: Vec2) {
+        // Borders are taken, sorry.
         // TODO: handle border-less themes?
-        let taken = self.borders.combined() + self.padding.combined();
-        size = size.saturating_sub(taken);
+        let available = size.saturating_sub(self.borders.combined());
+        let padded = available.saturating_sub(self.padding.combined());
 
         // Buttons are kings, we give them everything they want.
-        let mut buttons_height = 0;
+        let mut buttons_size =
+            Vec2::new(self.buttons.len().saturating_sub(1), 0);
         for button in self.buttons.iter_mut().rev() {
-            let size = button.button.required_size(size);
-            buttons_height = max(buttons_height, size.y + 1);
+            let size = button.button.required_size(padded);
+            buttons_size.x += size.x;
+            buttons_size.y = max(buttons_size.y, size.y + 1);
             button.button.layout(size);
         }
 
+        // The content and buttons come first, the padding gets what's left.
+        let required = self
+            .content
+            .required_size(available.saturating_sub((0, buttons_size.y)));
+        let wanted = Vec2::new(
+            max(required.x, buttons_size.x),
+            required.y + buttons_size.y,
+        );
+        let padding = self.padding.shrunk_to_fit(available, wanted);
+        let available = available.saturating_sub(padding.combined());
+
         // Poor content will have to make do with what's left.
-        if buttons_height > size.y {
-            buttons_height = size.y;
-        }
+        let buttons_height = min(buttons_size.y, available.y);
+        let available = available - (0, buttons_height);
 
-        self.content
-            .layout(size.saturating_sub((0, buttons_height)));
+        let (offset, size) = match self.content_align {
+            Some(ref align) => {
+                let size = required.or_min(available);
+                (align.get_offset(size, available), size)
+            }
+            None => (Vec2::zero(), available),
+        };
+        self.layout_padding = padding;
+        self.content_offset =
+            self.borders.top_left() + padding.top_left() + offset;
+        self.content.layout(size);
 
         self.invalidated = false;
     }
 
     fn on_event(&mut self, event: Event) -> EventResult {
+        if event.mouse_event() == Some(MouseEvent::Hover) {
+            return self.hover(event);
+        }
+
         // First: some mouse events can instantly change the focus.
+        let had_focus = self.focus == DialogFocus::Content;
         self.check_focus_grab(&event);
+        let grab = self.notify_content_focus(had_focus);
 
-        match self.focus {
+        let had_focus = self.focus == DialogFocus::Content;
+        let result = match self.focus {
             // If we are on the content, we can only go down.
             // TODO: Careful if/when we add buttons elsewhere on the dialog!
             DialogFocus::Content => self.on_event_content(event),
             // If we are on a button, we have more choice
             DialogFocus::Button(i) => self.on_event_button(event, i),
-        }
+        };
+
+        grab.and(result).and(self.notify_content_focus(had_focus))
     }
 
     fn take_focus(&mut self, source: Direction) -> bool {
@@ -630,7 +807,8 @@ impl View for Dialog {
                     self.focus = DialogFocus::Content;
                     true
                 } else if !self.buttons.is_empty() {
-                    self.focus = DialogFocus::Button(0);
+                    let i = self.default_button_id().unwrap_or(0);
+                    self.focus = DialogFocus::Button(i);
                     true
                 } else {
                     false
@@ -662,12 +840,189 @@ impl View for Dialog {
     }
 
     fn important_area(&self, _: Vec2) -> Rect {
-        self.content.important_area(self.content.size)
-            + self.borders.top_left()
-            + self.padding.top_left()
+        self.content.important_area(self.content.size) + self.content_offset
     }
 
     fn needs_relayout(&self) -> bool {
         self.invalidated || self.content.needs_relayout()
     }
+
+    fn follow_focus(&mut self, path: &mut FocusPath) {
+        match self.focus {
+            DialogFocus::Content => {
+                path.child(&mut *self.content.view, self.content_offset)
+            }
+            DialogFocus::Button(i) => {
+                let button = &mut self.buttons[i];
+                path.child(&mut button.button.view, button.offset.get());
+            }
+        }
+    }
+
+    fn debug_layout(&mut self, tree: &mut LayoutTree) {
+        let focus = self.focus;
+        tree.child(
+            &mut *self.content.view,
+            self.content_offset,
+            self.content.size,
+            focus == DialogFocus::Content,
+        );
+        // Buttons are placed when drawn, so they keep their last position.
+        for (i, button) in self.buttons.iter_mut().enumerate() {
+            tree.child(
+                &mut button.button.view,
+                button.offset.get(),
+                button.button.size,
+                focus == DialogFocus::Button(i),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backend::puppet;
+    use std::rc::Rc;
+    use theme::BorderGlyphs;
+    use views::{BoxView, Panel, SliderView};
+
+    #[test]
+    fn test_border_style() {
+        let backend = puppet::Backend::init(Some(Vec2::new(14, 7)));
+        let mut siv = Cursive::new(move || backend);
+        let panel = Panel::new(TextView::new("Hi"));
+        siv.add_layer(
+            Dialog::around(panel.border(BorderStyle::Double))
+                .title("T")
+                .border(BorderStyle::Rounded),
+        );
+
+        // The title fits between the corners, and the rounded border
+        // doesn't leak into the content.
+        assert_eq!(
+            siv.dump_screen(),
+            vec![
+                "              ",
+                "   ╭┤ T ├─╮   ",
+                "   │ ╔══╗ │   ",
+                "   │ ║Hi║ │   ",
+                "   │ ╚══╝ │   ",
+                "   ╰──────╯   ",
+                "              ",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_theme_border_glyphs() {
+        let backend = puppet::Backend::init(Some(Vec2::new(8, 5)));
+        let mut siv = Cursive::new(move || backend);
+        let mut theme = siv.current_theme().clone();
+        theme.glyphs.rounded_borders = BorderGlyphs {
+            top_left: "/".to_string(),
+            top_right: "\\".to_string(),
+            bottom_left: "\\".to_string(),
+            bottom_right: "/".to_string(),
+            horizontal: "-".to_string(),
+            vertical: "|".to_string(),
+            left_tee: "[".to_string(),
+            right_tee: "]".to_string(),
+        };
+        siv.set_theme(theme);
+        siv.add_layer(
+            Dialog::around(TextView::new("Hi")).border(BorderStyle::Rounded),
+        );
+
+        assert_eq!(
+            siv.dump_screen(),
+            vec![
+                "        ",
+                " /----\\ ",
+                " | Hi | ",
+                " \\----/ ",
+                "        ",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_default_button() {
+        let mut dialog = Dialog::text("Delete?")
+            .button("Delete", |_| ())
+            .dismiss_button("Cancel")
+            .default_button_label("Cancel");
+        dialog.layout(Vec2::new(30, 10));
+        assert!(dialog.take_focus(Direction::none()));
+        assert_eq!(dialog.focus(), DialogFocus::Button(1));
+
+        // Tab still cycles through the buttons.
+        dialog.on_event(Event::Shift(Key::Tab));
+        assert_eq!(dialog.focus(), DialogFocus::Button(0));
+        dialog.on_event(Event::Key(Key::Tab));
+        assert_eq!(dialog.focus(), DialogFocus::Button(1));
+
+        // Rebuilding the buttons keeps the default.
+        dialog.clear_buttons();
+        dialog.add_button("Cancel", |_| ());
+        dialog.add_button("Delete", |_| ());
+        dialog.add_button("Cancel", |_| ());
+        assert!(dialog.take_focus(Direction::none()));
+        assert_eq!(dialog.focus(), DialogFocus::Button(0));
+
+        dialog.set_default_button(2);
+        assert!(dialog.take_focus(Direction::none()));
+        assert_eq!(dialog.focus(), DialogFocus::Button(2));
+    }
+
+    #[test]
+    fn test_default_button_enter() {
+        let backend = puppet::Backend::init(Some(Vec2::new(30, 10)));
+        let mut siv = Cursive::new(move || backend);
+        let pressed = Rc::new(Cell::new(None));
+        let (p1, p2) = (Rc::clone(&pressed), Rc::clone(&pressed));
+        siv.add_layer(
+            Dialog::around(SliderView::horizontal(5))
+                .button("Ok", move |_| p1.set(Some("Ok")))
+                .button("Cancel", move |_| p2.set(Some("Cancel")))
+                .default_button(1),
+        );
+        siv.dump_screen();
+
+        // The slider keeps the focus, but ignores `Enter`.
+        siv.on_event(Event::Key(Key::Right));
+        siv.on_event(Event::Key(Key::Enter));
+        assert_eq!(pressed.get(), Some("Cancel"));
+    }
+
+    #[test]
+    fn test_content_align() {
+        let content = || BoxView::with_fixed_size((4, 2), DummyView);
+        let layout = |dialog: &mut Dialog, size: (usize, usize)| {
+            dialog.layout(Vec2::from(size));
+            (dialog.content_offset, dialog.content.size)
+        };
+
+        let mut dialog =
+            Dialog::around(content()).content_align(Align::center());
+        assert_eq!(
+            layout(&mut dialog, (20, 10)),
+            ((8, 4).into(), (4, 2).into())
+        );
+
+        // The buttons take the bottom rows.
+        dialog.add_button("Ok", |_| ());
+        assert_eq!(
+            layout(&mut dialog, (20, 10)),
+            ((8, 3).into(), (4, 2).into())
+        );
+
+        // Without enough room, the padding shrinks.
+        let mut dialog = dialog.padding((2, 2, 1, 1));
+        assert_eq!(
+            layout(&mut dialog, (8, 6)),
+            ((2, 1).into(), (4, 2).into())
+        );
+        assert_eq!(dialog.layout_padding, Margins::new(1, 1, 0, 0));
+    }
 }
@@ -1,14 +1,18 @@
-use direction::Direction;
+use direction::{Direction, TextDirection};
 use event::{Callback, Event, EventResult, Key, MouseEvent};
 use rect::Rect;
 use std::cell::RefCell;
+use std::cmp::min;
 use std::rc::Rc;
-use theme::{ColorStyle, Effect};
+use theme::{BaseColor, Color, ColorStyle, Effect, PaletteColor};
 use unicode_segmentation::UnicodeSegmentation;
-use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
-use utils::lines::simple::{simple_prefix, simple_suffix};
+use unicode_width::UnicodeWidthStr;
+use utils::lines::tabs::{self, DEFAULT_TAB_WIDTH};
+use utils::{clipboard, graphemes};
+use utils::undo::History;
 use vec::Vec2;
 use view::View;
+use views::autocomplete::{self, Autocomplete};
 use {Cursive, Printer, With};
 
 /// Closure type for callbacks when the content is modified.
@@ -22,6 +26,38 @@ pub type OnEdit = Fn(&mut Cursive, &str, usize);
 /// Arguments are the `Cursive` and the content of the input.
 pub type OnSubmit = Fn(&mut Cursive, &str);
 
+/// Closure type to validate edits.
+///
+/// Arguments are the proposed content and cursor position.
+pub type Validator = Fn(&str, usize) -> Validation;
+
+/// Decision of a validator about an edit in an `EditView`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use cursive::views::{EditView, Validation};
+/// // Only accept digits.
+/// let edit = EditView::new().validator(|content, _| {
+///     if content.chars().all(|c| c.is_ascii_digit()) {
+///         Validation::Accept
+///     } else {
+///         Validation::Reject
+///     }
+/// });
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Validation {
+    /// The edit is applied.
+    Accept,
+    /// The edit is applied, but the view is shown with its error style.
+    Invalid,
+    /// The edit is cancelled, and the previous content is kept.
+    Reject,
+    /// The given content and cursor position are used instead.
+    Replace(String, usize),
+}
+
 /// Input box where the user can enter and edit text.
 ///
 /// # Examples
@@ -101,15 +137,42 @@ pub struct EditView {
     /// Callback when <Enter> is pressed.
     on_submit: Option<Rc<OnSubmit>>,
 
-    /// When `true`, only print `*` instead of the true content.
+    /// Checks each edit before it is applied.
+    validator: Option<Rc<Validator>>,
+
+    /// `true` if the validator reported the content as invalid.
+    invalid: bool,
+
+    /// Style used instead of `style` when the content is invalid.
+    error_style: ColorStyle,
+
+    /// When `true`, only print `mask` instead of the true content.
     secret: bool,
 
+    /// Shown instead of each cell of the content in secret mode.
+    mask: String,
+
+    /// When `true`, show the content in clear even in secret mode.
+    revealed: bool,
+
     /// Character to fill empty space
     filler: String,
 
+    /// Whether the content reads left-to-right or right-to-left.
+    direction: TextDirection,
+
+    /// Number of cells between two tab stops.
+    tab_width: usize,
+
     enabled: bool,
 
     style: ColorStyle,
+
+    /// Edits that can be undone with `<Ctrl-Z>`.
+    history: History,
+
+    /// Suggests completions as the user types.
+    autocomplete: Option<Autocomplete>,
 }
 
 new_default!(EditView);
@@ -124,11 +187,23 @@ impl EditView {
             last_length: 0, // scrollable: false,
             on_edit: None,
             on_submit: None,
+            validator: None,
+            invalid: false,
+            error_style: ColorStyle::new(
+                Color::Dark(BaseColor::Red),
+                PaletteColor::View,
+            ),
             max_content_width: None,
             secret: false,
+            mask: "*".to_string(),
+            revealed: false,
             filler: "_".to_string(),
+            direction: TextDirection::LeftToRight,
+            tab_width: DEFAULT_TAB_WIDTH,
             enabled: true,
             style: ColorStyle::secondary(),
+            history: History::new(),
+            autocomplete: None,
         }
     }
 
@@ -152,7 +227,8 @@ impl EditView {
 
     /// If `secret` is `true`, the content won't be displayed in clear.
     ///
-    /// Only `*` will be shown.
+    /// Only `*` will be shown (see `set_mask()`), but `get_content()` still
+    /// returns the real content.
     pub fn set_secret(&mut self, secret: bool) {
         self.secret = secret;
     }
@@ -164,6 +240,46 @@ impl EditView {
         self.with(|s| s.set_secret(true))
     }
 
+    /// Sets the text shown for each cell of the content in secret mode.
+    ///
+    /// With an empty mask, the content is shown as blank cells.
+    ///
+    /// Defaults to "*".
+    pub fn set_mask<S: Into<String>>(&mut self, mask: S) {
+        self.mask = mask.into();
+    }
+
+    /// Sets the text shown for each cell of the content in secret mode.
+    ///
+    /// Chainable variant.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cursive::views::EditView;
+    /// let password = EditView::new().secret().mask("•");
+    /// ```
+    pub fn mask<S: Into<String>>(self, mask: S) -> Self {
+        self.with(|s| s.set_mask(mask))
+    }
+
+    /// Temporarily shows the content of a secret view in clear.
+    ///
+    /// This has no effect if the view is not in secret mode.
+    pub fn set_revealed(&mut self, revealed: bool) {
+        self.revealed = revealed;
+    }
+
+    /// Returns `true` if the content of a secret view is shown in clear.
+    pub fn is_revealed(&self) -> bool {
+        self.revealed
+    }
+
+    // `true` if the content should be hidden behind `mask`.
+    fn is_masked(&self) -> bool {
+        self.secret && !self.revealed
+    }
+
     /// Sets the character to fill in blank space.
     ///
     /// Defaults to "_".
@@ -185,6 +301,65 @@ impl EditView {
         self.with(|s| s.set_filler(filler))
     }
 
+    /// Sets the base direction of the content.
+    ///
+    /// Right-to-left content starts at the right edge of the view, and the
+    /// arrow keys move the cursor in the matching visual direction:
+    /// `<Left>` goes towards the end of the content.
+    ///
+    /// Defaults to `TextDirection::LeftToRight`.
+    pub fn set_direction(&mut self, direction: TextDirection) {
+        self.direction = direction;
+    }
+
+    /// Sets the base direction of the content.
+    ///
+    /// Chainable variant.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cursive::direction::TextDirection;
+    /// # use cursive::views::EditView;
+    /// let edit = EditView::new().direction(TextDirection::RightToLeft);
+    /// ```
+    pub fn direction(self, direction: TextDirection) -> Self {
+        self.with(|s| s.set_direction(direction))
+    }
+
+    /// Returns the base direction of the content.
+    pub fn get_direction(&self) -> TextDirection {
+        self.direction
+    }
+
+    /// Sets the number of cells between two tab stops.
+    ///
+    /// Tab characters expand to the next stop, counting from the start of
+    /// the content. The cursor still moves over a tab in one step.
+    ///
+    /// Defaults to 4.
+    pub fn set_tab_width(&mut self, tab_width: usize) {
+        self.tab_width = tab_width;
+        self.keep_cursor_in_view();
+    }
+
+    /// Sets the number of cells between two tab stops.
+    ///
+    /// Chainable variant.
+    pub fn tab_width(self, tab_width: usize) -> Self {
+        self.with(|s| s.set_tab_width(tab_width))
+    }
+
+    /// Returns the number of cells between two tab stops.
+    pub fn get_tab_width(&self) -> usize {
+        self.tab_width
+    }
+
+    /// Returns the column of the given byte offset in the content.
+    fn column_at(&self, offset: usize) -> usize {
+        tabs::width(&self.content[..offset], 0, self.tab_width)
+    }
+
     /// Disables this view.
     ///
     /// A disabled view cannot be selected.
@@ -336,6 +511,124 @@ impl EditView {
         self.with(|v| v.set_on_submit(callback))
     }
 
+    /// Sets a validator to check every edit made by the user.
+    ///
+    /// The validator is given the content and cursor position resulting
+    /// from the edit, and decides whether to apply it. It is not used by
+    /// `set_content()`.
+    ///
+    /// See the [`Validation`] enum for the possible decisions.
+    ///
+    /// [`Validation`]: enum.Validation.html
+    pub fn set_validator<F>(&mut self, validator: F)
+    where
+        F: Fn(&str, usize) -> Validation + 'static,
+    {
+        self.validator = Some(Rc::new(validator));
+    }
+
+    /// Sets a validator to check every edit made by the user.
+    ///
+    /// Chainable variant.
+    pub fn validator<F>(self, validator: F) -> Self
+    where
+        F: Fn(&str, usize) -> Validation + 'static,
+    {
+        self.with(|s| s.set_validator(validator))
+    }
+
+    /// Returns `true` if the validator reported the content as invalid.
+    pub fn is_invalid(&self) -> bool {
+        self.invalid
+    }
+
+    /// Sets the style used when the content is invalid.
+    ///
+    /// Defaults to dark red.
+    pub fn set_error_style(&mut self, style: ColorStyle) {
+        self.error_style = style;
+    }
+
+    /// Sets the style used when the content is invalid.
+    ///
+    /// Chainable variant.
+    pub fn error_style(self, style: ColorStyle) -> Self {
+        self.with(|s| s.set_error_style(style))
+    }
+
+    /// Sets a function suggesting completions for the content.
+    ///
+    /// As the user types, the suggestions are shown in a popup under the
+    /// view. `<Up>` and `<Down>` select a suggestion, `<Tab>` or `<Enter>`
+    /// replaces the content with it, and `<Esc>` closes the popup without
+    /// changing the content.
+    ///
+    /// The popup only opens when there is something to suggest, so
+    /// `<Enter>` still submits the content otherwise. Suggestions equal to
+    /// the content are skipped, and at most 10 are shown.
+    pub fn set_autocomplete<F>(&mut self, provider: F)
+    where
+        F: Fn(&str) -> Vec<String> + 'static,
+    {
+        self.autocomplete = Some(Autocomplete::new(Rc::new(provider)));
+    }
+
+    /// Sets a function suggesting completions for the content.
+    ///
+    /// Chainable variant.
+    pub fn autocomplete<F>(self, provider: F) -> Self
+    where
+        F: Fn(&str) -> Vec<String> + 'static,
+    {
+        self.with(|s| s.set_autocomplete(provider))
+    }
+
+    /// Suggests completions from a fixed list.
+    ///
+    /// Items starting with the content are suggested, ignoring case.
+    ///
+    /// See [`set_autocomplete`](#method.set_autocomplete).
+    pub fn set_autocomplete_list<I, S>(&mut self, items: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let items = items.into_iter().map(Into::into).collect();
+        let provider = autocomplete::list_provider(items);
+        self.autocomplete = Some(Autocomplete::new(provider));
+    }
+
+    /// Suggests completions from a fixed list.
+    ///
+    /// Chainable variant.
+    pub fn autocomplete_list<I, S>(self, items: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.with(|s| s.set_autocomplete_list(items))
+    }
+
+    /// Stops suggesting completions.
+    pub fn clear_autocomplete(&mut self) {
+        self.autocomplete = None;
+    }
+
+    // Applies the completion accepted in the popup, if any.
+    pub(crate) fn apply_completion(&mut self) -> Callback {
+        let accepted = self
+            .autocomplete
+            .as_ref()
+            .and_then(|autocomplete| autocomplete.take_accepted());
+        match accepted {
+            Some(completion) => {
+                let len = self.content.len();
+                self.replace(0, len, &completion)
+            }
+            None => Callback::dummy(),
+        }
+    }
+
     /// Enable or disable this view.
     pub fn set_enabled(&mut self, enabled: bool) {
         self.enabled = enabled;
@@ -348,6 +641,8 @@ impl EditView {
 
     /// Replace the entire content of the view with the given one.
     ///
+    /// This also clears the undo history.
+    ///
     /// Returns a callback in response to content change.
     ///
     /// You should run this callback with a `&mut Cursive`.
@@ -357,6 +652,8 @@ impl EditView {
 
         self.content = Rc::new(content);
         self.offset = 0;
+        self.invalid = false;
+        self.history.clear();
         self.set_cursor(len);
 
         self.make_edit_cb().unwrap_or_else(Callback::dummy)
@@ -390,45 +687,309 @@ impl EditView {
     ///
     /// You should run this callback with a `&mut Cursive`.
     pub fn insert(&mut self, ch: char) -> Callback {
+        let mut buffer = [0; 4];
+        let text = ch.encode_utf8(&mut buffer);
+
         // First, make sure we can actually insert anything.
         if let Some(width) = self.max_content_width {
-            // XXX: we assume here that the widths are linearly additive.
-            // Is that true? What about weird combined unicode thingies?
-            // Also, say the user copy+paste some content, do we want to
-            // stop halfway through a possibly split grapheme?
-            if ch.width().unwrap_or(0) + self.content.width() > width {
+            // Tabs after the cursor may change width, so measure them again.
+            let column = self.column_at(self.cursor);
+            let column =
+                column + tabs::grapheme_width(text, column, self.tab_width);
+            let rest = &self.content[self.cursor..];
+            if column + tabs::width(rest, column, self.tab_width) > width {
                 // ABORT
                 return Callback::dummy();
             }
         }
 
+        let cursor = self.cursor;
+        self.replace(cursor, cursor, text)
+    }
+
+    /// Insert `text` at the current cursor position.
+    ///
+    /// Control characters other than tabs (including newlines) are skipped.
+    /// If a maximum content width is set, only the part of `text` that fits
+    /// is inserted.
+    ///
+    /// Returns a callback in response to content change.
+    ///
+    /// You should run this callback with a `&mut Cursive`.
+    pub fn insert_str(&mut self, text: &str) -> Callback {
+        let mut text: String = text
+            .chars()
+            .filter(|&c| c == '\t' || !c.is_control())
+            .collect();
+
+        if let Some(width) = self.max_content_width {
+            let total = self.column_at(self.content.len());
+            let available = width.saturating_sub(total);
+            let column = self.column_at(self.cursor);
+            let length =
+                tabs::prefix(&text, column, available, self.tab_width).length;
+            text.truncate(length);
+        }
+
+        if text.is_empty() {
+            return Callback::dummy();
+        }
+
+        let cursor = self.cursor;
+        self.replace(cursor, cursor, &text)
+    }
+
+    /// Remove the character at the current cursor position.
+    ///
+    /// Returns a callback in response to content change.
+    ///
+    /// You should run this callback with a `&mut Cursive`.
+    pub fn remove(&mut self, len: usize) -> Callback {
+        let start = self.cursor;
+        self.replace(start, start + len, "")
+    }
+
+    /// Moves the cursor to the end of the next word.
+    pub fn move_to_next_word(&mut self) {
+        let cursor = graphemes::next_word_end(&self.content, self.cursor);
+        self.set_cursor(cursor);
+    }
+
+    /// Moves the cursor to the start of the previous word.
+    pub fn move_to_previous_word(&mut self) {
+        let cursor =
+            graphemes::previous_word_start(&self.content, self.cursor);
+        self.set_cursor(cursor);
+    }
+
+    /// Removes the word before the cursor, and the spaces after it.
+    ///
+    /// Returns a callback in response to content change.
+    ///
+    /// You should run this callback with a `&mut Cursive`.
+    pub fn remove_previous_word(&mut self) -> Callback {
+        let end = self.cursor;
+        let start = graphemes::previous_word_start(&self.content, end);
+        if start == end {
+            return Callback::dummy();
+        }
+        self.replace(start, end, "")
+    }
+
+    /// Removes everything before the cursor.
+    ///
+    /// Returns a callback in response to content change.
+    ///
+    /// You should run this callback with a `&mut Cursive`.
+    pub fn remove_to_start(&mut self) -> Callback {
+        let end = self.cursor;
+        if end == 0 {
+            return Callback::dummy();
+        }
+        self.replace(0, end, "")
+    }
+
+    /// Replaces the given byte range of the content with `text`.
+    ///
+    /// The cursor is moved after `text`, unless the validator decides
+    /// otherwise.
+    fn replace(&mut self, start: usize, end: usize, text: &str) -> Callback {
+        let cursor = start + text.len();
+
+        if let Some(validator) = self.validator.clone() {
+            let mut proposal = self.content[..start].to_string();
+            proposal.push_str(text);
+            proposal.push_str(&self.content[end..]);
+
+            match validator(&proposal, cursor) {
+                Validation::Accept => self.invalid = false,
+                Validation::Invalid => self.invalid = true,
+                Validation::Reject => return Callback::dummy(),
+                Validation::Replace(content, cursor) => {
+                    self.history.record_change(&self.content, &content);
+                    self.content = Rc::new(content);
+                    self.invalid = false;
+
+                    // Make sure the cursor is on a character boundary.
+                    let mut cursor = min(cursor, self.content.len());
+                    while !self.content.is_char_boundary(cursor) {
+                        cursor -= 1;
+                    }
+                    return self.after_edit(cursor);
+                }
+            }
+        }
+
+        let removed = &self.content[start..end];
+        if removed.is_empty() {
+            self.history.record_insert(start, text);
+        } else if text.is_empty() {
+            self.history.record_remove(start, removed);
+        } else {
+            self.history.record_replace(start, removed, text);
+        }
+
         // `make_mut` applies copy-on-write
         // It means it'll just return a ref if no one else has a ref,
         // and it will clone it into `self.content` otherwise.
+        Rc::make_mut(&mut self.content).replace_range(start..end, text);
 
-        Rc::make_mut(&mut self.content).insert(self.cursor, ch);
-        self.cursor += ch.len_utf8();
+        self.after_edit(cursor)
+    }
 
+    fn after_edit(&mut self, cursor: usize) -> Callback {
+        self.cursor = cursor;
         self.keep_cursor_in_view();
 
         self.make_edit_cb().unwrap_or_else(Callback::dummy)
     }
 
-    /// Remove the character at the current cursor position.
+    /// Reverts the last edit.
+    ///
+    /// Consecutive typed characters are undone together, up to a word.
     ///
     /// Returns a callback in response to content change.
     ///
     /// You should run this callback with a `&mut Cursive`.
-    pub fn remove(&mut self, len: usize) -> Callback {
-        let start = self.cursor;
-        let end = self.cursor + len;
-        for _ in Rc::make_mut(&mut self.content).drain(start..end) {}
+    pub fn undo(&mut self) -> Callback {
+        match self.history.undo(Rc::make_mut(&mut self.content)) {
+            Some(cursor) => self.after_history(cursor),
+            None => Callback::dummy(),
+        }
+    }
 
-        self.keep_cursor_in_view();
+    /// Applies again the last undone edit.
+    ///
+    /// Returns a callback in response to content change.
+    ///
+    /// You should run this callback with a `&mut Cursive`.
+    pub fn redo(&mut self) -> Callback {
+        match self.history.redo(Rc::make_mut(&mut self.content)) {
+            Some(cursor) => self.after_history(cursor),
+            None => Callback::dummy(),
+        }
+    }
+
+    /// Forgets every edit, so they can't be undone anymore.
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    fn after_history(&mut self, cursor: usize) -> Callback {
+        // The content may have shrunk below the current offset.
+        self.offset = 0;
+        self.set_cursor(cursor);
 
         self.make_edit_cb().unwrap_or_else(Callback::dummy)
     }
 
+    /// Handles events editing the content, or moving the cursor.
+    fn on_edit_event(&mut self, event: Event) -> EventResult {
+        // Arrow keys move visually: right-to-left content goes forward to
+        // the left.
+        let event = match (self.direction, event) {
+            (TextDirection::RightToLeft, Event::Key(Key::Left)) => {
+                Event::Key(Key::Right)
+            }
+            (TextDirection::RightToLeft, Event::Key(Key::Right)) => {
+                Event::Key(Key::Left)
+            }
+            (TextDirection::RightToLeft, Event::Ctrl(Key::Left)) => {
+                Event::Ctrl(Key::Right)
+            }
+            (TextDirection::RightToLeft, Event::Ctrl(Key::Right)) => {
+                Event::Ctrl(Key::Left)
+            }
+            (_, event) => event,
+        };
+
+        match event {
+            Event::Char(ch) => {
+                return EventResult::Consumed(Some(self.insert(ch)));
+            }
+            Event::Paste(ref text) => {
+                return EventResult::Consumed(Some(self.insert_str(text)));
+            }
+            Event::CtrlChar('v') => return clipboard::paste(),
+            Event::CtrlChar('z') => {
+                return EventResult::Consumed(Some(self.undo()));
+            }
+            Event::CtrlChar('y') | Event::CtrlChar('Z') => {
+                return EventResult::Consumed(Some(self.redo()));
+            }
+            Event::CtrlChar('w') | Event::Ctrl(Key::Backspace) => {
+                let cb = self.remove_previous_word();
+                return EventResult::Consumed(Some(cb));
+            }
+            Event::CtrlChar('u') => {
+                return EventResult::Consumed(Some(self.remove_to_start()));
+            }
+            Event::Ctrl(Key::Left) => self.move_to_previous_word(),
+            Event::Ctrl(Key::Right) => self.move_to_next_word(),
+            Event::Key(Key::Home) => self.set_cursor(0),
+            Event::Key(Key::End) => {
+                // When possible, NLL to the rescue!
+                let len = self.content.len();
+                self.set_cursor(len);
+            }
+            Event::Key(Key::Left) if self.cursor > 0 => {
+                let len = graphemes::previous(&self.content, self.cursor)
+                    .unwrap()
+                    .len();
+                let cursor = self.cursor - len;
+                self.set_cursor(cursor);
+            }
+            Event::Key(Key::Right) if self.cursor < self.content.len() => {
+                let len = graphemes::next(&self.content, self.cursor)
+                    .unwrap()
+                    .len();
+                let cursor = self.cursor + len;
+                self.set_cursor(cursor);
+            }
+            Event::Key(Key::Backspace) if self.cursor > 0 => {
+                // Removes the whole grapheme, not just its last code point.
+                let len = graphemes::previous(&self.content, self.cursor)
+                    .unwrap()
+                    .len();
+                let end = self.cursor;
+                let cb = self.replace(end - len, end, "");
+                return EventResult::Consumed(Some(cb));
+            }
+            Event::Key(Key::Del) if self.cursor < self.content.len() => {
+                let len = graphemes::next(&self.content, self.cursor)
+                    .unwrap()
+                    .len();
+                return EventResult::Consumed(Some(self.remove(len)));
+            }
+            Event::Key(Key::Enter) if self.on_submit.is_some() => {
+                let cb = self.on_submit.clone().unwrap();
+                let content = Rc::clone(&self.content);
+                return EventResult::with_cb(move |s| {
+                    cb(s, &content);
+                });
+            }
+            Event::Mouse {
+                event: MouseEvent::Press(_),
+                position,
+                offset,
+            } if position.fits_in_rect(offset, (self.last_length, 1)) => {
+                if let Some(position) = position.checked_sub(offset) {
+                    let x =
+                        self.direction.mirror(position.x, 1, self.last_length);
+                    let column = self.column_at(self.offset);
+                    let text = &self.content[self.offset..];
+                    self.cursor = self.offset
+                        + tabs::prefix(text, column, x, self.tab_width).length;
+                }
+            }
+            _ => return EventResult::Ignored,
+        }
+
+        // self.keep_cursor_in_view();
+
+        EventResult::Consumed(self.make_edit_cb())
+    }
+
     fn make_edit_cb(&self) -> Option<Callback> {
         self.on_edit.clone().map(|cb| {
             // Get a new Rc on the content
@@ -452,10 +1013,9 @@ impl EditView {
             // So we're against the right wall.
             // Let's find how much space will be taken by the selection
             // (either a char, or _)
-            let c_len = self.content[self.cursor..]
-                .graphemes(true)
-                .map(|g| g.width())
-                .next()
+            let column = self.column_at(self.cursor);
+            let c_len = graphemes::next(&self.content, self.cursor)
+                .map(|g| tabs::grapheme_width(g, column, self.tab_width))
                 .unwrap_or(1);
 
             // Now, we have to fit self.content[..self.cursor]
@@ -468,9 +1028,11 @@ impl EditView {
             // Look at the content before the cursor (we will print its tail).
             // From the end, count the length until we reach `available`.
             // Then sum the byte lengths.
-            let suffix_length = simple_suffix(
+            let suffix_length = tabs::suffix(
                 &self.content[self.offset..self.cursor],
+                self.column_at(self.offset),
                 available,
+                self.tab_width,
             )
             .length;
 
@@ -481,24 +1043,52 @@ impl EditView {
         }
 
         // If we have too much space
-        if self.content[self.offset..].width() < self.last_length {
+        let total = self.column_at(self.content.len());
+        if total - self.column_at(self.offset) < self.last_length {
             assert!(self.last_length >= 1);
-            let suffix_length =
-                simple_suffix(&self.content, self.last_length - 1).length;
+            let suffix_length = tabs::suffix(
+                &self.content,
+                0,
+                self.last_length - 1,
+                self.tab_width,
+            )
+            .length;
 
             assert!(self.content.len() >= suffix_length);
             self.offset = self.content.len() - suffix_length;
         }
     }
+
+    /// Prints `text` at column `x`, counted in reading order.
+    fn print_text(&self, printer: &Printer, x: usize, text: &str) {
+        let start = self.direction.mirror(x, text.width(), self.last_length);
+        match self.direction {
+            TextDirection::LeftToRight => printer.print((start, 0), text),
+            TextDirection::RightToLeft => {
+                printer.print((start, 0), &graphemes::reversed(text))
+            }
+        }
+    }
+
+    /// Fills `width` cells with `text` from column `x`, counted in reading
+    /// order.
+    fn print_fill(
+        &self, printer: &Printer, x: usize, width: usize, text: &str,
+    ) {
+        let text = repeat_to(text, width);
+        let start = self.direction.mirror(x, text.width(), self.last_length);
+        printer.print((start, 0), &text);
+    }
 }
 
-/// Returns a `&str` with `length` characters `*`.
+/// Repeats `text` to fill up to `width` cells.
 ///
-/// Only works for small `length` (1 or 2).
-/// Best used for single character replacement.
-fn make_small_stars(length: usize) -> &'static str {
-    // TODO: be able to use any character as hidden mode?
-    &"****"[..length]
+/// An empty `text` gives blank cells.
+fn repeat_to(text: &str, width: usize) -> String {
+    match text.width() {
+        0 => " ".repeat(width),
+        text_width => text.repeat(width / text_width),
+    }
 }
 
 impl View for EditView {
@@ -509,8 +1099,13 @@ impl View for EditView {
             self.last_length, printer.size.x
         );
 
-        let width = self.content.width();
-        printer.with_color(self.style, |printer| {
+        let width = self.column_at(self.content.len());
+        let style = if self.invalid {
+            self.error_style
+        } else {
+            self.style
+        };
+        printer.with_color(style, |printer| {
             let effect = if self.enabled && printer.enabled {
                 Effect::Reverse
             } else {
@@ -520,24 +1115,25 @@ impl View for EditView {
                 if width < self.last_length {
                     // No problem, everything fits.
                     assert!(printer.size.x >= width);
-                    if self.secret {
-                        printer.print_hline((0, 0), width, "*");
+                    if self.is_masked() {
+                        self.print_fill(printer, 0, width, &self.mask);
                     } else {
-                        printer.print((0, 0), &self.content);
+                        let content =
+                            tabs::expand(&self.content, 0, self.tab_width);
+                        self.print_text(printer, 0, &content);
                     }
-                    let filler_len =
-                        (printer.size.x - width) / self.filler.width();
-                    printer.print_hline(
-                        (width, 0),
-                        filler_len,
-                        self.filler.as_str(),
-                    );
+                    let filler = &self.filler;
+                    let filler_width = printer.size.x - width;
+                    self.print_fill(printer, width, filler_width, filler);
                 } else {
                     let content = &self.content[self.offset..];
+                    let column = self.column_at(self.offset);
+                    let tab_width = self.tab_width;
                     let display_bytes = content
                         .graphemes(true)
                         .scan(0, |w, g| {
-                            *w += g.width();
+                            let x = column + *w;
+                            *w += tabs::grapheme_width(g, x, tab_width);
                             if *w > self.last_length {
                                 None
                             } else {
@@ -548,49 +1144,58 @@ impl View for EditView {
                         .sum();
 
                     let content = &content[..display_bytes];
-                    let width = content.width();
+                    let width = tabs::width(content, column, tab_width);
 
-                    if self.secret {
-                        printer.print_hline((0, 0), width, "*");
+                    if self.is_masked() {
+                        self.print_fill(printer, 0, width, &self.mask);
                     } else {
-                        printer.print((0, 0), content);
+                        let content = tabs::expand(content, column, tab_width);
+                        self.print_text(printer, 0, &content);
                     }
 
                     if width < self.last_length {
-                        let filler_len =
-                            (self.last_length - width) / self.filler.width();
-                        printer.print_hline(
-                            (width, 0),
-                            filler_len,
-                            self.filler.as_str(),
+                        let filler_width = self.last_length - width;
+                        self.print_fill(
+                            printer,
+                            width,
+                            filler_width,
+                            &self.filler,
                         );
                     }
                 }
             });
 
-            // Now print cursor
-            if printer.focused {
-                let c: &str = if self.cursor == self.content.len() {
-                    &self.filler
+            // Now print cursor, also while choosing a completion.
+            let completing = self
+                .autocomplete
+                .as_ref()
+                .filter(|autocomplete| autocomplete.is_open())
+                .is_some();
+            if printer.focused || completing {
+                let column = self.column_at(self.cursor);
+                let c: String = if self.cursor == self.content.len() {
+                    self.filler.clone()
                 } else {
                     // Get the char from the string... Is it so hard?
-                    let selected = self.content[self.cursor..]
-                        .graphemes(true)
-                        .next()
+                    let selected = graphemes::next(&self.content, self.cursor)
                         .unwrap_or_else(|| {
                             panic!(
                                 "Found no char at cursor {} in {}",
                                 self.cursor, &self.content
                             )
                         });
-                    if self.secret {
-                        make_small_stars(selected.width())
+                    let width =
+                        tabs::grapheme_width(selected, column, self.tab_width);
+                    if self.is_masked() {
+                        repeat_to(&self.mask, width)
                     } else {
-                        selected
+                        // A tab shows as a wide cursor.
+                        tabs::expand(selected, column, self.tab_width)
+                            .into_owned()
                     }
                 };
-                let offset = self.content[self.offset..self.cursor].width();
-                printer.print((offset, 0), c);
+                let offset = column - self.column_at(self.offset);
+                self.print_text(printer, offset, &c);
             }
         });
     }
@@ -605,79 +1210,24 @@ impl View for EditView {
     }
 
     fn on_event(&mut self, event: Event) -> EventResult {
-        match event {
-            Event::Char(ch) => {
-                return EventResult::Consumed(Some(self.insert(ch)));
-            }
-            // TODO: handle ctrl-key?
-            Event::Key(Key::Home) => self.set_cursor(0),
-            Event::Key(Key::End) => {
-                // When possible, NLL to the rescue!
-                let len = self.content.len();
-                self.set_cursor(len);
-            }
-            Event::Key(Key::Left) if self.cursor > 0 => {
-                let len = self.content[..self.cursor]
-                    .graphemes(true)
-                    .last()
-                    .unwrap()
-                    .len();
-                let cursor = self.cursor - len;
-                self.set_cursor(cursor);
-            }
-            Event::Key(Key::Right) if self.cursor < self.content.len() => {
-                let len = self.content[self.cursor..]
-                    .graphemes(true)
-                    .next()
-                    .unwrap()
-                    .len();
-                let cursor = self.cursor + len;
-                self.set_cursor(cursor);
-            }
-            Event::Key(Key::Backspace) if self.cursor > 0 => {
-                let len = self.content[..self.cursor]
-                    .graphemes(true)
-                    .last()
-                    .unwrap()
-                    .len();
-                self.cursor -= len;
-                return EventResult::Consumed(Some(self.remove(len)));
-            }
-            Event::Key(Key::Del) if self.cursor < self.content.len() => {
-                let len = self.content[self.cursor..]
-                    .graphemes(true)
-                    .next()
-                    .unwrap()
-                    .len();
-                return EventResult::Consumed(Some(self.remove(len)));
-            }
-            Event::Key(Key::Enter) if self.on_submit.is_some() => {
-                let cb = self.on_submit.clone().unwrap();
-                let content = Rc::clone(&self.content);
-                return EventResult::with_cb(move |s| {
-                    cb(s, &content);
-                });
-            }
-            Event::Mouse {
-                event: MouseEvent::Press(_),
-                position,
-                offset,
-            } if position.fits_in_rect(offset, (self.last_length, 1)) => {
-                if let Some(position) = position.checked_sub(offset) {
-                    self.cursor = self.offset
-                        + simple_prefix(
-                            &self.content[self.offset..],
-                            position.x,
-                        )
-                        .length;
-                }
-            }
-            _ => return EventResult::Ignored,
+        if !self.enabled {
+            return EventResult::Ignored;
         }
 
-        // self.keep_cursor_in_view();
+        if self.autocomplete.is_none() {
+            return self.on_edit_event(event);
+        }
 
-        EventResult::Consumed(self.make_edit_cb())
+        let before = Rc::clone(&self.content);
+        let result = self.on_edit_event(event);
+        if *before == *self.content {
+            return result;
+        }
+
+        let popup = self.autocomplete.as_ref().and_then(|autocomplete| {
+            autocomplete.popup(&self.content, self.last_length)
+        });
+        result.and(EventResult::Consumed(popup))
     }
 
     fn important_area(&self, _: Vec2) -> Rect {
@@ -686,15 +1236,383 @@ impl View for EditView {
             1
         } else {
             // Otherwise look at the selected character.
-            self.content[self.cursor..]
-                .graphemes(true)
-                .next()
-                .unwrap()
-                .width()
+            let g = graphemes::next(&self.content, self.cursor).unwrap();
+            let column = self.column_at(self.cursor);
+            tabs::grapheme_width(g, column, self.tab_width)
         };
 
-        let x = self.content[..self.cursor].width();
+        // Only the content after `offset` is visible.
+        let x = self.column_at(self.cursor) - self.column_at(self.offset);
+        let x = self.direction.mirror(x, char_width, self.last_length);
 
         Rect::from_size((x, 0), (char_width, 1))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backend::puppet;
+    use event::MouseButton;
+    use std::cell::RefCell;
+    use view::Identifiable;
+    use views::{BoxView, LinearLayout, TextView};
+
+    #[test]
+    fn test_validator() {
+        let mut view = EditView::new().validator(|content, cursor| {
+            if !content.chars().all(|c| c.is_alphanumeric()) {
+                Validation::Reject
+            } else if content.len() > 3 {
+                Validation::Invalid
+            } else {
+                Validation::Replace(content.to_uppercase(), cursor)
+            }
+        });
+
+        for c in "a1-b".chars() {
+            view.on_event(Event::Char(c));
+        }
+        assert_eq!(&*view.get_content(), "A1B");
+        assert_eq!(view.cursor, 3);
+        assert!(!view.is_invalid());
+
+        view.on_event(Event::Char('c'));
+        assert_eq!(&*view.get_content(), "A1Bc");
+        assert!(view.is_invalid());
+
+        view.on_event(Event::Key(Key::Backspace));
+        assert!(!view.is_invalid());
+        view.on_event(Event::Key(Key::Home));
+        view.on_event(Event::Key(Key::Del));
+        assert_eq!(&*view.get_content(), "1B");
+        assert_eq!(view.cursor, 0);
+
+        view.on_event(Event::CtrlChar('z'));
+        assert_eq!(&*view.get_content(), "A1B");
+    }
+
+    #[test]
+    fn test_secret() {
+        let backend = puppet::Backend::init(Some(Vec2::new(8, 1)));
+        let mut siv = Cursive::new(move || backend);
+        let view = EditView::new().secret().mask("•").content("日本a");
+        siv.add_fullscreen_layer(BoxView::with_full_width(view.with_id("e")));
+
+        assert_eq!(siv.dump_screen(), vec!["•••••___"]);
+
+        siv.call_on_id("e", |view: &mut EditView| {
+            assert_eq!(&*view.get_content(), "日本a");
+            view.set_revealed(true);
+        });
+        assert_eq!(siv.dump_screen(), vec!["日本a___"]);
+
+        // An empty mask still hides the content.
+        siv.call_on_id("e", |view: &mut EditView| {
+            view.set_revealed(false);
+            view.set_mask("");
+            view.set_filler("");
+        });
+        assert_eq!(siv.dump_screen(), vec!["        "]);
+    }
+
+    #[test]
+    fn test_undo() {
+        let mut view = EditView::new();
+        for c in "foo bar".chars() {
+            view.on_event(Event::Char(c));
+        }
+        view.on_event(Event::Key(Key::Left));
+        view.on_event(Event::Key(Key::Backspace));
+        assert_eq!(&*view.get_content(), "foo br");
+
+        view.on_event(Event::CtrlChar('z'));
+        assert_eq!(&*view.get_content(), "foo bar");
+        assert_eq!(view.cursor, 6);
+
+        view.on_event(Event::CtrlChar('z'));
+        assert_eq!(&*view.get_content(), "foo ");
+        assert_eq!(view.cursor, 4);
+
+        view.on_event(Event::CtrlChar('Z'));
+        assert_eq!(&*view.get_content(), "foo bar");
+        assert_eq!(view.cursor, 7);
+
+        view.set_content("new");
+        view.on_event(Event::CtrlChar('z'));
+        assert_eq!(&*view.get_content(), "new");
+    }
+
+    #[test]
+    fn test_autocomplete() {
+        let backend = puppet::Backend::init(Some(Vec2::new(10, 4)));
+        let mut siv = Cursive::new(move || backend);
+
+        let submitted = Rc::new(RefCell::new(Vec::new()));
+        let result = Rc::clone(&submitted);
+        let view = EditView::new()
+            .autocomplete_list(vec!["apple", "Apricot", "banana"])
+            .on_submit(move |_, text| {
+                result.borrow_mut().push(text.to_string());
+            });
+        siv.add_fullscreen_layer(BoxView::with_full_width(view.with_id("e")));
+        siv.dump_screen();
+
+        let content = |siv: &mut Cursive| {
+            siv.call_on_id("e", |view: &mut EditView| view.get_content())
+                .unwrap()
+        };
+
+        for c in "ap".chars() {
+            siv.on_event(Event::Char(c));
+        }
+        assert_eq!(
+            siv.dump_screen(),
+            vec!["ap________", "apple     ", "Apricot   ", "          "]
+        );
+
+        siv.on_event(Event::Key(Key::Down));
+        siv.on_event(Event::Key(Key::Tab));
+        assert_eq!(&*content(&mut siv), "Apricot");
+        assert_eq!(siv.screen().len(), 1);
+
+        // Nothing to suggest: <Enter> submits.
+        siv.on_event(Event::Key(Key::Enter));
+        assert_eq!(&submitted.borrow()[..], &["Apricot".to_string()]);
+
+        // <Esc> keeps the content.
+        siv.on_event(Event::Key(Key::Backspace));
+        assert_eq!(siv.screen().len(), 2);
+        siv.on_event(Event::Key(Key::Esc));
+        assert_eq!(&*content(&mut siv), "Aprico");
+        assert_eq!(siv.screen().len(), 1);
+    }
+
+    #[test]
+    fn test_undo_completion() {
+        let backend = puppet::Backend::init(Some(Vec2::new(10, 4)));
+        let mut siv = Cursive::new(move || backend);
+        let view = EditView::new().autocomplete_list(vec!["apple", "Apricot"]);
+        siv.add_fullscreen_layer(BoxView::with_full_width(view.with_id("e")));
+        siv.dump_screen();
+
+        let content = |siv: &mut Cursive| {
+            siv.call_on_id("e", |view: &mut EditView| view.get_content())
+                .unwrap()
+        };
+
+        for c in "ap".chars() {
+            siv.on_event(Event::Char(c));
+        }
+        siv.on_event(Event::Key(Key::Down));
+        siv.on_event(Event::Key(Key::Tab));
+        assert_eq!(&*content(&mut siv), "Apricot");
+
+        // The completion is undone at once, back to the typed text.
+        siv.on_event(Event::CtrlChar('z'));
+        assert_eq!(&*content(&mut siv), "ap");
+        siv.on_event(Event::Key(Key::Esc));
+        siv.on_event(Event::CtrlChar('y'));
+        assert_eq!(&*content(&mut siv), "Apricot");
+    }
+
+    #[test]
+    fn test_autocomplete_popup() {
+        let backend = puppet::Backend::init(Some(Vec2::new(10, 5)));
+        let frames = backend.stream();
+        let mut siv = Cursive::new(move || backend);
+        let view = EditView::new().autocomplete_list(vec!["apple", "Apricot"]);
+        siv.add_fullscreen_layer(BoxView::with_full_width(
+            LinearLayout::vertical()
+                .child(TextView::new("Fruit:"))
+                .child(view),
+        ));
+        siv.dump_screen();
+
+        // The popup is found below the field, wherever it is.
+        siv.on_event(Event::Char('a'));
+        assert_eq!(
+            siv.dump_screen(),
+            vec![
+                "Fruit:    ",
+                "a_________",
+                "apple     ",
+                "Apricot   ",
+                "          ",
+            ]
+        );
+
+        // The cursor stays visible while the popup has the focus.
+        siv.refresh();
+        let screen = frames.try_iter().last().unwrap();
+        let effects = |x| screen.cell(Vec2::new(x, 1)).unwrap().style.effects;
+        assert!(!effects(1).contains(Effect::Reverse));
+        assert!(effects(2).contains(Effect::Reverse));
+    }
+
+    #[test]
+    fn test_autocomplete_below_menubar() {
+        let backend = puppet::Backend::init(Some(Vec2::new(10, 6)));
+        let mut siv = Cursive::new(move || backend);
+        siv.menubar().add_leaf("File", |_| ());
+        siv.set_autohide_menu(false);
+        let view = EditView::new().autocomplete_list(vec!["apple"]);
+        siv.add_fullscreen_layer(BoxView::with_full_width(view));
+        siv.dump_screen();
+
+        siv.on_event(Event::Char('a'));
+        assert_eq!(siv.dump_screen()[1..3], ["a_________", "apple     "]);
+    }
+
+    #[test]
+    fn test_paste() {
+        let backend = puppet::Backend::init(Some(Vec2::new(10, 1)));
+        let mut siv = Cursive::new(move || backend);
+        let view = EditView::new().content("ab");
+        siv.add_fullscreen_layer(BoxView::with_full_width(view.with_id("e")));
+
+        siv.clipboard_mut().set("xyz");
+        siv.on_event(Event::Key(Key::Left));
+        siv.on_event(Event::CtrlChar('v'));
+        // The clipboard sends the text back as an update.
+        siv.step();
+        assert_eq!(siv.dump_screen(), vec!["axyzb_____"]);
+    }
+
+    #[test]
+    fn test_rtl() {
+        let backend = puppet::Backend::init(Some(Vec2::new(6, 1)));
+        let mut siv = Cursive::new(move || backend);
+        let view = EditView::new()
+            .content("שלום")
+            .direction(TextDirection::RightToLeft);
+        siv.add_fullscreen_layer(BoxView::with_full_width(view.with_id("e")));
+
+        // The content starts on the right.
+        assert_eq!(siv.dump_screen(), vec!["__םולש"]);
+
+        let cursor = |siv: &mut Cursive| {
+            siv.call_on_id("e", |view: &mut EditView| {
+                (view.cursor, view.important_area(Vec2::new(6, 1)).left())
+            })
+            .unwrap()
+        };
+
+        // <Left> goes forward, towards the end of the content.
+        siv.on_event(Event::Key(Key::Home));
+        assert_eq!(cursor(&mut siv), (0, 5));
+        siv.on_event(Event::Key(Key::Left));
+        assert_eq!(cursor(&mut siv), (2, 4));
+        siv.on_event(Event::Key(Key::Right));
+        assert_eq!(cursor(&mut siv), (0, 5));
+
+        // Clicks land on the grapheme under the mouse.
+        siv.on_event(Event::Mouse {
+            offset: Vec2::zero(),
+            position: Vec2::new(3, 0),
+            event: MouseEvent::Press(MouseButton::Left),
+        });
+        assert_eq!(cursor(&mut siv), (4, 3));
+
+        siv.on_event(Event::Char('!'));
+        assert_eq!(siv.dump_screen(), vec!["_םו!לש"]);
+    }
+
+    #[test]
+    fn test_wide_cursor() {
+        let mut view = EditView::new().content("日本語abc");
+        view.layout(Vec2::new(5, 1));
+        view.on_event(Event::Key(Key::Home));
+
+        let mut areas = Vec::new();
+        for _ in 0..4 {
+            view.on_event(Event::Key(Key::Right));
+            let area = view.important_area(Vec2::new(5, 1));
+            areas.push((view.offset, area.left(), area.width()));
+        }
+
+        // The cursor stays in view, on the right cell.
+        assert_eq!(areas, vec![(0, 2, 2), (3, 2, 2), (3, 4, 1), (6, 3, 1)]);
+    }
+
+    #[test]
+    fn test_graphemes() {
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        let mut view =
+            EditView::new().content(format!("e\u{301}{}x", family));
+        view.layout(Vec2::new(10, 1));
+        view.on_event(Event::Key(Key::Home));
+
+        // Each grapheme is a single step, however many code points it has.
+        view.on_event(Event::Key(Key::Right));
+        assert_eq!(view.cursor, 3);
+        view.on_event(Event::Key(Key::Right));
+        assert_eq!(view.cursor, 3 + family.len());
+        assert_eq!(view.important_area(Vec2::new(10, 1)).left(), 3);
+        view.on_event(Event::Key(Key::Left));
+        assert_eq!(view.cursor, 3);
+        assert_eq!(view.important_area(Vec2::new(10, 1)).width(), 2);
+
+        view.on_event(Event::Key(Key::Del));
+        assert_eq!(&*view.get_content(), "e\u{301}x");
+        view.on_event(Event::Key(Key::Backspace));
+        assert_eq!(&*view.get_content(), "x");
+        assert_eq!(view.cursor, 0);
+    }
+
+    #[test]
+    fn test_words() {
+        let mut view = EditView::new()
+            .content("foo-bar,   cr\u{e8}me  br\u{fb}l\u{e9}e");
+        view.layout(Vec2::new(40, 1));
+        view.on_event(Event::Key(Key::Home));
+
+        let mut cursors = Vec::new();
+        for _ in 0..5 {
+            view.on_event(Event::Ctrl(Key::Right));
+            cursors.push(view.cursor);
+        }
+        assert_eq!(cursors, vec![3, 7, 17, 27, 27]);
+
+        cursors.clear();
+        for _ in 0..5 {
+            view.on_event(Event::Ctrl(Key::Left));
+            cursors.push(view.cursor);
+        }
+        assert_eq!(cursors, vec![19, 11, 4, 0, 0]);
+
+        view.on_event(Event::Key(Key::End));
+        view.on_event(Event::Key(Key::Backspace));
+        view.on_event(Event::CtrlChar('w'));
+        assert_eq!(&*view.get_content(), "foo-bar,   cr\u{e8}me  ");
+        // Deleting a word takes the spaces after it.
+        view.on_event(Event::Ctrl(Key::Backspace));
+        assert_eq!(&*view.get_content(), "foo-bar,   ");
+        view.undo();
+        assert_eq!(&*view.get_content(), "foo-bar,   cr\u{e8}me  ");
+
+        view.on_event(Event::Ctrl(Key::Left));
+        view.on_event(Event::CtrlChar('u'));
+        assert_eq!(&*view.get_content(), "cr\u{e8}me  ");
+        assert_eq!(view.cursor, 0);
+    }
+
+    #[test]
+    fn test_tabs() {
+        let mut view = EditView::new().content("a\tb");
+        view.layout(Vec2::new(10, 1));
+        view.on_event(Event::Key(Key::Home));
+
+        // The tab is a single step, and reaches the next tab stop.
+        view.on_event(Event::Key(Key::Right));
+        assert_eq!(view.important_area(Vec2::new(10, 1)).width(), 3);
+        view.on_event(Event::Key(Key::Right));
+        assert_eq!(view.cursor, 2);
+        assert_eq!(view.important_area(Vec2::new(10, 1)).left(), 4);
+
+        view.set_tab_width(2);
+        assert_eq!(view.important_area(Vec2::new(10, 1)).left(), 2);
+        view.on_event(Event::Key(Key::Backspace));
+        assert_eq!(&*view.get_content(), "ab");
+    }
+}
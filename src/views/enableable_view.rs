@@ -1,10 +1,12 @@
+use direction::Direction;
 use event::{Event, EventResult};
 use view::{View, ViewWrapper};
 use {Printer, With};
 
 /// Wrapper around another view that can be enabled/disabled at will.
 ///
-/// When disabled, all child views will be disabled and will stop receiving events.
+/// When disabled, all child views will be disabled: they will stop receiving
+/// events, and cannot take the focus.
 pub struct EnableableView<V> {
     view: V,
     enabled: bool,
@@ -38,4 +40,8 @@ impl<V: View> ViewWrapper for EnableableView<V> {
     fn wrap_draw(&self, printer: &Printer) {
         self.view.draw(&printer.enabled(self.enabled));
     }
+
+    fn wrap_take_focus(&mut self, source: Direction) -> bool {
+        self.enabled && self.view.take_focus(source)
+    }
 }
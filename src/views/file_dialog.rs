@@ -0,0 +1,445 @@
+use std::cell::RefCell;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use view::{Boxable, Finder, Identifiable, Scrollable, ViewWrapper};
+use views::{
+    Dialog, DummyView, EditView, LinearLayout, SelectView, TextContent,
+    TextView,
+};
+use Cursive;
+use With;
+
+type SubmitCallback = Fn(&mut Cursive, PathBuf);
+
+// Used to give a unique id to the list of each dialog.
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// An item from the directory listing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Entry {
+    /// A directory, including `..`.
+    Dir(PathBuf),
+    /// A regular file, or a link to one.
+    File(PathBuf),
+    /// A symbolic link to nothing.
+    BrokenLink(PathBuf),
+}
+
+impl Entry {
+    fn path(&self) -> &Path {
+        match *self {
+            Entry::Dir(ref path)
+            | Entry::File(ref path)
+            | Entry::BrokenLink(ref path) => path,
+        }
+    }
+}
+
+struct State {
+    dir: PathBuf,
+    // Lowercase extensions, without the leading dot.
+    extensions: Vec<String>,
+    on_submit: Option<Rc<SubmitCallback>>,
+    list_id: String,
+    path: TextContent,
+    error: TextContent,
+}
+
+impl State {
+    /// Lists `dir` in `list`.
+    ///
+    /// On error, keeps the current listing and shows the error instead.
+    fn open(&mut self, list: &mut SelectView<Entry>, dir: PathBuf) -> bool {
+        // Resolves `..` and symbolic links, so the parent is always known.
+        let dir = fs::canonicalize(&dir).unwrap_or(dir);
+        match list_dir(&dir, &self.extensions) {
+            Ok(entries) => {
+                list.clear();
+                for (label, entry) in entries {
+                    list.add_item(label, entry);
+                }
+                self.path.set_content(dir.display().to_string());
+                self.error.set_content("");
+                self.dir = dir;
+                true
+            }
+            Err(err) => {
+                self.show_error(&dir, &err);
+                false
+            }
+        }
+    }
+
+    fn show_error(&mut self, path: &Path, err: &io::Error) {
+        self.error
+            .set_content(format!("Cannot open {}: {}", path.display(), err));
+    }
+}
+
+/// Dialog to pick a file from the filesystem.
+///
+/// It lists a directory: pressing `<Enter>` on a directory opens it, and
+/// the `..` entry goes back to the parent directory. Pressing `<Enter>` on
+/// a file, or the `Open` button, submits it.
+///
+/// A path can also be typed in the text field at the bottom; relative paths
+/// are relative to the current directory.
+///
+/// Errors, like a directory without read permission or a broken symbolic
+/// link, are shown in the dialog.
+///
+/// Submitting a file removes the top layer, like the `Cancel` button, then
+/// runs the [`on_submit`] callback.
+///
+/// [`on_submit`]: #method.on_submit
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use cursive::views::{Dialog, FileDialog};
+/// # let mut siv = cursive::Cursive::dummy();
+/// siv.add_layer(
+///     FileDialog::new(".")
+///         .extensions(["toml", "rs"])
+///         .on_submit(|s, path| {
+///             let text = format!("Opening {}", path.display());
+///             s.add_layer(Dialog::info(text));
+///         }),
+/// );
+/// ```
+pub struct FileDialog {
+    dialog: Dialog,
+    state: Rc<RefCell<State>>,
+}
+
+impl FileDialog {
+    /// Creates a new `FileDialog` showing the content of `dir`.
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        let dir = dir.into();
+        let list_id = format!(
+            "_cursive_file_dialog_{}",
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        );
+        let state = Rc::new(RefCell::new(State {
+            dir: dir.clone(),
+            extensions: Vec::new(),
+            on_submit: None,
+            list_id: list_id.clone(),
+            path: TextContent::new(dir.display().to_string()),
+            error: TextContent::new(""),
+        }));
+
+        let mut list = SelectView::new();
+        if !state.borrow_mut().open(&mut list, dir.clone()) {
+            // Still offer a way out.
+            if let Some(parent) = dir.parent() {
+                list.add_item("../", Entry::Dir(parent.to_path_buf()));
+            }
+        }
+
+        let select_state = Rc::clone(&state);
+        list.set_on_select(move |_, entry: &Entry| {
+            let path = entry.path().display().to_string();
+            select_state.borrow_mut().path.set_content(path);
+        });
+
+        let submit_state = Rc::clone(&state);
+        list.set_on_submit(move |s, entry: &Entry| {
+            activate(s, &submit_state, entry.clone());
+        });
+
+        let edit_state = Rc::clone(&state);
+        let edit = EditView::new().on_submit(move |s, text| {
+            let path = edit_state.borrow().dir.join(text);
+            let entry = if path.is_dir() {
+                Entry::Dir(path)
+            } else {
+                Entry::File(path)
+            };
+            activate(s, &edit_state, entry);
+        });
+
+        let button_state = Rc::clone(&state);
+        let (path, error) = {
+            let state = state.borrow();
+            (state.path.clone(), state.error.clone())
+        };
+
+        let content = LinearLayout::vertical()
+            .child(TextView::new_with_content(path))
+            .child(DummyView)
+            .child(list.with_id(list_id).scrollable().fixed_size((40, 10)))
+            .child(TextView::new_with_content(error))
+            .child(edit);
+
+        let dialog = Dialog::around(content)
+            .title("Open file")
+            .button("Open", move |s| {
+                let id = button_state.borrow().list_id.clone();
+                let selection = s
+                    .call_on_id(&id, |list: &mut SelectView<Entry>| {
+                        list.selection()
+                    })
+                    .and_then(|selection| selection);
+                if let Some(entry) = selection {
+                    activate(s, &button_state, (*entry).clone());
+                }
+            })
+            .dismiss_button("Cancel");
+
+        FileDialog { dialog, state }
+    }
+
+    /// Only shows files with one of the given extensions.
+    ///
+    /// Extensions are given without the leading dot, and compared without
+    /// taking case into account. Directories are always shown.
+    ///
+    /// An empty list shows every file.
+    pub fn set_extensions<I, S>(&mut self, extensions: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let state = &self.state;
+        state.borrow_mut().extensions = extensions
+            .into_iter()
+            .map(|ext| ext.as_ref().to_lowercase())
+            .collect();
+
+        let (id, dir) = {
+            let state = state.borrow();
+            (state.list_id.clone(), state.dir.clone())
+        };
+        self.dialog
+            .call_on_id(&id, |list: &mut SelectView<Entry>| {
+                state.borrow_mut().open(list, dir)
+            });
+    }
+
+    /// Only shows files with one of the given extensions.
+    ///
+    /// Chainable variant.
+    pub fn extensions<I, S>(self, extensions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.with(|s| s.set_extensions(extensions))
+    }
+
+    /// Sets the callback to run when a file is submitted.
+    pub fn set_on_submit<F>(&mut self, cb: F)
+    where
+        F: Fn(&mut Cursive, PathBuf) + 'static,
+    {
+        self.state.borrow_mut().on_submit = Some(Rc::new(cb));
+    }
+
+    /// Sets the callback to run when a file is submitted.
+    ///
+    /// Chainable variant.
+    pub fn on_submit<F>(self, cb: F) -> Self
+    where
+        F: Fn(&mut Cursive, PathBuf) + 'static,
+    {
+        self.with(|s| s.set_on_submit(cb))
+    }
+
+    /// Sets the title of the dialog.
+    pub fn set_title<S: Into<String>>(&mut self, title: S) {
+        self.dialog.set_title(title);
+    }
+
+    /// Sets the title of the dialog.
+    ///
+    /// Chainable variant.
+    pub fn title<S: Into<String>>(self, title: S) -> Self {
+        self.with(|s| s.set_title(title))
+    }
+
+    /// Returns the directory currently shown.
+    pub fn directory(&self) -> PathBuf {
+        self.state.borrow().dir.clone()
+    }
+}
+
+impl ViewWrapper for FileDialog {
+    wrap_impl!(self.dialog: Dialog);
+}
+
+/// Opens a directory, or submits a file.
+fn activate(siv: &mut Cursive, state: &Rc<RefCell<State>>, entry: Entry) {
+    match entry {
+        Entry::Dir(dir) => {
+            let id = state.borrow().list_id.clone();
+            siv.call_on_id(&id, |list: &mut SelectView<Entry>| {
+                state.borrow_mut().open(list, dir)
+            });
+        }
+        Entry::File(ref path) if !path.is_file() => {
+            let err = io::Error::new(io::ErrorKind::NotFound, "not a file");
+            state.borrow_mut().show_error(path, &err);
+        }
+        Entry::File(path) => {
+            let cb = state.borrow().on_submit.clone();
+            siv.pop_layer();
+            if let Some(cb) = cb {
+                cb(siv, path);
+            }
+        }
+        Entry::BrokenLink(path) => {
+            let err = io::Error::new(io::ErrorKind::NotFound, "broken link");
+            state.borrow_mut().show_error(&path, &err);
+        }
+    }
+}
+
+/// Lists the content of `dir`, with labels.
+///
+/// The parent directory comes first, then directories, then files with one
+/// of the given extensions (or all files if `extensions` is empty).
+fn list_dir(
+    dir: &Path, extensions: &[String],
+) -> io::Result<Vec<(String, Entry)>> {
+    let mut dirs = Vec::new();
+    let mut files = Vec::new();
+
+    for entry in fs::read_dir(dir)? {
+        // Entries we cannot read are skipped.
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(_) => continue,
+        };
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let path = entry.path();
+
+        // `fs::metadata` follows symbolic links.
+        match fs::metadata(&path) {
+            Ok(ref metadata) if metadata.is_dir() => {
+                dirs.push((format!("{}/", name), Entry::Dir(path)));
+            }
+            Ok(_) => {
+                if matches_extension(&path, extensions) {
+                    files.push((name, Entry::File(path)));
+                }
+            }
+            Err(_) => {
+                let label = format!("{} (broken link)", name);
+                files.push((label, Entry::BrokenLink(path)));
+            }
+        }
+    }
+
+    dirs.sort_by(|a, b| a.0.cmp(&b.0));
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut result = Vec::new();
+    if let Some(parent) = dir.parent() {
+        result.push(("../".to_string(), Entry::Dir(parent.to_path_buf())));
+    }
+    result.extend(dirs);
+    result.extend(files);
+
+    Ok(result)
+}
+
+fn matches_extension(path: &Path, extensions: &[String]) -> bool {
+    if extensions.is_empty() {
+        return true;
+    }
+
+    path.extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .map(|ext| extensions.contains(&ext))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backend;
+    use event::{Event, Key};
+    use std::env;
+    use std::process;
+    use vec::Vec2;
+
+    // Creates a new directory with some files.
+    fn setup(name: &str) -> PathBuf {
+        let name = format!("cursive_file_dialog_{}_{}", name, process::id());
+        let dir = env::temp_dir().join(name);
+        if dir.exists() {
+            fs::remove_dir_all(&dir).unwrap();
+        }
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join("b.txt"), "").unwrap();
+        fs::write(dir.join("a.RS"), "").unwrap();
+        fs::write(dir.join("sub").join("c.txt"), "").unwrap();
+        fs::canonicalize(dir).unwrap()
+    }
+
+    fn labels(dir: &Path, extensions: &[&str]) -> Vec<String> {
+        let extensions: Vec<_> =
+            extensions.iter().map(|ext| ext.to_string()).collect();
+        list_dir(dir, &extensions)
+            .unwrap()
+            .into_iter()
+            .map(|(label, _)| label)
+            .collect()
+    }
+
+    #[test]
+    fn test_list_dir() {
+        let dir = setup("list");
+
+        assert_eq!(labels(&dir, &[]), vec!["../", "sub/", "a.RS", "b.txt"]);
+        assert_eq!(labels(&dir, &["rs"]), vec!["../", "sub/", "a.RS"]);
+        assert!(list_dir(&dir.join("nope"), &[]).is_err());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::symlink;
+            symlink(dir.join("nope"), dir.join("link")).unwrap();
+            assert_eq!(
+                labels(&dir, &["txt"]),
+                vec!["../", "sub/", "b.txt", "link (broken link)"]
+            );
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_navigation() {
+        let dir = setup("navigation");
+        let backend = backend::puppet::Backend::init(Some(Vec2::new(50, 20)));
+        let mut siv = Cursive::new(move || backend);
+
+        let submitted = Rc::new(RefCell::new(None));
+        let result = Rc::clone(&submitted);
+        siv.add_layer(FileDialog::new(&dir).extensions(["txt"]).on_submit(
+            move |_, path| {
+                *result.borrow_mut() = Some(path);
+            },
+        ));
+        siv.dump_screen();
+
+        // Open "sub/", then submit "c.txt".
+        siv.on_event(Event::Key(Key::Down));
+        siv.on_event(Event::Key(Key::Enter));
+        siv.dump_screen();
+        siv.on_event(Event::Key(Key::Down));
+        siv.on_event(Event::Key(Key::Enter));
+
+        assert_eq!(
+            submitted.borrow().clone(),
+            Some(dir.join("sub").join("c.txt"))
+        );
+        assert_eq!(siv.screen().len(), 0);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,212 @@
+use event::{Event, EventResult};
+use view::{View, ViewWrapper};
+use With;
+
+type FocusCallback<T> = Box<FnMut(&mut T) -> EventResult>;
+
+/// Runs callbacks when the wrapped view gains or loses the focus.
+///
+/// Layouts like [`LinearLayout`], [`ListView`] or [`Dialog`] send
+/// `Event::FocusGained` and `Event::FocusLost` to their children when the
+/// focus moves between them, after a key press or a mouse click. So does
+/// [`TabView`] when switching tabs. This view reacts to these events.
+///
+/// Changing the focus programmatically, for example with
+/// `LinearLayout::set_focus_index`, doesn't trigger the callbacks. The
+/// exception is [`StackView`]: its front layer is told when it changes,
+/// however it happened, before the next event or step of the event loop.
+///
+/// [`LinearLayout`]: struct.LinearLayout.html
+/// [`ListView`]: struct.ListView.html
+/// [`Dialog`]: struct.Dialog.html
+/// [`TabView`]: struct.TabView.html
+/// [`StackView`]: struct.StackView.html
+///
+/// # Examples
+///
+/// ```rust
+/// # use cursive::event::EventResult;
+/// # use cursive::views::{EditView, FocusTracker};
+/// let view = FocusTracker::new(EditView::new()).on_blur(|edit| {
+///     let valid = edit.get_content().parse::<u32>().is_ok();
+///     EventResult::with_cb(move |s| {
+///         if !valid {
+///             s.add_layer(cursive::views::Dialog::info("Invalid number!"));
+///         }
+///     })
+/// });
+/// ```
+pub struct FocusTracker<T> {
+    view: T,
+    on_focus: FocusCallback<T>,
+    on_blur: FocusCallback<T>,
+}
+
+impl<T> FocusTracker<T> {
+    /// Wraps `view` in a new `FocusTracker`.
+    pub fn new(view: T) -> Self {
+        FocusTracker {
+            view,
+            on_focus: Box::new(|_| EventResult::Ignored),
+            on_blur: Box::new(|_| EventResult::Ignored),
+        }
+    }
+
+    /// Sets a callback to run when the view gains the focus.
+    ///
+    /// It is given the wrapped view, and can return a callback to run on
+    /// the `Cursive` root.
+    pub fn set_on_focus<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut T) -> EventResult + 'static,
+    {
+        self.on_focus = Box::new(f);
+    }
+
+    /// Sets a callback to run when the view gains the focus.
+    ///
+    /// Chainable variant.
+    pub fn on_focus<F>(self, f: F) -> Self
+    where
+        F: FnMut(&mut T) -> EventResult + 'static,
+    {
+        self.with(|s| s.set_on_focus(f))
+    }
+
+    /// Sets a callback to run when the view loses the focus.
+    ///
+    /// It is given the wrapped view, and can return a callback to run on
+    /// the `Cursive` root.
+    pub fn set_on_blur<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut T) -> EventResult + 'static,
+    {
+        self.on_blur = Box::new(f);
+    }
+
+    /// Sets a callback to run when the view loses the focus.
+    ///
+    /// Chainable variant.
+    pub fn on_blur<F>(self, f: F) -> Self
+    where
+        F: FnMut(&mut T) -> EventResult + 'static,
+    {
+        self.with(|s| s.set_on_blur(f))
+    }
+}
+
+impl<T: View> ViewWrapper for FocusTracker<T> {
+    wrap_impl!(self.view: T);
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        // Nested views also need to know about the focus change.
+        let result = self.view.on_event(event.clone());
+
+        match event {
+            Event::FocusGained => result.and((self.on_focus)(&mut self.view)),
+            Event::FocusLost => result.and((self.on_blur)(&mut self.view)),
+            _ => result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backend;
+    use event::{Key, MouseButton, MouseEvent};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use vec::Vec2;
+    use views::{BoxView, Button, LinearLayout, TabView};
+    use Cursive;
+
+    type Log = Rc<RefCell<Vec<String>>>;
+
+    // A button logging its focus changes.
+    fn tracked(log: &Log, name: &'static str) -> FocusTracker<Button> {
+        let (focus, blur) = (Rc::clone(log), Rc::clone(log));
+        FocusTracker::new(Button::new(name, |_| ()))
+            .on_focus(move |_| {
+                focus.borrow_mut().push(format!("focus {}", name));
+                EventResult::Consumed(None)
+            })
+            .on_blur(move |_| {
+                blur.borrow_mut().push(format!("blur {}", name));
+                EventResult::Consumed(None)
+            })
+    }
+
+    #[test]
+    fn test_focus_events() {
+        let backend = backend::puppet::Backend::init(Some(Vec2::new(20, 5)));
+        let mut siv = Cursive::new(move || backend);
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let tracked = |name| tracked(&log, name);
+
+        siv.add_fullscreen_layer(BoxView::with_full_screen(
+            LinearLayout::vertical()
+                .child(tracked("a"))
+                .child(tracked("b")),
+        ));
+        siv.dump_screen();
+
+        siv.on_event(Event::Key(Key::Down));
+        // Already focused: nothing changes.
+        siv.on_event(Event::Mouse {
+            offset: Vec2::zero(),
+            position: Vec2::new(1, 1),
+            event: MouseEvent::Press(MouseButton::Left),
+        });
+        siv.on_event(Event::Mouse {
+            offset: Vec2::zero(),
+            position: Vec2::new(1, 0),
+            event: MouseEvent::Press(MouseButton::Left),
+        });
+
+        assert_eq!(
+            &log.borrow()[..],
+            &["blur a", "focus b", "blur b", "focus a"]
+        );
+    }
+
+    #[test]
+    fn test_tab_focus_events() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut tabs = TabView::new()
+            .tab("A", tracked(&log, "a"))
+            .tab("B", tracked(&log, "b"));
+        tabs.layout(Vec2::new(20, 5));
+
+        tabs.on_event(Event::Ctrl(Key::PageDown));
+        tabs.on_event(Event::Ctrl(Key::PageUp));
+        assert_eq!(
+            &log.borrow()[..],
+            &["blur a", "focus b", "blur b", "focus a"]
+        );
+    }
+
+    #[test]
+    fn test_layer_focus_events() {
+        let backend = backend::puppet::Backend::init(Some(Vec2::new(20, 5)));
+        let mut siv = Cursive::new(move || backend);
+
+        // Wakes up the event loop so `step` doesn't wait for input.
+        let step = |siv: &mut Cursive| {
+            siv.cb_sink().send(Box::new(|_: &mut Cursive| ())).unwrap();
+            siv.step();
+        };
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        siv.add_layer(tracked(&log, "a"));
+        step(&mut siv);
+        assert!(log.borrow().is_empty());
+
+        siv.add_layer(tracked(&log, "b"));
+        step(&mut siv);
+        siv.pop_layer();
+        siv.on_event(Event::Refresh);
+        assert_eq!(&log.borrow()[..], &["blur a", "focus b", "focus a"]);
+    }
+}
@@ -0,0 +1,796 @@
+use align::Align;
+use direction::{Absolute, Direction, Relative};
+use event::{AnyCb, Event, EventResult, Key, MouseEvent};
+use rect::Rect;
+use std::cmp::{max, min, Reverse};
+use vec::Vec2;
+use view::{FocusPath, LayoutTree, Selector, View};
+use Printer;
+use With;
+
+/// Arranges its children in a grid of rows and columns.
+///
+/// Unlike nested [`LinearLayout`]s, every cell in a column shares the same
+/// width, and every cell in a row shares the same height. A child can span
+/// several columns or rows.
+///
+/// Focus moves between cells in reading order with `<Tab>`, unless they
+/// were given a [`tab_index`], and between neighbouring cells with the
+/// arrow keys.
+///
+/// [`tab_index`]: #method.tab_index
+///
+/// [`LinearLayout`]: struct.LinearLayout.html
+///
+/// # Examples
+///
+/// ```rust
+/// # use cursive::align::Align;
+/// # use cursive::traits::*;
+/// # use cursive::views::{Button, EditView, GridLayout, TextView};
+/// let form = GridLayout::new()
+///     .gap((1, 0))
+///     .child((0, 0), TextView::new("Name"))
+///     .child((1, 0), EditView::new().fixed_width(20))
+///     .child((0, 1), TextView::new("Email"))
+///     .child((1, 1), EditView::new().fixed_width(20))
+///     .child((0, 2), Button::new("Ok", |s| s.quit()))
+///     .span((2, 1))
+///     .align(Align::center());
+/// ```
+pub struct GridLayout {
+    cells: Vec<Cell>,
+    focus: usize,
+    // Space between columns (x) and between rows (y).
+    gap: Vec2,
+
+    // Resolved by the last `required_size`.
+    widths: Vec<usize>,
+    heights: Vec<usize>,
+
+    invalidated: bool,
+}
+
+struct Cell {
+    view: Box<View>,
+    // Column (x) and row (y) of the top-left corner.
+    position: Vec2,
+    // Number of columns (x) and rows (y) covered.
+    span: Vec2,
+    // `None` fills the cell.
+    align: Option<Align>,
+    // Position in the `<Tab>` order.
+    tab_index: usize,
+
+    // Set during layout.
+    offset: Vec2,
+    size: Vec2,
+}
+
+impl Cell {
+    fn end(&self) -> Vec2 {
+        self.position + self.span
+    }
+
+    // Columns between this cell and `column`, 0 if it covers it.
+    fn column_distance(&self, column: usize) -> usize {
+        if column < self.position.x {
+            self.position.x - column
+        } else {
+            (column + 1).saturating_sub(self.end().x)
+        }
+    }
+}
+
+impl GridLayout {
+    /// Creates a new, empty grid.
+    pub fn new() -> Self {
+        GridLayout {
+            cells: Vec::new(),
+            focus: 0,
+            gap: Vec2::zero(),
+            widths: Vec::new(),
+            heights: Vec::new(),
+            invalidated: true,
+        }
+    }
+
+    /// Sets the space between columns (x) and between rows (y).
+    pub fn set_gap<S: Into<Vec2>>(&mut self, gap: S) {
+        self.gap = gap.into();
+        self.invalidated = true;
+    }
+
+    /// Sets the space between columns (x) and between rows (y).
+    ///
+    /// Chainable variant.
+    pub fn gap<S: Into<Vec2>>(self, gap: S) -> Self {
+        self.with(|s| s.set_gap(gap))
+    }
+
+    /// Adds a child at the given column (x) and row (y).
+    ///
+    /// The grid grows to include the new cell. The child covers one cell,
+    /// and fills it.
+    pub fn add_child<S, V>(&mut self, position: S, view: V)
+    where
+        S: Into<Vec2>,
+        V: View + 'static,
+    {
+        self.cells.push(Cell {
+            view: Box::new(view),
+            position: position.into(),
+            span: Vec2::new(1, 1),
+            align: None,
+            tab_index: 0,
+            offset: Vec2::zero(),
+            size: Vec2::zero(),
+        });
+        self.invalidated = true;
+    }
+
+    /// Adds a child at the given column (x) and row (y).
+    ///
+    /// Chainable variant.
+    pub fn child<S, V>(self, position: S, view: V) -> Self
+    where
+        S: Into<Vec2>,
+        V: View + 'static,
+    {
+        self.with(|s| s.add_child(position, view))
+    }
+
+    /// Makes the last child added cover `span` columns (x) and rows (y).
+    ///
+    /// It is an error to call this before adding a child (and it will panic).
+    pub fn span<S: Into<Vec2>>(mut self, span: S) -> Self {
+        let span = span.into().map(|v| max(v, 1));
+        self.cells.last_mut().unwrap().span = span;
+        self.invalidated = true;
+
+        self
+    }
+
+    /// Aligns the last child added within its cell.
+    ///
+    /// The child then keeps its required size, instead of filling the cell.
+    ///
+    /// It is an error to call this before adding a child (and it will panic).
+    pub fn align(mut self, align: Align) -> Self {
+        self.cells.last_mut().unwrap().align = Some(align);
+        self.invalidated = true;
+
+        self
+    }
+
+    /// Modifies the tab index of the last child added.
+    ///
+    /// `<Tab>` visits cells by increasing tab index, and cells with the same
+    /// index in reading order. Defaults to 0.
+    ///
+    /// It is an error to call this before adding a child (and it will panic).
+    pub fn tab_index(mut self, index: usize) -> Self {
+        self.cells.last_mut().unwrap().tab_index = index;
+
+        self
+    }
+
+    /// Returns the number of children.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Returns `true` if this view has no children.
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Returns the number of columns (x) and rows (y).
+    pub fn grid_size(&self) -> Vec2 {
+        self.cells.iter().map(Cell::end).fold(Vec2::zero(), Vec2::max)
+    }
+
+    /// Returns a reference to the child at the given position, if any.
+    pub fn get_child_at<S: Into<Vec2>>(&self, position: S) -> Option<&View> {
+        let position = position.into();
+        self.cells
+            .iter()
+            .find(|cell| cell.position == position)
+            .map(|cell| &*cell.view)
+    }
+
+    /// Returns a mutable reference to the child at the given position.
+    pub fn get_child_at_mut<S: Into<Vec2>>(
+        &mut self, position: S,
+    ) -> Option<&mut View> {
+        let position = position.into();
+        // Anything could happen to the child view.
+        self.invalidated = true;
+        self.cells
+            .iter_mut()
+            .find(|cell| cell.position == position)
+            .map(|cell| &mut *cell.view)
+    }
+
+    /// Returns the position of the focused child, if any.
+    pub fn get_focus_position(&self) -> Option<Vec2> {
+        self.cells.get(self.focus).map(|cell| cell.position)
+    }
+
+    // Indices of the cells, sorted by row then by column.
+    fn reading_order(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.cells.len()).collect();
+        order.sort_by_key(|&i| {
+            let position = self.cells[i].position;
+            (position.y, position.x)
+        });
+        order
+    }
+
+    // Indices of the cells, in `<Tab>` order.
+    fn tab_order(&self) -> Vec<usize> {
+        let mut order = self.reading_order();
+        order.sort_by_key(|&i| self.cells[i].tab_index);
+        order
+    }
+
+    // Focuses the first of `candidates` accepting the focus.
+    fn move_focus(&mut self, candidates: &[usize], source: Direction) -> bool {
+        for &i in candidates {
+            if self.cells[i].view.take_focus(source) {
+                self.focus = i;
+                return true;
+            }
+        }
+        false
+    }
+
+    // Cells to try, in order, when moving the focus after `event`.
+    //
+    // Also returns the direction the focus comes from.
+    fn neighbours(&self, event: &Event) -> Option<(Vec<usize>, Direction)> {
+        let order = self.reading_order();
+        let current = order.iter().position(|&i| i == self.focus).unwrap();
+        let (before, after) = (&order[..current], &order[current + 1..]);
+        let focused = &self.cells[self.focus];
+        let same_row = |i: &&usize| {
+            self.cells[**i].position.y == focused.position.y
+        };
+
+        let tabs = self.tab_order();
+        let tab = tabs.iter().position(|&i| i == self.focus).unwrap();
+
+        let candidates = match *event {
+            Event::Key(Key::Tab) => tabs[tab + 1..].to_vec(),
+            Event::Shift(Key::Tab) => {
+                tabs[..tab].iter().rev().cloned().collect()
+            }
+            Event::Key(Key::Right) => {
+                after.iter().filter(same_row).cloned().collect()
+            }
+            Event::Key(Key::Left) => {
+                before.iter().rev().filter(same_row).cloned().collect()
+            }
+            Event::Key(Key::Down) => {
+                let mut below: Vec<usize> = (0..self.cells.len())
+                    .filter(|&i| self.cells[i].position.y >= focused.end().y)
+                    .collect();
+                below.sort_by_key(|&i| {
+                    let cell = &self.cells[i];
+                    let distance = cell.column_distance(focused.position.x);
+                    (distance, cell.position.y, cell.position.x)
+                });
+                below
+            }
+            Event::Key(Key::Up) => {
+                let mut above: Vec<usize> = (0..self.cells.len())
+                    .filter(|&i| self.cells[i].end().y <= focused.position.y)
+                    .collect();
+                above.sort_by_key(|&i| {
+                    let cell = &self.cells[i];
+                    let distance = cell.column_distance(focused.position.x);
+                    (distance, Reverse(cell.end().y), cell.position.x)
+                });
+                above
+            }
+            _ => return None,
+        };
+
+        let source = match *event {
+            Event::Key(Key::Tab) => Direction::front(),
+            Event::Shift(Key::Tab) => Direction::back(),
+            Event::Key(Key::Right) => Direction::left(),
+            Event::Key(Key::Left) => Direction::right(),
+            Event::Key(Key::Down) => Direction::up(),
+            _ => Direction::down(),
+        };
+
+        Some((candidates, source))
+    }
+
+    // Tells the children about a focus change from `previous`.
+    fn notify_focus_change(&mut self, previous: usize) -> EventResult {
+        if previous == self.focus {
+            return EventResult::Ignored;
+        }
+
+        let lost = self.cells[previous].view.on_event(Event::FocusLost);
+        let gained = self.cells[self.focus].view.on_event(Event::FocusGained);
+
+        lost.and(gained)
+    }
+
+    // If the event is a mouse event,
+    // move the focus to the selected cell if needed.
+    // Gives a `Hover` event to the cell under the mouse.
+    fn hover(&mut self, event: Event) -> EventResult {
+        let position = match event {
+            Event::Mouse {
+                offset, position, ..
+            } => match position.checked_sub(offset) {
+                Some(position) => position,
+                None => return EventResult::Ignored,
+            },
+            _ => return EventResult::Ignored,
+        };
+
+        match self
+            .cells
+            .iter_mut()
+            .find(|cell| position.fits_in_rect(cell.offset, cell.size))
+        {
+            Some(cell) => cell.view.on_event(event.relativized(cell.offset)),
+            None => EventResult::Ignored,
+        }
+    }
+
+    fn check_focus_grab(&mut self, event: &Event) {
+        if let Event::Mouse {
+            offset,
+            position,
+            event,
+        } = *event
+        {
+            if !event.grabs_focus() {
+                return;
+            }
+
+            let position = match position.checked_sub(offset) {
+                None => return,
+                Some(pos) => pos,
+            };
+
+            if let Some(i) = self
+                .cells
+                .iter()
+                .position(|cell| position.fits_in_rect(cell.offset, cell.size))
+            {
+                if self.cells[i].view.take_focus(Direction::none()) {
+                    self.focus = i;
+                }
+            }
+        }
+    }
+}
+
+impl Default for GridLayout {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Returns the length of each track (column or row).
+//
+// Each item is the start, span and requested length of a cell.
+fn resolve<I>(count: usize, items: I, gap: usize) -> Vec<usize>
+where
+    I: Iterator<Item = (usize, usize, usize)> + Clone,
+{
+    let mut lengths = vec![0; count];
+
+    // Single cells first: spanning cells then only grow what they need.
+    for (start, _, length) in items.clone().filter(|item| item.1 == 1) {
+        lengths[start] = max(lengths[start], length);
+    }
+
+    for (start, span, length) in items.filter(|item| item.1 > 1) {
+        let current = span_length(&lengths, start, span, gap);
+        if length > current {
+            lengths[start + span - 1] += length - current;
+        }
+    }
+
+    lengths
+}
+
+// Shrinks the longest tracks until they fit in `available`.
+fn shrink(lengths: &mut [usize], available: usize) {
+    if lengths.iter().sum::<usize>() <= available {
+        return;
+    }
+
+    let mut sorted = lengths.to_vec();
+    sorted.sort();
+
+    // Find the largest length every track can be capped to.
+    let mut remaining = available;
+    let mut cap = 0;
+    let mut extra = 0;
+    for (i, &length) in sorted.iter().enumerate() {
+        let count = sorted.len() - i;
+        if length * count > remaining {
+            cap = remaining / count;
+            extra = remaining % count;
+            break;
+        }
+        remaining -= length;
+    }
+
+    for length in lengths.iter_mut().filter(|length| **length > cap) {
+        *length = cap;
+        if extra > 0 {
+            *length += 1;
+            extra -= 1;
+        }
+    }
+}
+
+// Returns the offset of the track `start`.
+fn track_offset(lengths: &[usize], start: usize, gap: usize) -> usize {
+    lengths[..start].iter().sum::<usize>() + gap * start
+}
+
+// Returns the length of `span` tracks from `start`, gaps included.
+fn span_length(
+    lengths: &[usize], start: usize, span: usize, gap: usize,
+) -> usize {
+    lengths[start..start + span].iter().sum::<usize>() + gap * (span - 1)
+}
+
+impl View for GridLayout {
+    fn draw(&self, printer: &Printer) {
+        for (i, cell) in self.cells.iter().enumerate() {
+            let printer = &printer
+                .offset(cell.offset)
+                .cropped(cell.size)
+                .focused(i == self.focus);
+            cell.view.draw(printer);
+        }
+    }
+
+    fn needs_relayout(&self) -> bool {
+        self.invalidated || self.cells.iter().any(|c| c.view.needs_relayout())
+    }
+
+    fn required_size(&mut self, req: Vec2) -> Vec2 {
+        let grid = self.grid_size();
+        let gaps = self.gap * grid.map(|v| v.saturating_sub(1));
+        let gap = self.gap;
+
+        // Columns first, with what the children would like.
+        let ideal: Vec<Vec2> = self
+            .cells
+            .iter_mut()
+            .map(|cell| cell.view.required_size(req))
+            .collect();
+        let mut widths = resolve(
+            grid.x,
+            self.cells
+                .iter()
+                .zip(&ideal)
+                .map(|(cell, size)| (cell.position.x, cell.span.x, size.x)),
+            gap.x,
+        );
+        shrink(&mut widths, req.x.saturating_sub(gaps.x));
+
+        // Then rows, now that the children know their width.
+        let sizes: Vec<Vec2> = self
+            .cells
+            .iter_mut()
+            .map(|cell| {
+                let width =
+                    span_length(&widths, cell.position.x, cell.span.x, gap.x);
+                cell.view.required_size(Vec2::new(width, req.y))
+            })
+            .collect();
+        let mut heights = resolve(
+            grid.y,
+            self.cells
+                .iter()
+                .zip(&sizes)
+                .map(|(cell, size)| (cell.position.y, cell.span.y, size.y)),
+            gap.y,
+        );
+        shrink(&mut heights, req.y.saturating_sub(gaps.y));
+
+        for (cell, size) in self.cells.iter_mut().zip(sizes) {
+            cell.size = size;
+        }
+
+        let size = Vec2::new(
+            widths.iter().sum::<usize>(),
+            heights.iter().sum::<usize>(),
+        ) + gaps;
+
+        self.widths = widths;
+        self.heights = heights;
+
+        size
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        self.required_size(size);
+        self.invalidated = false;
+
+        for cell in &mut self.cells {
+            let (x, y) = (cell.position.x, cell.position.y);
+            let offset = Vec2::new(
+                track_offset(&self.widths, x, self.gap.x),
+                track_offset(&self.heights, y, self.gap.y),
+            );
+            let area = Vec2::new(
+                span_length(&self.widths, x, cell.span.x, self.gap.x),
+                span_length(&self.heights, y, cell.span.y, self.gap.y),
+            );
+
+            match cell.align {
+                None => {
+                    cell.offset = offset;
+                    cell.size = area;
+                }
+                Some(ref align) => {
+                    let size = cell.size.zip_map(area, min);
+                    cell.offset = offset
+                        + Vec2::new(
+                            align.h.get_offset(size.x, area.x),
+                            align.v.get_offset(size.y, area.y),
+                        );
+                    cell.size = size;
+                }
+            }
+
+            cell.view.layout(cell.size);
+        }
+    }
+
+    fn take_focus(&mut self, source: Direction) -> bool {
+        // Coming from `<Tab>`: follow the tab order.
+        let mut order = match source {
+            Direction::Rel(_) => self.tab_order(),
+            Direction::Abs(_) => self.reading_order(),
+        };
+
+        match source {
+            Direction::Abs(Absolute::None) => {
+                // Keep the current focus if we can.
+                let focus = self.focus;
+                order.retain(|&i| i != focus);
+                order.insert(0, focus);
+            }
+            Direction::Rel(Relative::Back)
+            | Direction::Abs(Absolute::Right)
+            | Direction::Abs(Absolute::Down) => order.reverse(),
+            _ => (),
+        }
+
+        self.move_focus(&order, source)
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        if self.is_empty() {
+            return EventResult::Ignored;
+        }
+
+        if event.mouse_event() == Some(MouseEvent::Hover) {
+            return self.hover(event);
+        }
+
+        let previous = self.focus;
+        self.check_focus_grab(&event);
+        let grab = self.notify_focus_change(previous);
+
+        let result = {
+            let cell = &mut self.cells[self.focus];
+            cell.view.on_event(event.relativized(cell.offset))
+        };
+
+        let previous = self.focus;
+        let result = match result {
+            EventResult::Ignored => match self.neighbours(&event) {
+                Some((candidates, source))
+                    if self.move_focus(&candidates, source) =>
+                {
+                    EventResult::Consumed(None)
+                }
+                _ => EventResult::Ignored,
+            },
+            res => res,
+        };
+
+        grab.and(result).and(self.notify_focus_change(previous))
+    }
+
+    fn call_on_any<'a>(
+        &mut self, selector: &Selector, mut callback: AnyCb<'a>,
+    ) {
+        for cell in &mut self.cells {
+            cell.view
+                .call_on_any(selector, Box::new(|any| callback(any)));
+        }
+    }
+
+    fn focus_view(&mut self, selector: &Selector) -> Result<(), ()> {
+        for (i, cell) in self.cells.iter_mut().enumerate() {
+            if cell.view.focus_view(selector).is_ok() {
+                self.focus = i;
+                return Ok(());
+            }
+        }
+
+        Err(())
+    }
+
+    fn important_area(&self, _: Vec2) -> Rect {
+        match self.cells.get(self.focus) {
+            None => Rect::from((0, 0)),
+            Some(cell) => cell.view.important_area(cell.size) + cell.offset,
+        }
+    }
+
+    fn debug_layout(&mut self, tree: &mut LayoutTree) {
+        let focus = self.focus;
+        for (i, cell) in self.cells.iter_mut().enumerate() {
+            tree.child(&mut *cell.view, cell.offset, cell.size, i == focus);
+        }
+    }
+
+    fn follow_focus(&mut self, path: &mut FocusPath) {
+        if let Some(cell) = self.cells.get_mut(self.focus) {
+            path.child(&mut *cell.view, cell.offset);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use align::{HAlign, VAlign};
+    use backend;
+    use traits::Boxable;
+    use views::{BoxView, Button, DummyView, TextView};
+    use Cursive;
+
+    #[test]
+    fn test_columns() {
+        let mut grid = GridLayout::new()
+            .gap((1, 0))
+            .child((0, 0), TextView::new("Name"))
+            .child((1, 0), DummyView.fixed_size((3, 1)))
+            .child((0, 1), TextView::new("Email"))
+            .child((1, 1), DummyView.fixed_size((5, 2)))
+            .child((0, 2), DummyView.fixed_size((12, 1)))
+            .span((2, 1));
+
+        assert_eq!(grid.grid_size(), Vec2::new(2, 3));
+        assert_eq!(grid.required_size(Vec2::new(20, 10)), Vec2::new(12, 4));
+        assert_eq!(grid.widths, vec![5, 6]);
+        assert_eq!(grid.heights, vec![1, 2, 1]);
+
+        grid.layout(Vec2::new(20, 10));
+        let cell = |grid: &GridLayout, i: usize| {
+            (grid.cells[i].offset, grid.cells[i].size)
+        };
+        // Cells in the same column share the width.
+        assert_eq!(cell(&grid, 1), (Vec2::new(6, 0), Vec2::new(6, 1)));
+        assert_eq!(cell(&grid, 3), (Vec2::new(6, 1), Vec2::new(6, 2)));
+        assert_eq!(cell(&grid, 4), (Vec2::new(0, 3), Vec2::new(12, 1)));
+
+        // Not enough room: the widest column gives in.
+        assert_eq!(grid.required_size(Vec2::new(9, 10)).x, 9);
+        assert_eq!(grid.widths, vec![4, 4]);
+    }
+
+    #[test]
+    fn test_shrink() {
+        let mut lengths = vec![2, 10, 6];
+        shrink(&mut lengths, 12);
+        assert_eq!(lengths, vec![2, 5, 5]);
+
+        let mut lengths = vec![2, 10, 6];
+        shrink(&mut lengths, 11);
+        assert_eq!(lengths, vec![2, 5, 4]);
+
+        let mut lengths = vec![2, 10, 6];
+        shrink(&mut lengths, 20);
+        assert_eq!(lengths, vec![2, 10, 6]);
+    }
+
+    #[test]
+    fn test_align() {
+        let backend = backend::puppet::Backend::init(Some(Vec2::new(9, 3)));
+        let mut siv = Cursive::new(move || backend);
+
+        let grid = GridLayout::new()
+            .child((0, 0), TextView::new("left"))
+            .child((1, 0), TextView::new("right"))
+            .child((0, 1), TextView::new("mid"))
+            .span((2, 1))
+            .align(Align::new(HAlign::Center, VAlign::Top))
+            .child((1, 2), TextView::new("ab"))
+            .align(Align::top_right());
+        siv.add_fullscreen_layer(BoxView::with_full_screen(grid));
+
+        let screen = siv.dump_screen();
+        assert_eq!(&screen[..3], &["leftright", "   mid   ", "       ab"]);
+    }
+
+    #[test]
+    fn test_focus() {
+        let button = |label: &'static str| Button::new(label, |_| ());
+        let mut grid = GridLayout::new()
+            .gap((1, 0))
+            .child((0, 0), button("a"))
+            .child((1, 2), button("f"))
+            .child((1, 0), button("b"))
+            .child((0, 1), TextView::new("c"))
+            .child((1, 1), button("d"))
+            .child((0, 2), button("e"));
+        grid.layout(Vec2::new(20, 3));
+
+        assert!(grid.take_focus(Direction::front()));
+        let position = |grid: &GridLayout| grid.get_focus_position().unwrap();
+        assert_eq!(position(&grid), Vec2::new(0, 0));
+
+        // Reading order, skipping what can't take the focus.
+        let mut tabs = Vec::new();
+        while grid.on_event(Event::Key(Key::Tab)).is_consumed() {
+            let position = position(&grid);
+            tabs.push((position.x, position.y));
+        }
+        assert_eq!(tabs, vec![(1, 0), (1, 1), (0, 2), (1, 2)]);
+
+        grid.on_event(Event::Shift(Key::Tab));
+        assert_eq!(position(&grid), Vec2::new(0, 2));
+
+        // Up skips the text to reach the same column.
+        grid.on_event(Event::Key(Key::Up));
+        assert_eq!(position(&grid), Vec2::new(0, 0));
+        grid.on_event(Event::Key(Key::Right));
+        assert_eq!(position(&grid), Vec2::new(1, 0));
+        grid.on_event(Event::Key(Key::Down));
+        assert_eq!(position(&grid), Vec2::new(1, 1));
+        assert!(!grid.on_event(Event::Key(Key::Right)).is_consumed());
+
+        assert!(grid.take_focus(Direction::back()));
+        assert_eq!(position(&grid), Vec2::new(1, 2));
+    }
+
+    #[test]
+    fn test_tab_index() {
+        let button = |label: &'static str| Button::new(label, |_| ());
+        // Fields filled column by column.
+        let mut grid = GridLayout::new()
+            .child((0, 0), button("a"))
+            .child((1, 0), button("c"))
+            .tab_index(1)
+            .child((0, 1), button("b"))
+            .child((1, 1), button("d"))
+            .tab_index(1);
+        grid.layout(Vec2::new(20, 2));
+
+        assert!(grid.take_focus(Direction::front()));
+        let mut tabs = vec![grid.get_focus_position().unwrap()];
+        while grid.on_event(Event::Key(Key::Tab)).is_consumed() {
+            tabs.push(grid.get_focus_position().unwrap());
+        }
+        let tabs: Vec<_> = tabs.iter().map(|p| (p.x, p.y)).collect();
+        assert_eq!(tabs, vec![(0, 0), (0, 1), (1, 0), (1, 1)]);
+
+        grid.on_event(Event::Shift(Key::Tab));
+        assert_eq!(grid.get_focus_position(), Some(Vec2::new(1, 0)));
+
+        // Arrow keys still follow the layout.
+        grid.on_event(Event::Key(Key::Down));
+        assert_eq!(grid.get_focus_position(), Some(Vec2::new(1, 1)));
+    }
+}
@@ -0,0 +1,453 @@
+use direction::Direction;
+use event::{Callback, Event, EventResult, Key, MouseButton, MouseEvent};
+use rect::Rect;
+use std::cell::RefCell;
+use std::cmp::{max, min};
+use std::mem;
+use std::rc::Rc;
+use utils::markup::StyledString;
+use vec::Vec2;
+use view::{ScrollBase, View};
+use Cursive;
+use Printer;
+use With;
+
+/// Provides the rows shown by a [`LazyListView`].
+///
+/// Rows are only requested when they become visible.
+///
+/// [`LazyListView`]: ::views::LazyListView
+pub trait ListSource {
+    /// Returns the number of rows.
+    fn len(&self) -> usize;
+
+    /// Returns the row with the given index.
+    ///
+    /// `index` is always lower than `len()`.
+    fn row(&self, index: usize) -> StyledString;
+
+    /// Returns `true` if there is no row.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// List of rows produced on demand by a [`ListSource`].
+///
+/// Only the visible rows are requested from the source, so the list can
+/// hold millions of them. They are kept until they scroll out of view; use
+/// [`get_source_mut`] to change the source and forget them.
+///
+/// [`ListSource`]: ::views::ListSource
+/// [`get_source_mut`]: #method.get_source_mut
+///
+/// # Examples
+///
+/// ```rust
+/// # use cursive::utils::markup::StyledString;
+/// # use cursive::views::{LazyListView, ListSource};
+/// struct Numbers;
+///
+/// impl ListSource for Numbers {
+///     fn len(&self) -> usize {
+///         1_000_000
+///     }
+///
+///     fn row(&self, index: usize) -> StyledString {
+///         StyledString::plain(format!("Row {}", index))
+///     }
+/// }
+///
+/// let list = LazyListView::new(Numbers).on_submit(|_, index| {
+///     println!("Submitted row {}", index);
+/// });
+/// ```
+pub struct LazyListView<S> {
+    source: S,
+    focus: usize,
+    scrollbase: ScrollBase,
+    last_size: Vec2,
+
+    // Rows fetched for the last window drawn.
+    cache: RefCell<RowCache>,
+
+    on_submit: Option<Rc<OnSubmit>>,
+}
+
+/// Closure type for callbacks when a row is submitted.
+type OnSubmit = Fn(&mut Cursive, usize);
+
+// Consecutive rows, starting at `start`.
+#[derive(Default)]
+struct RowCache {
+    start: usize,
+    rows: Vec<StyledString>,
+}
+
+impl<S: ListSource> LazyListView<S> {
+    /// Creates a new list showing the rows of `source`.
+    pub fn new(source: S) -> Self {
+        LazyListView {
+            source,
+            focus: 0,
+            scrollbase: ScrollBase::new(),
+            last_size: Vec2::zero(),
+            cache: RefCell::default(),
+            on_submit: None,
+        }
+    }
+
+    /// Sets a callback to be used when `<Enter>` is pressed, or when a row
+    /// is clicked.
+    ///
+    /// The callback is given the index of the selected row.
+    pub fn set_on_submit<F>(&mut self, cb: F)
+    where
+        F: Fn(&mut Cursive, usize) + 'static,
+    {
+        self.on_submit = Some(Rc::new(cb));
+    }
+
+    /// Sets a callback to be used when `<Enter>` is pressed, or when a row
+    /// is clicked.
+    ///
+    /// Chainable variant.
+    pub fn on_submit<F>(self, cb: F) -> Self
+    where
+        F: Fn(&mut Cursive, usize) + 'static,
+    {
+        self.with(|s| s.set_on_submit(cb))
+    }
+
+    /// Returns the source of the rows.
+    pub fn get_source(&self) -> &S {
+        &self.source
+    }
+
+    /// Returns a mutable reference to the source of the rows.
+    ///
+    /// Rows already fetched are forgotten, and requested again when drawn.
+    pub fn get_source_mut(&mut self) -> &mut S {
+        *self.cache.borrow_mut() = RowCache::default();
+        &mut self.source
+    }
+
+    /// Returns the index of the selected row.
+    ///
+    /// Returns `None` if the list is empty.
+    pub fn selected_row(&self) -> Option<usize> {
+        if self.focus < self.source.len() {
+            Some(self.focus)
+        } else {
+            None
+        }
+    }
+
+    /// Selects the row with the given index, scrolling if needed.
+    ///
+    /// The last row is selected if `index` is too large.
+    pub fn set_selected_row(&mut self, index: usize) {
+        self.focus = min(index, self.source.len().saturating_sub(1));
+        self.scrollbase.scroll_to(self.focus);
+    }
+
+    fn focus_up(&mut self, n: usize) {
+        self.focus -= min(self.focus, n);
+    }
+
+    fn focus_down(&mut self, n: usize) {
+        self.focus = min(self.focus + n, self.source.len().saturating_sub(1));
+    }
+
+    // Keeps the selection visible after scrolling with the mouse.
+    fn focus_visible(&mut self) {
+        let start = self.scrollbase.start_line;
+        let end = start + self.scrollbase.view_height;
+        self.focus = max(start, min(self.focus, end.saturating_sub(1)));
+        self.focus = min(self.focus, self.source.len().saturating_sub(1));
+    }
+
+    fn submit(&mut self) -> EventResult {
+        let cb = self.on_submit.clone().unwrap();
+        EventResult::Consumed(
+            self.selected_row()
+                .map(|i| Callback::from_fn(move |s| cb(s, i))),
+        )
+    }
+
+    // Makes sure the cache holds the `count` rows from `start`.
+    //
+    // Rows already fetched are kept, so scrolling by one row only requests
+    // one more.
+    fn fetch(&self, start: usize, count: usize) {
+        let mut cache = self.cache.borrow_mut();
+        if cache.start == start && cache.rows.len() == count {
+            return;
+        }
+
+        let mut old = mem::take(&mut *cache);
+        cache.start = start;
+        cache.rows = (start..start + count)
+            .map(|i| match i.checked_sub(old.start) {
+                Some(j) if j < old.rows.len() => mem::take(&mut old.rows[j]),
+                _ => self.source.row(i),
+            })
+            .collect();
+    }
+
+    // Fetches the rows visible from `start` in a view of `height` rows.
+    fn fetch_window(&self, start: usize, height: usize) {
+        let len = self.source.len();
+        let start = min(start, len);
+        self.fetch(start, min(height, len - start));
+    }
+}
+
+impl<S: ListSource + 'static> View for LazyListView<S> {
+    fn draw(&self, printer: &Printer) {
+        let start = self.scrollbase.start_line;
+        self.fetch_window(start, self.scrollbase.view_height);
+
+        let cache = self.cache.borrow();
+        self.scrollbase.draw(printer, |printer, i| {
+            let row = match cache.rows.get(i - cache.start) {
+                Some(row) => row,
+                None => return,
+            };
+            printer.with_selection(i == self.focus, |printer| {
+                let width = row.width();
+                printer.print_styled((0, 0), row.into());
+                if width < printer.size.x {
+                    printer.print_hline(
+                        (width, 0),
+                        printer.size.x - width,
+                        " ",
+                    );
+                }
+            });
+        });
+    }
+
+    fn required_size(&mut self, constraint: Vec2) -> Vec2 {
+        let len = self.source.len();
+
+        // Only the rows that would be visible are measured.
+        self.fetch_window(self.scrollbase.start_line, constraint.y);
+        let width = self
+            .cache
+            .borrow()
+            .rows
+            .iter()
+            .map(StyledString::width)
+            .max()
+            .unwrap_or(0);
+
+        let scrollbar = if len > constraint.y {
+            1 + self.scrollbase.right_padding
+        } else {
+            0
+        };
+
+        Vec2::new(width + scrollbar, len)
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        self.last_size = size;
+        self.scrollbase.set_heights(size.y, self.source.len());
+        self.scrollbase.scroll_to(self.focus);
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        let len = self.source.len();
+        let page = max(1, self.scrollbase.view_height.saturating_sub(1));
+
+        match event {
+            Event::Key(Key::Up) if self.focus > 0 => self.focus_up(1),
+            Event::Key(Key::Down) if self.focus + 1 < len => {
+                self.focus_down(1)
+            }
+            Event::Key(Key::PageUp) => self.focus_up(page),
+            Event::Key(Key::PageDown) => self.focus_down(page),
+            Event::Key(Key::Home) => self.focus = 0,
+            Event::Key(Key::End) => self.focus = len.saturating_sub(1),
+            Event::Key(Key::Enter) if self.on_submit.is_some() && len > 0 => {
+                return self.submit();
+            }
+            Event::Mouse {
+                event: MouseEvent::WheelUp,
+                ..
+            } if self.scrollbase.can_scroll_up() => {
+                self.scrollbase.scroll_up(5);
+                self.focus_visible();
+                return EventResult::Consumed(None);
+            }
+            Event::Mouse {
+                event: MouseEvent::WheelDown,
+                ..
+            } if self.scrollbase.can_scroll_down() => {
+                self.scrollbase.scroll_down(5);
+                self.focus_visible();
+                return EventResult::Consumed(None);
+            }
+            Event::Mouse {
+                event: MouseEvent::Press(MouseButton::Left),
+                position,
+                offset,
+            } if self.scrollbase.scrollable()
+                && position
+                    .checked_sub(offset)
+                    .map(|position| {
+                        self.scrollbase.start_drag(position, self.last_size.x)
+                    })
+                    .unwrap_or(false) =>
+            {
+                self.focus_visible();
+                return EventResult::Consumed(None);
+            }
+            Event::Mouse {
+                event: MouseEvent::Hold(MouseButton::Left),
+                position,
+                offset,
+            } if self.scrollbase.is_dragging() => {
+                self.scrollbase.drag(position.saturating_sub(offset));
+                self.focus_visible();
+                return EventResult::Consumed(None);
+            }
+            Event::Mouse {
+                event: MouseEvent::Release(MouseButton::Left),
+                ..
+            } if self.scrollbase.is_dragging() => {
+                self.scrollbase.release_grab();
+                return EventResult::Consumed(None);
+            }
+            Event::Mouse {
+                event: MouseEvent::Press(_),
+                position,
+                offset,
+            } if position
+                .checked_sub(offset)
+                .map(|position| {
+                    position.x < self.last_size.x
+                        && position.y < self.scrollbase.view_height
+                        && position.y + self.scrollbase.start_line < len
+                })
+                .unwrap_or(false) =>
+            {
+                self.focus =
+                    position.y - offset.y + self.scrollbase.start_line;
+            }
+            Event::Mouse {
+                event: MouseEvent::Release(MouseButton::Left),
+                position,
+                offset,
+            } if self.on_submit.is_some()
+                && position
+                    .checked_sub(offset)
+                    .map(|position| {
+                        position.x < self.last_size.x
+                            && position.y + self.scrollbase.start_line
+                                == self.focus
+                    })
+                    .unwrap_or(false) =>
+            {
+                return self.submit();
+            }
+            _ => return EventResult::Ignored,
+        }
+
+        self.scrollbase.scroll_to(self.focus);
+        EventResult::Consumed(None)
+    }
+
+    fn take_focus(&mut self, _: Direction) -> bool {
+        !self.source.is_empty()
+    }
+
+    fn important_area(&self, size: Vec2) -> Rect {
+        // We scroll internally, so only count visible rows.
+        let row = self.focus.saturating_sub(self.scrollbase.start_line);
+        Rect::from_size((0, row), (size.x, 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backend::puppet;
+    use std::cell::Cell;
+    use views::BoxView;
+
+    // Counts the rows requested.
+    struct Numbers {
+        len: usize,
+        fetched: Rc<Cell<usize>>,
+    }
+
+    impl ListSource for Numbers {
+        fn len(&self) -> usize {
+            self.len
+        }
+
+        fn row(&self, index: usize) -> StyledString {
+            self.fetched.set(self.fetched.get() + 1);
+            StyledString::plain(format!("Row {}", index))
+        }
+    }
+
+    fn mouse(siv: &mut Cursive, event: MouseEvent, x: usize, y: usize) {
+        siv.on_event(Event::Mouse {
+            offset: Vec2::zero(),
+            position: Vec2::new(x, y),
+            event,
+        });
+    }
+
+    #[test]
+    fn test_lazy_rows() {
+        let backend = puppet::Backend::init(Some(Vec2::new(16, 4)));
+        let mut siv = Cursive::new(move || backend);
+
+        let fetched = Rc::new(Cell::new(0));
+        let submitted = Rc::new(Cell::new(None));
+        let s = Rc::clone(&submitted);
+        let list = LazyListView::new(Numbers {
+            len: 1_000_000,
+            fetched: Rc::clone(&fetched),
+        })
+        .on_submit(move |_, index| s.set(Some(index)));
+        siv.add_fullscreen_layer(BoxView::with_full_screen(list));
+
+        let screen = siv.dump_screen();
+        assert_eq!(screen[0], "Row 0          ▒");
+        assert_eq!(screen[3], "Row 3          |");
+        assert_eq!(fetched.get(), 4);
+
+        // Drawing again, or scrolling by one row, reuses the visible rows.
+        siv.dump_screen();
+        for _ in 0..4 {
+            siv.on_event(Event::Key(Key::Down));
+        }
+        assert_eq!(siv.dump_screen()[3], "Row 4          |");
+        assert_eq!(fetched.get(), 5);
+
+        siv.on_event(Event::Key(Key::End));
+        assert_eq!(siv.dump_screen()[3], "Row 999999     ▒");
+        assert_eq!(fetched.get(), 9);
+
+        // Clicking on the scrollbar jumps there, keeping a visible selection.
+        mouse(&mut siv, MouseEvent::Press(MouseButton::Left), 15, 1);
+        mouse(&mut siv, MouseEvent::Release(MouseButton::Left), 15, 1);
+        let screen = siv.dump_screen();
+        assert_eq!(screen[0], "Row 250000     |");
+        assert_eq!(fetched.get(), 13);
+
+        siv.on_event(Event::Key(Key::Enter));
+        assert_eq!(submitted.get(), Some(250_003));
+
+        // Clicking on a row selects it, and submits it.
+        mouse(&mut siv, MouseEvent::Press(MouseButton::Left), 2, 1);
+        mouse(&mut siv, MouseEvent::Release(MouseButton::Left), 2, 1);
+        assert_eq!(submitted.get(), Some(250_001));
+        assert_eq!(fetched.get(), 13);
+    }
+}
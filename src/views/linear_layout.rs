@@ -1,15 +1,20 @@
 use direction;
-use event::{AnyCb, Event, EventResult, Key};
+use event::{AnyCb, Event, EventResult, Key, MouseEvent};
 use rect::Rect;
 use std::cmp::min;
 use std::ops::Deref;
 use vec::Vec2;
-use view::{Selector, SizeCache, View};
+use view::{FocusPath, LayoutTree, Selector, SizeCache, View};
 use Printer;
 use With;
 use XY;
 
 /// Arranges its children linearly according to its orientation.
+///
+/// `<Tab>` moves the focus between children in insertion order, unless
+/// they were given a [`tab_index`].
+///
+/// [`tab_index`]: #method.tab_index
 pub struct LinearLayout {
     children: Vec<Child>,
     orientation: direction::Orientation,
@@ -24,6 +29,8 @@ struct Child {
     // Doesn't have to be what the child actually gets.
     size: Vec2,
     weight: usize,
+    // Position in the `<Tab>` order.
+    tab_index: usize,
 }
 
 impl Child {
@@ -128,6 +135,18 @@ impl LinearLayout {
         self
     }
 
+    /// Modifies the tab index of the last child added.
+    ///
+    /// `<Tab>` visits children by increasing tab index, and children with
+    /// the same index in insertion order. Defaults to 0.
+    ///
+    /// It is an error to call this before adding a child (and it will panic).
+    pub fn tab_index(mut self, index: usize) -> Self {
+        self.children.last_mut().unwrap().tab_index = index;
+
+        self
+    }
+
     /// Adds a child to the layout.
     ///
     /// Chainable variant.
@@ -141,6 +160,7 @@ impl LinearLayout {
             view: Box::new(view),
             size: Vec2::zero(),
             weight: 0,
+            tab_index: 0,
         });
         self.invalidate();
     }
@@ -276,6 +296,72 @@ impl LinearLayout {
         EventResult::Consumed(None)
     }
 
+    // Indices of the children, in `<Tab>` order.
+    fn tab_order(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.children.len()).collect();
+        order.sort_by_key(|&i| self.children[i].tab_index);
+        order
+    }
+
+    // Focuses the next child in `<Tab>` order accepting the focus.
+    fn move_tab_focus(&mut self, rel: direction::Relative) -> EventResult {
+        let order = self.tab_order();
+        let current = order.iter().position(|&i| i == self.focus).unwrap();
+        let candidates: Vec<usize> = match rel {
+            direction::Relative::Front => order[current + 1..].to_vec(),
+            direction::Relative::Back => {
+                order[..current].iter().rev().cloned().collect()
+            }
+        };
+
+        let source = direction::Direction::Rel(rel);
+        for i in candidates {
+            if self.children[i].view.take_focus(source) {
+                self.focus = i;
+                return EventResult::Consumed(None);
+            }
+        }
+        EventResult::Ignored
+    }
+
+    // Tells the children about a focus change from `previous`.
+    fn notify_focus_change(&mut self, previous: usize) -> EventResult {
+        if previous == self.focus {
+            return EventResult::Ignored;
+        }
+
+        let lost = self.children[previous].view.on_event(Event::FocusLost);
+        let gained = self.children[self.focus]
+            .view
+            .on_event(Event::FocusGained);
+
+        lost.and(gained)
+    }
+
+    // Gives a `Hover` event to the child under the mouse.
+    fn hover(&mut self, event: Event) -> EventResult {
+        let position = match event {
+            Event::Mouse {
+                offset, position, ..
+            } => match position.checked_sub(offset) {
+                Some(position) => *position.get(self.orientation),
+                None => return EventResult::Ignored,
+            },
+            _ => return EventResult::Ignored,
+        };
+
+        let o = self.orientation;
+        let item = ChildIterator::new(self.children.iter_mut(), o, usize::MAX)
+            .find(|item| item.offset + item.child.size.get(o) > position);
+        match item {
+            Some(item) => {
+                let offset = o.make_vec(item.offset, 0);
+                item.child.view.on_event(event.relativized(offset))
+            }
+            None => EventResult::Ignored,
+        }
+    }
+
     // If the event is a mouse event,
     // move the focus to the selected view if needed.
     fn check_focus_grab(&mut self, event: &Event) {
@@ -515,6 +601,24 @@ impl View for LinearLayout {
     }
 
     fn take_focus(&mut self, source: direction::Direction) -> bool {
+        // Coming from `<Tab>`: follow the tab order.
+        if let direction::Direction::Rel(rel) = source {
+            let mut order = self.tab_order();
+            if rel == direction::Relative::Back {
+                order.reverse();
+            }
+            return match order
+                .into_iter()
+                .find(|&i| self.children[i].view.take_focus(source))
+            {
+                Some(i) => {
+                    self.focus = i;
+                    true
+                }
+                None => false,
+            };
+        }
+
         // In what order will we iterate on the children?
         let rel = source.relative(self.orientation);
         // We activate from_focus only if coming from the "sides".
@@ -540,7 +644,13 @@ impl View for LinearLayout {
             return EventResult::Ignored;
         }
 
+        if event.mouse_event() == Some(MouseEvent::Hover) {
+            return self.hover(event);
+        }
+
+        let previous = self.focus;
         self.check_focus_grab(&event);
+        let grab = self.notify_focus_change(previous);
 
         let result = {
             let mut iterator = ChildIterator::new(
@@ -552,15 +662,15 @@ impl View for LinearLayout {
             let offset = self.orientation.make_vec(item.offset, 0);
             item.child.view.on_event(event.relativized(offset))
         };
-        match result {
+
+        let previous = self.focus;
+        let result = match result {
             EventResult::Ignored => match event {
-                Event::Shift(Key::Tab) if self.focus > 0 => {
-                    self.move_focus(direction::Direction::back())
+                Event::Shift(Key::Tab) => {
+                    self.move_tab_focus(direction::Relative::Back)
                 }
-                Event::Key(Key::Tab)
-                    if self.focus + 1 < self.children.len() =>
-                {
-                    self.move_focus(direction::Direction::front())
+                Event::Key(Key::Tab) => {
+                    self.move_tab_focus(direction::Relative::Front)
                 }
                 Event::Key(Key::Left)
                     if self.orientation
@@ -593,7 +703,9 @@ impl View for LinearLayout {
                 _ => EventResult::Ignored,
             },
             res => res,
-        }
+        };
+
+        grab.and(result).and(self.notify_focus_change(previous))
     }
 
     fn call_on_any<'a>(
@@ -642,4 +754,103 @@ impl View for LinearLayout {
         // Add `offset` to the rect.
         rect + offset
     }
+
+    fn debug_layout(&mut self, tree: &mut LayoutTree) {
+        if let Some(cache) = self.cache {
+            tree.set_required_size(cache.map(SizeCache::value));
+        }
+
+        let (o, focus) = (self.orientation, self.focus);
+        let size = tree.size();
+
+        for (i, item) in
+            ChildIterator::new(self.children.iter_mut(), o, *size.get(o))
+                .enumerate()
+        {
+            tree.child(
+                &mut *item.child.view,
+                o.make_vec(item.offset, 0),
+                size.with_axis(o, item.length),
+                i == focus,
+            );
+        }
+    }
+
+    fn follow_focus(&mut self, path: &mut FocusPath) {
+        let (o, focus) = (self.orientation, self.focus);
+        // The children already fit in the size of the last layout.
+        let available = usize::MAX;
+        let item = ChildIterator::new(self.children.iter_mut(), o, available)
+            .nth(focus);
+        if let Some(item) = item {
+            path.child(&mut *item.child.view, o.make_vec(item.offset, 0));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use event::{MouseButton, MouseEvent};
+    use views::{Button, EnableableView, HideableView, SliderView};
+
+    #[test]
+    fn test_skip_disabled() {
+        let mut layout = LinearLayout::vertical()
+            .child(Button::new("a", |_| ()))
+            .child(Button::new("b", |_| ()).disabled())
+            .child(EnableableView::new(Button::new("c", |_| ())).disabled())
+            .child(SliderView::horizontal(5).disabled())
+            .child(Button::new("d", |_| ()));
+        layout.layout(Vec2::new(10, 5));
+
+        assert!(layout.take_focus(direction::Direction::front()));
+        assert_eq!(layout.get_focus_index(), 0);
+
+        layout.on_event(Event::Key(Key::Tab));
+        assert_eq!(layout.get_focus_index(), 4);
+
+        layout.on_event(Event::Shift(Key::Tab));
+        assert_eq!(layout.get_focus_index(), 0);
+    }
+
+    #[test]
+    fn test_tab_index() {
+        let button = |label: &'static str| Button::new(label, |_| ());
+        let mut layout = LinearLayout::vertical()
+            .child(button("a"))
+            .tab_index(2)
+            .child(button("b"))
+            .child(button("c").disabled())
+            .tab_index(1)
+            .child(button("d"))
+            .tab_index(1)
+            .child(HideableView::new(button("e")).hidden())
+            .child(button("f"));
+        layout.layout(Vec2::new(10, 6));
+
+        assert!(layout.take_focus(direction::Direction::front()));
+        assert_eq!(layout.get_focus_index(), 1);
+
+        // Skips the disabled and hidden children.
+        let mut tabs = Vec::new();
+        while layout.on_event(Event::Key(Key::Tab)).is_consumed() {
+            tabs.push(layout.get_focus_index());
+        }
+        assert_eq!(tabs, vec![5, 3, 0]);
+
+        layout.on_event(Event::Shift(Key::Tab));
+        assert_eq!(layout.get_focus_index(), 3);
+
+        // Clicks still focus the child under the mouse.
+        layout.on_event(Event::Mouse {
+            offset: Vec2::zero(),
+            position: Vec2::new(0, 1),
+            event: MouseEvent::Press(MouseButton::Left),
+        });
+        assert_eq!(layout.get_focus_index(), 1);
+
+        assert!(layout.take_focus(direction::Direction::back()));
+        assert_eq!(layout.get_focus_index(), 0);
+    }
 }
@@ -1,10 +1,10 @@
 use direction;
-use event::{AnyCb, Callback, Event, EventResult, Key};
+use event::{AnyCb, Callback, Event, EventResult, Key, MouseEvent};
 use rect::Rect;
 use std::rc::Rc;
 use unicode_width::UnicodeWidthStr;
 use vec::Vec2;
-use view::{Selector, View};
+use view::{FocusPath, LayoutTree, Selector, View};
 use Cursive;
 use Printer;
 use With;
@@ -201,6 +201,44 @@ impl ListView {
             .unwrap_or(0)
     }
 
+    // Tells the children about a focus change from `previous`.
+    fn notify_focus_change(&mut self, previous: usize) -> EventResult {
+        if previous == self.focus {
+            return EventResult::Ignored;
+        }
+
+        let focus = self.focus;
+        let mut notify = |i: usize, event: Event| {
+            self.children[i]
+                .view()
+                .map_or(EventResult::Ignored, |view| view.on_event(event))
+        };
+
+        let lost = notify(previous, Event::FocusLost);
+        lost.and(notify(focus, Event::FocusGained))
+    }
+
+    // Gives a `Hover` event to the row under the mouse.
+    fn hover(&mut self, event: Event) -> EventResult {
+        let row = match event {
+            Event::Mouse {
+                offset, position, ..
+            } => match position.checked_sub(offset) {
+                Some(position) => position.y,
+                None => return EventResult::Ignored,
+            },
+            _ => return EventResult::Ignored,
+        };
+
+        let labels_width = self.labels_width();
+        match self.children.get_mut(row) {
+            Some(&mut ListChild::Row(_, ref mut view)) => {
+                view.on_event(event.relativized((labels_width + 1, row)))
+            }
+            _ => EventResult::Ignored,
+        }
+    }
+
     fn check_focus_grab(&mut self, event: &Event) {
         if let Event::Mouse {
             offset,
@@ -320,7 +358,13 @@ impl View for ListView {
             return EventResult::Ignored;
         }
 
+        if event.mouse_event() == Some(MouseEvent::Hover) {
+            return self.hover(event);
+        }
+
+        let previous = self.focus;
         self.check_focus_grab(&event);
+        let grab = self.notify_focus_change(previous);
 
         // Send the event to the focused child.
         let labels_width = self.labels_width();
@@ -330,12 +374,13 @@ impl View for ListView {
             let offset = (labels_width + 1, self.focus);
             let result = view.on_event(event.relativized(offset));
             if result.is_consumed() {
-                return result;
+                return grab.and(result);
             }
         }
 
         // If the child ignored this event, change the focus.
-        match event {
+        let previous = self.focus;
+        let result = match event {
             Event::Key(Key::Up) if self.focus > 0 => {
                 self.move_focus(1, direction::Direction::down())
             }
@@ -359,7 +404,9 @@ impl View for ListView {
                 self.move_focus(1, direction::Direction::back())
             }
             _ => EventResult::Ignored,
-        }
+        };
+
+        grab.and(result).and(self.notify_focus_change(previous))
     }
 
     fn take_focus(&mut self, source: direction::Direction) -> bool {
@@ -420,4 +467,23 @@ impl View for ListView {
 
         area + (0, self.focus)
     }
+
+    fn debug_layout(&mut self, tree: &mut LayoutTree) {
+        let (x, focus) = (self.labels_width() + 1, self.focus);
+        let size = Vec2::new(self.last_size.x.saturating_sub(x), 1);
+        for (i, child) in self.children.iter_mut().enumerate() {
+            if let ListChild::Row(_, ref mut view) = *child {
+                tree.child(&mut **view, Vec2::new(x, i), size, i == focus);
+            }
+        }
+    }
+
+    fn follow_focus(&mut self, path: &mut FocusPath) {
+        let offset = Vec2::new(self.labels_width() + 1, self.focus);
+        if let Some(&mut ListChild::Row(_, ref mut view)) =
+            self.children.get_mut(self.focus)
+        {
+            path.child(&mut **view, offset);
+        }
+    }
 }
@@ -0,0 +1,310 @@
+use direction::Direction;
+use event::{Event, EventResult, Key, MouseButton, MouseEvent};
+use std::cmp::max;
+use std::collections::vec_deque::{self, VecDeque};
+use utils::markup::StyledString;
+use vec::Vec2;
+use view::{ScrollBase, View};
+use Printer;
+
+/// Shows the last lines of a stream, like a log.
+///
+/// Lines are kept in a ring buffer: once `capacity` is reached, each new
+/// line drops the oldest one. Appending never goes through the rest of the
+/// content, so it stays fast for large logs.
+///
+/// The view follows new lines as long as it is scrolled to the bottom.
+/// Scrolling up stops following, and scrolling back down starts again.
+///
+/// Each line is shown on a single row, cut if it is too long.
+///
+/// # Examples
+///
+/// Lines can be appended from another thread through
+/// [`Cursive::cb_sink()`](../struct.Cursive.html#method.cb_sink):
+///
+/// ```rust
+/// # extern crate cursive;
+/// # use cursive::traits::*;
+/// # use cursive::views::LogView;
+/// # use cursive::Cursive;
+/// # use std::thread;
+/// # fn main() {
+/// let mut siv = Cursive::dummy();
+/// siv.add_layer(LogView::new(1000).with_id("log"));
+///
+/// let sink = siv.cb_sink().clone();
+/// thread::spawn(move || {
+///     for i in 0..10 {
+///         let line = format!("Line {}", i);
+///         let append = move |s: &mut Cursive| {
+///             s.call_on_id("log", |log: &mut LogView| log.append(line));
+///         };
+///         if sink.send(Box::new(append)).is_err() {
+///             return;
+///         }
+///     }
+/// });
+/// # }
+/// ```
+pub struct LogView {
+    lines: VecDeque<StyledString>,
+    capacity: usize,
+    scrollbase: ScrollBase,
+    last_size: Vec2,
+
+    // `true` to scroll to new lines.
+    follow: bool,
+
+    // Width of the longest line appended since the last `clear()`.
+    width: usize,
+}
+
+impl LogView {
+    /// Creates a new, empty log keeping at most `capacity` lines.
+    pub fn new(capacity: usize) -> Self {
+        LogView {
+            lines: VecDeque::with_capacity(capacity),
+            capacity,
+            scrollbase: ScrollBase::new(),
+            last_size: Vec2::zero(),
+            follow: true,
+            width: 0,
+        }
+    }
+
+    /// Adds a line at the end of the log.
+    ///
+    /// If the log is full, the oldest line is dropped.
+    pub fn append<S: Into<StyledString>>(&mut self, line: S) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let line = line.into();
+        self.width = max(self.width, line.width());
+        self.lines.push_back(line);
+        if self.lines.len() > self.capacity {
+            self.lines.pop_front();
+            if !self.follow {
+                // Keep showing the same lines.
+                self.scrollbase.start_line =
+                    self.scrollbase.start_line.saturating_sub(1);
+            }
+        }
+    }
+
+    /// Sets the maximum number of lines kept.
+    ///
+    /// Drops the oldest lines if there are too many.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        let extra = self.lines.len().saturating_sub(capacity);
+        self.lines.drain(..extra);
+    }
+
+    /// Returns the maximum number of lines kept.
+    pub fn get_capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Removes every line, and follows new lines again.
+    pub fn clear(&mut self) {
+        self.lines.clear();
+        self.width = 0;
+        self.follow = true;
+        self.scrollbase.scroll_top();
+    }
+
+    /// Returns the lines currently kept, from the oldest to the newest.
+    pub fn lines(&self) -> vec_deque::Iter<'_, StyledString> {
+        self.lines.iter()
+    }
+
+    /// Returns the number of lines currently kept.
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Returns `true` if there is no line.
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Returns `true` if the view scrolls to show new lines.
+    pub fn is_following(&self) -> bool {
+        self.follow
+    }
+
+    // Follows new lines again once the bottom is reached.
+    fn update_follow(&mut self) {
+        self.follow = !self.scrollbase.can_scroll_down();
+    }
+}
+
+impl View for LogView {
+    fn draw(&self, printer: &Printer) {
+        self.scrollbase.draw(printer, |printer, i| {
+            printer.print_styled((0, 0), (&self.lines[i]).into());
+        });
+    }
+
+    fn required_size(&mut self, constraint: Vec2) -> Vec2 {
+        let scrollbar = if self.lines.len() > constraint.y {
+            1 + self.scrollbase.right_padding
+        } else {
+            0
+        };
+
+        Vec2::new(self.width + scrollbar, self.lines.len())
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        self.last_size = size;
+        self.scrollbase.set_heights(size.y, self.lines.len());
+        if self.follow {
+            self.scrollbase.scroll_bottom();
+        }
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        let page = max(1, self.scrollbase.view_height.saturating_sub(1));
+
+        match event {
+            Event::Key(Key::Up) if self.scrollbase.can_scroll_up() => {
+                self.scrollbase.scroll_up(1)
+            }
+            Event::Key(Key::Down) if self.scrollbase.can_scroll_down() => {
+                self.scrollbase.scroll_down(1)
+            }
+            Event::Key(Key::PageUp) if self.scrollbase.can_scroll_up() => {
+                self.scrollbase.scroll_up(page)
+            }
+            Event::Key(Key::PageDown)
+                if self.scrollbase.can_scroll_down() =>
+            {
+                self.scrollbase.scroll_down(page)
+            }
+            Event::Key(Key::Home) if self.scrollbase.can_scroll_up() => {
+                self.scrollbase.scroll_top()
+            }
+            Event::Key(Key::End) if self.scrollbase.can_scroll_down() => {
+                self.scrollbase.scroll_bottom()
+            }
+            Event::Mouse {
+                event: MouseEvent::WheelUp,
+                ..
+            } if self.scrollbase.can_scroll_up() => {
+                self.scrollbase.scroll_up(3)
+            }
+            Event::Mouse {
+                event: MouseEvent::WheelDown,
+                ..
+            } if self.scrollbase.can_scroll_down() => {
+                self.scrollbase.scroll_down(3)
+            }
+            Event::Mouse {
+                event: MouseEvent::Press(MouseButton::Left),
+                position,
+                offset,
+            } if self.scrollbase.scrollable()
+                && position
+                    .checked_sub(offset)
+                    .map(|position| {
+                        self.scrollbase.start_drag(position, self.last_size.x)
+                    })
+                    .unwrap_or(false) =>
+            {
+            }
+            Event::Mouse {
+                event: MouseEvent::Hold(MouseButton::Left),
+                position,
+                offset,
+            } if self.scrollbase.is_dragging() => {
+                self.scrollbase.drag(position.saturating_sub(offset))
+            }
+            Event::Mouse {
+                event: MouseEvent::Release(MouseButton::Left),
+                ..
+            } if self.scrollbase.is_dragging() => {
+                self.scrollbase.release_grab()
+            }
+            _ => return EventResult::Ignored,
+        }
+
+        self.update_follow();
+        EventResult::Consumed(None)
+    }
+
+    fn take_focus(&mut self, _: Direction) -> bool {
+        self.scrollbase.scrollable()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backend::puppet;
+    use traits::Identifiable;
+    use views::BoxView;
+    use Cursive;
+
+    fn lines(log: &LogView) -> Vec<&str> {
+        log.lines().map(StyledString::source).collect()
+    }
+
+    #[test]
+    fn test_capacity() {
+        let mut log = LogView::new(3);
+        for i in 0..100_000 {
+            log.append(format!("Line {}", i));
+        }
+        assert_eq!(
+            lines(&log),
+            vec!["Line 99997", "Line 99998", "Line 99999"]
+        );
+
+        log.set_capacity(2);
+        assert_eq!(lines(&log), vec!["Line 99998", "Line 99999"]);
+
+        log.clear();
+        assert!(log.is_empty());
+    }
+
+    #[test]
+    fn test_follow() {
+        let backend = puppet::Backend::init(Some(Vec2::new(10, 3)));
+        let mut siv = Cursive::new(move || backend);
+        let mut log = LogView::new(5);
+        for i in 0..4 {
+            log.append(format!("Line {}", i));
+        }
+        siv.add_fullscreen_layer(BoxView::with_full_screen(
+            log.with_id("log"),
+        ));
+
+        let top = |siv: &mut Cursive| siv.dump_screen()[0][..6].to_string();
+        let append = |siv: &mut Cursive, line: &str| {
+            siv.call_on_id("log", |log: &mut LogView| log.append(line));
+        };
+        assert_eq!(top(&mut siv), "Line 1");
+
+        append(&mut siv, "Line 4");
+        assert_eq!(top(&mut siv), "Line 2");
+
+        // Scrolling up stops following new lines.
+        siv.on_event(Event::Key(Key::Up));
+        assert_eq!(top(&mut siv), "Line 1");
+        append(&mut siv, "Line 5");
+        assert_eq!(top(&mut siv), "Line 1");
+
+        // Unless the line on top is dropped.
+        append(&mut siv, "Line 6");
+        assert_eq!(top(&mut siv), "Line 2");
+
+        // Going back to the bottom follows them again.
+        siv.on_event(Event::Key(Key::End));
+        append(&mut siv, "Line 7");
+        assert_eq!(top(&mut siv), "Line 5");
+    }
+}
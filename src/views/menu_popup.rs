@@ -6,7 +6,7 @@ use std::cmp::min;
 use std::rc::Rc;
 use unicode_width::UnicodeWidthStr;
 use vec::Vec2;
-use view::{Position, ScrollBase, View};
+use view::{LayoutTree, Position, ScrollBase, View};
 use views::OnEventView;
 use Cursive;
 use Printer;
@@ -215,6 +215,13 @@ impl View for MenuPopup {
         // Start with a box
         printer.print_box(Vec2::new(0, 0), printer.size, false);
 
+        // Items hidden above or below are shown on the borders.
+        let hidden = &self.scrollbase;
+        printer
+            .inner_size((printer.size.x, hidden.content_height + 2))
+            .content_offset((0, hidden.start_line))
+            .print_overflow_markers();
+
         // We're giving it a reduced size because of borders.
         // But we're keeping the full width,
         // to integrate horizontal delimiters in the frame.
@@ -407,4 +414,50 @@ impl View for MenuPopup {
 
         Rect::from_size((0, self.focus), (size.x, 1))
     }
+
+    fn debug_layout(&mut self, tree: &mut LayoutTree) {
+        if let Some(item) = self.menu.children.get(self.focus) {
+            tree.set_info(format!("selects {:?}", item.label()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backend::puppet;
+    use theme::{self, Glyphs, Theme};
+
+    fn render(popup: &mut MenuPopup, theme: &Theme) -> Vec<String> {
+        let size = Vec2::new(10, 5);
+        popup.layout(size);
+        let backend = puppet::Backend::init(Some(size));
+        popup.draw(&Printer::new(size, theme, &*backend));
+        backend.screen().rows()
+    }
+
+    #[test]
+    fn test_overflow_markers() {
+        let mut menu = MenuTree::new();
+        for i in 0..6 {
+            menu.add_leaf(format!("Item {}", i), |_| ());
+        }
+        let mut popup = MenuPopup::new(Rc::new(menu));
+        let mut theme = theme::load_default();
+
+        // Only 3 items fit between the borders.
+        let rows = render(&mut popup, &theme);
+        assert_eq!(rows[0], "┌────────┐");
+        assert_eq!(rows[4], "└────▼───┘");
+        popup.on_event(Event::Key(Key::End));
+        let rows = render(&mut popup, &theme);
+        assert_eq!(rows[0], "┌────▲───┐");
+        assert_eq!(rows[4], "└────────┘");
+        assert!(rows[1].contains("Item 3"));
+
+        theme.glyphs = Glyphs::ascii();
+        let rows = render(&mut popup, &theme);
+        assert_eq!(rows[0], "+----^---+");
+        assert_eq!(rows[4], "+--------+");
+    }
 }
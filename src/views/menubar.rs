@@ -6,7 +6,7 @@ use std::rc::Rc;
 use theme::ColorStyle;
 use unicode_width::UnicodeWidthStr;
 use vec::Vec2;
-use view::{Position, View};
+use view::{LayoutTree, Position, View};
 use views::{MenuPopup, OnEventView};
 use Cursive;
 use Printer;
@@ -210,7 +210,8 @@ impl Menubar {
                         .iter()
                         .map(|child| child.label().width() + 2)
                         .sum(),
-                    if self.autohide { 1 } else { 0 },
+                    // The views are below the menubar while it's shown.
+                    0,
                 );
                 // Since the closure will be called multiple times,
                 // we also need a new Rc on every call.
@@ -402,4 +403,13 @@ impl View for Menubar {
 
         Rect::from_size((x, 0), (width, 1))
     }
+
+    fn debug_layout(&mut self, tree: &mut LayoutTree) {
+        if self.state == State::Inactive {
+            return;
+        }
+        if let Some(item) = self.root.children.get(self.focus) {
+            tree.set_info(format!("selects {:?}", item.label()));
+        }
+    }
 }
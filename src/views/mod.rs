@@ -35,21 +35,30 @@ macro_rules! impl_enabled {
     }
 }
 
+mod accessible_view;
+mod autocomplete;
 mod box_view;
 mod button;
 mod canvas;
 mod checkbox;
+mod color_picker;
 mod circular_focus;
+mod context_menu_view;
 mod debug_view;
 mod dialog;
 mod dummy;
 mod edit_view;
 mod enableable_view;
+mod file_dialog;
+mod focus_tracker;
+mod grid_layout;
 mod hideable_view;
 mod id_view;
 mod layer;
+mod lazy_list_view;
 mod linear_layout;
 mod list_view;
+mod log_view;
 mod menu_popup;
 mod menubar;
 mod on_event_view;
@@ -62,27 +71,40 @@ mod select_view;
 mod shadow_view;
 mod sized_view;
 mod slider_view;
+mod spin_view;
 mod stack_view;
+mod tab_view;
+mod table_view;
 mod text_area;
 mod text_view;
+mod tooltip_view;
 mod tracked_view;
+mod tree_view;
 mod view_box;
 
+pub use self::accessible_view::{AccessibleView, Description, Role};
 pub use self::box_view::BoxView;
 pub use self::button::Button;
 pub use self::canvas::Canvas;
-pub use self::checkbox::Checkbox;
+pub use self::checkbox::{Checkbox, CheckboxState};
+pub use self::color_picker::ColorPicker;
 pub use self::circular_focus::CircularFocus;
+pub use self::context_menu_view::ContextMenuView;
 pub use self::debug_view::DebugView;
 pub use self::dialog::{Dialog, DialogFocus};
 pub use self::dummy::DummyView;
-pub use self::edit_view::EditView;
+pub use self::edit_view::{EditView, Validation};
 pub use self::enableable_view::EnableableView;
+pub use self::file_dialog::FileDialog;
+pub use self::focus_tracker::FocusTracker;
+pub use self::grid_layout::GridLayout;
 pub use self::hideable_view::HideableView;
 pub use self::id_view::{IdView, ViewRef};
 pub use self::layer::Layer;
+pub use self::lazy_list_view::{LazyListView, ListSource};
 pub use self::linear_layout::LinearLayout;
 pub use self::list_view::{ListChild, ListView};
+pub use self::log_view::LogView;
 pub use self::menu_popup::MenuPopup;
 pub use self::menubar::Menubar;
 pub use self::on_event_view::OnEventView;
@@ -95,8 +117,17 @@ pub use self::select_view::SelectView;
 pub use self::shadow_view::ShadowView;
 pub use self::sized_view::SizedView;
 pub use self::slider_view::SliderView;
-pub use self::stack_view::{LayerPosition, StackView};
+pub use self::spin_view::SpinView;
+pub use self::stack_view::{
+    LayerHandle, LayerPosition, StackView, StaleLayerHandle, Transition,
+};
+pub use self::tab_view::{TabBarPosition, TabView};
+pub use self::table_view::{TableColumn, TableView, TableViewItem};
 pub use self::text_area::TextArea;
-pub use self::text_view::{TextContent, TextContentRef, TextView};
+pub use self::text_view::{TextContent, TextContentRef, TextView, WrapMode};
+pub use self::tooltip_view::TooltipView;
 pub use self::tracked_view::TrackedView;
+pub use self::tree_view::{TreeGlyphs, TreeView};
 pub use self::view_box::ViewBox;
+
+pub(crate) use self::tooltip_view::Hovered;
@@ -1,6 +1,6 @@
 use event::{Event, EventResult};
 use vec::Vec2;
-use view::{Margins, View, ViewWrapper};
+use view::{FocusPath, LayoutTree, Margins, View, ViewWrapper};
 use Printer;
 
 /// Adds padding to another view.
@@ -62,4 +62,14 @@ impl<V: View> ViewWrapper for PaddedView<V> {
         let printer = &printer.offset(top_left).shrinked(bot_right);
         self.view.draw(printer);
     }
+
+    fn wrap_debug_layout(&mut self, tree: &mut LayoutTree) {
+        let size = tree.size().saturating_sub(self.margins.combined());
+        let offset = self.margins.top_left();
+        tree.child(&mut self.view, offset, size, true);
+    }
+
+    fn wrap_follow_focus(&mut self, path: &mut FocusPath) {
+        path.child(&mut self.view, self.margins.top_left());
+    }
 }
@@ -1,10 +1,9 @@
 use align::*;
 use event::{Event, EventResult};
 use rect::Rect;
-use theme::ColorStyle;
-use unicode_width::UnicodeWidthStr;
+use theme::BorderStyle;
 use vec::Vec2;
-use view::{View, ViewWrapper};
+use view::{FocusPath, LayoutTree, Margins, View, ViewWrapper};
 use Printer;
 use With;
 
@@ -20,6 +19,19 @@ pub struct Panel<V: View> {
     // Where to put the title position
     title_position: HAlign,
 
+    // Overrides the theme's border style.
+    border_style: Option<BorderStyle>,
+
+    // Padding around the inner view, inside the borders.
+    padding: Margins,
+
+    // Where to put the inner view, if it doesn't fill the panel.
+    content_align: Option<Align>,
+
+    // Position and size of the inner view, from the last layout.
+    content_offset: Vec2,
+    content_size: Vec2,
+
     // `true` when we needs to relayout
     invalidated: bool,
 }
@@ -31,6 +43,11 @@ impl<V: View> Panel<V> {
             view,
             title: String::new(),
             title_position: HAlign::Center,
+            border_style: None,
+            padding: Margins::new(0, 0, 0, 0),
+            content_align: None,
+            content_offset: Vec2::new(1, 1),
+            content_size: Vec2::zero(),
             invalidated: true,
         }
     }
@@ -62,25 +79,57 @@ impl<V: View> Panel<V> {
         self.title_position = align;
     }
 
+    /// Sets the style of the border, instead of the theme's.
+    pub fn set_border(&mut self, style: BorderStyle) {
+        self.border_style = Some(style);
+    }
+
+    /// Sets the style of the border, instead of the theme's.
+    ///
+    /// Chainable variant.
+    pub fn border(self, style: BorderStyle) -> Self {
+        self.with(|s| s.set_border(style))
+    }
+
+    /// Sets the padding between the borders and the inner view.
+    ///
+    /// If there is not enough room for the inner view, the padding shrinks.
+    ///
+    /// Defaults to no padding.
+    pub fn set_padding<M: Into<Margins>>(&mut self, padding: M) {
+        self.padding = padding.into();
+        self.invalidate();
+    }
+
+    /// Sets the padding between the borders and the inner view.
+    ///
+    /// Chainable variant.
+    pub fn padding<M: Into<Margins>>(self, padding: M) -> Self {
+        self.with(|s| s.set_padding(padding))
+    }
+
+    /// Gives the inner view only the size it requires, placed with `align`.
+    ///
+    /// By default, the inner view takes all the space inside the panel.
+    pub fn set_content_align(&mut self, align: Align) {
+        self.content_align = Some(align);
+        self.invalidate();
+    }
+
+    /// Gives the inner view only the size it requires, placed with `align`.
+    ///
+    /// Chainable variant.
+    pub fn content_align(self, align: Align) -> Self {
+        self.with(|s| s.set_content_align(align))
+    }
+
     fn draw_title(&self, printer: &Printer) {
-        if !self.title.is_empty() {
-            let len = self.title.width();
-            if len + 4 > printer.size.x {
-                return;
-            }
-            let spacing = 3; //minimum distance to borders
-            let x = spacing + self
-                .title_position
-                .get_offset(len, printer.size.x - 2 * spacing);
-            printer.with_high_border(false, |printer| {
-                printer.print((x - 2, 0), "┤ ");
-                printer.print((x + len, 0), " ├");
-            });
-
-            printer.with_color(ColorStyle::title_primary(), |p| {
-                p.print((x, 0), &self.title)
-            });
-        }
+        printer.print_box_title(
+            (0, 0),
+            printer.size.x,
+            &self.title,
+            self.title_position,
+        );
     }
 
     fn invalidate(&mut self) {
@@ -92,34 +141,116 @@ impl<V: View> ViewWrapper for Panel<V> {
     wrap_impl!(self.view: V);
 
     fn wrap_on_event(&mut self, event: Event) -> EventResult {
-        self.view.on_event(event.relativized((1, 1)))
+        self.view.on_event(event.relativized(self.content_offset))
     }
 
     fn wrap_required_size(&mut self, req: Vec2) -> Vec2 {
         // TODO: make borders conditional?
-        let req = req.saturating_sub((2, 2));
+        let taken = self.padding.combined() + (2, 2);
+        let req = req.saturating_sub(taken);
 
-        self.view.required_size(req) + (2, 2)
+        self.view.required_size(req) + taken
     }
 
     fn wrap_draw(&self, printer: &Printer) {
-        printer.print_box((0, 0), printer.size, true);
-        self.draw_title(&printer);
+        let draw_border = |printer: &Printer| {
+            printer.print_box((0, 0), printer.size, true);
+            self.draw_title(printer);
+        };
+        match self.border_style {
+            Some(style) => printer.with_borders(style, draw_border),
+            None => draw_border(printer),
+        }
 
-        let printer = printer.offset((1, 1)).shrinked((1, 1));
+        let printer = printer
+            .offset(self.content_offset)
+            .cropped(self.content_size);
         self.view.draw(&printer);
     }
 
     fn wrap_layout(&mut self, size: Vec2) {
-        self.view.layout(size.saturating_sub((2, 2)));
+        let available = size.saturating_sub((2, 2));
+
+        // The inner view comes first, the padding gets what's left.
+        let required = self.view.required_size(available);
+        let padding = self.padding.shrunk_to_fit(available, required);
+        let available = available.saturating_sub(padding.combined());
+
+        let (offset, size) = match self.content_align {
+            Some(ref align) => {
+                let size = required.or_min(available);
+                (align.get_offset(size, available), size)
+            }
+            None => (Vec2::zero(), available),
+        };
+        self.content_offset = Vec2::new(1, 1) + padding.top_left() + offset;
+        self.content_size = size;
+        self.view.layout(size);
+        self.invalidated = false;
     }
 
-    fn wrap_important_area(&self, size: Vec2) -> Rect {
-        let inner_size = size.saturating_sub((2, 2));
-        self.view.important_area(inner_size) + (1, 1)
+    fn wrap_important_area(&self, _: Vec2) -> Rect {
+        self.view.important_area(self.content_size) + self.content_offset
     }
 
     fn wrap_needs_relayout(&self) -> bool {
         self.invalidated || self.view.needs_relayout()
     }
+
+    fn wrap_debug_layout(&mut self, tree: &mut LayoutTree) {
+        let (offset, size) = (self.content_offset, self.content_size);
+        tree.child(&mut self.view, offset, size, true);
+    }
+
+    fn wrap_follow_focus(&mut self, path: &mut FocusPath) {
+        path.child(&mut self.view, self.content_offset);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use views::{BoxView, DummyView};
+
+    type Content = BoxView<DummyView>;
+
+    fn layout(panel: &mut Panel<Content>, size: (usize, usize)) -> Rect {
+        panel.layout(Vec2::from(size));
+        Rect::from_size(panel.content_offset, panel.content_size)
+    }
+
+    #[test]
+    fn test_content_layout() {
+        let content = || BoxView::with_fixed_size((4, 2), DummyView);
+
+        // By default, the content fills the panel, inside the padding.
+        let mut panel = Panel::new(content()).padding((1, 2, 1, 0));
+        assert_eq!(
+            layout(&mut panel, (20, 10)),
+            Rect::from_size((2, 2), (15, 7))
+        );
+
+        panel.set_content_align(Align::bot_right());
+        assert_eq!(
+            layout(&mut panel, (20, 10)),
+            Rect::from_size((13, 7), (4, 2))
+        );
+
+        let mut panel = Panel::new(content()).content_align(Align::center());
+        assert_eq!(
+            layout(&mut panel, (20, 10)),
+            Rect::from_size((8, 4), (4, 2))
+        );
+
+        // Without enough room, the padding shrinks.
+        panel.set_padding((4, 4, 2, 2));
+        assert_eq!(
+            layout(&mut panel, (8, 5)),
+            Rect::from_size((2, 1), (4, 2))
+        );
+        assert_eq!(
+            layout(&mut panel, (4, 3)),
+            Rect::from_size((1, 1), (2, 1))
+        );
+    }
 }
@@ -1,4 +1,5 @@
 use align::HAlign;
+use std::cell::Cell;
 use std::cmp;
 use std::thread;
 use theme::{ColorStyle, ColorType, Effect};
@@ -20,6 +21,10 @@ use {Printer, With};
 /// The bar defaults to the current theme's highlight color,
 /// but that can be customized.
 ///
+/// When the progress cannot be measured, the bar can be made
+/// [indeterminate](#method.set_indeterminate): it then shows a block
+/// bouncing from side to side instead.
+///
 /// # Example
 ///
 /// ```
@@ -41,6 +46,12 @@ pub struct ProgressBar {
     color: ColorType,
     // TODO: use a Promise instead?
     label_maker: Box<Fn(usize, (usize, usize)) -> String>,
+
+    indeterminate: bool,
+    // Number of frames for the block to go back and forth.
+    animation_frames: usize,
+    // Current frame, advanced on every draw.
+    phase: Cell<usize>,
 }
 
 fn make_percentage(value: usize, (min, max): (usize, usize)) -> String {
@@ -91,6 +102,9 @@ impl ProgressBar {
             value: Counter::new(0),
             color: ColorStyle::highlight().back,
             label_maker: Box::new(make_percentage),
+            indeterminate: false,
+            animation_frames: 40,
+            phase: Cell::new(0),
         }
     }
 
@@ -189,6 +203,39 @@ impl ProgressBar {
         self.value.set(value);
     }
 
+    /// Sets whether the progress is indeterminate.
+    ///
+    /// An indeterminate bar ignores its value and label, and instead shows a
+    /// block going back and forth, advancing on every redraw.
+    ///
+    /// Use `Cursive::set_fps` to keep the animation running.
+    pub fn set_indeterminate(&mut self, indeterminate: bool) {
+        self.indeterminate = indeterminate;
+    }
+
+    /// Makes the progress indeterminate.
+    ///
+    /// Chainable variant of `set_indeterminate(true)`.
+    pub fn indeterminate(self) -> Self {
+        self.with(|s| s.set_indeterminate(true))
+    }
+
+    /// Sets the number of frames for the indeterminate animation.
+    ///
+    /// This is how many redraws it takes the block to go back and forth.
+    ///
+    /// Defaults to 40. Values under 2 are changed to 2.
+    pub fn set_animation_frames(&mut self, frames: usize) {
+        self.animation_frames = cmp::max(2, frames);
+    }
+
+    /// Sets the number of frames for the indeterminate animation.
+    ///
+    /// Chainable variant of `set_animation_frames`.
+    pub fn with_animation_frames(self, frames: usize) -> Self {
+        self.with(|s| s.set_animation_frames(frames))
+    }
+
     /// Sets the color style.
     ///
     /// The default color is `PaletteColor::Highlight`.
@@ -224,8 +271,37 @@ fn sub_block(extra: usize) -> &'static str {
     }
 }
 
+impl ProgressBar {
+    fn draw_indeterminate(&self, printer: &Printer) {
+        let available = printer.size.x;
+        let block = cmp::max(1, available / 5);
+        let free = available.saturating_sub(block);
+
+        // The block goes right during the first half of the frames,
+        // then comes back left.
+        let frames = self.animation_frames;
+        let phase = self.phase.get() % frames;
+        self.phase.set(phase + 1);
+
+        let half = frames / 2;
+        let step = if phase <= half { phase } else { frames - phase };
+        let start = free * step / half;
+
+        let color_style =
+            ColorStyle::new(ColorStyle::highlight().front, self.color);
+        printer.with_color(color_style, |printer| {
+            printer.print_hline((start, 0), block, " ");
+        });
+    }
+}
+
 impl View for ProgressBar {
     fn draw(&self, printer: &Printer) {
+        if self.indeterminate {
+            self.draw_indeterminate(printer);
+            return;
+        }
+
         // Now, the bar itself...
         let available = printer.size.x;
 
@@ -260,3 +336,50 @@ impl View for ProgressBar {
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backend::puppet;
+    use theme::{self, PaletteColor};
+    use Vec2;
+
+    // Returns the columns drawn with the bar color.
+    fn draw(bar: &ProgressBar) -> Vec<usize> {
+        let size = Vec2::new(10, 1);
+        let backend = puppet::Backend::init(Some(size));
+        let theme = theme::load_default();
+        bar.draw(&Printer::new(size, &theme, &*backend));
+
+        let screen = backend.screen();
+        assert_eq!(screen.row(0).trim(), "");
+        let color = theme.palette[PaletteColor::Highlight];
+        (0..size.x)
+            .filter(|&x| {
+                screen.cell(Vec2::new(x, 0)).unwrap().style.colors.back
+                    == color
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_indeterminate() {
+        let mut bar = ProgressBar::new()
+            .indeterminate()
+            .with_animation_frames(8)
+            .with_label(|_, _| "label".to_string());
+        bar.set_value(50);
+
+        // The block moves right to the edge, comes back, and starts again.
+        let starts: Vec<_> = (0..10)
+            .map(|_| {
+                // A block going past the edges would be cropped.
+                let block = draw(&bar);
+                assert_eq!(block.len(), 2);
+                assert_eq!(block[1], block[0] + 1);
+                block[0]
+            })
+            .collect();
+        assert_eq!(starts, vec![0, 2, 4, 6, 8, 6, 4, 2, 0, 2]);
+    }
+}
@@ -1,10 +1,11 @@
 use direction::Direction;
-use event::{Event, EventResult, Key, MouseButton, MouseEvent};
+use event::{Callback, Event, EventResult, Key, MouseButton, MouseEvent};
 use std::cell::RefCell;
 use std::rc::Rc;
 use theme::ColorStyle;
 use vec::Vec2;
 use view::View;
+use views::LinearLayout;
 use Cursive;
 use {Printer, With};
 
@@ -15,10 +16,24 @@ struct SharedState<T> {
     on_change: Option<Rc<Fn(&mut Cursive, &T)>>,
 }
 
-impl<T> SharedState<T> {
+impl<T: 'static> SharedState<T> {
     pub fn selection(&self) -> Rc<T> {
         Rc::clone(&self.values[self.selection])
     }
+
+    // Returns the `on_change` callback to run, if any.
+    fn select(&mut self, id: usize) -> Option<Callback> {
+        self.selection = id;
+        if id >= self.values.len() {
+            // The button will be selected once created.
+            return None;
+        }
+        self.on_change.as_ref().map(|on_change| {
+            let on_change = Rc::clone(on_change);
+            let value = self.selection();
+            Callback::from_fn(move |s| on_change(s, &value))
+        })
+    }
 }
 
 /// Group to coordinate multiple radio buttons.
@@ -63,6 +78,76 @@ impl<T: 'static> RadioGroup<T> {
         RadioButton::new(Rc::clone(&self.state), count, label.into())
     }
 
+    /// Creates a group with a button for each `(label, value)` pair.
+    ///
+    /// Returns the group, to read the selection, and the buttons in a
+    /// vertical `LinearLayout`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cursive::views::{Dialog, RadioGroup};
+    /// let (group, buttons) = RadioGroup::from_options(vec![
+    ///     ("Small", 5),
+    ///     ("Medium", 15),
+    ///     ("Large", 25),
+    /// ]);
+    /// let group = group.selected(1).on_change(|s, size| {
+    ///     s.add_layer(Dialog::info(format!("Size: {}cm", size)));
+    /// });
+    ///
+    /// assert_eq!(*group.selection(), 15);
+    /// let dialog = Dialog::around(buttons).title("Size");
+    /// ```
+    pub fn from_options<S, I>(options: I) -> (Self, LinearLayout)
+    where
+        S: Into<String>,
+        I: IntoIterator<Item = (S, T)>,
+    {
+        let mut group = Self::new();
+        let buttons = group.buttons(options);
+        (group, buttons)
+    }
+
+    /// Adds a button for each `(label, value)` pair to the group.
+    ///
+    /// The buttons are returned in a vertical `LinearLayout`.
+    pub fn buttons<S, I>(&mut self, options: I) -> LinearLayout
+    where
+        S: Into<String>,
+        I: IntoIterator<Item = (S, T)>,
+    {
+        let mut layout = LinearLayout::vertical();
+        for (label, value) in options {
+            layout.add_child(self.button(value, label));
+        }
+        layout
+    }
+
+    /// Selects the button with the given id.
+    ///
+    /// Returns a callback in response to the selection change.
+    ///
+    /// You should run this callback with a `&mut Cursive`.
+    ///
+    /// If no button has this id yet, it will be selected once it's added to
+    /// the group, and the returned callback does nothing.
+    pub fn set_selection(&mut self, id: usize) -> Callback {
+        let mut state = self.state.borrow_mut();
+        state.select(id).unwrap_or_else(Callback::dummy)
+    }
+
+    /// Selects the button with the given id.
+    ///
+    /// Chainable variant.
+    ///
+    /// Does not apply the `on_change` callback.
+    pub fn selected(self, id: usize) -> Self {
+        self.with(|s| {
+            s.set_selection(id);
+        })
+    }
+
     /// Returns the id of the selected button.
     ///
     /// Buttons are indexed in the order they are created, starting from 0.
@@ -141,15 +226,7 @@ impl<T: 'static> RadioButton<T> {
 
     /// Selects this button, un-selecting any other in the same group.
     pub fn select(&mut self) -> EventResult {
-        let mut state = self.state.borrow_mut();
-        state.selection = self.id;
-        if let Some(ref on_change) = state.on_change {
-            let on_change = Rc::clone(on_change);
-            let value = state.selection();
-            EventResult::with_cb(move |s| on_change(s, &value))
-        } else {
-            EventResult::Consumed(None)
-        }
+        EventResult::Consumed(self.state.borrow_mut().select(self.id))
     }
 
     /// Selects this button, un-selecting any other in the same group.
@@ -206,6 +283,10 @@ impl<T: 'static> View for RadioButton<T> {
     }
 
     fn on_event(&mut self, event: Event) -> EventResult {
+        if !self.enabled {
+            return EventResult::Ignored;
+        }
+
         match event {
             Event::Key(Key::Enter) | Event::Char(' ') => {
                 self.select()
@@ -221,3 +302,70 @@ impl<T: 'static> View for RadioButton<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backend::puppet;
+    use std::cell::Cell;
+    use views::Dialog;
+
+    fn click(siv: &mut Cursive, x: usize, y: usize) {
+        let button = MouseButton::Left;
+        for &event in &[MouseEvent::Press(button), MouseEvent::Release(button)]
+        {
+            siv.on_event(Event::Mouse {
+                offset: Vec2::zero(),
+                position: Vec2::new(x, y),
+                event,
+            });
+        }
+    }
+
+    #[test]
+    fn test_from_options() {
+        let backend = puppet::Backend::init(Some(Vec2::new(20, 5)));
+        let mut siv = Cursive::new(move || backend);
+
+        let changes = Rc::new(Cell::new(0));
+        let c = Rc::clone(&changes);
+        let (group, buttons) =
+            RadioGroup::from_options(vec![("Red", 'r'), ("Green", 'g')]);
+        let group = group.selected(1).on_change(move |_, _| {
+            c.set(c.get() + 1);
+        });
+        siv.add_fullscreen_layer(Dialog::around(buttons));
+
+        let screen = siv.dump_screen();
+        let row = |name| screen.iter().position(|row| row.contains(name));
+        let (red, green) = (row("Red").unwrap(), row("Green").unwrap());
+        let x = screen[red].find('(').unwrap();
+        assert!(screen[green].contains("(X) Green"));
+
+        click(&mut siv, x, red);
+        click(&mut siv, x, green);
+        let screen = siv.dump_screen();
+        assert!(screen[red].contains("( ) Red"));
+        assert!(screen[green].contains("(X) Green"));
+        assert_eq!(*group.selection(), 'g');
+        assert_eq!(changes.get(), 2);
+    }
+
+    #[test]
+    fn test_select_missing_button() {
+        let changes = Rc::new(Cell::new(0));
+        let c = Rc::clone(&changes);
+        let mut group = RadioGroup::new().on_change(move |_, _| {
+            c.set(c.get() + 1);
+        });
+
+        let mut siv = Cursive::dummy();
+        group.set_selection(1)(&mut siv);
+        assert_eq!(changes.get(), 0);
+        assert_eq!(group.selected_id(), 1);
+
+        group.button('r', "Red");
+        group.button('g', "Green");
+        assert_eq!(*group.selection(), 'g');
+    }
+}
@@ -1,11 +1,17 @@
 use std::cmp::min;
+use std::rc::Rc;
 
 use direction::{Direction, Orientation};
 use event::{AnyCb, Event, EventResult, Key, MouseButton, MouseEvent};
 use rect::Rect;
 use theme::ColorStyle;
-use view::{ScrollStrategy, Selector, SizeCache, View};
-use {Printer, Vec2, With, XY};
+use view::{
+    FocusPath, HorizontalScrollStrategy, LayoutTree, RevealStrategy,
+    ScrollPosition, ScrollStrategy, Selector, SizeCache, View,
+};
+use {Cursive, Printer, Vec2, With, XY};
+
+type ScrollCallback = Fn(&mut Cursive, ScrollPosition);
 
 /// Wraps a view in a scrollable area.
 pub struct ScrollView<V> {
@@ -37,6 +43,9 @@ pub struct ScrollView<V> {
     /// TODO: have an option to show scrollbar on top/left.
     show_scrollbars: bool,
 
+    /// Should we show arrows where some content is hidden?
+    show_overflow_markers: bool,
+
     /// How much padding should be between content and scrollbar?
     ///
     /// scrollbar_padding.x is the horizontal padding before the vertical scrollbar.
@@ -50,6 +59,18 @@ pub struct ScrollView<V> {
 
     /// Defines how to update the offset when the view size changes.
     scroll_strategy: ScrollStrategy,
+
+    /// Same as `scroll_strategy`, for the horizontal offset.
+    horizontal_scroll_strategy: HorizontalScrollStrategy,
+
+    /// Called when an event changes the offset.
+    on_scroll: Option<Rc<ScrollCallback>>,
+
+    /// Defines how to scroll when the important area of the child moves.
+    reveal_strategy: RevealStrategy,
+
+    /// Important area of the child last time we revealed it.
+    last_important: Option<Rect>,
 }
 
 impl<V> ScrollView<V>
@@ -65,10 +86,15 @@ where
             last_size: Vec2::zero(),
             enabled: XY::new(false, true),
             show_scrollbars: true,
+            show_overflow_markers: false,
             scrollbar_padding: Vec2::new(1, 0),
             thumb_grab: None,
             size_cache: None,
             scroll_strategy: ScrollStrategy::KeepRow,
+            horizontal_scroll_strategy: HorizontalScrollStrategy::KeepColumn,
+            on_scroll: None,
+            reveal_strategy: RevealStrategy::Nearest,
+            last_important: None,
         }
     }
 
@@ -82,8 +108,10 @@ where
     /// The scroll strategy defines how the scrolling position is adjusted
     /// when the size of the view or the content change.
     ///
-    /// It is reset to `ScrollStrategy::KeepRow` whenever the user scrolls
-    /// manually.
+    /// `StickToTop` and `StickToBottom` are reset to `ScrollStrategy::KeepRow`
+    /// whenever the user scrolls manually, so the view doesn't jump back.
+    /// Other strategies are kept. A new strategy is applied right away, and
+    /// on each layout.
     pub fn set_scroll_strategy(&mut self, strategy: ScrollStrategy) {
         self.scroll_strategy = strategy;
         self.adjust_scroll();
@@ -96,6 +124,56 @@ where
         self.with(|s| s.set_scroll_strategy(strategy))
     }
 
+    /// Returns the current scroll strategy.
+    pub fn get_scroll_strategy(&self) -> ScrollStrategy {
+        self.scroll_strategy
+    }
+
+    /// Defines the way horizontal scrolling is adjusted on content or size
+    /// change.
+    ///
+    /// This only matters if horizontal scrolling is enabled with
+    /// `set_scroll_x()`.
+    ///
+    /// `StickToLeft` and `StickToRight` are reset to
+    /// `HorizontalScrollStrategy::KeepColumn` whenever the user scrolls
+    /// manually.
+    pub fn set_horizontal_scroll_strategy(
+        &mut self, strategy: HorizontalScrollStrategy,
+    ) {
+        self.horizontal_scroll_strategy = strategy;
+        self.adjust_scroll();
+    }
+
+    /// Defines the way horizontal scrolling is adjusted on content or size
+    /// change.
+    ///
+    /// Chainable variant.
+    pub fn horizontal_scroll_strategy(
+        self, strategy: HorizontalScrollStrategy,
+    ) -> Self {
+        self.with(|s| s.set_horizontal_scroll_strategy(strategy))
+    }
+
+    /// Defines how to scroll when the important area of the inner view
+    /// moves, for example when its selection changes.
+    ///
+    /// The area is revealed whether it was moved by an event or by code,
+    /// like `SelectView::set_selection()`.
+    ///
+    /// Defaults to `RevealStrategy::Nearest`.
+    pub fn set_reveal_strategy(&mut self, strategy: RevealStrategy) {
+        self.reveal_strategy = strategy;
+    }
+
+    /// Defines how to scroll when the important area of the inner view
+    /// moves.
+    ///
+    /// Chainable variant.
+    pub fn reveal_strategy(self, strategy: RevealStrategy) -> Self {
+        self.with(|s| s.set_reveal_strategy(strategy))
+    }
+
     /// Control whether scroll bars are visibile.
     ///
     /// Defaults to `true`.
@@ -110,6 +188,25 @@ where
         self.with(|s| s.set_show_scrollbars(show_scrollbars))
     }
 
+    /// Control whether arrows are shown on the edges hiding some content.
+    ///
+    /// The arrows follow the scroll offset: `▲` is shown on the first
+    /// visible row when some content is above it, `▼` on the last one
+    /// when some content is below it, and likewise with `◀` and `▶`. They
+    /// are drawn over the content, which is useful without scroll bars.
+    ///
+    /// Defaults to `false`.
+    pub fn set_show_overflow_markers(&mut self, show: bool) {
+        self.show_overflow_markers = show;
+    }
+
+    /// Control whether arrows are shown on the edges hiding some content.
+    ///
+    /// Chainable variant
+    pub fn show_overflow_markers(self, show: bool) -> Self {
+        self.with(|s| s.set_show_overflow_markers(show))
+    }
+
     /// Sets the scroll offset to the given value
     pub fn set_offset<S>(&mut self, offset: S)
     where
@@ -119,6 +216,52 @@ where
         self.offset = offset.into().or_min(max_offset);
     }
 
+    /// Sets a callback to run when the scroll offset changes.
+    ///
+    /// It is given the new position, which tells if the view now shows the
+    /// top or the bottom of the content. For example, a log view can use
+    /// it to resume following new content when the user scrolls back to
+    /// the bottom.
+    ///
+    /// Only changes caused by an event run the callback: moving the offset
+    /// programmatically, or a scroll strategy reacting to a layout change,
+    /// does not.
+    pub fn set_on_scroll<F>(&mut self, cb: F)
+    where
+        F: Fn(&mut Cursive, ScrollPosition) + 'static,
+    {
+        self.on_scroll = Some(Rc::new(cb));
+    }
+
+    /// Sets a callback to run when the scroll offset changes.
+    ///
+    /// Chainable variant.
+    pub fn on_scroll<F>(self, cb: F) -> Self
+    where
+        F: Fn(&mut Cursive, ScrollPosition) + 'static,
+    {
+        self.with(|s| s.set_on_scroll(cb))
+    }
+
+    /// Returns the current scroll position.
+    pub fn scroll_position(&self) -> ScrollPosition {
+        ScrollPosition {
+            offset: self.offset,
+            at_top: self.is_at_top(),
+            at_bottom: self.is_at_bottom(),
+        }
+    }
+
+    /// Returns `true` if the top of the content is visible.
+    pub fn is_at_top(&self) -> bool {
+        self.offset.y == 0
+    }
+
+    /// Returns `true` if the bottom of the content is visible.
+    pub fn is_at_bottom(&self) -> bool {
+        self.offset.y + self.available_size().y >= self.inner_size.y
+    }
+
     /// Controls whether this view can scroll vertically.
     ///
     /// Defaults to `true`.
@@ -153,6 +296,85 @@ where
         self.with(|s| s.set_scroll_x(enabled))
     }
 
+    /// Programmatically scroll just enough to show `position`.
+    ///
+    /// `position` is a cell of the inner view. Use `set_offset()` to put
+    /// a given cell at the top-left corner instead.
+    pub fn scroll_to<S>(&mut self, position: S)
+    where
+        S: Into<Vec2>,
+    {
+        let position = position.into();
+        let available = self.available_size().or_max((1, 1));
+
+        // The furthest top-left offset still showing `position`.
+        let min_offset = (position + (1, 1)).saturating_sub(available);
+        let offset = self.offset.or_max(min_offset).or_min(position);
+        self.set_offset(offset);
+    }
+
+    /// Programmatically scroll just enough to show `rect`.
+    ///
+    /// `rect` is an area of the inner view. If it is larger than the
+    /// viewport, as much of it as possible is shown.
+    pub fn scroll_to_rect(&mut self, rect: Rect) {
+        // The furthest top-left we can go
+        let top_left = (rect.bottom_right() + (1, 1))
+            .saturating_sub(self.available_size());
+        // The furthest bottom-right we can go
+        let bottom_right = rect.top_left();
+
+        // "top_left < bottom_right" is NOT guaranteed
+        // if the rect is larger than the view.
+        let offset_min = Vec2::min(top_left, bottom_right);
+        let offset_max = Vec2::max(top_left, bottom_right);
+
+        let offset = self.offset.or_max(offset_min).or_min(offset_max);
+        self.set_offset(offset);
+    }
+
+    /// Programmatically scroll to show `rect` in the middle of the view.
+    ///
+    /// Nothing moves along the axes where `rect` is already visible, or
+    /// where it is larger than the viewport.
+    pub fn center_on_rect(&mut self, rect: Rect) {
+        let available = self.available_size();
+        let old_offset = self.offset;
+        self.scroll_to_rect(rect);
+
+        let centered = (rect.top_left() + rect.size() / 2)
+            .saturating_sub(available / 2);
+        let fits = rect.size().zip_map(available, |r, a| r < a);
+        let offset = old_offset.zip4(self.offset, centered, fits).map(
+            |(old, new, centered, fits)| {
+                if old != new && fits {
+                    centered
+                } else {
+                    new
+                }
+            },
+        );
+        self.set_offset(offset);
+    }
+
+    /// Scrolls to the important area of the inner view, if it moved.
+    fn reveal_important_area(&mut self) {
+        let important = self.inner.important_area(self.inner_size);
+        if self.last_important == Some(important) {
+            return;
+        }
+        self.last_important = Some(important);
+        self.reveal(important);
+    }
+
+    /// Scrolls to `important`, following the reveal strategy.
+    fn reveal(&mut self, important: Rect) {
+        match self.reveal_strategy {
+            RevealStrategy::Nearest => self.scroll_to_rect(important),
+            RevealStrategy::Center => self.center_on_rect(important),
+        }
+    }
+
     /// Programmatically scroll to the top of the view.
     pub fn scroll_to_top(&mut self) {
         let curr_x = self.offset.x;
@@ -278,7 +500,7 @@ where
         // Iterate on axises, and keep the one we grabbed.
         if let Some((orientation, pos, length, offset)) =
             XY::zip4(Orientation::pair(), position, lengths, offsets)
-                .keep(grabbed.and(self.enabled))
+                .keep(grabbed.and(self.enabled).and(self.is_scrolling()))
                 .into_iter()
                 .filter_map(|x| x)
                 .next()
@@ -415,87 +637,36 @@ where
         match self.scroll_strategy {
             ScrollStrategy::StickToTop => self.scroll_to_top(),
             ScrollStrategy::StickToBottom => self.scroll_to_bottom(),
-            ScrollStrategy::KeepRow => (),
+            ScrollStrategy::KeepCursor => {
+                let important = self.inner.important_area(self.inner_size);
+                self.reveal(important);
+            }
+            ScrollStrategy::KeepRow | ScrollStrategy::Manual => (),
         }
-    }
 
-    /// Returns the wrapped view.
-    pub fn into_inner(self) -> V {
-        self.inner
+        match self.horizontal_scroll_strategy {
+            HorizontalScrollStrategy::StickToLeft => self.scroll_to_left(),
+            HorizontalScrollStrategy::StickToRight => self.scroll_to_right(),
+            HorizontalScrollStrategy::KeepColumn => (),
+        }
     }
 
-    inner_getters!(self.inner: V);
-}
-
-impl<V> View for ScrollView<V>
-where
-    V: View,
-{
-    fn draw(&self, printer: &Printer) {
-        // Draw scrollbar?
-        let scrolling = self.is_scrolling();
-
-        let lengths = self.scrollbar_thumb_lengths();
-        let offsets = self.scrollbar_thumb_offsets(lengths);
-
-        let line_c = XY::new("-", "|");
-
-        let color = if printer.focused {
-            ColorStyle::highlight()
-        } else {
-            ColorStyle::highlight_inactive()
-        };
-
-        let size = self.available_size();
-
-        // Draw the scrollbars
-        XY::zip5(lengths, offsets, size, line_c, Orientation::pair()).run_if(
-            scrolling,
-            |(length, offset, size, c, orientation)| {
-                let start = printer
-                    .size
-                    .saturating_sub((1, 1))
-                    .with_axis(orientation, 0);
-                let offset = orientation.make_vec(offset, 0);
-
-                printer.print_line(orientation, start, size, c);
-
-                let thumb_c = if self
-                    .thumb_grab
-                    .map(|(o, _)| o == orientation)
-                    .unwrap_or(false)
-                {
-                    " "
-                } else {
-                    "▒"
-                };
-                printer.with_color(color, |printer| {
-                    printer.print_line(
-                        orientation,
-                        start + offset,
-                        length,
-                        thumb_c,
-                    );
-                });
-            },
-        );
-
-        // Draw the X between the two scrollbars.
-        if scrolling.both() {
-            printer.print(printer.size.saturating_sub((1, 1)), "╳");
+    /// Stops following an edge, after the user scrolled away from it.
+    fn stop_sticking(&mut self) {
+        match self.scroll_strategy {
+            ScrollStrategy::StickToTop | ScrollStrategy::StickToBottom => {
+                self.scroll_strategy = ScrollStrategy::KeepRow;
+            }
+            _ => (),
         }
-
-        // Draw content
-        let printer = printer
-            .cropped(size)
-            .content_offset(self.offset)
-            .inner_size(self.inner_size);
-        self.inner.draw(&printer);
+        self.horizontal_scroll_strategy = HorizontalScrollStrategy::KeepColumn;
     }
 
-    fn on_event(&mut self, event: Event) -> EventResult {
+    /// Handles the event, possibly scrolling.
+    fn scroll_on_event(&mut self, event: Event) -> EventResult {
         // Relativize event accorging to the offset
         let mut relative_event = event.clone();
+        let hover = event.mouse_event() == Some(MouseEvent::Hover);
 
         // Should the event be treated inside, by the inner view?
         let inside = if let Event::Mouse {
@@ -554,6 +725,29 @@ where
                             self.offset.y + 3,
                         );
                     }
+                    Event::Mouse {
+                        event: MouseEvent::WheelLeft,
+                        ..
+                    }
+                        if self.enabled.x && self.offset.x > 0 =>
+                    {
+                        self.offset.x = self.offset.x.saturating_sub(3);
+                    }
+                    Event::Mouse {
+                        event: MouseEvent::WheelRight,
+                        ..
+                    }
+                        if self.enabled.x
+                            && (self.offset.x + self.available_size().x
+                                < self.inner_size.x) =>
+                    {
+                        self.offset.x = min(
+                            self.inner_size
+                                .x
+                                .saturating_sub(self.available_size().x),
+                            self.offset.x + 3,
+                        );
+                    }
                     Event::Mouse {
                         event: MouseEvent::Press(MouseButton::Left),
                         position,
@@ -632,31 +826,114 @@ where
                     _ => return EventResult::Ignored,
                 };
 
-                // We just scrolled manually, so reset the scroll strategy.
-                self.scroll_strategy = ScrollStrategy::KeepRow;
-                // TODO: return callback on_scroll?
+                // We just scrolled manually, so stop sticking to an edge.
+                self.stop_sticking();
                 EventResult::Consumed(None)
             }
+            // Hovering a child doesn't move the focus.
+            other if hover => other,
             other => {
                 // Fix offset?
-                let important = self.inner.important_area(self.inner_size);
+                if self.scroll_strategy != ScrollStrategy::Manual {
+                    self.reveal_important_area();
+                }
+
+                other
+            }
+        }
+    }
+
+    /// Returns the wrapped view.
+    pub fn into_inner(self) -> V {
+        self.inner
+    }
+
+    inner_getters!(self.inner: V);
+}
 
-                // The furthest top-left we can go
-                let top_left = (important.bottom_right() + (1, 1))
-                    .saturating_sub(self.available_size());
-                // The furthest bottom-right we can go
-                let bottom_right = important.top_left();
+impl<V> View for ScrollView<V>
+where
+    V: View,
+{
+    fn draw(&self, printer: &Printer) {
+        // Draw scrollbar?
+        let scrolling = self.is_scrolling();
 
-                // "top_left < bottom_right" is NOT guaranteed
-                // if the child is larger than the view.
-                let offset_min = Vec2::min(top_left, bottom_right);
-                let offset_max = Vec2::max(top_left, bottom_right);
+        let lengths = self.scrollbar_thumb_lengths();
+        let offsets = self.scrollbar_thumb_offsets(lengths);
 
-                self.offset =
-                    self.offset.or_max(offset_min).or_min(offset_max);
+        let line_c = XY::new("-", "|");
 
-                other
+        let color = if printer.focused {
+            ColorStyle::highlight()
+        } else {
+            ColorStyle::highlight_inactive()
+        };
+
+        let size = self.available_size();
+
+        // Draw the scrollbars
+        XY::zip5(lengths, offsets, size, line_c, Orientation::pair()).run_if(
+            scrolling,
+            |(length, offset, size, c, orientation)| {
+                let start = printer
+                    .size
+                    .saturating_sub((1, 1))
+                    .with_axis(orientation, 0);
+                let offset = orientation.make_vec(offset, 0);
+
+                printer.print_line(orientation, start, size, c);
+
+                let thumb_c = if self
+                    .thumb_grab
+                    .map(|(o, _)| o == orientation)
+                    .unwrap_or(false)
+                {
+                    " "
+                } else {
+                    "▒"
+                };
+                printer.with_color(color, |printer| {
+                    printer.print_line(
+                        orientation,
+                        start + offset,
+                        length,
+                        thumb_c,
+                    );
+                });
+            },
+        );
+
+        // Draw the X between the two scrollbars.
+        if scrolling.both() {
+            printer.print(printer.size.saturating_sub((1, 1)), "╳");
+        }
+
+        // Draw content
+        let printer = printer
+            .cropped(size)
+            .content_offset(self.offset)
+            .inner_size(self.inner_size);
+        self.inner.draw(&printer);
+
+        if self.show_overflow_markers {
+            printer.with_color(color, |printer| {
+                printer.print_overflow_markers()
+            });
+        }
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        let offset = self.offset;
+        let result = self.scroll_on_event(event);
+
+        match self.on_scroll {
+            Some(ref cb) if self.offset != offset => {
+                let cb = Rc::clone(cb);
+                let position = self.scroll_position();
+                result.and(EventResult::with_cb(move |s| cb(s, position)))
             }
+            _ => result,
         }
     }
 
@@ -678,6 +955,15 @@ where
             .offset
             .or_min(self.inner_size.saturating_sub(self.available_size()));
 
+        // Follow the important area if it moved since the last time, for
+        // example if the selection of the child was changed by code.
+        if self.scroll_strategy == ScrollStrategy::Manual {
+            let important = self.inner.important_area(self.inner_size);
+            self.last_important = Some(important);
+        } else {
+            self.reveal_important_area();
+        }
+
         // Possibly update the offset if we're following a specific strategy.
         self.adjust_scroll();
     }
@@ -704,4 +990,269 @@ where
         let is_scrollable = self.is_scrolling().any();
         self.inner.take_focus(source) || is_scrollable
     }
+
+    fn debug_layout(&mut self, tree: &mut LayoutTree) {
+        // The child is described unscrolled, with its full size.
+        let (x, y) = self.offset.pair();
+        tree.set_info(format!("offset ({}, {})", x, y));
+        let size = self.inner_size;
+        tree.child(&mut self.inner, Vec2::zero(), size, true);
+    }
+
+    fn follow_focus(&mut self, path: &mut FocusPath) {
+        // The content is drawn scrolled.
+        let offset = Vec2::zero().signed() - self.offset.signed();
+        path.child(&mut self.inner, offset);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backend;
+    use std::cell::RefCell;
+    use view::Identifiable;
+    use views::{
+        BoxView, DummyView, IdView, SelectView, TextContent, TextView,
+    };
+
+    fn wheel(event: MouseEvent) -> Event {
+        Event::Mouse {
+            event,
+            position: Vec2::zero(),
+            offset: Vec2::zero(),
+        }
+    }
+
+    #[test]
+    fn test_horizontal_scroll() {
+        let inner = BoxView::with_fixed_size((20, 2), DummyView);
+        let mut view = ScrollView::new(inner).scroll_x(true).scroll_y(false);
+        view.layout(Vec2::new(10, 3));
+        // The horizontal scrollbar takes the last row.
+        assert_eq!(view.content_viewport(), Rect::from_size((0, 0), (10, 2)));
+
+        view.on_event(wheel(MouseEvent::WheelRight));
+        assert_eq!(view.content_viewport().left(), 3);
+        view.on_event(Event::Key(Key::Left));
+        assert_eq!(view.content_viewport().left(), 2);
+
+        // Clicking at the end of the scrollbar jumps there.
+        view.on_event(Event::Mouse {
+            event: MouseEvent::Press(MouseButton::Left),
+            position: Vec2::new(9, 2),
+            offset: Vec2::zero(),
+        });
+        assert_eq!(view.content_viewport().left(), 10);
+
+        let strategy = HorizontalScrollStrategy::StickToLeft;
+        view.set_horizontal_scroll_strategy(strategy);
+        view.layout(Vec2::new(8, 3));
+        assert_eq!(view.content_viewport().left(), 0);
+    }
+
+    #[test]
+    fn test_scroll_to() {
+        let inner = BoxView::with_fixed_size((5, 20), DummyView);
+        let mut view = ScrollView::new(inner);
+        view.layout(Vec2::new(7, 5));
+
+        view.scroll_to((0, 10));
+        assert_eq!(view.content_viewport().top(), 6);
+        // Already visible: nothing moves.
+        view.scroll_to((0, 8));
+        assert_eq!(view.content_viewport().top(), 6);
+        view.scroll_to((0, 2));
+        assert_eq!(view.content_viewport().top(), 2);
+
+        assert!(!view.is_at_bottom());
+        view.scroll_to_bottom();
+        assert!(view.is_at_bottom());
+        assert_eq!(view.content_viewport().top(), 15);
+    }
+
+    #[test]
+    fn test_reveal_selection() {
+        type Scroll = ScrollView<IdView<SelectView>>;
+        let backend = backend::puppet::Backend::init(Some(Vec2::new(10, 5)));
+        let mut siv = Cursive::new(move || backend);
+
+        let items = (0..100).map(|i| i.to_string());
+        let select = SelectView::new().with_all_str(items);
+        siv.add_fullscreen_layer(BoxView::with_full_screen(
+            ScrollView::new(select.with_id("select")).with_id("scroll"),
+        ));
+        siv.dump_screen();
+
+        let select_and_top = |siv: &mut Cursive, i: usize| {
+            siv.call_on_id("select", |view: &mut SelectView| {
+                view.set_selection(i)
+            });
+            siv.dump_screen();
+            siv.call_on_id("scroll", |view: &mut Scroll| {
+                view.content_viewport().top()
+            })
+            .unwrap()
+        };
+
+        // Far below the fold: scrolls just enough to show it.
+        assert_eq!(select_and_top(&mut siv, 50), 46);
+        assert_eq!(select_and_top(&mut siv, 48), 46);
+        assert_eq!(select_and_top(&mut siv, 10), 10);
+
+        siv.call_on_id("scroll", |view: &mut Scroll| {
+            view.set_reveal_strategy(RevealStrategy::Center)
+        });
+        assert_eq!(select_and_top(&mut siv, 70), 68);
+        // Already visible.
+        assert_eq!(select_and_top(&mut siv, 71), 68);
+        assert_eq!(select_and_top(&mut siv, 99), 95);
+    }
+
+    #[test]
+    fn test_on_scroll() {
+        let backend = backend::puppet::Backend::init(Some(Vec2::new(10, 3)));
+        let mut siv = Cursive::new(move || backend);
+
+        let mut content = TextContent::new("0\n1\n2\n3\n4");
+        let positions = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::clone(&positions);
+
+        // Follows new lines, unless the user scrolled up.
+        let view = ScrollView::new(TextView::new_with_content(content.clone()))
+            .scroll_strategy(ScrollStrategy::StickToBottom)
+            .on_scroll(move |s, position| {
+                log.borrow_mut().push((position.offset.y, position.at_bottom));
+                if position.at_bottom {
+                    s.call_on_id("log", |view: &mut ScrollView<TextView>| {
+                        view.set_scroll_strategy(ScrollStrategy::StickToBottom)
+                    });
+                }
+            })
+            .with_id("log");
+        siv.add_fullscreen_layer(BoxView::with_full_screen(view));
+        // Skips the scrollbar.
+        let last_row =
+            |siv: &mut Cursive| siv.dump_screen()[2][..1].to_owned();
+        assert_eq!(last_row(&mut siv), "4");
+
+        siv.on_event(Event::Key(Key::Up));
+        siv.on_event(Event::Key(Key::Up));
+        content.append("\n5");
+        assert_eq!(last_row(&mut siv), "2");
+
+        siv.on_event(Event::Key(Key::End));
+        content.append("\n6");
+        assert_eq!(last_row(&mut siv), "6");
+
+        // Not scrolling anymore: no callback.
+        siv.on_event(Event::Key(Key::Down));
+        let expected = [(1, false), (0, false), (3, true)];
+        assert_eq!(&positions.borrow()[..], &expected);
+    }
+
+    #[test]
+    fn test_scroll_strategies() {
+        let backend = backend::puppet::Backend::init(Some(Vec2::new(10, 3)));
+        let mut siv = Cursive::new(move || backend);
+        let mut content = TextContent::new("0\n1\n2\n3\n4");
+        let view = ScrollView::new(TextView::new_with_content(content.clone()))
+            .scroll_strategy(ScrollStrategy::StickToBottom)
+            .with_id("scroll");
+        siv.add_fullscreen_layer(BoxView::with_full_screen(view));
+        let top = |siv: &mut Cursive| siv.dump_screen()[0][..1].to_owned();
+        let set_strategy = |siv: &mut Cursive, strategy| {
+            siv.call_on_id("scroll", |view: &mut ScrollView<TextView>| {
+                view.set_scroll_strategy(strategy)
+            });
+        };
+
+        // New lines push the content up.
+        assert_eq!(top(&mut siv), "2");
+        content.append("\n5");
+        assert_eq!(top(&mut siv), "3");
+
+        // Switching strategies applies on the next layout.
+        set_strategy(&mut siv, ScrollStrategy::StickToTop);
+        content.append("\n6");
+        assert_eq!(top(&mut siv), "0");
+        content.append("\n7");
+        assert_eq!(top(&mut siv), "0");
+
+        set_strategy(&mut siv, ScrollStrategy::StickToBottom);
+        assert_eq!(top(&mut siv), "5");
+    }
+
+    #[test]
+    fn test_keep_cursor() {
+        type Scroll = ScrollView<SelectView>;
+
+        let backend = backend::puppet::Backend::init(Some(Vec2::new(10, 3)));
+        let mut siv = Cursive::new(move || backend);
+        let select =
+            SelectView::new().with_all_str((0..10).map(|i| i.to_string()));
+        siv.add_fullscreen_layer(BoxView::with_full_screen(
+            ScrollView::new(select)
+                .scroll_strategy(ScrollStrategy::Manual)
+                .with_id("scroll"),
+        ));
+        let top = |siv: &mut Cursive| siv.dump_screen()[0][..1].to_owned();
+
+        // The selection moved by code is not revealed.
+        siv.call_on_id("scroll", |view: &mut Scroll| {
+            view.get_inner_mut().set_selection(8);
+        });
+        assert_eq!(top(&mut siv), "0");
+
+        // Neither is the selection moved by the user.
+        siv.on_event(Event::Key(Key::Down));
+        assert_eq!(top(&mut siv), "0");
+
+        // Scrolling manually keeps the strategy.
+        siv.on_event(Event::Key(Key::Down));
+        assert_eq!(top(&mut siv), "1");
+        let strategy = siv.call_on_id("scroll", |view: &mut Scroll| {
+            view.get_scroll_strategy()
+        });
+        assert_eq!(strategy, Some(ScrollStrategy::Manual));
+
+        siv.call_on_id("scroll", |view: &mut Scroll| {
+            view.set_scroll_strategy(ScrollStrategy::KeepCursor);
+        });
+        assert_eq!(top(&mut siv), "7");
+        let strategy = siv.call_on_id("scroll", |view: &mut Scroll| {
+            view.get_scroll_strategy()
+        });
+        assert_eq!(strategy, Some(ScrollStrategy::KeepCursor));
+    }
+
+    #[test]
+    fn test_overflow_markers() {
+        let text: Vec<_> = (0..10).map(|i| format!("Line {}", i)).collect();
+        let mut view = ScrollView::new(TextView::new(text.join("\n")))
+            .show_scrollbars(false)
+            .show_overflow_markers(true);
+        let render = |view: &mut ScrollView<TextView>, size: (usize, usize)| {
+            let size = Vec2::from(size);
+            view.layout(size);
+            let backend = backend::puppet::Backend::init(Some(size));
+            let theme = ::theme::load_default();
+            view.draw(&Printer::new(size, &theme, &*backend));
+            backend.screen().rows()
+        };
+
+        // The arrows follow the scroll position.
+        let rows = render(&mut view, (6, 4));
+        assert_eq!(rows, vec!["Line 0", "Line 1", "Line 2", "Lin▼ 3"]);
+        view.set_offset((0, 3));
+        let rows = render(&mut view, (6, 4));
+        assert_eq!(rows, vec!["Lin▲ 3", "Line 4", "Line 5", "Lin▼ 6"]);
+        view.scroll_to_bottom();
+        let rows = render(&mut view, (6, 4));
+        assert_eq!(rows[0], "Lin▲ 6");
+        assert_eq!(rows[3], "Line 9");
+
+        // Nothing is shown when everything fits.
+        assert_eq!(render(&mut view, (6, 10)), text);
+    }
 }
@@ -5,9 +5,10 @@ use menu::MenuTree;
 use rect::Rect;
 use std::borrow::Borrow;
 use std::cell::Cell;
-use std::cmp::min;
+use std::cmp::{max, min};
 use std::rc::Rc;
 use theme::ColorStyle;
+use unicode_width::UnicodeWidthStr;
 use utils::markup::StyledString;
 use vec::Vec2;
 use view::{Position, View};
@@ -65,10 +66,26 @@ pub struct SelectView<T = String> {
     // TODO: add the previous selection? Indices?
     on_select: Option<Rc<Fn(&mut Cursive, &T)>>,
 
+    // If `true`, items can be individually selected with <Space>.
+    multi_select: bool,
+
+    // This callback is called when an item is selected or unselected in
+    // multi-select mode.
+    on_selection_change: Option<SelectionChangeCallback<T>>,
+
     // If `true`, when a character is pressed, jump to the next item starting
     // with this character.
     autojump: bool,
 
+    // Only items accepted by this predicate are shown.
+    filter: Option<LabelFilter>,
+
+    // Only items containing this string (ignoring case) are shown.
+    query: String,
+
+    // If `true`, typed characters edit `query`.
+    filter_on_type: bool,
+
     align: Align,
 
     // `true` if we show a one-line view, with popup on selection.
@@ -95,9 +112,14 @@ impl<T: 'static> SelectView<T> {
             focus: Rc::new(Cell::new(0)),
             on_select: None,
             on_submit: None,
+            multi_select: false,
+            on_selection_change: None,
             align: Align::top_left(),
             popup: false,
             autojump: false,
+            filter: None,
+            query: String::new(),
+            filter_on_type: false,
             last_offset: Cell::new(Vec2::zero()),
             last_size: Vec2::zero(),
         }
@@ -121,6 +143,129 @@ impl<T: 'static> SelectView<T> {
         self.with(|s| s.set_autojump(true))
     }
 
+    /// Only shows items with a label accepted by the given predicate.
+    ///
+    /// Hidden items are kept, and come back when the filter is cleared.
+    /// If the selected item gets hidden, the selection moves to the next
+    /// visible item.
+    ///
+    /// Popup select views always show every item.
+    ///
+    /// Returns a callback in response to the selection change.
+    ///
+    /// You should run this callback with a `&mut Cursive`.
+    pub fn set_filter<F>(&mut self, filter: F) -> Callback
+    where
+        F: Fn(&str) -> bool + 'static,
+    {
+        self.filter = Some(Box::new(filter));
+        self.refilter()
+    }
+
+    /// Only shows items with a label accepted by the given predicate.
+    ///
+    /// Chainable variant.
+    pub fn filter<F>(self, filter: F) -> Self
+    where
+        F: Fn(&str) -> bool + 'static,
+    {
+        self.with(|s| {
+            s.set_filter(filter);
+        })
+    }
+
+    /// Only shows items with a label containing `query`, ignoring case.
+    ///
+    /// This is combined with the predicate given to `set_filter()`, and is
+    /// the query edited when `filter_on_type` is enabled.
+    ///
+    /// Returns a callback in response to the selection change.
+    ///
+    /// You should run this callback with a `&mut Cursive`.
+    pub fn set_filter_query<S: Into<String>>(&mut self, query: S) -> Callback {
+        self.query = query.into();
+        self.refilter()
+    }
+
+    /// Returns the current filter query.
+    pub fn filter_query(&self) -> &str {
+        &self.query
+    }
+
+    /// Removes both the filter predicate and the filter query.
+    ///
+    /// Returns a callback in response to the selection change.
+    ///
+    /// You should run this callback with a `&mut Cursive`.
+    pub fn clear_filter(&mut self) -> Callback {
+        self.filter = None;
+        self.query.clear();
+        self.refilter()
+    }
+
+    /// Sets the "filter-on-type" property for this view.
+    ///
+    /// If enabled, typed characters are added to the filter query, which is
+    /// shown below the items. `<Backspace>` removes the last character, and
+    /// `<Esc>` clears the query.
+    ///
+    /// This takes precedence over auto-jump.
+    pub fn set_filter_on_type(&mut self, filter_on_type: bool) {
+        self.filter_on_type = filter_on_type;
+    }
+
+    /// Sets the "filter-on-type" property for this view.
+    ///
+    /// Chainable variant.
+    pub fn filter_on_type(self) -> Self {
+        self.with(|s| s.set_filter_on_type(true))
+    }
+
+    /// Sets the "multi-select" property for this view.
+    ///
+    /// If enabled, each item can be selected or unselected with `<Space>` or
+    /// a click, independently of the cursor moved with the arrow keys. A
+    /// marker is shown before each item, and `selected_values()` returns the
+    /// selected items.
+    ///
+    /// `on_select` and `on_submit` still refer to the item under the cursor.
+    pub fn set_multi_select(&mut self, multi_select: bool) {
+        self.multi_select = multi_select;
+    }
+
+    /// Sets the "multi-select" property for this view.
+    ///
+    /// Chainable variant.
+    pub fn multi_select(self) -> Self {
+        self.with(|s| s.set_multi_select(true))
+    }
+
+    /// Returns `true` if this view is in multi-select mode.
+    pub fn is_multi_select(&self) -> bool {
+        self.multi_select
+    }
+
+    /// Sets a callback to be used when the set of selected items changes.
+    ///
+    /// Only used in multi-select mode. The callback is given every
+    /// selected value.
+    pub fn set_on_selection_change<F>(&mut self, cb: F)
+    where
+        F: Fn(&mut Cursive, &[Rc<T>]) + 'static,
+    {
+        self.on_selection_change = Some(Rc::new(cb));
+    }
+
+    /// Sets a callback to be used when the set of selected items changes.
+    ///
+    /// Chainable variant.
+    pub fn on_selection_change<F>(self, cb: F) -> Self
+    where
+        F: Fn(&mut Cursive, &[Rc<T>]) + 'static,
+    {
+        self.with(|s| s.set_on_selection_change(cb))
+    }
+
     /// Turns `self` into a popup select view.
     ///
     /// Chainable variant.
@@ -236,16 +381,54 @@ impl<T: 'static> SelectView<T> {
 
     /// Returns the value of the currently selected item.
     ///
-    /// Returns `None` if the list is empty.
+    /// Returns `None` if the list is empty, or if every item is filtered out.
     pub fn selection(&self) -> Option<Rc<T>> {
         let focus = self.focus();
-        if self.len() <= focus {
+        if self.len() <= focus || !self.is_visible(focus) {
             None
         } else {
             Some(Rc::clone(&self.items[focus].value))
         }
     }
 
+    /// Returns the values of all items selected in multi-select mode.
+    ///
+    /// Items hidden by a filter are included.
+    pub fn selected_values(&self) -> Vec<Rc<T>> {
+        self.items
+            .iter()
+            .filter(|item| item.selected)
+            .map(|item| Rc::clone(&item.value))
+            .collect()
+    }
+
+    /// Returns the ids of all items selected in multi-select mode.
+    pub fn selected_ids(&self) -> Vec<usize> {
+        (0..self.len()).filter(|&i| self.items[i].selected).collect()
+    }
+
+    /// Returns `true` if the item at the given position is selected in
+    /// multi-select mode.
+    pub fn is_item_selected(&self, i: usize) -> bool {
+        self.items.get(i).map(|item| item.selected).unwrap_or(false)
+    }
+
+    /// Selects or unselects the item at the given position.
+    ///
+    /// Does nothing if `i` is out of bounds.
+    ///
+    /// Returns a callback in response to the selection change.
+    ///
+    /// You should run this callback with a `&mut Cursive`.
+    pub fn set_item_selected(&mut self, i: usize, selected: bool) -> Callback {
+        match self.items.get_mut(i) {
+            Some(item) => item.selected = selected,
+            None => return Callback::dummy(),
+        }
+        self.make_selection_change_cb()
+            .unwrap_or_else(Callback::dummy)
+    }
+
     /// Removes all items from this view.
     pub fn clear(&mut self) {
         self.items.clear();
@@ -307,6 +490,7 @@ impl<T: 'static> SelectView<T> {
         if focus >= id && focus > 0 {
             self.focus.set(focus - 1);
         }
+        self.clamp_focus();
 
         self.make_select_cb().unwrap_or_else(Callback::dummy)
     }
@@ -348,6 +532,14 @@ impl<T: 'static> SelectView<T> {
     }
 
     fn draw_item(&self, printer: &Printer, i: usize) {
+        let printer = &if self.multi_select {
+            let marker = if self.items[i].selected { "[x] " } else { "[ ] " };
+            printer.print((0, 0), marker);
+            printer.offset((MARKER_WIDTH, 0))
+        } else {
+            printer.clone()
+        };
+
         let l = self.items[i].label.width();
         let x = self.align.h.get_offset(l, printer.size.x);
         printer.print_hline((0, 0), x, " ");
@@ -360,9 +552,9 @@ impl<T: 'static> SelectView<T> {
 
     /// Returns the id of the item currently selected.
     ///
-    /// Returns `None` if the list is empty.
+    /// Returns `None` if the list is empty, or if every item is filtered out.
     pub fn selected_id(&self) -> Option<usize> {
-        if self.items.is_empty() {
+        if self.items.is_empty() || !self.is_visible(self.focus()) {
             None
         } else {
             Some(self.focus())
@@ -444,13 +636,65 @@ impl<T: 'static> SelectView<T> {
     }
 
     fn focus_up(&mut self, n: usize) {
-        let focus = self.focus().saturating_sub(n);
-        self.focus.set(focus);
+        let visible = self.visible_items();
+        if let Some(row) = self.focus_row() {
+            self.focus.set(visible[row.saturating_sub(n)]);
+        }
     }
 
     fn focus_down(&mut self, n: usize) {
-        let focus = min(self.focus() + n, self.items.len().saturating_sub(1));
-        self.focus.set(focus);
+        let visible = self.visible_items();
+        if let Some(row) = self.focus_row() {
+            self.focus.set(visible[min(row + n, visible.len() - 1)]);
+        }
+    }
+
+    fn is_visible(&self, i: usize) -> bool {
+        let label = self.items[i].label.source();
+
+        let accepted = match self.filter {
+            Some(ref filter) => filter(label),
+            None => true,
+        };
+
+        accepted
+            && label.to_lowercase().contains(&self.query.to_lowercase())
+    }
+
+    // Returns the index of every item not filtered out.
+    fn visible_items(&self) -> Vec<usize> {
+        (0..self.len()).filter(|&i| self.is_visible(i)).collect()
+    }
+
+    // Returns the row of the selected item among visible items.
+    fn focus_row(&self) -> Option<usize> {
+        let focus = self.focus();
+        self.visible_items().iter().position(|&i| i == focus)
+    }
+
+    // `true` if the filter query should be drawn below the items.
+    fn shows_query(&self) -> bool {
+        self.filter_on_type && !self.query.is_empty()
+    }
+
+    // Moves the focus to a visible item, if the focused one is hidden.
+    fn clamp_focus(&mut self) {
+        let focus = self.focus();
+        let visible = self.visible_items();
+        if visible.contains(&focus) {
+            return;
+        }
+
+        // Prefer the next visible item, or the last one if there is none.
+        let next = visible.iter().find(|&&i| i > focus).or(visible.last());
+        if let Some(&i) = next {
+            self.focus.set(i);
+        }
+    }
+
+    fn refilter(&mut self) -> Callback {
+        self.clamp_focus();
+        self.make_select_cb().unwrap_or_else(Callback::dummy)
     }
 
     fn submit(&mut self) -> EventResult {
@@ -474,8 +718,12 @@ impl<T: 'static> SelectView<T> {
             let lower_c: Vec<char> = c.to_lowercase().collect();
             let lower_c: &[char] = &lower_c;
 
+            let len = self.len();
             if let Some((i, _)) = iter.enumerate().skip(self.focus() + 1).find(
-                |&(_, (label, _))| label.to_lowercase().starts_with(lower_c),
+                |&(i, (label, _))| {
+                    label.to_lowercase().starts_with(lower_c)
+                        && self.is_visible(i % len)
+                },
             ) {
                 i % self.len()
             } else {
@@ -489,17 +737,67 @@ impl<T: 'static> SelectView<T> {
         EventResult::Consumed(Some(cb))
     }
 
+    fn toggle_focused(&mut self) -> EventResult {
+        if self.selected_id().is_none() {
+            return EventResult::Ignored;
+        }
+
+        let item = &mut self.items[self.focus.get()];
+        item.selected = !item.selected;
+        EventResult::Consumed(self.make_selection_change_cb())
+    }
+
     fn on_event_regular(&mut self, event: Event) -> EventResult {
+        let visible = self.visible_items();
+        let row = self.focus_row();
+
         match event {
-            Event::Key(Key::Up) if self.focus() > 0 => self.focus_up(1),
-            Event::Key(Key::Down) if self.focus() + 1 < self.items.len() => {
+            Event::Key(Key::Up) if row.unwrap_or(0) > 0 => self.focus_up(1),
+            Event::Key(Key::Down)
+                if row.map_or(0, |row| row + 1) < visible.len() =>
+            {
                 self.focus_down(1)
             }
             Event::Key(Key::PageUp) => self.focus_up(10),
             Event::Key(Key::PageDown) => self.focus_down(10),
-            Event::Key(Key::Home) => self.focus.set(0),
-            Event::Key(Key::End) => {
-                self.focus.set(self.items.len().saturating_sub(1))
+            Event::Key(Key::Home) if !visible.is_empty() => {
+                self.focus.set(visible[0])
+            }
+            Event::Key(Key::End) if !visible.is_empty() => {
+                self.focus.set(visible[visible.len() - 1])
+            }
+            Event::Char(' ') if self.multi_select => {
+                return self.toggle_focused();
+            }
+            Event::Mouse {
+                event: MouseEvent::Release(MouseButton::Left),
+                position,
+                offset,
+            } if self.multi_select
+                && position
+                    .checked_sub(offset)
+                    .map(|position| {
+                        position < self.last_size && Some(position.y) == row
+                    })
+                    .unwrap_or(false) =>
+            {
+                return self.toggle_focused();
+            }
+            Event::Char(c) if self.filter_on_type => {
+                self.query.push(c);
+                self.clamp_focus();
+            }
+            Event::Key(Key::Backspace)
+                if self.filter_on_type && !self.query.is_empty() =>
+            {
+                self.query.pop();
+                self.clamp_focus();
+            }
+            Event::Key(Key::Esc)
+                if self.filter_on_type && !self.query.is_empty() =>
+            {
+                self.query.clear();
+                self.clamp_focus();
             }
             Event::Mouse {
                 event: MouseEvent::Press(_),
@@ -508,11 +806,11 @@ impl<T: 'static> SelectView<T> {
             } if position
                 .checked_sub(offset)
                 .map(|position| {
-                    position < self.last_size && position.y < self.len()
+                    position < self.last_size && position.y < visible.len()
                 })
                 .unwrap_or(false) =>
             {
-                self.focus.set(position.y - offset.y)
+                self.focus.set(visible[position.y - offset.y])
             }
             Event::Mouse {
                 event: MouseEvent::Release(MouseButton::Left),
@@ -522,7 +820,7 @@ impl<T: 'static> SelectView<T> {
                 && position
                     .checked_sub(offset)
                     .map(|position| {
-                        position < self.last_size && position.y == self.focus()
+                        position < self.last_size && Some(position.y) == row
                     })
                     .unwrap_or(false) =>
             {
@@ -546,6 +844,14 @@ impl<T: 'static> SelectView<T> {
         })
     }
 
+    /// Returns a callback from a change in the set of selected items.
+    fn make_selection_change_cb(&self) -> Option<Callback> {
+        self.on_selection_change.clone().map(|cb| {
+            let values = self.selected_values();
+            Callback::from_fn(move |s| cb(s, &values))
+        })
+    }
+
     fn open_popup(&mut self) -> EventResult {
         // Build a shallow menu tree to mimick the items array.
         // TODO: cache it?
@@ -703,13 +1009,14 @@ impl<T: 'static> View for SelectView<T> {
                 printer.print_styled((offset, 0), label.into());
             });
         } else {
-            // Non-popup mode: we always print the entire list.
-            let h = self.items.len();
+            // Non-popup mode: we print every visible item.
+            let visible = self.visible_items();
+            let h = visible.len() + if self.shows_query() { 1 } else { 0 };
             let offset = self.align.v.get_offset(h, printer.size.y);
             let printer = &printer.offset((0, offset));
 
-            for i in 0..self.len() {
-                printer.offset((0, i)).with_selection(
+            for (row, &i) in visible.iter().enumerate() {
+                printer.offset((0, row)).with_selection(
                     i == self.focus(),
                     |printer| {
                         if i != self.focus()
@@ -725,6 +1032,13 @@ impl<T: 'static> View for SelectView<T> {
                     },
                 );
             }
+
+            if self.shows_query() {
+                printer.with_color(ColorStyle::secondary(), |printer| {
+                    printer.print((0, visible.len()), "/");
+                    printer.print((1, visible.len()), &self.query);
+                });
+            }
         }
     }
 
@@ -738,16 +1052,25 @@ impl<T: 'static> View for SelectView<T> {
             .map(|item| item.label.width())
             .max()
             .unwrap_or(1);
+        let w = if self.multi_select { w + MARKER_WIDTH } else { w };
         if self.popup {
             Vec2::new(w + 2, 1)
+        } else if self.shows_query() {
+            let h = self.visible_items().len() + 1;
+
+            Vec2::new(max(w, self.query.width() + 1), h)
         } else {
-            let h = self.items.len();
+            let h = self.visible_items().len();
 
             Vec2::new(w, h)
         }
     }
 
     fn on_event(&mut self, event: Event) -> EventResult {
+        if !self.enabled {
+            return EventResult::Ignored;
+        }
+
         if self.popup {
             self.on_event_popup(event)
         } else {
@@ -764,21 +1087,107 @@ impl<T: 'static> View for SelectView<T> {
     }
 
     fn important_area(&self, size: Vec2) -> Rect {
-        self.selected_id()
-            .map(|i| Rect::from_size((0, i), (size.x, 1)))
+        self.focus_row()
+            .map(|row| Rect::from_size((0, row), (size.x, 1)))
             .unwrap_or_else(|| Rect::from((0, 0)))
     }
 }
 
+// Width of the `[x] ` marker shown in multi-select mode.
+const MARKER_WIDTH: usize = 4;
+
+type LabelFilter = Box<Fn(&str) -> bool>;
+type SelectionChangeCallback<T> = Rc<Fn(&mut Cursive, &[Rc<T>])>;
+
 // We wrap each value in a `Rc` and add a label
 struct Item<T> {
     label: StyledString,
     value: Rc<T>,
+    // Only used in multi-select mode.
+    selected: bool,
 }
 
 impl<T> Item<T> {
     fn new(label: StyledString, value: T) -> Self {
         let value = Rc::new(value);
-        Item { label, value }
+        Item {
+            label,
+            value,
+            selected: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_clamps_selection() {
+        let mut view = SelectView::new()
+            .with_all_str(vec!["apple", "banana", "cherry", "date"]);
+        view.set_selection(1);
+
+        view.set_filter(|label| label.contains('e'));
+        assert_eq!(view.selected_id(), Some(2));
+        assert_eq!(view.required_size(Vec2::zero()).y, 3);
+
+        view.on_event(Event::Key(Key::Down));
+        assert_eq!(view.selection().unwrap().as_str(), "date");
+
+        view.clear_filter();
+        assert_eq!(view.selected_id(), Some(3));
+        assert_eq!(view.required_size(Vec2::zero()).y, 4);
+    }
+
+    #[test]
+    fn test_multi_select() {
+        let mut view = SelectView::new()
+            .with_all_str(vec!["a", "b", "c"])
+            .multi_select();
+
+        view.on_event(Event::Char(' '));
+        view.on_event(Event::Key(Key::Down));
+        view.on_event(Event::Key(Key::Down));
+        view.on_event(Event::Char(' '));
+        assert_eq!(view.selected_ids(), vec![0, 2]);
+        assert_eq!(view.selection().unwrap().as_str(), "c");
+
+        view.on_event(Event::Key(Key::Up));
+        view.on_event(Event::Key(Key::Up));
+        view.on_event(Event::Char(' '));
+        let values = view.selected_values();
+        let values: Vec<&str> = values.iter().map(|v| v.as_str()).collect();
+        assert_eq!(values, vec!["c"]);
+        assert_eq!(view.required_size(Vec2::zero()), Vec2::new(5, 3));
+
+        view.set_item_selected(1, true);
+        view.set_item_selected(3, true);
+        assert_eq!(view.selected_ids(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_filter_on_type() {
+        let mut view = SelectView::new()
+            .with_all_str(vec!["apple", "banana", "cherry"])
+            .filter_on_type();
+
+        view.on_event(Event::Char('A'));
+        view.on_event(Event::Char('n'));
+        assert_eq!(view.filter_query(), "An");
+        assert_eq!(view.selection().unwrap().as_str(), "banana");
+        // One line for the item, one for the query.
+        assert_eq!(view.required_size(Vec2::zero()).y, 2);
+
+        view.on_event(Event::Char('x'));
+        assert_eq!(view.selection(), None);
+
+        view.on_event(Event::Key(Key::Backspace));
+        assert_eq!(view.filter_query(), "An");
+
+        view.on_event(Event::Key(Key::Esc));
+        assert_eq!(view.filter_query(), "");
+        assert_eq!(view.required_size(Vec2::zero()).y, 3);
+        assert!(!view.on_event(Event::Key(Key::Esc)).is_consumed());
     }
 }
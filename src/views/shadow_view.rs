@@ -1,16 +1,24 @@
 use event::{Event, EventResult};
 use theme::ColorStyle;
 use vec::Vec2;
-use view::{View, ViewWrapper};
+use view::{FocusPath, LayoutTree, View, ViewWrapper};
 use Printer;
+use With;
 
 /// Wrapper view that adds a shadow.
 ///
 /// It reserves a 1 pixel border on each side.
+///
+/// The shadow is only drawn if the theme enables shadows, unless this view
+/// overrides it with [`set_shadow`]. It uses the `Shadow` palette color.
+///
+/// [`set_shadow`]: #method.set_shadow
 pub struct ShadowView<T: View> {
     view: T,
     top_padding: bool,
     left_padding: bool,
+    // Overrides the theme's `shadow` setting.
+    shadow: Option<bool>,
     // TODO: invalidate if we change the padding?
 }
 
@@ -21,14 +29,17 @@ impl<T: View> ShadowView<T> {
             view,
             top_padding: true,
             left_padding: true,
+            shadow: None,
         }
     }
 
-    fn padding(&self) -> Vec2 {
+    /// Returns the rows and columns around the view.
+    pub(crate) fn padding(&self) -> Vec2 {
         self.top_left_padding() + (1, 1)
     }
 
-    fn top_left_padding(&self) -> Vec2 {
+    /// Returns the rows and columns above and left of the view.
+    pub(crate) fn top_left_padding(&self) -> Vec2 {
         Vec2::new(self.left_padding as usize, self.top_padding as usize)
     }
 
@@ -48,6 +59,20 @@ impl<T: View> ShadowView<T> {
         self
     }
 
+    /// Shows or hides the shadow, whatever the theme says.
+    ///
+    /// The space for the shadow is kept either way.
+    pub fn set_shadow(&mut self, shadow: bool) {
+        self.shadow = Some(shadow);
+    }
+
+    /// Shows or hides the shadow, whatever the theme says.
+    ///
+    /// Chainable variant.
+    pub fn shadow(self, shadow: bool) -> Self {
+        self.with(|s| s.set_shadow(shadow))
+    }
+
     inner_getters!(self.view: T);
 }
 
@@ -70,6 +95,17 @@ impl<T: View> ViewWrapper for ShadowView<T> {
         self.view.on_event(event.relativized(padding))
     }
 
+    fn wrap_debug_layout(&mut self, tree: &mut LayoutTree) {
+        let size = tree.size().saturating_sub(self.padding());
+        let offset = self.top_left_padding();
+        tree.child(&mut self.view, offset, size, true);
+    }
+
+    fn wrap_follow_focus(&mut self, path: &mut FocusPath) {
+        let offset = self.top_left_padding();
+        path.child(&mut self.view, offset);
+    }
+
     fn wrap_draw(&self, printer: &Printer) {
         if printer.size.y <= self.top_padding as usize
             || printer.size.x <= self.left_padding as usize
@@ -82,7 +118,7 @@ impl<T: View> ViewWrapper for ShadowView<T> {
         let offset =
             Vec2::new(self.left_padding as usize, self.top_padding as usize);
         let printer = &printer.offset(offset);
-        if printer.theme.shadow {
+        if self.shadow.unwrap_or(printer.theme.shadow) {
             let h = printer.size.y;
             let w = printer.size.x;
 
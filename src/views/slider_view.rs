@@ -1,20 +1,46 @@
 use direction::{Direction, Orientation};
 use event::{Callback, Event, EventResult, Key, MouseButton, MouseEvent};
+use std::cmp::{max, min};
 use std::rc::Rc;
-use theme::ColorStyle;
+use theme::{ColorStyle, Effect};
 use vec::Vec2;
 use view::View;
 use With;
 use {Cursive, Printer};
 
+type OnChange = Fn(&mut Cursive, usize);
+
 /// A horizontal or vertical slider.
+///
+/// The value goes from 0 to `max_value - 1`. Arrow keys move it by the
+/// [step], `PageUp` and `PageDown` by the [page step], and clicking the
+/// track jumps to the nearest multiple of the step.
+///
+/// [step]: #method.set_step
+/// [page step]: #method.set_page_step
+///
+/// # Examples
+///
+/// ```rust
+/// # use cursive::views::{Dialog, SliderView};
+/// let slider = SliderView::horizontal(101)
+///     .step(5)
+///     .show_value(true)
+///     .on_change(|s, value| {
+///         s.add_layer(Dialog::info(format!("Volume: {}%", value)));
+///     });
+/// ```
 pub struct SliderView {
     orientation: Orientation,
-    on_change: Option<Rc<Fn(&mut Cursive, usize)>>,
-    on_enter: Option<Rc<Fn(&mut Cursive, usize)>>,
+    on_change: Option<Rc<OnChange>>,
+    on_enter: Option<Rc<OnChange>>,
     value: usize,
     max_value: usize,
+    step: usize,
+    page_step: usize,
+    show_value: bool,
     dragging: bool,
+    enabled: bool,
 }
 
 impl SliderView {
@@ -27,9 +53,13 @@ impl SliderView {
             orientation,
             value: 0,
             max_value,
+            step: 1,
+            page_step: 10,
+            show_value: false,
             on_change: None,
             on_enter: None,
             dragging: false,
+            enabled: true,
         }
     }
 
@@ -43,15 +73,24 @@ impl SliderView {
         Self::new(Orientation::Horizontal, max_value)
     }
 
+    impl_enabled!(self.enabled);
+
     /// Sets the current value.
     ///
+    /// The value is capped at `max_value - 1`.
+    ///
     /// Returns an event result with a possible callback,
     /// if `on_change` was set..
     pub fn set_value(&mut self, value: usize) -> EventResult {
-        self.value = value;
+        self.value = min(value, self.max_value.saturating_sub(1));
         self.get_change_result()
     }
 
+    /// Returns the current value.
+    pub fn get_value(&self) -> usize {
+        self.value
+    }
+
     /// Sets the current value.
     ///
     /// Chainable variant.
@@ -61,13 +100,64 @@ impl SliderView {
         })
     }
 
+    /// Sets how much the arrow keys move the value.
+    ///
+    /// Defaults to 1.
+    pub fn set_step(&mut self, step: usize) {
+        self.step = max(step, 1);
+    }
+
+    /// Sets how much the arrow keys move the value.
+    ///
+    /// Chainable variant.
+    pub fn step(self, step: usize) -> Self {
+        self.with(|s| s.set_step(step))
+    }
+
+    /// Sets how much `PageUp` and `PageDown` move the value.
+    ///
+    /// Defaults to 10.
+    pub fn set_page_step(&mut self, page_step: usize) {
+        self.page_step = max(page_step, 1);
+    }
+
+    /// Sets how much `PageUp` and `PageDown` move the value.
+    ///
+    /// Chainable variant.
+    pub fn page_step(self, page_step: usize) -> Self {
+        self.with(|s| s.set_page_step(page_step))
+    }
+
+    /// Sets whether the current value is shown after the track.
+    ///
+    /// Defaults to `false`.
+    pub fn set_show_value(&mut self, show_value: bool) {
+        self.show_value = show_value;
+    }
+
+    /// Sets whether the current value is shown after the track.
+    ///
+    /// Chainable variant.
+    pub fn show_value(self, show_value: bool) -> Self {
+        self.with(|s| s.set_show_value(show_value))
+    }
+
     /// Sets a callback to be called when the slider is moved.
-    pub fn on_change<F>(mut self, callback: F) -> Self
+    pub fn set_on_change<F>(&mut self, callback: F)
     where
         F: Fn(&mut Cursive, usize) + 'static,
     {
         self.on_change = Some(Rc::new(callback));
-        self
+    }
+
+    /// Sets a callback to be called when the slider is moved.
+    ///
+    /// Chainable variant.
+    pub fn on_change<F>(self, callback: F) -> Self
+    where
+        F: Fn(&mut Cursive, usize) + 'static,
+    {
+        self.with(|s| s.set_on_change(callback))
     }
 
     /// Sets a callback to be called when the <Enter> key is pressed.
@@ -88,31 +178,54 @@ impl SliderView {
         }))
     }
 
-    fn slide_plus(&mut self) -> EventResult {
+    fn slide_plus(&mut self, amount: usize) -> EventResult {
         if self.value + 1 < self.max_value {
-            self.value += 1;
-            self.get_change_result()
+            self.set_value(self.value.saturating_add(amount))
         } else {
             EventResult::Ignored
         }
     }
 
-    fn slide_minus(&mut self) -> EventResult {
+    fn slide_minus(&mut self, amount: usize) -> EventResult {
         if self.value > 0 {
-            self.value -= 1;
-            self.get_change_result()
+            self.set_value(self.value.saturating_sub(amount))
         } else {
             EventResult::Ignored
         }
     }
 
-    fn req_size(&self) -> Vec2 {
+    // Moves to the step closest to the given position on the track.
+    fn jump_to(&mut self, position: usize) -> EventResult {
+        let step = self.step;
+        self.set_value((position + step / 2) / step * step)
+    }
+
+    // Width of the value shown after the track.
+    fn label_width(&self) -> usize {
+        if self.show_value {
+            self.max_value.saturating_sub(1).to_string().len()
+        } else {
+            0
+        }
+    }
+
+    fn track_size(&self) -> Vec2 {
         self.orientation.make_vec(self.max_value, 1)
     }
-}
 
-impl View for SliderView {
-    fn draw(&self, printer: &Printer) {
+    fn req_size(&self) -> Vec2 {
+        if !self.show_value {
+            return self.track_size();
+        }
+
+        let (length, label) = (self.max_value, self.label_width());
+        match self.orientation {
+            Orientation::Horizontal => Vec2::new(length + 1 + label, 1),
+            Orientation::Vertical => Vec2::new(label, length + 1),
+        }
+    }
+
+    fn draw_track(&self, printer: &Printer) {
         match self.orientation {
             Orientation::Vertical => {
                 printer.print_vline((0, 0), self.max_value, "|")
@@ -121,15 +234,41 @@ impl View for SliderView {
                 printer.print_hline((0, 0), self.max_value, "-")
             }
         }
+    }
+}
+
+impl View for SliderView {
+    fn draw(&self, printer: &Printer) {
+        let knob = self.orientation.make_vec(self.value, 0);
+
+        if self.enabled && printer.enabled {
+            self.draw_track(printer);
 
-        let color = if printer.focused {
-            ColorStyle::highlight()
+            let color = if printer.focused {
+                ColorStyle::highlight()
+            } else {
+                ColorStyle::highlight_inactive()
+            };
+            printer.with_color(color, |printer| {
+                printer.print(knob, " ");
+            });
         } else {
-            ColorStyle::highlight_inactive()
-        };
-        printer.with_color(color, |printer| {
-            printer.print(self.orientation.make_vec(self.value, 0), " ");
-        });
+            printer.with_color(ColorStyle::secondary(), |printer| {
+                self.draw_track(printer);
+                printer.with_effect(Effect::Reverse, |printer| {
+                    printer.print(knob, " ");
+                });
+            });
+        }
+
+        if self.show_value {
+            let label = format!("{:>1$}", self.value, self.label_width());
+            let position = match self.orientation {
+                Orientation::Horizontal => Vec2::new(self.max_value + 1, 0),
+                Orientation::Vertical => Vec2::new(0, self.max_value),
+            };
+            printer.print(position, &label);
+        }
     }
 
     fn required_size(&mut self, _: Vec2) -> Vec2 {
@@ -137,27 +276,33 @@ impl View for SliderView {
     }
 
     fn on_event(&mut self, event: Event) -> EventResult {
+        if !self.enabled {
+            return EventResult::Ignored;
+        }
+
         match event {
             Event::Key(Key::Left)
                 if self.orientation == Orientation::Horizontal =>
             {
-                self.slide_minus()
+                self.slide_minus(self.step)
             }
             Event::Key(Key::Right)
                 if self.orientation == Orientation::Horizontal =>
             {
-                self.slide_plus()
+                self.slide_plus(self.step)
             }
             Event::Key(Key::Up)
                 if self.orientation == Orientation::Vertical =>
             {
-                self.slide_minus()
+                self.slide_minus(self.step)
             }
             Event::Key(Key::Down)
                 if self.orientation == Orientation::Vertical =>
             {
-                self.slide_plus()
+                self.slide_plus(self.step)
             }
+            Event::Key(Key::PageUp) => self.slide_minus(self.page_step),
+            Event::Key(Key::PageDown) => self.slide_plus(self.page_step),
             Event::Key(Key::Enter) if self.on_enter.is_some() => {
                 let value = self.value;
                 let cb = self.on_enter.clone().unwrap();
@@ -173,26 +318,22 @@ impl View for SliderView {
                 if self.dragging =>
             {
                 let position = position.saturating_sub(offset);
-                let position = self.orientation.get(&position);
-                let position = ::std::cmp::min(
-                    position,
-                    self.max_value.saturating_sub(1),
-                );
-                self.value = position;
-                self.get_change_result()
+                self.jump_to(self.orientation.get(&position))
             }
             Event::Mouse {
                 event: MouseEvent::Press(MouseButton::Left),
                 position,
                 offset,
             }
-                if position.fits_in_rect(offset, self.req_size()) =>
+                if position.fits_in_rect(offset, self.track_size()) =>
             {
-                if let Some(position) = position.checked_sub(offset) {
-                    self.dragging = true;
-                    self.value = self.orientation.get(&position);
+                match position.checked_sub(offset) {
+                    Some(position) => {
+                        self.dragging = true;
+                        self.jump_to(self.orientation.get(&position))
+                    }
+                    None => self.get_change_result(),
                 }
-                self.get_change_result()
             }
             Event::Mouse {
                 event: MouseEvent::Release(MouseButton::Left),
@@ -206,6 +347,63 @@ impl View for SliderView {
     }
 
     fn take_focus(&mut self, _: Direction) -> bool {
-        true
+        self.enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn click(slider: &mut SliderView, x: usize) {
+        slider.on_event(Event::Mouse {
+            offset: Vec2::zero(),
+            position: Vec2::new(x, 0),
+            event: MouseEvent::Press(MouseButton::Left),
+        });
+    }
+
+    #[test]
+    fn test_step() {
+        let mut slider = SliderView::horizontal(23).step(5).page_step(20);
+        slider.on_event(Event::Key(Key::Right));
+        slider.on_event(Event::Key(Key::Right));
+        assert_eq!(slider.get_value(), 10);
+
+        slider.on_event(Event::Key(Key::Left));
+        assert_eq!(slider.get_value(), 5);
+
+        // Stops at the bounds.
+        slider.on_event(Event::Key(Key::PageDown));
+        assert_eq!(slider.get_value(), 22);
+        slider.on_event(Event::Key(Key::PageUp));
+        slider.on_event(Event::Key(Key::PageUp));
+        assert_eq!(slider.get_value(), 0);
+        assert!(!slider.on_event(Event::Key(Key::Left)).is_consumed());
+
+        slider.set_value(100);
+        assert_eq!(slider.get_value(), 22);
+
+        let mut slider = SliderView::horizontal(23).page_step(usize::MAX);
+        slider.on_event(Event::Key(Key::Right));
+        slider.on_event(Event::Key(Key::PageDown));
+        assert_eq!(slider.get_value(), 22);
+    }
+
+    #[test]
+    fn test_click() {
+        let mut slider = SliderView::horizontal(21).step(5).show_value(true);
+        assert_eq!(slider.required_size(Vec2::zero()), Vec2::new(24, 1));
+
+        click(&mut slider, 8);
+        assert_eq!(slider.get_value(), 10);
+        click(&mut slider, 7);
+        assert_eq!(slider.get_value(), 5);
+        click(&mut slider, 20);
+        assert_eq!(slider.get_value(), 20);
+
+        // Clicking the value doesn't move the slider.
+        click(&mut slider, 22);
+        assert_eq!(slider.get_value(), 20);
     }
 }
@@ -0,0 +1,332 @@
+use direction::Direction;
+use event::{Callback, Event, EventResult, Key, MouseButton, MouseEvent};
+use num::PrimInt;
+use std::cmp::{max, min};
+use std::fmt::Display;
+use std::rc::Rc;
+use theme::ColorStyle;
+use vec::Vec2;
+use view::View;
+use With;
+use {Cursive, Printer};
+
+/// Closure type for callbacks when the value changes.
+type OnChange<T> = Fn(&mut Cursive, T);
+
+/// Input for a bounded integer, changed with the arrow keys.
+///
+/// `<Up>`/`<Right>` and `<Down>`/`<Left>` change the value by one step,
+/// `<PageUp>` and `<PageDown>` by a larger step. The mouse wheel and
+/// clicks on the arrows also work.
+///
+/// # Examples
+///
+/// ```rust
+/// # use cursive::views::SpinView;
+/// let spin = SpinView::new(0u8, 100).value(50).step(5).on_change(|_, v| {
+///     assert_eq!(v % 5, 0);
+/// });
+/// assert_eq!(spin.get_value(), 50);
+/// ```
+pub struct SpinView<T> {
+    value: T,
+    min: T,
+    max: T,
+    step: T,
+    // Defaults to 10 steps.
+    page_step: Option<T>,
+    show_arrows: bool,
+    enabled: bool,
+    on_change: Option<Rc<OnChange<T>>>,
+}
+
+impl<T> SpinView<T>
+where
+    T: PrimInt + Display + 'static,
+{
+    /// Creates a new `SpinView` for values between `min` and `max`.
+    ///
+    /// The initial value is `min`.
+    ///
+    /// # Panics
+    ///
+    /// If `min > max`.
+    pub fn new(min: T, max: T) -> Self {
+        assert!(min <= max, "SpinView: min should not exceed max");
+
+        SpinView {
+            value: min,
+            min,
+            max,
+            step: T::one(),
+            page_step: None,
+            show_arrows: true,
+            enabled: true,
+            on_change: None,
+        }
+    }
+
+    /// Sets the current value.
+    ///
+    /// The value is clamped between `min` and `max`.
+    ///
+    /// Returns an event result with a possible callback,
+    /// if `on_change` was set.
+    pub fn set_value(&mut self, value: T) -> EventResult {
+        self.value = min(max(value, self.min), self.max);
+        self.get_change_result()
+    }
+
+    /// Sets the current value.
+    ///
+    /// Chainable variant.
+    pub fn value(self, value: T) -> Self {
+        self.with(|s| {
+            s.set_value(value);
+        })
+    }
+
+    /// Returns the current value.
+    pub fn get_value(&self) -> T {
+        self.value
+    }
+
+    /// Sets the amount added or removed by the arrow keys.
+    ///
+    /// Defaults to 1.
+    pub fn set_step(&mut self, step: T) {
+        self.step = step;
+    }
+
+    /// Sets the amount added or removed by the arrow keys.
+    ///
+    /// Chainable variant.
+    pub fn step(self, step: T) -> Self {
+        self.with(|s| s.set_step(step))
+    }
+
+    /// Sets the amount added or removed by `<PageUp>` and `<PageDown>`.
+    ///
+    /// Defaults to 10 times the step.
+    pub fn set_page_step(&mut self, page_step: T) {
+        self.page_step = Some(page_step);
+    }
+
+    /// Sets the amount added or removed by `<PageUp>` and `<PageDown>`.
+    ///
+    /// Chainable variant.
+    pub fn page_step(self, page_step: T) -> Self {
+        self.with(|s| s.set_page_step(page_step))
+    }
+
+    /// Control whether the increment and decrement arrows are visible.
+    ///
+    /// Defaults to `true`.
+    pub fn set_show_arrows(&mut self, show_arrows: bool) {
+        self.show_arrows = show_arrows;
+    }
+
+    /// Control whether the increment and decrement arrows are visible.
+    ///
+    /// Chainable variant.
+    pub fn show_arrows(self, show_arrows: bool) -> Self {
+        self.with(|s| s.set_show_arrows(show_arrows))
+    }
+
+    /// Sets a callback to be called when the value changes.
+    pub fn set_on_change<F>(&mut self, callback: F)
+    where
+        F: Fn(&mut Cursive, T) + 'static,
+    {
+        self.on_change = Some(Rc::new(callback));
+    }
+
+    /// Sets a callback to be called when the value changes.
+    ///
+    /// Chainable variant.
+    pub fn on_change<F>(self, callback: F) -> Self
+    where
+        F: Fn(&mut Cursive, T) + 'static,
+    {
+        self.with(|s| s.set_on_change(callback))
+    }
+
+    impl_enabled!(self.enabled);
+
+    fn get_change_result(&self) -> EventResult {
+        EventResult::Consumed(self.on_change.clone().map(|cb| {
+            let value = self.value;
+            Callback::from_fn(move |s| {
+                cb(s, value);
+            })
+        }))
+    }
+
+    fn get_page_step(&self) -> T {
+        self.page_step.unwrap_or_else(|| {
+            T::from(10)
+                .and_then(|ten| self.step.checked_mul(&ten))
+                .unwrap_or_else(T::max_value)
+        })
+    }
+
+    fn increment(&mut self, step: T) -> EventResult {
+        if self.value == self.max {
+            return EventResult::Ignored;
+        }
+
+        // Stop at the bound if we would overflow the type.
+        self.value = match self.value.checked_add(&step) {
+            Some(value) => min(value, self.max),
+            None => self.max,
+        };
+        self.get_change_result()
+    }
+
+    fn decrement(&mut self, step: T) -> EventResult {
+        if self.value == self.min {
+            return EventResult::Ignored;
+        }
+
+        self.value = match self.value.checked_sub(&step) {
+            Some(value) => max(value, self.min),
+            None => self.min,
+        };
+        self.get_change_result()
+    }
+
+    // Width needed to print any value in the range.
+    fn value_width(&self) -> usize {
+        max(self.min.to_string().len(), self.max.to_string().len())
+    }
+
+    fn req_size(&self) -> Vec2 {
+        if self.show_arrows {
+            Vec2::new(self.value_width() + 4, 1)
+        } else {
+            Vec2::new(self.value_width(), 1)
+        }
+    }
+
+    fn draw_internal(&self, printer: &Printer) {
+        let width = self.value_width();
+        let text = format!("{:>width$}", self.value, width = width);
+
+        if self.show_arrows {
+            let left = if self.value > self.min { "<" } else { " " };
+            let right = if self.value < self.max { ">" } else { " " };
+            printer.print((0, 0), &format!("{} {} {}", left, text, right));
+        } else {
+            printer.print((0, 0), &text);
+        }
+    }
+}
+
+impl<T> View for SpinView<T>
+where
+    T: PrimInt + Display + 'static,
+{
+    fn draw(&self, printer: &Printer) {
+        if self.enabled && printer.enabled {
+            printer.with_selection(printer.focused, |printer| {
+                self.draw_internal(printer)
+            });
+        } else {
+            printer.with_color(ColorStyle::secondary(), |printer| {
+                self.draw_internal(printer)
+            });
+        }
+    }
+
+    fn required_size(&mut self, _: Vec2) -> Vec2 {
+        self.req_size()
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        if !self.enabled {
+            return EventResult::Ignored;
+        }
+
+        let step = self.step;
+        let page_step = self.get_page_step();
+        let size = self.req_size();
+
+        match event {
+            Event::Key(Key::Up) | Event::Key(Key::Right) => {
+                self.increment(step)
+            }
+            Event::Key(Key::Down) | Event::Key(Key::Left) => {
+                self.decrement(step)
+            }
+            Event::Key(Key::PageUp) => self.increment(page_step),
+            Event::Key(Key::PageDown) => self.decrement(page_step),
+            Event::Mouse {
+                event: MouseEvent::WheelUp,
+                position,
+                offset,
+            } if position.fits_in_rect(offset, size) => self.increment(step),
+            Event::Mouse {
+                event: MouseEvent::WheelDown,
+                position,
+                offset,
+            } if position.fits_in_rect(offset, size) => self.decrement(step),
+            Event::Mouse {
+                event: MouseEvent::Release(MouseButton::Left),
+                position,
+                offset,
+            } if self.show_arrows && position.fits_in_rect(offset, size) => {
+                match position.checked_sub(offset) {
+                    Some(Vec2 { x: 0, .. }) => self.decrement(step),
+                    Some(Vec2 { x, .. }) if x + 1 == size.x => {
+                        self.increment(step)
+                    }
+                    _ => EventResult::Ignored,
+                }
+            }
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn take_focus(&mut self, _: Direction) -> bool {
+        self.enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounds() {
+        let mut spin = SpinView::new(-5i32, 12).value(8).step(3);
+
+        spin.on_event(Event::Key(Key::Up));
+        assert_eq!(spin.get_value(), 11);
+        spin.on_event(Event::Key(Key::Up));
+        assert_eq!(spin.get_value(), 12);
+        assert!(!spin.on_event(Event::Key(Key::Up)).is_consumed());
+
+        spin.on_event(Event::Key(Key::PageDown));
+        assert_eq!(spin.get_value(), -5);
+
+        spin.set_value(100);
+        assert_eq!(spin.get_value(), 12);
+    }
+
+    #[test]
+    fn test_overflow() {
+        let mut spin = SpinView::new(i8::MIN, i8::MAX).value(120).step(100);
+
+        spin.on_event(Event::Key(Key::PageUp));
+        assert_eq!(spin.get_value(), i8::MAX);
+
+        spin.on_event(Event::Key(Key::Down));
+        spin.on_event(Event::Key(Key::Down));
+        spin.on_event(Event::Key(Key::Down));
+        assert_eq!(spin.get_value(), i8::MIN);
+
+        let mut spin = SpinView::new(0u8, 255);
+        assert!(!spin.on_event(Event::Key(Key::Left)).is_consumed());
+        assert_eq!(spin.required_size(Vec2::zero()), Vec2::new(7, 1));
+    }
+}
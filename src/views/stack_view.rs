@@ -1,16 +1,34 @@
+use backend::Backend;
 use direction::Direction;
-use event::{AnyCb, Event, EventResult};
-use std::cell;
+use enumset::EnumSet;
+use event::{AnyCb, Event, EventResult, MouseButton, MouseEvent};
+use std::cell::{self, RefCell};
+use std::cmp::max;
+use std::error;
+use std::fmt;
+use std::mem;
 use std::ops::Deref;
-use theme::ColorStyle;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use theme::{Color, ColorPair, ColorStyle, Effect, Theme};
 use vec::Vec2;
-use view::{IntoBoxedView, Offset, Position, Selector, View, ViewWrapper};
+use xy::XY;
+use view::{
+    FocusPath, IntoBoxedView, LayoutTree, Offset, Position, Selector, View,
+    ViewWrapper,
+};
 use views::{CircularFocus, Layer, ShadowView, ViewBox};
 use Printer;
 use With;
 
 /// Simple stack of views.
 /// Only the top-most view is active and can receive input.
+///
+/// Layers can be animated when they enter or leave the stack, see
+/// [`set_transition`]. An entering layer already gets keyboard input, but
+/// mouse events are ignored until it is in place.
+///
+/// [`set_transition`]: #method.set_transition
 pub struct StackView {
     // Store layers from back to front.
     layers: Vec<Child>,
@@ -18,6 +36,122 @@ pub struct StackView {
     // Flag indicates if undrawn areas of the background are exposed
     // and therefore need redrawing.
     bg_dirty: cell::Cell<bool>,
+
+    transition: Transition,
+    transition_duration: Duration,
+    // Layers removed, but still drawn while they leave the screen.
+    leaving: Vec<Leaving>,
+    // Layer being dragged, and where it was grabbed (relative to it).
+    drag: Option<(LayerHandle, Vec2)>,
+    // Front layer when we last told the layers about the focus.
+    focused: Option<LayerHandle>,
+}
+
+/// Animation played when a layer enters or leaves a [`StackView`].
+///
+/// Layers leave the way they came in.
+///
+/// [`StackView`]: struct.StackView.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Transition {
+    /// Layers appear and disappear at once.
+    None,
+    /// Layers slide in from the top of the screen.
+    SlideFromTop,
+    /// Layers slide in from the bottom of the screen.
+    SlideFromBottom,
+    /// Layers slide in from the left of the screen.
+    SlideFromLeft,
+    /// Layers slide in from the right of the screen.
+    SlideFromRight,
+    /// Layers fade in, first hidden then dimmed.
+    ///
+    /// Terminals have no transparency, so this uses the `Dim` effect.
+    Fade,
+}
+
+impl Transition {
+    /// Returns how far from its place a sliding layer is drawn.
+    ///
+    /// `visible` goes from 0 (out of the screen) to 1 (in place).
+    fn shift(
+        self, offset: Vec2, size: Vec2, screen: Vec2, visible: f64,
+    ) -> XY<isize> {
+        let distance =
+            |d: usize| (d as f64 * (1.0 - visible)).round() as isize;
+
+        match self {
+            Transition::SlideFromTop => {
+                XY::new(0, -distance(offset.y + size.y))
+            }
+            Transition::SlideFromBottom => {
+                XY::new(0, distance(screen.y.saturating_sub(offset.y)))
+            }
+            Transition::SlideFromLeft => {
+                XY::new(-distance(offset.x + size.x), 0)
+            }
+            Transition::SlideFromRight => {
+                XY::new(distance(screen.x.saturating_sub(offset.x)), 0)
+            }
+            Transition::None | Transition::Fade => XY::new(0, 0),
+        }
+    }
+}
+
+// Prints made by a layer: position, text, colors and effects.
+type Print = (Vec2, String, ColorPair, EnumSet<Effect>);
+
+/// A removed layer, drawn from what it printed before leaving.
+struct Leaving {
+    prints: Vec<Print>,
+    offset: Vec2,
+    size: Vec2,
+    start: Instant,
+}
+
+/// Backend recording the prints of a layer about to leave.
+struct Recorder {
+    size: Vec2,
+    prints: RefCell<Vec<Print>>,
+    colors: cell::Cell<ColorPair>,
+    effects: cell::Cell<EnumSet<Effect>>,
+}
+
+impl Backend for Recorder {
+    fn finish(&mut self) {}
+
+    fn refresh(&mut self) {}
+
+    fn has_colors(&self) -> bool {
+        true
+    }
+
+    fn screen_size(&self) -> Vec2 {
+        self.size
+    }
+
+    fn print_at(&self, pos: Vec2, text: &str) {
+        self.prints.borrow_mut().push((
+            pos,
+            text.to_string(),
+            self.colors.get(),
+            self.effects.get(),
+        ));
+    }
+
+    fn clear(&self, _: Color) {}
+
+    fn set_color(&self, colors: ColorPair) -> ColorPair {
+        self.colors.replace(colors)
+    }
+
+    fn set_effect(&self, effect: Effect) {
+        self.effects.set(self.effects.get() | effect);
+    }
+
+    fn unset_effect(&self, effect: Effect) {
+        self.effects.set(self.effects.get() - effect);
+    }
 }
 
 /// Where should the view be on the screen (per dimension).
@@ -38,6 +172,36 @@ pub enum LayerPosition {
     FromFront(usize),
 }
 
+// Used to give a unique handle to each layer, in any stack.
+static NEXT_HANDLE: AtomicUsize = AtomicUsize::new(0);
+
+/// Identifies a layer in a `StackView`, wherever it moves in the stack.
+///
+/// Unlike a [`LayerPosition`], a handle keeps pointing to the same layer
+/// when other layers are added or removed. Handles are returned when adding
+/// a layer, or by [`StackView::layer_handle()`].
+///
+/// Handles are never reused, even by another `StackView`.
+///
+/// [`LayerPosition`]: enum.LayerPosition.html
+/// [`StackView::layer_handle()`]: struct.StackView.html#method.layer_handle
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct LayerHandle(usize);
+
+/// Error returned when the layer of a [`LayerHandle`] was already removed.
+///
+/// [`LayerHandle`]: struct.LayerHandle.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StaleLayerHandle;
+
+impl fmt::Display for StaleLayerHandle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the layer was already removed")
+    }
+}
+
+impl error::Error for StaleLayerHandle {}
+
 impl Placement {
     pub fn compute_offset<S, A, P>(
         &self, size: S, available: A, parent: P,
@@ -97,6 +261,17 @@ impl<T: View> ChildWrapper<T> {
 }
 
 impl<T: View> ChildWrapper<T> {
+    // Returns the rows and columns around the view, before and after it.
+    fn margins(&self) -> (Vec2, Vec2) {
+        match *self {
+            ChildWrapper::Shadow(ref v) => {
+                let top_left = v.top_left_padding();
+                (top_left, v.padding() - top_left)
+            }
+            _ => (Vec2::zero(), Vec2::zero()),
+        }
+    }
+
     /// Returns a reference to the inner view
     pub fn get_inner(&self) -> &T {
         match *self {
@@ -187,6 +362,14 @@ impl<T: View> View for ChildWrapper<T> {
             ChildWrapper::Plain(ref mut v) => v.focus_view(selector),
         }
     }
+
+    fn follow_focus(&mut self, path: &mut FocusPath) {
+        match *self {
+            ChildWrapper::Shadow(ref mut v) => v.follow_focus(path),
+            ChildWrapper::Backfilled(ref mut v) => v.follow_focus(path),
+            ChildWrapper::Plain(ref mut v) => v.follow_focus(path),
+        }
+    }
 }
 
 struct Child {
@@ -199,6 +382,14 @@ struct Child {
     // So we want to call `take_focus` right after the first call to `layout`.
     // This flag remembers when we've done that.
     virgin: bool,
+
+    // When the layer started to enter the stack, while it's animated.
+    entered: Option<Instant>,
+
+    handle: LayerHandle,
+
+    // Can the user move it with the mouse?
+    draggable: bool,
 }
 
 new_default!(StackView);
@@ -210,6 +401,134 @@ impl StackView {
             layers: Vec::new(),
             last_size: Vec2::zero(),
             bg_dirty: cell::Cell::new(true),
+            transition: Transition::None,
+            transition_duration: Duration::from_millis(200),
+            leaving: Vec::new(),
+            drag: None,
+            focused: None,
+        }
+    }
+
+    /// Sets the animation played when layers enter or leave the stack.
+    ///
+    /// Defaults to `Transition::None`.
+    ///
+    /// Only `Cursive::pop_layer` (or `pop_layer_animated`) animates the
+    /// leaving layer: `StackView::pop_layer` removes it at once.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cursive::views::{Dialog, StackView, Transition};
+    /// let mut stack = StackView::new();
+    /// stack.set_transition(Transition::SlideFromTop);
+    /// stack.add_layer(Dialog::info("Hello!"));
+    /// assert!(stack.is_animating());
+    /// ```
+    pub fn set_transition(&mut self, transition: Transition) {
+        self.transition = transition;
+    }
+
+    /// Sets the animation played when layers enter or leave the stack.
+    ///
+    /// Chainable variant.
+    pub fn transition(self, transition: Transition) -> Self {
+        self.with(|s| s.set_transition(transition))
+    }
+
+    /// Returns the animation played when layers enter or leave the stack.
+    pub fn get_transition(&self) -> Transition {
+        self.transition
+    }
+
+    /// Sets how long transitions last.
+    ///
+    /// Defaults to 200ms.
+    pub fn set_transition_duration(&mut self, duration: Duration) {
+        self.transition_duration = duration;
+    }
+
+    /// Sets how long transitions last.
+    ///
+    /// Chainable variant.
+    pub fn transition_duration(self, duration: Duration) -> Self {
+        self.with(|s| s.set_transition_duration(duration))
+    }
+
+    /// Returns `true` if a layer is entering or leaving the stack.
+    ///
+    /// The stack needs to be redrawn regularly until this is `false`.
+    pub fn is_animating(&self) -> bool {
+        !self.leaving.is_empty()
+            || self.layers.iter().any(|layer| layer.entered.is_some())
+    }
+
+    // Returns a handle that was never given to another layer.
+    fn new_handle() -> LayerHandle {
+        LayerHandle(NEXT_HANDLE.fetch_add(1, Ordering::Relaxed))
+    }
+
+    // Returns the start of the transition for a new layer.
+    fn enter(&self) -> Option<Instant> {
+        if self.transition == Transition::None {
+            return None;
+        }
+
+        self.bg_dirty.set(true);
+        Some(Instant::now())
+    }
+
+    // Returns how far a transition started at `start` got, from 0 to 1.
+    fn progress(&self, start: Instant) -> f64 {
+        let duration = self.transition_duration;
+        let elapsed = start.elapsed();
+        if elapsed >= duration {
+            return 1.0;
+        }
+
+        let millis = |d: Duration| {
+            d.as_secs() as f64 * 1000.0 + f64::from(d.subsec_nanos()) / 1e6
+        };
+        millis(elapsed) / millis(duration)
+    }
+
+    // Draws a layer, `visible` being how far it got in its transition.
+    //
+    // At 1, the layer is drawn the same as without a transition.
+    fn draw_transition<F>(
+        &self, printer: &Printer, offset: Vec2, size: Vec2, visible: f64,
+        draw: F,
+    ) where
+        F: FnOnce(&Printer),
+    {
+        if visible >= 1.0 {
+            draw(&printer.offset(offset).cropped(size));
+            return;
+        }
+
+        match self.transition {
+            Transition::Fade => {
+                if visible >= 1.0 / 3.0 {
+                    printer.with_effect(Effect::Dim, |printer| {
+                        draw(&printer.offset(offset).cropped(size))
+                    });
+                }
+            }
+            transition => {
+                let shift =
+                    transition.shift(offset, size, printer.size, visible);
+                // The part of the layer out of the screen is cut.
+                let hidden = offset
+                    .signed()
+                    .zip_map(shift, |o, s| max(0, -(o + s)) as usize);
+                draw(
+                    &printer
+                        .offset(offset.saturating_add(shift))
+                        .cropped(size.saturating_sub(hidden))
+                        .content_offset(hidden)
+                        .inner_size(size),
+                );
+            }
         }
     }
 
@@ -232,11 +551,14 @@ impl StackView {
     /// Adds a new full-screen layer on top of the stack.
     ///
     /// Fullscreen layers have no shadow.
-    pub fn add_fullscreen_layer<T>(&mut self, view: T)
+    ///
+    /// Returns a handle to the new layer.
+    pub fn add_fullscreen_layer<T>(&mut self, view: T) -> LayerHandle
     where
         T: IntoBoxedView,
     {
         let boxed = ViewBox::boxed(view);
+        let handle = Self::new_handle();
         self.layers.push(Child {
             view: ChildWrapper::Backfilled(Layer::new(
                 CircularFocus::wrap_tab(boxed),
@@ -244,15 +566,21 @@ impl StackView {
             size: Vec2::zero(),
             placement: Placement::Fullscreen,
             virgin: true,
+            entered: self.enter(),
+            handle,
+            draggable: false,
         });
+        handle
     }
 
     /// Adds new view on top of the stack in the center of the screen.
-    pub fn add_layer<T>(&mut self, view: T)
+    ///
+    /// Returns a handle to the new layer.
+    pub fn add_layer<T>(&mut self, view: T) -> LayerHandle
     where
         T: IntoBoxedView,
     {
-        self.add_layer_at(Position::center(), view);
+        self.add_layer_at(Position::center(), view)
     }
 
     /// Adds new view on top of the stack in the center of the screen.
@@ -262,7 +590,9 @@ impl StackView {
     where
         T: IntoBoxedView,
     {
-        self.with(|s| s.add_layer(view))
+        self.with(|s| {
+            s.add_layer(view);
+        })
     }
 
     /// Returns a reference to the layer at the given position.
@@ -328,7 +658,9 @@ impl StackView {
     where
         T: IntoBoxedView,
     {
-        self.with(|s| s.add_fullscreen_layer(view))
+        self.with(|s| {
+            s.add_fullscreen_layer(view);
+        })
     }
 
     /// Adds a new transparent layer on top of the stack.
@@ -338,15 +670,22 @@ impl StackView {
     where
         T: IntoBoxedView,
     {
-        self.with(|s| s.add_transparent_layer(view))
+        self.with(|s| {
+            s.add_transparent_layer(view);
+        })
     }
 
     /// Adds a view on top of the stack.
-    pub fn add_layer_at<T>(&mut self, position: Position, view: T)
+    ///
+    /// Returns a handle to the new layer.
+    pub fn add_layer_at<T>(
+        &mut self, position: Position, view: T,
+    ) -> LayerHandle
     where
         T: IntoBoxedView,
     {
         let boxed = ViewBox::boxed(view);
+        let handle = Self::new_handle();
         self.layers.push(Child {
             // Skip padding for absolute/parent-placed views
             view: ChildWrapper::Shadow(
@@ -357,29 +696,44 @@ impl StackView {
             size: Vec2::new(0, 0),
             placement: Placement::Floating(position),
             virgin: true,
+            entered: self.enter(),
+            handle,
+            draggable: false,
         });
+        handle
     }
 
     /// Adds a transparent view on top of the stack in the center of the screen.
-    pub fn add_transparent_layer<T>(&mut self, view: T)
+    ///
+    /// Returns a handle to the new layer.
+    pub fn add_transparent_layer<T>(&mut self, view: T) -> LayerHandle
     where
         T: IntoBoxedView,
     {
-        self.add_transparent_layer_at(Position::center(), view);
+        self.add_transparent_layer_at(Position::center(), view)
     }
 
     /// Adds a transparent view on top of the stack.
-    pub fn add_transparent_layer_at<T>(&mut self, position: Position, view: T)
+    ///
+    /// Returns a handle to the new layer.
+    pub fn add_transparent_layer_at<T>(
+        &mut self, position: Position, view: T,
+    ) -> LayerHandle
     where
         T: IntoBoxedView,
     {
         let boxed = ViewBox::boxed(view);
+        let handle = Self::new_handle();
         self.layers.push(Child {
             view: ChildWrapper::Plain(CircularFocus::wrap_tab(boxed)),
             size: Vec2::new(0, 0),
             placement: Placement::Floating(position),
             virgin: true,
+            entered: self.enter(),
+            handle,
+            draggable: false,
         });
+        handle
     }
 
     /// Adds a view on top of the stack at the given position.
@@ -389,7 +743,9 @@ impl StackView {
     where
         T: IntoBoxedView,
     {
-        self.with(|s| s.add_layer_at(position, view))
+        self.with(|s| {
+            s.add_layer_at(position, view);
+        })
     }
 
     /// Remove a layer from this `StackView`.
@@ -402,6 +758,68 @@ impl StackView {
         self.layers.remove(i).view.unwrap().unwrap()
     }
 
+    /// Returns a handle to the layer at the given position.
+    ///
+    /// The handle stays valid until this layer is removed, even if other
+    /// layers are added, removed or moved around.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cursive::views::{Dialog, LayerPosition, StackView, TextView};
+    /// let mut stack = StackView::new();
+    /// stack.add_layer(TextView::new("Loading..."));
+    /// let status = stack.layer_handle(LayerPosition::FromFront(0)).unwrap();
+    /// stack.add_layer(Dialog::info("Hello!"));
+    ///
+    /// // Removes the status, leaving the dialog alone.
+    /// assert!(stack.remove_layer_by_handle(status).is_ok());
+    /// assert!(stack.remove_layer_by_handle(status).is_err());
+    /// ```
+    pub fn layer_handle(
+        &self, position: LayerPosition,
+    ) -> Option<LayerHandle> {
+        self.get_index(position)
+            .and_then(|i| self.layers.get(i))
+            .map(|child| child.handle)
+    }
+
+    /// Returns the current position of the layer with the given handle.
+    ///
+    /// Returns `None` if this layer was removed.
+    pub fn find_layer(&self, handle: LayerHandle) -> Option<LayerPosition> {
+        self.find_handle(handle).map(LayerPosition::FromBack)
+    }
+
+    fn find_handle(&self, handle: LayerHandle) -> Option<usize> {
+        self.layers.iter().position(|child| child.handle == handle)
+    }
+
+    /// Removes the layer with the given handle, wherever it is in the stack.
+    ///
+    /// If it was the top-most layer, the new top-most layer gets the focus
+    /// back, as with `pop_layer()`. Otherwise, the focus doesn't move.
+    ///
+    /// Returns an error if this layer was already removed.
+    pub fn remove_layer_by_handle(
+        &mut self, handle: LayerHandle,
+    ) -> Result<Box<View>, StaleLayerHandle> {
+        let i = self.find_handle(handle).ok_or(StaleLayerHandle)?;
+        self.bg_dirty.set(true);
+        Ok(self.layers.remove(i).view.unwrap().unwrap())
+    }
+
+    /// Moves the layer with the given handle to a new position on the screen.
+    ///
+    /// Returns an error if this layer was already removed.
+    pub fn reposition_layer_by_handle(
+        &mut self, handle: LayerHandle, position: Position,
+    ) -> Result<(), StaleLayerHandle> {
+        let i = self.find_handle(handle).ok_or(StaleLayerHandle)?;
+        self.reposition_layer(LayerPosition::FromBack(i), position);
+        Ok(())
+    }
+
     /// Remove the top-most layer.
     pub fn pop_layer(&mut self) -> Option<Box<View>> {
         self.bg_dirty.set(true);
@@ -412,6 +830,64 @@ impl StackView {
             .map(ViewBox::unwrap)
     }
 
+    /// Remove the top-most layer, playing the leave transition.
+    ///
+    /// The layer is drawn one last time using `theme`, and this drawing is
+    /// animated; the view itself is returned right away.
+    ///
+    /// `Cursive::pop_layer` calls this with the current theme.
+    pub fn pop_layer_animated(&mut self, theme: &Theme) -> Option<Box<View>> {
+        if self.transition != Transition::None && !self.layers.is_empty() {
+            let offset = self.offset();
+            let child = &self.layers[self.layers.len() - 1];
+            let recorder = Recorder {
+                size: child.size,
+                prints: RefCell::new(Vec::new()),
+                colors: cell::Cell::new(ColorPair {
+                    front: Color::TerminalDefault,
+                    back: Color::TerminalDefault,
+                }),
+                effects: cell::Cell::new(EnumSet::new()),
+            };
+            let printer = Printer::new(child.size, theme, &recorder);
+            printer.with_color(ColorStyle::primary(), |printer| {
+                child.view.draw(printer);
+            });
+
+            self.leaving.push(Leaving {
+                prints: recorder.prints.into_inner(),
+                offset,
+                size: child.size,
+                start: Instant::now(),
+            });
+        }
+
+        self.pop_layer()
+    }
+
+    // Tells the layers when the front layer changed since the last call.
+    //
+    // The previous front layer gets `Event::FocusLost` if it's still there,
+    // and the new one `Event::FocusGained`.
+    pub(crate) fn notify_focus_change(&mut self) -> EventResult {
+        let front = self.layers.last().map(|child| child.handle);
+        let previous = mem::replace(&mut self.focused, front);
+        if previous.is_none() || front == previous {
+            return EventResult::Ignored;
+        }
+
+        let lost = match previous.and_then(|h| self.find_handle(h)) {
+            Some(i) => self.layers[i].view.on_event(Event::FocusLost),
+            None => EventResult::Ignored,
+        };
+        let gained = match self.layers.last_mut() {
+            Some(child) => child.view.on_event(Event::FocusGained),
+            None => EventResult::Ignored,
+        };
+
+        lost.and(gained)
+    }
+
     /// Computes the offset of the current top view.
     pub fn offset(&self) -> Vec2 {
         let mut previous = Vec2::zero();
@@ -475,6 +951,94 @@ impl StackView {
         self.move_layer(layer, LayerPosition::FromBack(0));
     }
 
+    /// Shows or hides the shadow of a layer, whatever the theme says.
+    ///
+    /// Fullscreen and transparent layers have no shadow, so this does
+    /// nothing for them.
+    ///
+    /// # Panics
+    ///
+    /// If `layer` is out of bounds.
+    pub fn set_layer_shadow(&mut self, layer: LayerPosition, shadow: bool) {
+        let i = self.get_index(layer).unwrap();
+        if let ChildWrapper::Shadow(ref mut view) = self.layers[i].view {
+            view.set_shadow(shadow);
+            self.bg_dirty.set(true);
+        }
+    }
+
+    /// Lets the user move a layer by dragging its top row with the mouse.
+    ///
+    /// For a `Dialog`, this is the title bar. The layer stays on the screen,
+    /// and the view itself doesn't see the mouse events of the drag. Only
+    /// the top-most layer can be dragged.
+    ///
+    /// Fullscreen layers can't be moved, so this does nothing for them.
+    ///
+    /// # Panics
+    ///
+    /// If `layer` is out of bounds.
+    pub fn set_layer_draggable(
+        &mut self, layer: LayerPosition, draggable: bool,
+    ) {
+        let i = self.get_index(layer).unwrap();
+        let child = &mut self.layers[i];
+        if let Placement::Floating(_) = child.placement {
+            child.draggable = draggable;
+        }
+    }
+
+    // Moves the top-most layer when it's dragged by its top row.
+    //
+    // Returns `None` if the event is not part of a drag.
+    fn drag_layer(&mut self, event: &Event) -> Option<EventResult> {
+        let (event, position) = match *event {
+            Event::Mouse {
+                event,
+                position,
+                offset,
+            } => (event, position.saturating_sub(offset)),
+            _ => return None,
+        };
+        let offset = self.offset();
+        let top = self.layers.last_mut()?;
+
+        match event {
+            MouseEvent::Press(MouseButton::Left) if top.draggable => {
+                let (before, after) = top.view.margins();
+                let start = offset + before;
+                let end = (offset + top.size).saturating_sub(after);
+                if position.y != start.y
+                    || position.x < start.x
+                    || position.x >= end.x
+                {
+                    return None;
+                }
+                self.drag = Some((top.handle, position - offset));
+            }
+            MouseEvent::Hold(MouseButton::Left) => {
+                let (handle, grab) = self.drag?;
+                if top.handle != handle {
+                    // The layer is gone.
+                    self.drag = None;
+                    return None;
+                }
+                let max_offset = self.last_size.saturating_sub(top.size);
+                let offset = position.saturating_sub(grab).or_min(max_offset);
+                let position = Position::absolute(offset);
+                top.placement = Placement::Floating(position);
+                self.bg_dirty.set(true);
+            }
+            // Wherever the mouse is now.
+            MouseEvent::Release(MouseButton::Left) => {
+                self.drag.take()?;
+            }
+            _ => return None,
+        }
+
+        Some(EventResult::Consumed(None))
+    }
+
     /// Moves a layer to a new position on the screen.
     ///
     /// # Panics
@@ -510,7 +1074,8 @@ impl StackView {
             }
 
             // set background as clean, so we don't need to do this every frame
-            self.bg_dirty.set(false);
+            // Moving layers keep uncovering it during transitions, though.
+            self.bg_dirty.set(self.is_animating());
         }
     }
 
@@ -527,11 +1092,39 @@ impl StackView {
                 StackPositionIterator::new(self.layers.iter(), printer.size)
                     .enumerate()
             {
-                v.view.draw(
-                    &printer
-                        .offset(offset)
-                        .cropped(v.size)
-                        .focused(i + 1 == last),
+                let visible = v.entered.map_or(1.0, |t| self.progress(t));
+                self.draw_transition(
+                    &printer.focused(i + 1 == last),
+                    offset,
+                    v.size,
+                    visible,
+                    |printer| v.view.draw(printer),
+                );
+            }
+
+            for leaving in &self.leaving {
+                let visible = 1.0 - self.progress(leaving.start);
+                if visible <= 0.0 {
+                    continue;
+                }
+                self.draw_transition(
+                    printer,
+                    leaving.offset,
+                    leaving.size,
+                    visible,
+                    |printer| {
+                        for &(pos, ref text, colors, effects) in
+                            &leaving.prints
+                        {
+                            let style =
+                                ColorStyle::new(colors.front, colors.back);
+                            printer.with_color(style, |printer| {
+                                printer.with_effects(effects, |printer| {
+                                    printer.print(pos, text)
+                                })
+                            });
+                        }
+                    },
                 );
             }
         });
@@ -594,12 +1187,22 @@ impl View for StackView {
     }
 
     fn on_event(&mut self, event: Event) -> EventResult {
-        if event == Event::WindowResize {
-            self.bg_dirty.set(true);
+        match event {
+            Event::WindowResize => self.bg_dirty.set(true),
+            Event::Refresh => (),
+            // Layers are not where they are drawn yet.
+            Event::Mouse { .. } if self.is_animating() => {
+                return EventResult::Ignored
+            }
+            _ => (),
+        }
+        let focus = self.notify_focus_change();
+        if let Some(result) = self.drag_layer(&event) {
+            return focus.and(result);
         }
         // Use the stack position iterator to get the offset of the top layer.
         // TODO: save it instead when drawing?
-        match StackPositionIterator::new(
+        let result = match StackPositionIterator::new(
             self.layers.iter_mut(),
             self.last_size,
         )
@@ -607,12 +1210,28 @@ impl View for StackView {
         {
             None => EventResult::Ignored,
             Some((v, offset)) => v.view.on_event(event.relativized(offset)),
-        }
+        };
+        focus.and(result)
     }
 
     fn layout(&mut self, size: Vec2) {
         self.last_size = size;
 
+        // Finished transitions leave the layers as if they had none.
+        let duration = self.transition_duration;
+        let done = |start: Instant| start.elapsed() >= duration;
+        let leaving = self.leaving.len();
+        self.leaving.retain(|leaving| !done(leaving.start));
+        if self.leaving.len() != leaving {
+            self.bg_dirty.set(true);
+        }
+        for layer in &mut self.layers {
+            if layer.entered.filter(|&start| done(start)).is_some() {
+                layer.entered = None;
+                self.bg_dirty.set(true);
+            }
+        }
+
         // The call has been made, we can't ask for more space anymore.
         // Let's make do with what we have.
 
@@ -668,12 +1287,54 @@ impl View for StackView {
 
         Err(())
     }
+
+    fn debug_layout(&mut self, tree: &mut LayoutTree) {
+        // Layers are described without the shadows and backgrounds we add.
+        let last = self.layers.len();
+        for (i, (layer, offset)) in
+            StackPositionIterator::new(self.layers.iter_mut(), self.last_size)
+                .enumerate()
+        {
+            let (before, after) = layer.view.margins();
+            tree.child(
+                &mut **layer.view.get_inner_mut(),
+                offset + before,
+                layer.size.saturating_sub(before + after),
+                i + 1 == last,
+            );
+        }
+    }
+
+    fn follow_focus(&mut self, path: &mut FocusPath) {
+        // Only the front layer has the focus.
+        let front = StackPositionIterator::new(
+            self.layers.iter_mut(),
+            self.last_size,
+        )
+        .last();
+        if let Some((layer, offset)) = front {
+            path.child(&mut layer.view, offset);
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use views::TextView;
+    use backend::puppet;
+    use event::{MouseButton, MouseEvent};
+    use theme::{self, ColorStyle};
+    use view::Boxable;
+    use views::{Dialog, EditView, TextView};
+
+    fn render(stack: &StackView, theme: &Theme) -> puppet::ObservedScreen {
+        let size = Vec2::new(16, 5);
+        let backend = puppet::Backend::init(Some(size));
+        // Each new screen needs the background.
+        stack.bg_dirty.set(true);
+        stack.draw(&Printer::new(size, theme, &*backend));
+        backend.screen()
+    }
 
     #[test]
     fn pop_add() {
@@ -767,4 +1428,185 @@ mod tests {
             .as_any_mut()
             .is::<TextView>());
     }
+
+    #[test]
+    fn layer_shadow() {
+        let size = Vec2::new(16, 5);
+        let mut stack = StackView::new()
+            .layer_at(Position::absolute((0, 0)), Dialog::text("a"))
+            .layer_at(Position::absolute((8, 0)), Dialog::text("b"));
+        stack.set_layer_shadow(LayerPosition::FromFront(0), false);
+        stack.layout(size);
+
+        let backend = puppet::Backend::init(Some(size));
+        let theme = theme::load_default();
+        stack.draw(&Printer::new(size, &theme, &*backend));
+
+        let screen = backend.screen();
+        let is_shadow = |x, y| {
+            screen.cell(Vec2::new(x, y)).unwrap().style.colors
+                == ColorStyle::shadow().resolve(&theme.palette)
+        };
+        assert_eq!(screen.row(0), "┌───┐   ┌───┐   ");
+        // Only the first dialog has a shadow, right and below it.
+        assert!(is_shadow(5, 1) && is_shadow(1, 3) && is_shadow(5, 3));
+        assert!(!is_shadow(0, 3) && !is_shadow(5, 0));
+        assert!(!is_shadow(13, 1) && !is_shadow(9, 3));
+    }
+
+    #[test]
+    fn transitions() {
+        let theme = theme::load_default();
+        let size = Vec2::new(16, 5);
+        let mut plain = StackView::new().layer(Dialog::text("a"));
+        plain.layout(size);
+        let mut empty = StackView::new();
+        empty.layout(size);
+
+        let mut stack = StackView::new()
+            .transition(Transition::SlideFromTop)
+            .transition_duration(Duration::from_secs(3600));
+        stack.add_layer(Dialog::text("a"));
+        stack.layout(size);
+        // Still out of the screen.
+        assert!(stack.is_animating());
+        assert_eq!(render(&stack, &theme), render(&empty, &theme));
+
+        // Once over, it looks like there was no transition.
+        stack.set_transition_duration(Duration::from_secs(0));
+        stack.layout(size);
+        assert!(!stack.is_animating());
+        assert_eq!(render(&stack, &theme), render(&plain, &theme));
+
+        // A leaving layer is still shown.
+        stack.set_transition(Transition::Fade);
+        stack.set_transition_duration(Duration::from_secs(3600));
+        assert!(stack.pop_layer_animated(&theme).is_some());
+        assert_eq!(stack.len(), 0);
+        assert!(stack.is_animating());
+        let screen = render(&stack, &theme);
+        assert_eq!(screen.rows(), render(&plain, &theme).rows());
+        let cell = screen.cell(Vec2::new(7, 2)).unwrap();
+        assert_eq!(cell.letter, "a");
+        assert!(cell.style.effects.contains(Effect::Dim));
+
+        stack.set_transition_duration(Duration::from_secs(0));
+        stack.layout(size);
+        assert!(!stack.is_animating());
+        assert_eq!(render(&stack, &theme), render(&empty, &theme));
+    }
+
+    #[test]
+    fn test_input_during_transitions() {
+        let mut stack = StackView::new()
+            .transition(Transition::SlideFromLeft)
+            .transition_duration(Duration::from_secs(3600));
+        stack.add_layer(EditView::new().fixed_width(5));
+        stack.layout(Vec2::new(16, 5));
+        assert!(stack.is_animating());
+
+        // Keys reach the entering layer, but clicks are left to others.
+        assert!(stack.on_event(Event::Char('x')).is_consumed());
+        let click = Event::Mouse {
+            offset: Vec2::zero(),
+            position: Vec2::new(8, 2),
+            event: MouseEvent::Press(MouseButton::Left),
+        };
+        assert!(!stack.on_event(click).is_consumed());
+
+        stack.set_transition_duration(Duration::from_secs(0));
+        stack.layout(Vec2::new(16, 5));
+        let screen = render(&stack, &theme::load_default());
+        assert_eq!(screen.rows()[2], "     x____      ");
+    }
+
+    #[test]
+    fn test_layer_handles() {
+        let mut stack = StackView::new();
+        let first = stack.add_layer(TextView::new("1"));
+        let second = stack.add_fullscreen_layer(TextView::new("2"));
+        assert_ne!(first, second);
+        let position = LayerPosition::FromBack(1);
+        assert_eq!(stack.layer_handle(position), Some(second));
+        stack.add_layer(TextView::new("3"));
+        stack.move_to_front(LayerPosition::FromBack(1));
+
+        // Handles follow their layer in the stack.
+        let position = stack.find_layer(second);
+        assert_eq!(position, Some(LayerPosition::FromBack(2)));
+        let content = |view: Box<View>| {
+            let view = view.as_any().downcast_ref::<TextView>().unwrap();
+            view.get_content().source().to_string()
+        };
+        let removed = stack.remove_layer_by_handle(first).unwrap();
+        assert_eq!(content(removed), "1");
+        let position = stack.find_layer(second);
+        assert_eq!(position, Some(LayerPosition::FromBack(1)));
+
+        // Stale handles are reported, even once new layers are added.
+        stack.add_layer(TextView::new("4"));
+        assert_eq!(stack.find_layer(first), None);
+        let removed = stack.remove_layer_by_handle(first);
+        assert_eq!(removed.err(), Some(StaleLayerHandle));
+        let position = || Position::absolute((0, 0));
+        assert!(stack.reposition_layer_by_handle(first, position()).is_err());
+        assert!(stack.reposition_layer_by_handle(second, position()).is_ok());
+        assert_eq!(stack.len(), 3);
+
+        // Handles from another stack are stale too.
+        let other = StackView::new().layer(TextView::new("5"));
+        let handle = other.layer_handle(LayerPosition::FromBack(0)).unwrap();
+        assert_eq!(stack.find_layer(handle), None);
+    }
+
+    #[test]
+    fn test_drag_layer() {
+        let mut stack = StackView::new()
+            .layer_at(Position::absolute((2, 1)), Dialog::info("Hello"));
+        stack.layout(Vec2::new(30, 10));
+        let size = stack.layer_sizes()[0];
+        let left = MouseButton::Left;
+        let drag = |stack: &mut StackView, event, position| {
+            let position = Vec2::from(position);
+            let offset = Vec2::zero();
+            stack.on_event(Event::Mouse {
+                event,
+                position,
+                offset,
+            });
+            stack.offset()
+        };
+
+        // Not draggable by default.
+        drag(&mut stack, MouseEvent::Press(left), (5, 1));
+        let offset = drag(&mut stack, MouseEvent::Hold(left), (8, 4));
+        assert_eq!(offset, Vec2::new(2, 1));
+        drag(&mut stack, MouseEvent::Release(left), (8, 4));
+
+        // Only from the top row.
+        stack.set_layer_draggable(LayerPosition::FromFront(0), true);
+        drag(&mut stack, MouseEvent::Press(left), (5, 2));
+        let offset = drag(&mut stack, MouseEvent::Hold(left), (8, 4));
+        assert_eq!(offset, Vec2::new(2, 1));
+        drag(&mut stack, MouseEvent::Release(left), (8, 4));
+
+        let press = Event::Mouse {
+            event: MouseEvent::Press(left),
+            position: Vec2::new(5, 1),
+            offset: Vec2::zero(),
+        };
+        assert!(stack.on_event(press).is_consumed());
+        let offset = drag(&mut stack, MouseEvent::Hold(left), (8, 4));
+        assert_eq!(offset, Vec2::new(5, 4));
+
+        // The layer stays on the screen.
+        let bottom_right = Vec2::new(30, 10) - size;
+        let offset = drag(&mut stack, MouseEvent::Hold(left), (99, 99));
+        assert_eq!(offset, bottom_right);
+
+        // Released out of the view, the drag is still over.
+        drag(&mut stack, MouseEvent::Release(left), (99, 99));
+        let offset = drag(&mut stack, MouseEvent::Hold(left), (8, 4));
+        assert_eq!(offset, bottom_right);
+    }
 }
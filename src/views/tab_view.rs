@@ -0,0 +1,492 @@
+use direction::Direction;
+use event::{AnyCb, Callback, Event, EventResult, Key, MouseButton, MouseEvent};
+use rect::Rect;
+use std::cmp::max;
+use std::rc::Rc;
+use unicode_width::UnicodeWidthStr;
+use vec::Vec2;
+use view::{FocusPath, LayoutTree, Selector, View};
+use Cursive;
+use Printer;
+use With;
+
+/// Where the tab bar of a [`TabView`] is drawn.
+///
+/// [`TabView`]: ::views::TabView
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TabBarPosition {
+    /// Above the active tab.
+    Top,
+    /// Below the active tab.
+    Bottom,
+}
+
+/// Container showing one of several named views, with a bar to switch
+/// between them.
+///
+/// Only the active tab is laid out, drawn, and receives events. Clicking on
+/// a title in the tab bar activates the tab, and so do `<Ctrl-PageDown>` and
+/// `<Ctrl-PageUp>` (see [`set_switch_keys`]).
+///
+/// [`set_switch_keys`]: #method.set_switch_keys
+///
+/// # Examples
+///
+/// ```rust
+/// # use cursive::views::{TabView, TextView};
+/// let mut tabs = TabView::new()
+///     .tab("Intro", TextView::new("Hello"))
+///     .tab("Details", TextView::new("World"));
+///
+/// tabs.set_active_tab(1);
+/// assert_eq!(tabs.active_tab(), 1);
+/// ```
+pub struct TabView {
+    tabs: Vec<Tab>,
+    active: usize,
+    bar_position: TabBarPosition,
+
+    next_key: Event,
+    previous_key: Event,
+
+    on_tab_change: Option<Rc<OnTabChange>>,
+
+    last_size: Vec2,
+}
+
+/// Closure type for callbacks when the active tab changes.
+type OnTabChange = Fn(&mut Cursive, usize);
+
+struct Tab {
+    title: String,
+    view: Box<View>,
+}
+
+impl Default for TabView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TabView {
+    /// Creates a new `TabView` without any tab.
+    pub fn new() -> Self {
+        TabView {
+            tabs: Vec::new(),
+            active: 0,
+            bar_position: TabBarPosition::Top,
+            next_key: Event::Ctrl(Key::PageDown),
+            previous_key: Event::Ctrl(Key::PageUp),
+            on_tab_change: None,
+            last_size: Vec2::zero(),
+        }
+    }
+
+    /// Adds a tab after the existing ones.
+    ///
+    /// The first tab added is the active one.
+    pub fn add_tab<S, V>(&mut self, title: S, view: V)
+    where
+        S: Into<String>,
+        V: View + 'static,
+    {
+        self.tabs.push(Tab {
+            title: title.into(),
+            view: Box::new(view),
+        });
+    }
+
+    /// Adds a tab after the existing ones.
+    ///
+    /// Chainable variant.
+    pub fn tab<S, V>(self, title: S, view: V) -> Self
+    where
+        S: Into<String>,
+        V: View + 'static,
+    {
+        self.with(|s| s.add_tab(title, view))
+    }
+
+    /// Removes and returns the tab at the given position.
+    ///
+    /// Returns `None` if `i` is out of bounds.
+    pub fn remove_tab(&mut self, i: usize) -> Option<Box<View>> {
+        if i >= self.tabs.len() {
+            return None;
+        }
+
+        let tab = self.tabs.remove(i);
+        if self.active > i || self.active == self.tabs.len() {
+            self.active = self.active.saturating_sub(1);
+        }
+        Some(tab.view)
+    }
+
+    /// Returns the number of tabs.
+    pub fn len(&self) -> usize {
+        self.tabs.len()
+    }
+
+    /// Returns `true` if there is no tab.
+    pub fn is_empty(&self) -> bool {
+        self.tabs.is_empty()
+    }
+
+    /// Returns the title of the tab at the given position.
+    pub fn tab_title(&self, i: usize) -> Option<&str> {
+        self.tabs.get(i).map(|tab| &tab.title[..])
+    }
+
+    /// Returns the position of the active tab.
+    pub fn active_tab(&self) -> usize {
+        self.active
+    }
+
+    /// Activates the tab at the given position, and gives it the focus.
+    ///
+    /// Returns a callback in response to the tab change.
+    ///
+    /// You should run this callback with a `&mut Cursive`.
+    ///
+    /// # Panics
+    ///
+    /// If `i` is out of bounds.
+    pub fn set_active_tab(&mut self, i: usize) -> Callback {
+        assert!(i < self.tabs.len(), "TabView: no tab at index {}", i);
+
+        self.active = i;
+        self.tabs[i].view.take_focus(Direction::none());
+
+        self.on_tab_change
+            .clone()
+            .map(|cb| Callback::from_fn(move |s| cb(s, i)))
+            .unwrap_or_else(Callback::dummy)
+    }
+
+    /// Sets where the tab bar is drawn.
+    ///
+    /// Defaults to `TabBarPosition::Top`.
+    pub fn set_bar_position(&mut self, position: TabBarPosition) {
+        self.bar_position = position;
+    }
+
+    /// Sets where the tab bar is drawn.
+    ///
+    /// Chainable variant.
+    pub fn bar_position(self, position: TabBarPosition) -> Self {
+        self.with(|s| s.set_bar_position(position))
+    }
+
+    /// Sets the events activating the next and previous tabs.
+    ///
+    /// Defaults to `<Ctrl-PageDown>` and `<Ctrl-PageUp>`. These events are
+    /// not sent to the active tab.
+    pub fn set_switch_keys<N, P>(&mut self, next: N, previous: P)
+    where
+        N: Into<Event>,
+        P: Into<Event>,
+    {
+        self.next_key = next.into();
+        self.previous_key = previous.into();
+    }
+
+    /// Sets the events activating the next and previous tabs.
+    ///
+    /// Chainable variant.
+    pub fn switch_keys<N, P>(self, next: N, previous: P) -> Self
+    where
+        N: Into<Event>,
+        P: Into<Event>,
+    {
+        self.with(|s| s.set_switch_keys(next, previous))
+    }
+
+    /// Sets a callback to be used when the active tab changes.
+    ///
+    /// The callback is given the position of the new active tab.
+    pub fn set_on_tab_change<F>(&mut self, cb: F)
+    where
+        F: Fn(&mut Cursive, usize) + 'static,
+    {
+        self.on_tab_change = Some(Rc::new(cb));
+    }
+
+    /// Sets a callback to be used when the active tab changes.
+    ///
+    /// Chainable variant.
+    pub fn on_tab_change<F>(self, cb: F) -> Self
+    where
+        F: Fn(&mut Cursive, usize) + 'static,
+    {
+        self.with(|s| s.set_on_tab_change(cb))
+    }
+
+    // Row of the tab bar.
+    fn bar_y(&self, height: usize) -> usize {
+        match self.bar_position {
+            TabBarPosition::Top => 0,
+            TabBarPosition::Bottom => height.saturating_sub(1),
+        }
+    }
+
+    fn view_offset(&self) -> Vec2 {
+        match self.bar_position {
+            TabBarPosition::Top => Vec2::new(0, 1),
+            TabBarPosition::Bottom => Vec2::zero(),
+        }
+    }
+
+    // Returns the tab whose title is at the given column.
+    fn tab_at(&self, x: usize) -> Option<usize> {
+        let mut start = 0;
+        for (i, tab) in self.tabs.iter().enumerate() {
+            let end = start + tab.title.width() + 2;
+            if x >= start && x < end {
+                return Some(i);
+            }
+            // One column for the separator.
+            start = end + 1;
+        }
+        None
+    }
+
+    fn bar_width(&self) -> usize {
+        let titles: usize =
+            self.tabs.iter().map(|tab| tab.title.width() + 2).sum();
+        titles + self.tabs.len().saturating_sub(1)
+    }
+
+    fn draw_bar(&self, printer: &Printer) {
+        let mut x = 0;
+        for (i, tab) in self.tabs.iter().enumerate() {
+            if i > 0 {
+                printer.print((x, 0), "│");
+                x += 1;
+            }
+            printer.with_selection(i == self.active, |printer| {
+                printer.print((x, 0), &format!(" {} ", tab.title));
+            });
+            x += tab.title.width() + 2;
+        }
+    }
+
+    fn switch_to(&mut self, i: usize) -> EventResult {
+        if i == self.active {
+            return EventResult::Consumed(None);
+        }
+
+        let lost = self.tabs[self.active].view.on_event(Event::FocusLost);
+        let cb = self.set_active_tab(i);
+        let gained = self.tabs[i].view.on_event(Event::FocusGained);
+
+        lost.and(gained).and(EventResult::Consumed(Some(cb)))
+    }
+}
+
+impl View for TabView {
+    fn draw(&self, printer: &Printer) {
+        let bar_y = self.bar_y(printer.size.y);
+        let bar = printer.offset((0, bar_y)).cropped((printer.size.x, 1));
+        self.draw_bar(&bar);
+
+        if let Some(tab) = self.tabs.get(self.active) {
+            let printer = printer
+                .offset(self.view_offset())
+                .cropped(printer.size.saturating_sub((0, 1)));
+            tab.view.draw(&printer);
+        }
+    }
+
+    fn required_size(&mut self, constraint: Vec2) -> Vec2 {
+        let bar_width = self.bar_width();
+        let size = match self.tabs.get_mut(self.active) {
+            Some(tab) => {
+                tab.view.required_size(constraint.saturating_sub((0, 1)))
+            }
+            None => Vec2::zero(),
+        };
+
+        Vec2::new(max(size.x, bar_width), size.y + 1)
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        self.last_size = size;
+        if let Some(tab) = self.tabs.get_mut(self.active) {
+            tab.view.layout(size.saturating_sub((0, 1)));
+        }
+    }
+
+    fn needs_relayout(&self) -> bool {
+        self.tabs
+            .get(self.active)
+            .map(|tab| tab.view.needs_relayout())
+            .unwrap_or(true)
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        if self.tabs.is_empty() {
+            return EventResult::Ignored;
+        }
+
+        if event == self.next_key {
+            let next = (self.active + 1) % self.tabs.len();
+            return self.switch_to(next);
+        }
+        if event == self.previous_key {
+            let previous =
+                (self.active + self.tabs.len() - 1) % self.tabs.len();
+            return self.switch_to(previous);
+        }
+
+        if let Event::Mouse {
+            event: MouseEvent::Press(MouseButton::Left),
+            position,
+            offset,
+        } = event
+        {
+            let bar_y = self.bar_y(self.last_size.y);
+            let clicked = position
+                .checked_sub(offset)
+                .filter(|position| {
+                    position.y == bar_y && position.x < self.last_size.x
+                })
+                .and_then(|position| self.tab_at(position.x));
+            if let Some(i) = clicked {
+                return self.switch_to(i);
+            }
+        }
+
+        let offset = self.view_offset();
+        self.tabs[self.active].view.on_event(event.relativized(offset))
+    }
+
+    fn take_focus(&mut self, source: Direction) -> bool {
+        let active = self.active;
+        let focused = self
+            .tabs
+            .get_mut(active)
+            .map(|tab| tab.view.take_focus(source))
+            .unwrap_or(false);
+
+        // Keep the focus to switch tabs, even if the tab doesn't want it.
+        focused || self.tabs.len() > 1
+    }
+
+    fn call_on_any<'a>(
+        &mut self, selector: &Selector, mut callback: AnyCb<'a>,
+    ) {
+        for tab in &mut self.tabs {
+            tab.view
+                .call_on_any(selector, Box::new(|any| callback(any)));
+        }
+    }
+
+    fn focus_view(&mut self, selector: &Selector) -> Result<(), ()> {
+        for (i, tab) in self.tabs.iter_mut().enumerate() {
+            if tab.view.focus_view(selector).is_ok() {
+                self.active = i;
+                return Ok(());
+            }
+        }
+
+        Err(())
+    }
+
+    fn important_area(&self, size: Vec2) -> Rect {
+        match self.tabs.get(self.active) {
+            Some(tab) => {
+                tab.view.important_area(size.saturating_sub((0, 1)))
+                    + self.view_offset()
+            }
+            None => Rect::from((0, 0)),
+        }
+    }
+
+    fn debug_layout(&mut self, tree: &mut LayoutTree) {
+        // Only the active tab is laid out.
+        let offset = self.view_offset();
+        let size = self.last_size.saturating_sub((0, 1));
+        if let Some(tab) = self.tabs.get_mut(self.active) {
+            tree.set_info(format!("tab {:?}", tab.title));
+            tree.child(&mut *tab.view, offset, size, true);
+        }
+    }
+
+    fn follow_focus(&mut self, path: &mut FocusPath) {
+        let offset = self.view_offset();
+        if let Some(tab) = self.tabs.get_mut(self.active) {
+            path.child(&mut *tab.view, offset);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backend::puppet;
+    use std::cell::Cell;
+    use view::{Finder, Identifiable};
+    use views::{BoxView, EditView, TextView};
+
+    #[test]
+    fn test_switch() {
+        let changes = Rc::new(Cell::new(0));
+        let mut tabs = TabView::new()
+            .tab("A", EditView::new().with_id("a"))
+            .tab("B", EditView::new().with_id("b"))
+            .on_tab_change({
+                let changes = Rc::clone(&changes);
+                move |_, i| changes.set(changes.get() * 10 + i + 1)
+            });
+        tabs.layout(Vec2::new(20, 5));
+
+        let mut siv = Cursive::dummy();
+        let mut send = |tabs: &mut TabView, event: Event| {
+            if let EventResult::Consumed(Some(cb)) = tabs.on_event(event) {
+                cb(&mut siv);
+            }
+        };
+
+        // Only the active tab gets events.
+        send(&mut tabs, Event::Char('x'));
+        send(&mut tabs, Event::Ctrl(Key::PageDown));
+        send(&mut tabs, Event::Char('y'));
+        assert_eq!(tabs.active_tab(), 1);
+
+        // Wraps around.
+        send(&mut tabs, Event::Ctrl(Key::PageDown));
+        assert_eq!(tabs.active_tab(), 0);
+
+        // Clicks on " B " activate the second tab.
+        send(
+            &mut tabs,
+            Event::Mouse {
+                offset: Vec2::zero(),
+                position: Vec2::new(4, 0),
+                event: MouseEvent::Press(MouseButton::Left),
+            },
+        );
+        assert_eq!(tabs.active_tab(), 1);
+        assert_eq!(changes.get(), 212);
+
+        let content = |tabs: &mut TabView, id| {
+            tabs.call_on_id(id, |view: &mut EditView| view.get_content())
+                .unwrap()
+        };
+        assert_eq!(&*content(&mut tabs, "a"), "x");
+        assert_eq!(&*content(&mut tabs, "b"), "y");
+    }
+
+    #[test]
+    fn test_bar_position() {
+        let backend = puppet::Backend::init(Some(Vec2::new(12, 2)));
+        let mut siv = Cursive::new(move || backend);
+        let tabs = TabView::new()
+            .tab("One", TextView::new("1"))
+            .tab("Two", TextView::new("2"))
+            .bar_position(TabBarPosition::Bottom);
+        siv.add_fullscreen_layer(BoxView::with_full_screen(tabs));
+
+        assert_eq!(siv.dump_screen(), vec!["1           ", " One │ Two  "]);
+    }
+}
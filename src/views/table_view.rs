@@ -0,0 +1,888 @@
+use align::HAlign;
+use direction::Direction;
+use event::{Callback, Event, EventResult, Key, MouseButton, MouseEvent};
+use rect::Rect;
+use std::cmp::{max, min, Ordering};
+use std::rc::Rc;
+use theme::ColorStyle;
+use unicode_width::UnicodeWidthStr;
+use vec::Vec2;
+use view::{ScrollBase, View};
+use Cursive;
+use Printer;
+use With;
+
+/// A row that can be displayed in a [`TableView`].
+///
+/// `H` identifies the columns, and is usually a small `enum`.
+///
+/// [`TableView`]: ::views::TableView
+pub trait TableViewItem<H> {
+    /// Returns the text to show for this row in the given column.
+    fn to_column(&self, column: H) -> String;
+
+    /// Compares two rows on the given column.
+    ///
+    /// This is used to sort the table.
+    fn compare(&self, other: &Self, column: H) -> Ordering
+    where
+        Self: Sized;
+}
+
+/// View to show a list of items in several columns.
+///
+/// Clicking on a column header sorts the table on this column, and clicking
+/// again reverses the order. The header stays visible while the rows scroll.
+///
+/// # Examples
+///
+/// ```rust
+/// # use cursive::align::HAlign;
+/// # use cursive::views::{TableView, TableViewItem};
+/// # use std::cmp::Ordering;
+/// #[derive(Clone, Copy, PartialEq)]
+/// enum Column {
+///     Name,
+///     Size,
+/// }
+///
+/// struct File {
+///     name: String,
+///     size: u64,
+/// }
+///
+/// impl TableViewItem<Column> for File {
+///     fn to_column(&self, column: Column) -> String {
+///         match column {
+///             Column::Name => self.name.clone(),
+///             Column::Size => self.size.to_string(),
+///         }
+///     }
+///
+///     fn compare(&self, other: &Self, column: Column) -> Ordering {
+///         match column {
+///             Column::Name => self.name.cmp(&other.name),
+///             Column::Size => self.size.cmp(&other.size),
+///         }
+///     }
+/// }
+///
+/// let table = TableView::new()
+///     .column(Column::Name, "Name", |c| c.width_percent(70))
+///     .column(Column::Size, "Size", |c| c.align(HAlign::Right))
+///     .items(vec![File { name: "a.txt".to_string(), size: 12 }]);
+///
+/// assert_eq!(table.borrow_item(0).unwrap().size, 12);
+/// ```
+pub struct TableView<T, H> {
+    columns: Vec<TableColumn<H>>,
+    items: Vec<T>,
+
+    // Index in `items` of each row, in display order.
+    rows: Vec<usize>,
+
+    // Selected row, as an index in `rows`.
+    focus: usize,
+
+    // Column used for sorting, and `true` for an ascending order.
+    sort: Option<(H, bool)>,
+
+    enabled: bool,
+    scrollbase: ScrollBase,
+    last_size: Vec2,
+
+    // Called with the index of the item when "Enter" is pressed or when a
+    // row is clicked.
+    on_submit: Option<Rc<OnSubmit>>,
+}
+
+/// Closure type for callbacks when a row is submitted.
+type OnSubmit = Fn(&mut Cursive, usize);
+
+// Header line and the line below it.
+const HEADER_HEIGHT: usize = 2;
+
+// Printed between two columns.
+const SEPARATOR: &str = " │ ";
+
+/// A column in a [`TableView`].
+///
+/// Columns are configured when added with [`TableView::column`].
+///
+/// [`TableView`]: ::views::TableView
+/// [`TableView::column`]: ::views::TableView::column
+pub struct TableColumn<H> {
+    column: H,
+    title: String,
+    width: ColumnWidth,
+    align: HAlign,
+
+    // Computed on layout.
+    computed_width: usize,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ColumnWidth {
+    Fixed(usize),
+    Percent(usize),
+    Fill,
+}
+
+impl<H> TableColumn<H> {
+    fn new(column: H, title: String) -> Self {
+        TableColumn {
+            column,
+            title,
+            width: ColumnWidth::Fill,
+            align: HAlign::Left,
+            computed_width: 0,
+        }
+    }
+
+    /// Gives this column a fixed width.
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = ColumnWidth::Fixed(width);
+        self
+    }
+
+    /// Gives this column a percentage of the table width.
+    pub fn width_percent(mut self, percent: usize) -> Self {
+        self.width = ColumnWidth::Percent(min(percent, 100));
+        self
+    }
+
+    /// Makes this column share the remaining width with other such columns.
+    ///
+    /// This is the default.
+    pub fn width_fill(mut self) -> Self {
+        self.width = ColumnWidth::Fill;
+        self
+    }
+
+    /// Sets the alignment of the title and content of this column.
+    ///
+    /// Defaults to `HAlign::Left`.
+    pub fn align(mut self, align: HAlign) -> Self {
+        self.align = align;
+        self
+    }
+}
+
+impl<T, H> Default for TableView<T, H>
+where
+    T: TableViewItem<H> + 'static,
+    H: Copy + PartialEq + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, H> TableView<T, H>
+where
+    T: TableViewItem<H> + 'static,
+    H: Copy + PartialEq + 'static,
+{
+    /// Creates a new empty `TableView`, without any column.
+    pub fn new() -> Self {
+        TableView {
+            columns: Vec::new(),
+            items: Vec::new(),
+            rows: Vec::new(),
+            focus: 0,
+            sort: None,
+            enabled: true,
+            scrollbase: ScrollBase::new(),
+            last_size: Vec2::zero(),
+            on_submit: None,
+        }
+    }
+
+    /// Adds a column to the right of the table.
+    ///
+    /// `configure` can be used to change the width or the alignment of the
+    /// column.
+    pub fn add_column<S, F>(&mut self, column: H, title: S, configure: F)
+    where
+        S: Into<String>,
+        F: FnOnce(TableColumn<H>) -> TableColumn<H>,
+    {
+        self.columns
+            .push(configure(TableColumn::new(column, title.into())));
+    }
+
+    /// Adds a column to the right of the table.
+    ///
+    /// Chainable variant.
+    pub fn column<S, F>(self, column: H, title: S, configure: F) -> Self
+    where
+        S: Into<String>,
+        F: FnOnce(TableColumn<H>) -> TableColumn<H>,
+    {
+        self.with(|s| s.add_column(column, title, configure))
+    }
+
+    impl_enabled!(self.enabled);
+
+    /// Sets a callback to be used when `<Enter>` is pressed, or when a row
+    /// is clicked.
+    ///
+    /// The callback is given the index of the selected item.
+    pub fn set_on_submit<F>(&mut self, cb: F)
+    where
+        F: Fn(&mut Cursive, usize) + 'static,
+    {
+        self.on_submit = Some(Rc::new(cb));
+    }
+
+    /// Sets a callback to be used when `<Enter>` is pressed, or when a row
+    /// is clicked.
+    ///
+    /// Chainable variant.
+    pub fn on_submit<F>(self, cb: F) -> Self
+    where
+        F: Fn(&mut Cursive, usize) + 'static,
+    {
+        self.with(|s| s.set_on_submit(cb))
+    }
+
+    /// Replaces the content of the table.
+    ///
+    /// The items are sorted if a sort column is set, and the first row is
+    /// selected.
+    pub fn set_items(&mut self, items: Vec<T>) {
+        self.rows = (0..items.len()).collect();
+        self.items = items;
+        self.sort_rows();
+        self.focus = 0;
+        self.scrollbase.scroll_top();
+    }
+
+    /// Replaces the content of the table.
+    ///
+    /// Chainable variant.
+    pub fn items(self, items: Vec<T>) -> Self {
+        self.with(|s| s.set_items(items))
+    }
+
+    /// Returns the item with the given index, if any.
+    ///
+    /// Indices follow the order in which items were added, not the order
+    /// they are displayed in.
+    pub fn borrow_item(&self, index: usize) -> Option<&T> {
+        self.items.get(index)
+    }
+
+    /// Returns a mutable reference to the item with the given index, if any.
+    ///
+    /// The table is not sorted again after changing an item; use
+    /// [`sort_by`](#method.sort_by) for that.
+    pub fn borrow_item_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.items.get_mut(index)
+    }
+
+    /// Returns every item, in the order they were added.
+    pub fn borrow_items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Adds an item to the table.
+    ///
+    /// It is placed according to the current sort order.
+    pub fn insert_item(&mut self, item: T) {
+        self.rows.push(self.items.len());
+        self.items.push(item);
+        self.sort_rows();
+    }
+
+    /// Removes and returns the item with the given index, if any.
+    ///
+    /// The index of every item added after this one is shifted down by one.
+    pub fn remove_item(&mut self, index: usize) -> Option<T> {
+        if index >= self.items.len() {
+            return None;
+        }
+
+        let item = self.items.remove(index);
+        self.rows.retain(|&i| i != index);
+        for i in &mut self.rows {
+            if *i > index {
+                *i -= 1;
+            }
+        }
+        self.focus = min(self.focus, self.rows.len().saturating_sub(1));
+
+        Some(item)
+    }
+
+    /// Removes all items from this view.
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.rows.clear();
+        self.focus = 0;
+    }
+
+    /// Returns the number of items in this table.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if this table has no item.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns the index of the selected item.
+    ///
+    /// Returns `None` if the table is empty.
+    pub fn selected_item(&self) -> Option<usize> {
+        self.rows.get(self.focus).cloned()
+    }
+
+    /// Selects the row showing the item with the given index.
+    pub fn set_selected_item(&mut self, index: usize) {
+        if let Some(row) = self.rows.iter().position(|&i| i == index) {
+            self.focus = row;
+            self.scrollbase.scroll_to(row);
+        }
+    }
+
+    /// Sorts the table on the given column.
+    ///
+    /// The selected item stays selected. Items added later are also sorted.
+    pub fn sort_by(&mut self, column: H, ascending: bool) {
+        self.sort = Some((column, ascending));
+        self.sort_rows();
+    }
+
+    /// Sorts the table on the given column.
+    ///
+    /// Chainable variant.
+    pub fn sorted_by(self, column: H, ascending: bool) -> Self {
+        self.with(|s| s.sort_by(column, ascending))
+    }
+
+    /// Returns the column used for sorting, and `true` if the order is
+    /// ascending.
+    pub fn sorting(&self) -> Option<(H, bool)> {
+        self.sort
+    }
+
+    fn sort_rows(&mut self) {
+        let selected = self.selected_item();
+
+        if let Some((column, ascending)) = self.sort {
+            let items = &self.items;
+            self.rows.sort_by(|&a, &b| {
+                let order = items[a].compare(&items[b], column);
+                if ascending {
+                    order
+                } else {
+                    order.reverse()
+                }
+            });
+        }
+
+        if let Some(index) = selected {
+            self.set_selected_item(index);
+        }
+    }
+
+    fn toggle_sort(&mut self, column: H) {
+        let ascending = match self.sort {
+            Some((c, ascending)) if c == column => !ascending,
+            _ => true,
+        };
+        self.sort_by(column, ascending);
+    }
+
+    fn focus_up(&mut self, n: usize) {
+        self.focus -= min(self.focus, n);
+    }
+
+    fn focus_down(&mut self, n: usize) {
+        self.focus = min(self.focus + n, self.rows.len().saturating_sub(1));
+    }
+
+    // Keeps the selection visible after scrolling with the mouse.
+    fn focus_visible(&mut self) {
+        let start = self.scrollbase.start_line;
+        let end = start + self.scrollbase.view_height;
+        self.focus = max(start, min(self.focus, end.saturating_sub(1)));
+        self.focus = min(self.focus, self.rows.len().saturating_sub(1));
+    }
+
+    fn submit(&mut self) -> EventResult {
+        let cb = self.on_submit.clone().unwrap();
+        EventResult::Consumed(
+            self.selected_item()
+                .map(|i| Callback::from_fn(move |s| cb(s, i))),
+        )
+    }
+
+    // Returns the X position of each column.
+    fn column_offsets(&self) -> Vec<usize> {
+        let mut x = 0;
+        self.columns
+            .iter()
+            .map(|column| {
+                let start = x;
+                x += column.computed_width + SEPARATOR.width();
+                start
+            })
+            .collect()
+    }
+
+    // Returns the column at the given X position, if any.
+    fn column_at(&self, x: usize) -> Option<H> {
+        self.columns
+            .iter()
+            .zip(self.column_offsets())
+            .find(|&(column, start)| {
+                x >= start && x < start + column.computed_width
+            })
+            .map(|(column, _)| column.column)
+    }
+
+    fn compute_widths(&mut self, width: usize) {
+        let separators =
+            SEPARATOR.width() * self.columns.len().saturating_sub(1);
+        let available = width.saturating_sub(separators);
+
+        let mut remaining = available;
+        let mut fill = 0;
+        for column in &mut self.columns {
+            column.computed_width = match column.width {
+                ColumnWidth::Fixed(width) => width,
+                ColumnWidth::Percent(percent) => available * percent / 100,
+                ColumnWidth::Fill => {
+                    fill += 1;
+                    0
+                }
+            };
+            remaining = remaining.saturating_sub(column.computed_width);
+        }
+
+        // Split what's left between the `Fill` columns.
+        let fill_columns = self
+            .columns
+            .iter_mut()
+            .filter(|column| column.width == ColumnWidth::Fill);
+        for (i, column) in fill_columns.enumerate() {
+            let extra = if i < remaining % fill { 1 } else { 0 };
+            column.computed_width = remaining / fill + extra;
+        }
+    }
+
+    // Calls `f` with a printer for each column.
+    fn draw_columns<F>(&self, printer: &Printer, f: F)
+    where
+        F: Fn(&Printer, &TableColumn<H>),
+    {
+        for (column, x) in self.columns.iter().zip(self.column_offsets()) {
+            if x > 0 {
+                printer.print((x - SEPARATOR.width(), 0), SEPARATOR);
+            }
+            f(
+                &printer.offset((x, 0)).cropped((column.computed_width, 1)),
+                column,
+            );
+        }
+    }
+
+    fn draw_header(&self, printer: &Printer) {
+        self.draw_columns(printer, |printer, column| {
+            let title = match self.sort {
+                Some((c, true)) if c == column.column => {
+                    format!("{} ▲", column.title)
+                }
+                Some((c, false)) if c == column.column => {
+                    format!("{} ▼", column.title)
+                }
+                _ => column.title.clone(),
+            };
+            printer.with_color(ColorStyle::title_primary(), |printer| {
+                print_aligned(printer, &title, &column.align);
+            });
+        });
+
+        printer.print_hline((0, 1), printer.size.x, "─");
+        for x in self.column_offsets().into_iter().skip(1) {
+            // The middle of the separator.
+            printer.print((x - 2, 1), "┼");
+        }
+    }
+
+    fn draw_row(&self, printer: &Printer, row: usize) {
+        let item = &self.items[self.rows[row]];
+        printer.print_hline((0, 0), printer.size.x, " ");
+        self.draw_columns(printer, |printer, column| {
+            let text = item.to_column(column.column);
+            print_aligned(printer, &text, &column.align);
+        });
+    }
+}
+
+fn print_aligned(printer: &Printer, text: &str, align: &HAlign) {
+    let x = align.get_offset(text.width(), printer.size.x);
+    printer.print((x, 0), text);
+}
+
+impl<T, H> View for TableView<T, H>
+where
+    T: TableViewItem<H> + 'static,
+    H: Copy + PartialEq + 'static,
+{
+    fn draw(&self, printer: &Printer) {
+        let enabled = self.enabled && printer.enabled;
+
+        if enabled {
+            self.draw_header(printer);
+        } else {
+            printer.with_color(ColorStyle::secondary(), |printer| {
+                self.draw_header(printer)
+            });
+        }
+
+        let printer = &printer.offset((0, HEADER_HEIGHT));
+        self.scrollbase.draw(printer, |printer, row| {
+            let selected = row == self.focus;
+            printer.with_selection(selected, |printer| {
+                if !selected && !enabled {
+                    printer.with_color(ColorStyle::secondary(), |printer| {
+                        self.draw_row(printer, row)
+                    });
+                } else {
+                    self.draw_row(printer, row);
+                }
+            });
+        });
+    }
+
+    fn required_size(&mut self, constraint: Vec2) -> Vec2 {
+        let widths: usize = self
+            .columns
+            .iter()
+            .map(|column| match column.width {
+                ColumnWidth::Fixed(width) => width,
+                _ => {
+                    // Leave room for the sort arrow.
+                    let title = column.title.width() + 2;
+                    self.items
+                        .iter()
+                        .map(|item| item.to_column(column.column).width())
+                        .fold(title, max)
+                }
+            })
+            .sum();
+        let separators =
+            SEPARATOR.width() * self.columns.len().saturating_sub(1);
+
+        let h = HEADER_HEIGHT + self.rows.len();
+        let scrollbar = if h > constraint.y {
+            1 + self.scrollbase.right_padding
+        } else {
+            0
+        };
+
+        Vec2::new(widths + separators + scrollbar, h)
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        self.last_size = size;
+        self.scrollbase.set_heights(
+            size.y.saturating_sub(HEADER_HEIGHT),
+            self.rows.len(),
+        );
+        self.scrollbase.scroll_to(self.focus);
+
+        let width = if self.scrollbase.scrollable() {
+            size.x.saturating_sub(1 + self.scrollbase.right_padding)
+        } else {
+            size.x
+        };
+        self.compute_widths(width);
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        if !self.enabled {
+            return EventResult::Ignored;
+        }
+
+        match event {
+            Event::Key(Key::Up) if self.focus > 0 => self.focus_up(1),
+            Event::Key(Key::Down) if self.focus + 1 < self.rows.len() => {
+                self.focus_down(1)
+            }
+            Event::Key(Key::PageUp) => self.focus_up(10),
+            Event::Key(Key::PageDown) => self.focus_down(10),
+            Event::Key(Key::Home) => self.focus = 0,
+            Event::Key(Key::End) => {
+                self.focus = self.rows.len().saturating_sub(1)
+            }
+            Event::Key(Key::Enter)
+                if self.on_submit.is_some() && !self.rows.is_empty() =>
+            {
+                return self.submit();
+            }
+            Event::Mouse {
+                event: MouseEvent::WheelUp,
+                ..
+            } if self.scrollbase.can_scroll_up() => {
+                self.scrollbase.scroll_up(5);
+                self.focus_visible();
+                return EventResult::Consumed(None);
+            }
+            Event::Mouse {
+                event: MouseEvent::WheelDown,
+                ..
+            } if self.scrollbase.can_scroll_down() => {
+                self.scrollbase.scroll_down(5);
+                self.focus_visible();
+                return EventResult::Consumed(None);
+            }
+            Event::Mouse {
+                event: MouseEvent::Press(MouseButton::Left),
+                position,
+                offset,
+            } if self.scrollbase.scrollable()
+                && position
+                    .checked_sub(offset + (0, HEADER_HEIGHT))
+                    .map(|position| {
+                        self.scrollbase.start_drag(position, self.last_size.x)
+                    })
+                    .unwrap_or(false) =>
+            {
+                self.focus_visible();
+                return EventResult::Consumed(None);
+            }
+            Event::Mouse {
+                event: MouseEvent::Hold(MouseButton::Left),
+                position,
+                offset,
+            } if self.scrollbase.is_dragging() => {
+                let position =
+                    position.saturating_sub(offset + (0, HEADER_HEIGHT));
+                self.scrollbase.drag(position);
+                self.focus_visible();
+                return EventResult::Consumed(None);
+            }
+            Event::Mouse {
+                event: MouseEvent::Release(MouseButton::Left),
+                ..
+            } if self.scrollbase.is_dragging() => {
+                self.scrollbase.release_grab();
+                return EventResult::Consumed(None);
+            }
+            Event::Mouse {
+                event: MouseEvent::Release(MouseButton::Left),
+                position,
+                offset,
+            } if position
+                .checked_sub(offset)
+                .map(|position| position < self.last_size && position.y == 0)
+                .unwrap_or(false) =>
+            {
+                match self.column_at(position.x - offset.x) {
+                    Some(column) => self.toggle_sort(column),
+                    None => return EventResult::Ignored,
+                }
+            }
+            Event::Mouse {
+                event: MouseEvent::Press(_),
+                position,
+                offset,
+            } if position
+                .checked_sub(offset + (0, HEADER_HEIGHT))
+                .map(|position| {
+                    position.x < self.last_size.x
+                        && position.y < self.scrollbase.view_height
+                        && position.y + self.scrollbase.start_line
+                            < self.rows.len()
+                })
+                .unwrap_or(false) =>
+            {
+                self.focus = position.y - offset.y - HEADER_HEIGHT
+                    + self.scrollbase.start_line;
+            }
+            Event::Mouse {
+                event: MouseEvent::Release(MouseButton::Left),
+                position,
+                offset,
+            } if self.on_submit.is_some()
+                && position
+                    .checked_sub(offset + (0, HEADER_HEIGHT))
+                    .map(|position| {
+                        position.x < self.last_size.x
+                            && position.y + self.scrollbase.start_line
+                                == self.focus
+                    })
+                    .unwrap_or(false) =>
+            {
+                return self.submit();
+            }
+            _ => return EventResult::Ignored,
+        }
+
+        self.scrollbase.scroll_to(self.focus);
+        EventResult::Consumed(None)
+    }
+
+    fn take_focus(&mut self, _: Direction) -> bool {
+        self.enabled && !self.rows.is_empty()
+    }
+
+    fn important_area(&self, size: Vec2) -> Rect {
+        // We scroll internally, so only count visible rows.
+        let row = self.focus.saturating_sub(self.scrollbase.start_line);
+        Rect::from_size((0, HEADER_HEIGHT + row), (size.x, 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backend::puppet;
+    use views::BoxView;
+
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    enum Column {
+        Name,
+        Count,
+    }
+
+    struct Entry {
+        name: &'static str,
+        count: usize,
+    }
+
+    impl TableViewItem<Column> for Entry {
+        fn to_column(&self, column: Column) -> String {
+            match column {
+                Column::Name => self.name.to_string(),
+                Column::Count => self.count.to_string(),
+            }
+        }
+
+        fn compare(&self, other: &Self, column: Column) -> Ordering {
+            match column {
+                Column::Name => self.name.cmp(other.name),
+                Column::Count => self.count.cmp(&other.count),
+            }
+        }
+    }
+
+    fn names(table: &TableView<Entry, Column>) -> Vec<&str> {
+        table.rows.iter().map(|&i| table.items[i].name).collect()
+    }
+
+    fn click(x: usize, y: usize) -> Event {
+        Event::Mouse {
+            offset: Vec2::zero(),
+            position: Vec2::new(x, y),
+            event: MouseEvent::Release(MouseButton::Left),
+        }
+    }
+
+    #[test]
+    fn test_sort_on_click() {
+        let mut table = TableView::new()
+            .column(Column::Name, "Name", |c| c.width(10))
+            .column(Column::Count, "Count", |c| c)
+            .items(vec![
+                Entry { name: "b", count: 2 },
+                Entry { name: "a", count: 3 },
+                Entry { name: "c", count: 1 },
+            ]);
+        table.layout(Vec2::new(30, 10));
+        assert_eq!(names(&table), vec!["b", "a", "c"]);
+
+        // "Count" starts after the name and the separator.
+        table.on_event(click(15, 0));
+        assert_eq!(table.sorting(), Some((Column::Count, true)));
+        assert_eq!(names(&table), vec!["c", "b", "a"]);
+
+        table.on_event(click(15, 0));
+        assert_eq!(names(&table), vec!["a", "b", "c"]);
+
+        // The selection follows the item.
+        assert_eq!(table.selected_item(), Some(0));
+        assert_eq!(table.focus, 1);
+
+        // New items are sorted too.
+        table.insert_item(Entry { name: "d", count: 5 });
+        assert_eq!(names(&table), vec!["d", "a", "b", "c"]);
+
+        table.on_event(click(2, 0));
+        assert_eq!(names(&table), vec!["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn test_wheel_scroll() {
+        let items = (0..20).map(|count| Entry { name: "a", count }).collect();
+        let mut table = TableView::new()
+            .column(Column::Name, "Name", |c| c)
+            .items(items);
+        table.layout(Vec2::new(10, 7));
+
+        table.on_event(Event::Mouse {
+            offset: Vec2::zero(),
+            position: Vec2::new(2, 3),
+            event: MouseEvent::WheelDown,
+        });
+        table.layout(Vec2::new(10, 7));
+
+        // The view stays where it was scrolled, and the selection follows.
+        assert_eq!(table.scrollbase.start_line, 5);
+        assert_eq!(table.focus, 5);
+    }
+
+    #[test]
+    fn test_column_widths() {
+        let mut table: TableView<Entry, Column> = TableView::new()
+            .column(Column::Name, "A", |c| c.width(4))
+            .column(Column::Name, "B", |c| c.width_percent(50))
+            .column(Column::Count, "C", |c| c)
+            .column(Column::Count, "D", |c| c.width_fill());
+
+        // 3 separators of 3 cells, and 41 cells for the columns.
+        table.layout(Vec2::new(50, 10));
+        let widths: Vec<usize> =
+            table.columns.iter().map(|c| c.computed_width).collect();
+        assert_eq!(widths, vec![4, 20, 9, 8]);
+        assert_eq!(table.column_offsets(), vec![0, 7, 30, 42]);
+
+        // Leave room for the scrollbar.
+        let items = (0..20).map(|count| Entry { name: "a", count }).collect();
+        table.set_items(items);
+        table.layout(Vec2::new(50, 10));
+        let widths: Vec<usize> =
+            table.columns.iter().map(|c| c.computed_width).collect();
+        assert_eq!(widths, vec![4, 19, 8, 8]);
+    }
+
+    #[test]
+    fn test_draw() {
+        let backend = puppet::Backend::init(Some(Vec2::new(20, 4)));
+        let mut siv = Cursive::new(move || backend);
+        let table = TableView::new()
+            .column(Column::Name, "Name", |c| c.width(6))
+            .column(Column::Count, "Count", |c| c.align(HAlign::Right))
+            .items(vec![
+                Entry { name: "b", count: 20 },
+                Entry { name: "a", count: 3 },
+            ])
+            .sorted_by(Column::Count, true);
+        siv.add_fullscreen_layer(BoxView::with_full_screen(table));
+
+        assert_eq!(
+            siv.dump_screen(),
+            vec![
+                "Name   │     Count ▲",
+                "───────┼────────────",
+                "a      │           3",
+                "b      │          20",
+            ]
+        );
+    }
+}
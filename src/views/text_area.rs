@@ -3,9 +3,10 @@ use event::{Event, EventResult, Key, MouseButton, MouseEvent};
 use rect::Rect;
 use std::cmp::min;
 use theme::{ColorStyle, Effect};
-use unicode_segmentation::UnicodeSegmentation;
-use unicode_width::UnicodeWidthStr;
-use utils::lines::simple::{prefix, simple_prefix, LinesIterator, Row};
+use utils::lines::simple::{LinesIterator, Row};
+use utils::lines::tabs::{self, DEFAULT_TAB_WIDTH};
+use utils::{clipboard, graphemes};
+use utils::undo::History;
 use vec::Vec2;
 use view::{ScrollBase, SizeCache, View};
 use {Printer, With, XY};
@@ -15,6 +16,14 @@ use {Printer, With, XY};
 /// A `TextArea` will attempt to grow vertically and horizontally
 /// dependent on the content.  Wrap it in a `BoxView` to
 /// constrain its size.
+///
+/// `<Enter>` inserts a newline, and long lines are wrapped. The arrow keys
+/// move the cursor by visual row, so `<Up>` and `<Down>` go through each
+/// part of a wrapped line; `<Home>` and `<End>` stay on the current row.
+///
+/// When the content is taller than the available space, the view scrolls
+/// vertically and keeps the cursor in sight. `important_area()` reports the
+/// cursor position, so a parent `ScrollView` can keep it visible too.
 pub struct TextArea {
     // TODO: use a smarter data structure (rope?)
     content: String,
@@ -36,12 +45,21 @@ pub struct TextArea {
 
     /// Byte offset of the currently selected grapheme.
     cursor: usize,
+
+    /// Number of cells between two tab stops.
+    tab_width: usize,
+
+    /// Edits that can be undone with `<Ctrl-Z>`.
+    history: History,
 }
 
-fn make_rows(text: &str, width: usize) -> Vec<Row> {
+fn make_rows(text: &str, width: usize, tab_width: usize) -> Vec<Row> {
     // We can't make rows with width=0, so force at least width=1.
     let width = usize::max(width, 1);
-    LinesIterator::new(text, width).show_spaces().collect()
+    LinesIterator::new(text, width)
+        .show_spaces()
+        .tab_width(tab_width)
+        .collect()
 }
 
 new_default!(TextArea);
@@ -57,7 +75,10 @@ impl TextArea {
             size_cache: None,
             last_size: Vec2::zero(),
             cursor: 0,
+            tab_width: DEFAULT_TAB_WIDTH,
+            history: History::new(),
         }
+        .with(TextArea::fix_ghost_row)
     }
 
     /// Retrieves the content of the view.
@@ -88,8 +109,14 @@ impl TextArea {
     }
 
     /// Sets the content of the view.
+    ///
+    /// The content can contain newlines: `get_content()` returns it
+    /// unchanged.
+    ///
+    /// This also clears the undo history.
     pub fn set_content<S: Into<String>>(&mut self, content: S) {
         self.content = content.into();
+        self.history.clear();
 
         // First, make sure we are within the bounds.
         self.cursor = min(self.cursor, self.content.len());
@@ -100,9 +127,43 @@ impl TextArea {
             self.cursor -= 1;
         }
 
+        self.reset_rows();
+    }
+
+    /// Reverts the last edit.
+    ///
+    /// Consecutive typed characters are undone together, up to a word.
+    pub fn undo(&mut self) {
+        if let Some(cursor) = self.history.undo(&mut self.content) {
+            self.cursor = cursor;
+            self.reset_rows();
+        }
+    }
+
+    /// Applies again the last undone edit.
+    pub fn redo(&mut self) {
+        if let Some(cursor) = self.history.redo(&mut self.content) {
+            self.cursor = cursor;
+            self.reset_rows();
+        }
+    }
+
+    /// Forgets every edit, so they can't be undone anymore.
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    // Re-computes every row after the content was replaced.
+    fn reset_rows(&mut self) {
         if let Some(size) = self.size_cache.map(|s| s.map(|s| s.value)) {
             self.invalidate();
             self.compute_rows(size);
+        } else {
+            // We don't know our width yet: only break rows on newlines
+            // until the next layout.
+            self.rows =
+                make_rows(&self.content, usize::MAX, self.tab_width);
+            self.fix_ghost_row();
         }
     }
 
@@ -144,6 +205,29 @@ impl TextArea {
         self.enabled
     }
 
+    /// Sets the number of cells between two tab stops.
+    ///
+    /// Tab characters expand to the next stop, counting from the start of
+    /// each row. The cursor still moves over a tab in one step.
+    ///
+    /// Defaults to 4.
+    pub fn set_tab_width(&mut self, tab_width: usize) {
+        self.tab_width = tab_width;
+        self.reset_rows();
+    }
+
+    /// Sets the number of cells between two tab stops.
+    ///
+    /// Chainable variant.
+    pub fn tab_width(self, tab_width: usize) -> Self {
+        self.with(|s| s.set_tab_width(tab_width))
+    }
+
+    /// Returns the number of cells between two tab stops.
+    pub fn get_tab_width(&self) -> usize {
+        self.tab_width
+    }
+
     /// Finds the row containing the grapheme at the given offset
     fn row_at(&self, offset: usize) -> usize {
         debug!("Offset: {}", offset);
@@ -164,7 +248,7 @@ impl TextArea {
         let row_id = self.row_at(offset);
         let row = self.rows[row_id];
         // Number of cells to the left of the cursor
-        self.content[row.start..offset].width()
+        tabs::width(&self.content[row.start..offset], 0, self.tab_width)
     }
 
     /// Finds the row containing the cursor
@@ -197,11 +281,7 @@ impl TextArea {
 
         // Number of cells to the left of the cursor
         let x = self.col_at(self.cursor);
-
-        let prev_row = self.rows[row_id - 1];
-        let prev_text = &self.content[prev_row.start..prev_row.end];
-        let offset = prefix(prev_text.graphemes(true), x, "").length;
-        self.cursor = prev_row.start + offset;
+        self.move_to_col(row_id - 1, x);
     }
 
     fn move_down(&mut self) {
@@ -210,11 +290,23 @@ impl TextArea {
             return;
         }
         let x = self.col_at(self.cursor);
+        self.move_to_col(row_id + 1, x);
+    }
 
-        let next_row = self.rows[row_id + 1];
-        let next_text = &self.content[next_row.start..next_row.end];
-        let offset = prefix(next_text.graphemes(true), x, "").length;
-        self.cursor = next_row.start + offset;
+    /// Moves the cursor to the given column of the given row.
+    ///
+    /// If the row is shorter, goes to the end of that row.
+    fn move_to_col(&mut self, row_id: usize, x: usize) {
+        let row = self.rows[row_id];
+        let text = &self.content[row.start..row.end];
+        let prefix = tabs::prefix(text, 0, x, self.tab_width);
+        self.cursor = row.start + prefix.length;
+
+        // When a line is wrapped, the end of a row is also the start of the
+        // next one. Step back to stay on this row.
+        if self.cursor > row.start && self.selected_row() != row_id {
+            self.move_left();
+        }
     }
 
     /// Moves the cursor to the left.
@@ -230,7 +322,7 @@ impl TextArea {
             }
 
             let text = &self.content[self.rows[row].start..self.cursor];
-            text.graphemes(true).last().unwrap().len()
+            graphemes::previous(text, text.len()).unwrap().len()
         };
         self.cursor -= len;
     }
@@ -239,11 +331,7 @@ impl TextArea {
     ///
     /// Jumps to the next line is required.
     fn move_right(&mut self) {
-        let len = self.content[self.cursor..]
-            .graphemes(true)
-            .next()
-            .unwrap()
-            .len();
+        let len = graphemes::next(&self.content, self.cursor).unwrap().len();
         self.cursor += len;
     }
 
@@ -281,13 +369,13 @@ impl TextArea {
 
         let mut available = size.x;
 
-        self.rows = make_rows(&self.content, available);
+        self.rows = make_rows(&self.content, available, self.tab_width);
         self.fix_ghost_row();
 
         if self.rows.len() > size.y {
             available = available.saturating_sub(1);
             // Apparently we'll need a scrollbar. Doh :(
-            self.rows = make_rows(&self.content, available);
+            self.rows = make_rows(&self.content, available, self.tab_width);
             self.fix_ghost_row();
         }
 
@@ -313,15 +401,12 @@ impl TextArea {
             return;
         }
         debug!("Rows: {:?}", self.rows);
-        let len = self.content[self.cursor..]
-            .graphemes(true)
-            .next()
-            .unwrap()
-            .len();
+        let len = graphemes::next(&self.content, self.cursor).unwrap().len();
         let start = self.cursor;
         let end = self.cursor + len;
         debug!("Start/end: {}/{}", start, end);
         debug!("Content: `{}`", self.content);
+        self.history.record_remove(start, &self.content[start..end]);
         for _ in self.content.drain(start..end) {}
         debug!("Content: `{}`", self.content);
 
@@ -347,6 +432,27 @@ impl TextArea {
     }
 
     fn insert(&mut self, ch: char) {
+        let mut buffer = [0; 4];
+        self.history
+            .record_insert(self.cursor, ch.encode_utf8(&mut buffer));
+        self.insert_unrecorded(ch);
+    }
+
+    // Inserts `text` at the cursor, undone as a single edit.
+    fn paste(&mut self, text: &str) {
+        let text: String = text.chars().filter(|&ch| ch != '\r').collect();
+        if text.is_empty() {
+            return;
+        }
+
+        self.history.record_insert(self.cursor, &text);
+        for ch in text.chars() {
+            self.insert_unrecorded(ch);
+        }
+    }
+
+    // Inserts `ch` at the cursor, without recording it in the history.
+    fn insert_unrecorded(&mut self, ch: char) {
         // First, we inject the data, but keep the cursor unmoved
         // (So the cursor is to the left of the injected char)
         self.content.insert(self.cursor, ch);
@@ -414,8 +520,11 @@ impl TextArea {
 
         // First attempt, if scrollbase status didn't change.
         debug!("Rows: {:?}", self.rows);
-        let new_rows =
-            make_rows(&self.content[first_byte..last_byte], available);
+        let new_rows = make_rows(
+            &self.content[first_byte..last_byte],
+            available,
+            self.tab_width,
+        );
         // How much did this add?
         debug!("New rows: {:?}", new_rows);
         debug!("{}-{}", first_row, last_row);
@@ -479,6 +588,7 @@ impl View for TextArea {
             });
 
             debug!("Content: `{}`", &self.content);
+            let tab_width = self.tab_width;
             self.scrollbase.draw(printer, |printer, i| {
                 debug!("Drawing row {}", i);
                 let row = &self.rows[i];
@@ -486,7 +596,7 @@ impl View for TextArea {
                 let text = &self.content[row.start..row.end];
                 debug!("row text: `{}`", text);
                 printer.with_effect(effect, |printer| {
-                    printer.print((0, 0), text);
+                    printer.print((0, 0), &tabs::expand(text, 0, tab_width));
                 });
 
                 if printer.focused && i == self.selected_row() {
@@ -494,21 +604,30 @@ impl View for TextArea {
                     let c = if cursor_offset == text.len() {
                         "_"
                     } else {
-                        text[cursor_offset..]
-                            .graphemes(true)
-                            .next()
+                        graphemes::next(text, cursor_offset)
                             .expect("Found no char!")
                     };
-                    let offset = text[..cursor_offset].width();
-                    printer.print((offset, 0), c);
+                    let offset =
+                        tabs::width(&text[..cursor_offset], 0, tab_width);
+                    // A tab shows as a wide cursor.
+                    let c = tabs::expand(c, offset, tab_width);
+                    printer.print((offset, 0), &c);
                 }
             });
         });
     }
 
     fn on_event(&mut self, event: Event) -> EventResult {
+        if !self.enabled {
+            return EventResult::Ignored;
+        }
+
         let mut fix_scroll = true;
         match event {
+            Event::CtrlChar('v') => return clipboard::paste(),
+            Event::Paste(ref text) => self.paste(text),
+            Event::CtrlChar('z') => self.undo(),
+            Event::CtrlChar('y') | Event::CtrlChar('Z') => self.redo(),
             Event::Char(ch) => self.insert(ch),
             Event::Key(Key::Enter) => self.insert('\n'),
             Event::Key(Key::Backspace) if self.cursor > 0 => self.backspace(),
@@ -588,11 +707,7 @@ impl View for TextArea {
                 if let Some(position) = position.checked_sub(offset) {
                     let y = position.y + self.scrollbase.start_line;
                     let y = min(y, self.rows.len() - 1);
-                    let x = position.x;
-                    let row = &self.rows[y];
-                    let content = &self.content[row.start..row.end];
-
-                    self.cursor = row.start + simple_prefix(content, x).length;
+                    self.move_to_col(y, position.x);
                 }
             }
             _ => return EventResult::Ignored,
@@ -623,16 +738,195 @@ impl View for TextArea {
             1
         } else {
             // Otherwise it's the selected grapheme
-            self.content[self.cursor..]
-                .graphemes(true)
-                .next()
-                .unwrap()
-                .width()
+            let g = graphemes::next(&self.content, self.cursor).unwrap();
+            tabs::grapheme_width(g, self.selected_col(), self.tab_width)
         };
 
-        Rect::from_size(
-            (self.selected_col(), self.selected_row()),
-            (char_width, 1),
-        )
+        // We scroll internally, so only count visible rows.
+        let row = self
+            .selected_row()
+            .saturating_sub(self.scrollbase.start_line);
+
+        Rect::from_size((self.selected_col(), row), (char_width, 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row_texts(area: &TextArea) -> Vec<&str> {
+        area.rows
+            .iter()
+            .map(|row| &area.content[row.start..row.end])
+            .collect()
+    }
+
+    #[test]
+    fn test_insert_newline() {
+        let mut area = TextArea::new().content("hello world");
+        area.layout(Vec2::new(20, 5));
+        area.set_cursor(5);
+
+        area.on_event(Event::Key(Key::Enter));
+        assert_eq!(area.get_content(), "hello\n world");
+        assert_eq!(row_texts(&area), vec!["hello", " world"]);
+        assert_eq!((area.selected_row(), area.selected_col()), (1, 0));
+    }
+
+    #[test]
+    fn test_rewrap() {
+        let mut area = TextArea::new().content("aaaa bbbb cccc");
+        area.layout(Vec2::new(10, 5));
+        assert_eq!(row_texts(&area), vec!["aaaa bbbb", "cccc"]);
+
+        area.set_cursor(4);
+        area.on_event(Event::Key(Key::Enter));
+        assert_eq!(row_texts(&area), vec!["aaaa", " bbbb ", "cccc"]);
+
+        area.on_event(Event::Key(Key::Backspace));
+        assert_eq!(area.get_content(), "aaaa bbbb cccc");
+        assert_eq!(row_texts(&area), vec!["aaaa bbbb", "cccc"]);
+    }
+
+    #[test]
+    fn test_move_in_wrapped_line() {
+        let mut area = TextArea::new().content("aaaa bbbbbbb cc");
+        area.layout(Vec2::new(10, 5));
+        assert_eq!(row_texts(&area), vec!["aaaa ", "bbbbbbb ", "cc"]);
+
+        // Going up from a longer row stops at the end of the shorter one,
+        // not at the start of the next row.
+        area.set_cursor(12);
+        area.on_event(Event::Key(Key::Up));
+        assert_eq!((area.selected_row(), area.selected_col()), (0, 4));
+
+        area.on_event(Event::Key(Key::Down));
+        area.on_event(Event::Key(Key::Down));
+        assert_eq!((area.selected_row(), area.selected_col()), (2, 2));
+
+        area.on_event(Event::Key(Key::Up));
+        area.on_event(Event::Key(Key::End));
+        assert_eq!((area.selected_row(), area.selected_col()), (1, 7));
+        area.on_event(Event::Key(Key::Home));
+        assert_eq!(area.cursor(), 5);
+    }
+
+    #[test]
+    fn test_undo() {
+        let mut area = TextArea::new();
+        area.layout(Vec2::new(10, 5));
+        for c in "ab cd".chars() {
+            area.on_event(Event::Char(c));
+        }
+        area.on_event(Event::Key(Key::Enter));
+        area.on_event(Event::Key(Key::Backspace));
+        assert_eq!(area.get_content(), "ab cd");
+
+        area.on_event(Event::CtrlChar('z'));
+        assert_eq!(area.get_content(), "ab cd\n");
+        assert_eq!((area.selected_row(), area.selected_col()), (1, 0));
+
+        // The newline was typed right after "cd", so it goes with it.
+        area.on_event(Event::CtrlChar('z'));
+        assert_eq!(area.get_content(), "ab ");
+        assert_eq!(area.cursor(), 3);
+
+        area.on_event(Event::CtrlChar('y'));
+        assert_eq!(area.get_content(), "ab cd\n");
+        assert_eq!(area.cursor(), 6);
+        assert_eq!(row_texts(&area), vec!["ab cd", ""]);
+    }
+
+    #[test]
+    fn test_content_round_trip() {
+        let text = "first\n\n  third\n";
+        let mut area = TextArea::new().content(text);
+        assert_eq!(area.get_content(), text);
+
+        // Editing before the first layout works too.
+        area.set_cursor(0);
+        area.on_event(Event::Char('>'));
+        area.layout(Vec2::new(3, 10));
+        assert_eq!(area.get_content(), ">first\n\n  third\n");
+
+        area.set_content(text);
+        assert_eq!(area.get_content(), text);
+    }
+
+    #[test]
+    fn test_paste() {
+        let mut area = TextArea::new().content("ab");
+        area.layout(Vec2::new(10, 5));
+        area.set_cursor(1);
+        area.on_event(Event::Paste("x\r\ny".to_string()));
+        assert_eq!(area.get_content(), "ax\nyb");
+        assert_eq!(row_texts(&area), vec!["ax", "yb"]);
+    }
+
+    #[test]
+    fn test_undo_paste() {
+        let mut area = TextArea::new().content("ab");
+        area.layout(Vec2::new(10, 5));
+        area.set_cursor(1);
+        area.on_event(Event::Paste("x y\nz".to_string()));
+        area.on_event(Event::Char('!'));
+        assert_eq!(area.get_content(), "ax y\nz!b");
+
+        area.on_event(Event::CtrlChar('z'));
+        assert_eq!(area.get_content(), "ax y\nzb");
+
+        // The whole paste is undone at once.
+        area.on_event(Event::CtrlChar('z'));
+        assert_eq!(area.get_content(), "ab");
+        assert_eq!(area.cursor(), 1);
+    }
+
+    #[test]
+    fn test_graphemes() {
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        let mut area =
+            TextArea::new().content(format!("e\u{301}{}x", family));
+        area.layout(Vec2::new(10, 5));
+        area.set_cursor(0);
+
+        area.on_event(Event::Key(Key::Right));
+        area.on_event(Event::Key(Key::Right));
+        assert_eq!(area.selected_col(), 3);
+
+        area.on_event(Event::Key(Key::Backspace));
+        assert_eq!(area.get_content(), "e\u{301}x");
+        assert_eq!(area.selected_col(), 1);
+        area.on_event(Event::Key(Key::Left));
+        area.on_event(Event::Key(Key::Del));
+        assert_eq!(area.get_content(), "x");
+    }
+
+    #[test]
+    fn test_tabs() {
+        let mut area = TextArea::new().content("a\tbc\n  \tx");
+        area.layout(Vec2::new(9, 5));
+        assert_eq!(row_texts(&area), vec!["a\tbc", "  \tx"]);
+        area.set_cursor(0);
+
+        // The tab is a single step, but takes 3 cells.
+        area.on_event(Event::Key(Key::Right));
+        assert_eq!(area.selected_col(), 1);
+        assert_eq!(area.important_area(Vec2::new(9, 5)).width(), 3);
+        area.on_event(Event::Key(Key::Right));
+        assert_eq!(area.selected_col(), 4);
+        area.on_event(Event::Key(Key::Left));
+        assert_eq!(area.cursor(), 1);
+
+        // Columns on other rows count the tabs too.
+        area.on_event(Event::Ctrl(Key::End));
+        area.on_event(Event::Key(Key::Left));
+        assert_eq!(area.selected_col(), 4);
+        area.on_event(Event::Key(Key::Up));
+        assert_eq!(area.cursor(), 2);
+
+        // Wider tabs wrap earlier.
+        area.set_tab_width(8);
+        assert_eq!(row_texts(&area), vec!["a\t", "bc", "  \t", "x"]);
     }
 }
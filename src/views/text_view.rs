@@ -1,15 +1,21 @@
+use std::borrow::Cow;
 use std::ops::Deref;
 use std::sync::Arc;
 use std::sync::{Mutex, MutexGuard};
 
 use owning_ref::{ArcRef, OwningHandle};
-use unicode_width::UnicodeWidthStr;
+use unicode_segmentation::UnicodeSegmentation;
 
 use align::*;
-use theme::Effect;
-use utils::lines::spans::{LinesIterator, Row};
+use direction::{Direction, TextDirection};
+use event::{Event, EventResult, Key, MouseButton, MouseEvent};
+use theme::{ColorStyle, Effect};
+use utils::graphemes;
+use utils::lines::spans::{LinesIterator, Row, Segment};
+use utils::lines::tabs::{self, DEFAULT_TAB_WIDTH};
 use utils::markup::StyledString;
-use view::{SizeCache, View};
+use utils::span::SpannedStr;
+use view::{LayoutTree, SizeCache, View};
 use {Printer, Vec2, With, XY};
 
 /// Provides access to the content of a [`TextView`].
@@ -148,8 +154,110 @@ impl TextContentInner {
     }
 }
 
+/// Position in a text: a byte offset in one of its spans.
+///
+/// Positions are ordered like the text itself.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+struct Position {
+    span: usize,
+    offset: usize,
+}
+
+impl Position {
+    fn new(span: usize, offset: usize) -> Self {
+        Position { span, offset }
+    }
+
+    fn is_valid(self, content: &StyledString) -> bool {
+        self.span < content.spans_raw().len() && {
+            let text = span_text(content, self.span);
+            self.offset <= text.len() && text.is_char_boundary(self.offset)
+        }
+    }
+
+    /// Returns the position one grapheme further.
+    fn next(self, content: &StyledString) -> Self {
+        let mut pos = self;
+        loop {
+            let text = span_text(content, pos.span);
+            if let Some(g) = graphemes::next(text, pos.offset) {
+                return Position::new(pos.span, pos.offset + g.len());
+            }
+            if pos.span + 1 >= content.spans_raw().len() {
+                return pos;
+            }
+            pos = Position::new(pos.span + 1, 0);
+        }
+    }
+
+    /// Returns the position one grapheme before.
+    fn previous(self, content: &StyledString) -> Self {
+        let mut pos = self;
+        loop {
+            let text = span_text(content, pos.span);
+            if let Some(g) = graphemes::previous(text, pos.offset) {
+                return Position::new(pos.span, pos.offset - g.len());
+            }
+            if pos.span == 0 {
+                return pos;
+            }
+            let span = pos.span - 1;
+            pos = Position::new(span, span_text(content, span).len());
+        }
+    }
+}
+
+fn span_text(content: &StyledString, span: usize) -> &str {
+    content.spans_raw()[span].content.resolve(content.source())
+}
+
+/// Returns the part of `segment` between `start` and `end`.
+///
+/// The range is given in bytes, relative to the segment.
+fn segment_range(
+    segment: &Segment, start: Position, end: Position,
+) -> (usize, usize) {
+    let clamp = |pos: Position| {
+        if pos.span < segment.span_id {
+            segment.start
+        } else if pos.span > segment.span_id {
+            segment.end
+        } else {
+            pos.offset.max(segment.start).min(segment.end)
+        }
+    };
+
+    (clamp(start) - segment.start, clamp(end) - segment.start)
+}
+
+/// How a [`TextView`] breaks lines too long for its width.
+///
+/// [`TextView`]: struct.TextView.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WrapMode {
+    /// Break lines between words.
+    ///
+    /// A word wider than the view is still split, on its own line.
+    Word,
+    /// Break lines anywhere, filling each line up to the view width.
+    ///
+    /// Useful for content without spaces, like hex dumps or URLs.
+    Char,
+    /// Never break lines.
+    ///
+    /// The view requests its full unwrapped width; wrap it in a
+    /// [`ScrollView`] to scroll horizontally.
+    ///
+    /// [`ScrollView`]: struct.ScrollView.html
+    None,
+}
+
 /// A simple view showing a fixed text.
 ///
+/// The text can be made selectable with [`set_selectable`].
+///
+/// [`set_selectable`]: #method.set_selectable
+///
 /// # Examples
 ///
 /// ```rust
@@ -167,12 +275,25 @@ pub struct TextView {
     align: Align,
     effect: Effect,
 
-    // True if we can wrap long lines.
-    wrap: bool,
+    // How long lines are wrapped.
+    wrap: WrapMode,
+
+    // Whether the text reads left-to-right or right-to-left.
+    direction: TextDirection,
+
+    // Number of cells between two tab stops.
+    tab_width: usize,
 
     // ScrollBase make many scrolling-related things easier
     last_size: Vec2,
     width: Option<usize>,
+
+    // True if the user can select text.
+    selectable: bool,
+    // Where the selection started, and where it ends now.
+    selection: Option<(Position, Position)>,
+    // True while the mouse is selecting text.
+    dragging: bool,
 }
 
 impl TextView {
@@ -205,10 +326,15 @@ impl TextView {
             content: content.content,
             effect: Effect::Simple,
             rows: Vec::new(),
-            wrap: true,
+            wrap: WrapMode::Word,
+            direction: TextDirection::LeftToRight,
+            tab_width: DEFAULT_TAB_WIDTH,
             align: Align::top_left(),
             last_size: Vec2::zero(),
             width: None,
+            selectable: false,
+            selection: None,
+            dragging: false,
         }
     }
 
@@ -238,9 +364,130 @@ impl TextView {
 
     /// Controls content wrap for this view.
     ///
-    /// If `true` (the default), text will wrap long lines when needed.
+    /// If `true` (the default), text will wrap long lines between words.
+    /// If `false`, lines are never wrapped.
+    ///
+    /// Use [`set_wrap_mode`] for more control.
+    ///
+    /// [`set_wrap_mode`]: #method.set_wrap_mode
     pub fn set_content_wrap(&mut self, wrap: bool) {
-        self.wrap = wrap;
+        self.set_wrap_mode(if wrap { WrapMode::Word } else { WrapMode::None });
+    }
+
+    /// Sets how long lines are wrapped.
+    ///
+    /// Defaults to `WrapMode::Word`.
+    pub fn set_wrap_mode(&mut self, mode: WrapMode) {
+        self.wrap = mode;
+        self.invalidate();
+    }
+
+    /// Sets how long lines are wrapped.
+    ///
+    /// Chainable variant.
+    pub fn wrap_mode(self, mode: WrapMode) -> Self {
+        self.with(|s| s.set_wrap_mode(mode))
+    }
+
+    /// Returns how long lines are wrapped.
+    pub fn get_wrap_mode(&self) -> WrapMode {
+        self.wrap
+    }
+
+    /// Sets the base direction of the text.
+    ///
+    /// Right-to-left text is drawn from the right edge of the view.
+    /// Horizontal alignment follows the text direction: with
+    /// `HAlign::Left`, right-to-left text is aligned on the right.
+    ///
+    /// Defaults to `TextDirection::LeftToRight`.
+    pub fn set_direction(&mut self, direction: TextDirection) {
+        self.direction = direction;
+    }
+
+    /// Sets the base direction of the text.
+    ///
+    /// Chainable variant.
+    pub fn direction(self, direction: TextDirection) -> Self {
+        self.with(|s| s.set_direction(direction))
+    }
+
+    /// Returns the base direction of the text.
+    pub fn get_direction(&self) -> TextDirection {
+        self.direction
+    }
+
+    /// Sets the number of cells between two tab stops.
+    ///
+    /// Tab characters expand to the next stop, counting from the start of
+    /// each row.
+    ///
+    /// Defaults to 4.
+    pub fn set_tab_width(&mut self, tab_width: usize) {
+        self.tab_width = tab_width;
+        self.invalidate();
+    }
+
+    /// Sets the number of cells between two tab stops.
+    ///
+    /// Chainable variant.
+    pub fn tab_width(self, tab_width: usize) -> Self {
+        self.with(|s| s.set_tab_width(tab_width))
+    }
+
+    /// Returns the number of cells between two tab stops.
+    pub fn get_tab_width(&self) -> usize {
+        self.tab_width
+    }
+
+    /// Lets the user select text.
+    ///
+    /// Text is selected by dragging the mouse, or with `<Shift>` and the
+    /// arrow keys. `<Ctrl-C>` copies the selection to the clipboard set with
+    /// [`Cursive::set_clipboard`].
+    ///
+    /// A selectable view can take the focus.
+    ///
+    /// [`Cursive::set_clipboard`]: ../struct.Cursive.html#method.set_clipboard
+    pub fn set_selectable(&mut self, selectable: bool) {
+        self.selectable = selectable;
+        if !selectable {
+            self.clear_selection();
+        }
+    }
+
+    /// Lets the user select text.
+    ///
+    /// Chainable variant.
+    pub fn selectable(self) -> Self {
+        self.with(|s| s.set_selectable(true))
+    }
+
+    /// Returns the selected text, if any.
+    pub fn selected_text(&self) -> Option<String> {
+        let content = self.content.lock().unwrap();
+        let content = &content.content;
+        let (start, end) = self.selection_range(content)?;
+
+        let mut text = String::new();
+        for span in start.span..=end.span {
+            let span_text = span_text(content, span);
+            let from = if span == start.span { start.offset } else { 0 };
+            let to = if span == end.span {
+                end.offset
+            } else {
+                span_text.len()
+            };
+            text.push_str(&span_text[from..to]);
+        }
+
+        Some(text)
+    }
+
+    /// Unselects the text.
+    pub fn clear_selection(&mut self) {
+        self.selection = None;
+        self.dragging = false;
     }
 
     /// Sets the horizontal alignment for this view.
@@ -286,6 +533,7 @@ impl TextView {
         S: Into<StyledString>,
     {
         self.content.lock().unwrap().content = content.into();
+        self.clear_selection();
         self.invalidate();
     }
 
@@ -316,7 +564,11 @@ impl TextView {
     // This must be non-destructive, as it may be called
     // multiple times during layout.
     fn compute_rows(&mut self, size: Vec2) {
-        let size = if self.wrap { size } else { Vec2::max_value() };
+        let size = if self.wrap == WrapMode::None {
+            Vec2::max_value()
+        } else {
+            size
+        };
 
         let mut content = self.content.lock().unwrap();
         if content.is_cache_valid(size) {
@@ -332,12 +584,140 @@ impl TextView {
             return;
         }
 
-        self.rows = LinesIterator::new(&content.content, size.x).collect();
+        let lines = LinesIterator::new(&content.content, size.x)
+            .tab_width(self.tab_width);
+        self.rows = if self.wrap == WrapMode::Char {
+            lines.char_wrap().collect()
+        } else {
+            lines.collect()
+        };
 
         // Desired width
         self.width = self.rows.iter().map(|row| row.width).max();
     }
 
+    /// Returns the ordered bounds of the selection, if it is not empty.
+    ///
+    /// The content may have been changed through a `TextContent` since the
+    /// selection was made; in this case, returns `None`.
+    fn selection_range(
+        &self, content: &StyledString,
+    ) -> Option<(Position, Position)> {
+        let (anchor, cursor) = self.selection?;
+        if anchor == cursor
+            || !anchor.is_valid(content)
+            || !cursor.is_valid(content)
+        {
+            return None;
+        }
+
+        Some((anchor.min(cursor), anchor.max(cursor)))
+    }
+
+    /// Returns the position at the end of `row`.
+    ///
+    /// Empty rows use the end of the previous row.
+    fn row_end(&self, row: usize) -> Position {
+        self.rows[..=row.min(self.rows.len().saturating_sub(1))]
+            .iter()
+            .rev()
+            .filter_map(|row| row.segments.last())
+            .map(|segment| Position::new(segment.span_id, segment.end))
+            .next()
+            .unwrap_or_default()
+    }
+
+    /// Returns the position of the given cell, relative to the text.
+    fn position_at(&self, content: &StyledString, cell: Vec2) -> Position {
+        let row = match self.rows.get(cell.y) {
+            Some(row) => row,
+            None => return self.row_end(cell.y),
+        };
+
+        let mut column = 0;
+        for segment in &row.segments {
+            let text = span_text(content, segment.span_id);
+            let text = &text[segment.start..segment.end];
+            for (i, g) in text.grapheme_indices(true) {
+                column += tabs::grapheme_width(g, column, self.tab_width);
+                if cell.x < column {
+                    return Position::new(segment.span_id, segment.start + i);
+                }
+            }
+        }
+
+        self.row_end(cell.y)
+    }
+
+    /// Returns the cell showing `pos`, relative to the text.
+    fn cell_at(&self, content: &StyledString, pos: Position) -> Vec2 {
+        for (y, row) in self.rows.iter().enumerate() {
+            let end = match row.segments.last() {
+                Some(last) => Position::new(last.span_id, last.end),
+                None => continue,
+            };
+            if pos > end {
+                continue;
+            }
+
+            let mut x = 0;
+            for segment in &row.segments {
+                let (start, end) =
+                    segment_range(segment, Position::default(), pos);
+                let text = span_text(content, segment.span_id);
+                let text = &text[segment.start + start..segment.start + end];
+                x += tabs::width(text, x, self.tab_width);
+            }
+            return Vec2::new(x, y);
+        }
+
+        let y = self.rows.len().saturating_sub(1);
+        Vec2::new(self.rows.get(y).map(|row| row.width).unwrap_or(0), y)
+    }
+
+    /// Returns the position under the mouse, relative to the view.
+    fn mouse_position(
+        &self, content: &StyledString, position: Vec2,
+    ) -> Position {
+        let height = self.rows.len();
+        let offset = self.align.v.get_offset(height, self.last_size.y);
+        let y = position.y.saturating_sub(offset);
+        let width = self.rows.get(y).map(|row| row.width).unwrap_or(0);
+        let offset = self.align.h.get_offset(width, self.last_size.x);
+        // Back to reading order.
+        let x = self.direction.mirror(position.x, 1, self.last_size.x);
+        let x = x.saturating_sub(offset);
+
+        self.position_at(content, Vec2::new(x, y))
+    }
+
+    /// Moves the end of the selection, with the keyboard.
+    fn extend_selection<F>(&mut self, f: F) -> EventResult
+    where
+        F: FnOnce(&TextView, &StyledString, Position) -> Position,
+    {
+        let content = Arc::clone(&self.content);
+        let content = &content.lock().unwrap().content;
+        if content.spans_raw().is_empty() {
+            return EventResult::Ignored;
+        }
+
+        let (anchor, cursor) = self.selection.unwrap_or_default();
+        let cursor = f(self, content, cursor);
+        self.selection = Some((anchor, cursor));
+
+        EventResult::Consumed(None)
+    }
+
+    fn copy_selection(&self) -> EventResult {
+        match self.selected_text() {
+            Some(text) => EventResult::with_cb(move |s| {
+                s.clipboard_mut().set(&text);
+            }),
+            None => EventResult::Ignored,
+        }
+    }
+
     // Invalidates the cache, so next call will recompute everything.
     fn invalidate(&mut self) {
         let mut content = self.content.lock().unwrap();
@@ -353,17 +733,59 @@ impl View for TextView {
         let printer = &printer.offset((0, offset));
 
         let content = self.content.lock().unwrap();
+        let selection = self.selection_range(&content.content);
+        let source = SpannedStr::from(&content.content);
+
+        let selection_style = if printer.focused {
+            ColorStyle::highlight()
+        } else {
+            ColorStyle::highlight_inactive()
+        };
 
         printer.with_effect(self.effect, |printer| {
             for (y, row) in self.rows.iter().enumerate() {
                 let l = row.width;
                 let mut x = self.align.h.get_offset(l, printer.size.x);
+                // Tab stops are counted from the start of the row.
+                let mut column = 0;
 
-                for span in row.resolve(&content.content) {
-                    printer.with_style(*span.attr, |printer| {
-                        printer.print((x, y), span.content);
-                        x += span.content.width();
-                    });
+                for segment in &row.segments {
+                    let span = segment.resolve(&source);
+                    let (start, end) = selection
+                        .map(|(start, end)| segment_range(segment, start, end))
+                        .unwrap_or((0, 0));
+                    let parts = [
+                        (&span.content[..start], false),
+                        (&span.content[start..end], true),
+                        (&span.content[end..], false),
+                    ];
+
+                    for &(text, selected) in &parts {
+                        if text.is_empty() {
+                            continue;
+                        }
+                        let width = tabs::width(text, column, self.tab_width);
+                        let start =
+                            self.direction.mirror(x, width, printer.size.x);
+                        let text = tabs::expand(text, column, self.tab_width);
+                        let text = match self.direction {
+                            TextDirection::LeftToRight => text,
+                            TextDirection::RightToLeft => {
+                                Cow::Owned(graphemes::reversed(&text))
+                            }
+                        };
+                        printer.with_style(*span.attr, |printer| {
+                            if selected {
+                                printer.with_color(selection_style, |p| {
+                                    p.print((start, y), &text)
+                                });
+                            } else {
+                                printer.print((start, y), &text);
+                            }
+                        });
+                        x += width;
+                        column += width;
+                    }
                 }
             }
         });
@@ -392,4 +814,267 @@ impl View for TextView {
         let mut content = self.content.lock().unwrap();
         content.size_cache = Some(SizeCache::build(my_size, size));
     }
+
+    fn take_focus(&mut self, _: Direction) -> bool {
+        self.selectable
+    }
+
+    fn debug_layout(&mut self, tree: &mut LayoutTree) {
+        let content = self.content.lock().unwrap();
+        if let Some(cache) = content.size_cache {
+            tree.set_required_size(cache.map(SizeCache::value));
+        }
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        if !self.selectable {
+            return EventResult::Ignored;
+        }
+
+        match event {
+            Event::Mouse {
+                event: MouseEvent::Press(MouseButton::Left),
+                position,
+                offset,
+            } if position.fits_in_rect(offset, self.last_size) => {
+                let content = Arc::clone(&self.content);
+                let content = &content.lock().unwrap().content;
+                let pos = self.mouse_position(content, position - offset);
+                self.selection = Some((pos, pos));
+                self.dragging = true;
+                EventResult::Consumed(None)
+            }
+            Event::Mouse {
+                event: MouseEvent::Hold(MouseButton::Left),
+                position,
+                offset,
+            } if self.dragging => {
+                let content = Arc::clone(&self.content);
+                let content = &content.lock().unwrap().content;
+                let position = position.saturating_sub(offset);
+                let pos = self.mouse_position(content, position);
+                let anchor = self.selection.map(|(anchor, _)| anchor);
+                self.selection = Some((anchor.unwrap_or(pos), pos));
+                EventResult::Consumed(None)
+            }
+            Event::Mouse {
+                event: MouseEvent::Release(MouseButton::Left),
+                ..
+            } if self.dragging => {
+                self.dragging = false;
+                EventResult::Consumed(None)
+            }
+            // Right-to-left text goes forward to the left.
+            Event::Shift(Key::Left)
+                if self.direction == TextDirection::RightToLeft =>
+            {
+                self.extend_selection(|_, content, pos| pos.next(content))
+            }
+            Event::Shift(Key::Right)
+                if self.direction == TextDirection::RightToLeft =>
+            {
+                self.extend_selection(|_, content, pos| pos.previous(content))
+            }
+            Event::Shift(Key::Left) => {
+                self.extend_selection(|_, content, pos| pos.previous(content))
+            }
+            Event::Shift(Key::Right) => {
+                self.extend_selection(|_, content, pos| pos.next(content))
+            }
+            Event::Shift(Key::Up) => {
+                self.extend_selection(|view, content, pos| {
+                    let cell = view.cell_at(content, pos);
+                    match cell.y.checked_sub(1) {
+                        Some(y) => {
+                            view.position_at(content, Vec2::new(cell.x, y))
+                        }
+                        None => Position::default(),
+                    }
+                })
+            }
+            Event::Shift(Key::Down) => {
+                self.extend_selection(|view, content, pos| {
+                    let cell = view.cell_at(content, pos) + (0, 1);
+                    view.position_at(content, cell)
+                })
+            }
+            Event::CtrlChar('c') => self.copy_selection(),
+            _ => EventResult::Ignored,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backend::puppet;
+    use view::Identifiable;
+    use views::BoxView;
+    use Cursive;
+
+    fn mouse(event: MouseEvent, x: usize, y: usize) -> Event {
+        Event::Mouse {
+            offset: Vec2::zero(),
+            position: Vec2::new(x, y),
+            event,
+        }
+    }
+
+    #[test]
+    fn test_selection() {
+        let backend = puppet::Backend::init(Some(Vec2::new(10, 4)));
+        let mut siv = Cursive::new(move || backend);
+
+        let mut text = StyledString::plain("Some ");
+        text.append_styled("styled", Effect::Bold);
+        text.append_plain(" text\nthat wraps");
+        let view = TextView::new(text).selectable().with_id("text");
+        siv.add_fullscreen_layer(BoxView::with_full_screen(view));
+        assert_eq!(
+            siv.dump_screen(),
+            vec!["Some      ", "styled    ", "text      ", "that wraps"]
+        );
+
+        let selected = |siv: &mut Cursive| {
+            siv.call_on_id("text", |view: &mut TextView| view.selected_text())
+                .unwrap()
+        };
+
+        // From "me" to "ty", across a wrapped line.
+        siv.on_event(mouse(MouseEvent::Press(MouseButton::Left), 2, 0));
+        siv.on_event(mouse(MouseEvent::Hold(MouseButton::Left), 2, 1));
+        siv.on_event(mouse(MouseEvent::Release(MouseButton::Left), 2, 1));
+        assert_eq!(selected(&mut siv), Some("me st".to_string()));
+
+        siv.on_event(Event::Shift(Key::Right));
+        siv.on_event(Event::Shift(Key::Down));
+        siv.on_event(Event::Shift(Key::Down));
+        assert_eq!(
+            selected(&mut siv),
+            Some("me styled text\ntha".to_string())
+        );
+        siv.on_event(Event::Shift(Key::Left));
+        siv.on_event(Event::Shift(Key::Up));
+        assert_eq!(selected(&mut siv), Some("me styled te".to_string()));
+
+        siv.on_event(Event::CtrlChar('c'));
+        assert_eq!(
+            siv.clipboard().get(),
+            Some("me styled te".to_string())
+        );
+
+        // A simple click clears the selection.
+        siv.on_event(mouse(MouseEvent::Press(MouseButton::Left), 0, 3));
+        assert_eq!(selected(&mut siv), None);
+    }
+
+    #[test]
+    fn test_wide_wrap() {
+        let backend = puppet::Backend::init(Some(Vec2::new(5, 4)));
+        let mut siv = Cursive::new(move || backend);
+        let view = TextView::new("ab日本語cd 日本x");
+        siv.add_fullscreen_layer(BoxView::with_full_screen(view));
+
+        // Double-width characters take two columns.
+        assert_eq!(
+            siv.dump_screen(),
+            vec!["ab日 ", "本語 ", "cd 日", "本x  "]
+        );
+    }
+
+    #[test]
+    fn test_wrap_modes() {
+        let url = "Visit https://example.org/0123456789abcdef";
+        let screen = |mode: WrapMode| {
+            let backend = puppet::Backend::init(Some(Vec2::new(12, 5)));
+            let mut siv = Cursive::new(move || backend);
+            let view = TextView::new(url).wrap_mode(mode);
+            siv.add_fullscreen_layer(BoxView::with_full_screen(view));
+            siv.dump_screen()
+        };
+
+        // Lines break after slashes, and the long number is split.
+        assert_eq!(
+            screen(WrapMode::Word),
+            vec![
+                "Visit       ",
+                "https://    ",
+                "example.org/",
+                "0123456789ab",
+                "cdef        ",
+            ]
+        );
+        assert_eq!(
+            screen(WrapMode::Char),
+            vec![
+                "Visit https:",
+                "//example.or",
+                "g/0123456789",
+                "abcdef      ",
+                "            ",
+            ]
+        );
+        assert_eq!(
+            screen(WrapMode::None),
+            vec![
+                "Visit https:",
+                "            ",
+                "            ",
+                "            ",
+                "            ",
+            ]
+        );
+
+        // Without wrapping, the view asks for the entire line.
+        let mut view = TextView::new(url).wrap_mode(WrapMode::None);
+        assert_eq!(view.required_size(Vec2::new(12, 5)), Vec2::new(42, 1));
+    }
+
+    #[test]
+    fn test_rtl() {
+        let backend = puppet::Backend::init(Some(Vec2::new(7, 2)));
+        let mut siv = Cursive::new(move || backend);
+        let view = TextView::new("שלום עולם")
+            .direction(TextDirection::RightToLeft)
+            .selectable()
+            .with_id("text");
+        siv.add_fullscreen_layer(BoxView::with_full_screen(view));
+
+        // Lines start on the right, and read from right to left.
+        assert_eq!(siv.dump_screen(), vec!["   םולש", "   םלוע"]);
+
+        // Dragging to the left selects forward.
+        siv.on_event(mouse(MouseEvent::Press(MouseButton::Left), 5, 0));
+        siv.on_event(mouse(MouseEvent::Hold(MouseButton::Left), 3, 0));
+        siv.on_event(mouse(MouseEvent::Release(MouseButton::Left), 3, 0));
+        siv.on_event(Event::Shift(Key::Left));
+        let selected = siv
+            .call_on_id("text", |view: &mut TextView| view.selected_text())
+            .unwrap();
+        assert_eq!(selected, Some("לום".to_string()));
+    }
+
+    #[test]
+    fn test_tabs() {
+        let backend = puppet::Backend::init(Some(Vec2::new(8, 4)));
+        let mut siv = Cursive::new(move || backend);
+        let view = TextView::new("a\tb\n  \tc\td\nab\tcd\tef")
+            .selectable()
+            .with_id("text");
+        siv.add_fullscreen_layer(BoxView::with_full_screen(view));
+
+        // Tabs reach the next stop, and lines wrap at the expanded width.
+        assert_eq!(
+            siv.dump_screen(),
+            vec!["a   b   ", "    c   ", "d       ", "ab  cd  "]
+        );
+
+        // The whole tab is selected at once.
+        siv.on_event(mouse(MouseEvent::Press(MouseButton::Left), 2, 0));
+        siv.on_event(mouse(MouseEvent::Hold(MouseButton::Left), 4, 0));
+        let selected = siv
+            .call_on_id("text", |view: &mut TextView| view.selected_text())
+            .unwrap();
+        assert_eq!(selected, Some("\t".to_string()));
+    }
 }
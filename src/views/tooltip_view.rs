@@ -0,0 +1,254 @@
+use event::{Event, EventResult, MouseEvent};
+use std::rc::{Rc, Weak};
+use std::time::Duration;
+use vec::Vec2;
+use view::{View, ViewWrapper};
+use With;
+
+/// Tooltip found under the mouse.
+#[derive(Clone)]
+pub(crate) struct Hovered {
+    pub text: String,
+    pub delay: Duration,
+    // Gone when the `TooltipView` is dropped.
+    pub owner: Weak<()>,
+}
+
+/// Shows a tooltip when the mouse stays over the wrapped view.
+///
+/// The text appears near the mouse after it stopped over the view for some
+/// time, and disappears when it moves away or clicks. The tooltip never
+/// takes the focus or receives input.
+///
+/// Terminals only report the mouse when a button is used, so this relies on
+/// the positions given with `Hold` or `Release` events. These are followed
+/// by a [`MouseEvent::Hover`], which layouts give to the view under the
+/// mouse even if it doesn't have the focus.
+///
+/// [`MouseEvent::Hover`]: ../event/enum.MouseEvent.html#variant.Hover
+///
+/// # Examples
+///
+/// ```rust
+/// # use cursive::views::{Button, TooltipView};
+/// let save = Button::new("Save", |_| ());
+/// let button = TooltipView::new(save, "Saves the file");
+/// ```
+pub struct TooltipView<T> {
+    view: T,
+    text: String,
+    delay: Duration,
+    last_size: Vec2,
+    // Tooltips shown for this view hold a weak reference to this.
+    owner: Rc<()>,
+}
+
+impl<T> TooltipView<T> {
+    /// Wraps `view`, showing `text` as a tooltip.
+    pub fn new<S: Into<String>>(view: T, text: S) -> Self {
+        TooltipView {
+            view,
+            text: text.into(),
+            delay: Duration::from_millis(500),
+            last_size: Vec2::zero(),
+            owner: Rc::new(()),
+        }
+    }
+
+    /// Sets the text of the tooltip.
+    pub fn set_tooltip<S: Into<String>>(&mut self, text: S) {
+        self.text = text.into();
+    }
+
+    /// Sets the text of the tooltip.
+    ///
+    /// Chainable variant.
+    pub fn tooltip<S: Into<String>>(self, text: S) -> Self {
+        self.with(|s| s.set_tooltip(text))
+    }
+
+    /// Returns the text of the tooltip.
+    pub fn get_tooltip(&self) -> &str {
+        &self.text
+    }
+
+    /// Sets how long the mouse must stay still before showing the tooltip.
+    ///
+    /// Defaults to 500ms.
+    pub fn set_delay(&mut self, delay: Duration) {
+        self.delay = delay;
+    }
+
+    /// Sets how long the mouse must stay still before showing the tooltip.
+    ///
+    /// Chainable variant.
+    pub fn delay(self, delay: Duration) -> Self {
+        self.with(|s| s.set_delay(delay))
+    }
+}
+
+impl<T: View> ViewWrapper for TooltipView<T> {
+    wrap_impl!(self.view: T);
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        let hovered = match event {
+            Event::Mouse {
+                offset,
+                position,
+                event: MouseEvent::Hover,
+            } => position.fits_in_rect(offset, self.last_size),
+            _ => false,
+        };
+
+        let result = self.view.on_event(event);
+        // Nested tooltips win.
+        if !hovered || result.is_consumed() {
+            return result;
+        }
+
+        let hovered = Hovered {
+            text: self.text.clone(),
+            delay: self.delay,
+            owner: Rc::downgrade(&self.owner),
+        };
+        EventResult::with_cb(move |s| s.set_hovered(hovered.clone()))
+    }
+
+    fn wrap_layout(&mut self, size: Vec2) {
+        self.last_size = size;
+        self.view.layout(size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backend;
+    use event::{MouseButton, MouseEvent};
+    use std::thread;
+    use views::{Button, LinearLayout};
+    use Cursive;
+
+    fn mouse(siv: &mut Cursive, x: usize, event: MouseEvent) {
+        siv.on_event(Event::Mouse {
+            offset: Vec2::zero(),
+            position: Vec2::new(x, 0),
+            event,
+        });
+    }
+
+    fn shown(siv: &mut Cursive) -> bool {
+        siv.dump_screen()[1].contains("Saves the file")
+    }
+
+    #[test]
+    fn test_tooltip() {
+        let backend = backend::puppet::Backend::init(Some(Vec2::new(20, 5)));
+        let mut siv = Cursive::new(move || backend);
+        let hold = MouseEvent::Hold(MouseButton::Left);
+        // Keeps `step()` from waiting for input.
+        siv.add_timer(Duration::from_millis(1), |_| ());
+
+        siv.add_fullscreen_layer(
+            LinearLayout::horizontal()
+                .child(
+                    TooltipView::new(Button::new("Save", |_| ()), "")
+                        .tooltip("Saves the file")
+                        .delay(Duration::from_millis(5)),
+                )
+                .child(Button::new("Quit", |_| ())),
+        );
+        siv.dump_screen();
+
+        mouse(&mut siv, 1, hold);
+        assert!(!shown(&mut siv));
+        thread::sleep(Duration::from_millis(10));
+        siv.step();
+        assert!(shown(&mut siv));
+
+        // Moving away hides it.
+        mouse(&mut siv, 8, hold);
+        assert!(!shown(&mut siv));
+
+        // So does a click, until the mouse moves again.
+        mouse(&mut siv, 1, hold);
+        thread::sleep(Duration::from_millis(10));
+        siv.step();
+        mouse(&mut siv, 1, MouseEvent::Press(MouseButton::Left));
+        mouse(&mut siv, 1, MouseEvent::Release(MouseButton::Left));
+        thread::sleep(Duration::from_millis(10));
+        siv.step();
+        assert!(!shown(&mut siv));
+
+        // Removing the view removes its tooltip.
+        mouse(&mut siv, 2, hold);
+        thread::sleep(Duration::from_millis(10));
+        siv.step();
+        assert!(shown(&mut siv));
+        siv.pop_layer();
+        assert!(!shown(&mut siv));
+    }
+
+    #[test]
+    fn test_tooltip_on_move() {
+        let backend = backend::puppet::Backend::init(Some(Vec2::new(20, 5)));
+        let mut siv = Cursive::new(move || backend);
+        siv.add_timer(Duration::from_millis(1), |_| ());
+
+        siv.add_fullscreen_layer(
+            LinearLayout::horizontal()
+                .child(
+                    TooltipView::new(Button::new("Save", |_| ()), "")
+                        .tooltip("Saves the file")
+                        .delay(Duration::from_millis(5)),
+                )
+                .child(Button::new("Quit", |_| ())),
+        );
+        siv.dump_screen();
+
+        // Backends send `Hover` when the mouse moves without a button.
+        mouse(&mut siv, 1, MouseEvent::Hover);
+        assert!(!shown(&mut siv));
+        thread::sleep(Duration::from_millis(10));
+        siv.step();
+        assert!(shown(&mut siv));
+
+        mouse(&mut siv, 8, MouseEvent::Hover);
+        thread::sleep(Duration::from_millis(10));
+        siv.step();
+        assert!(!shown(&mut siv));
+    }
+
+    #[test]
+    fn test_tooltip_without_focus() {
+        let backend = backend::puppet::Backend::init(Some(Vec2::new(20, 5)));
+        let mut siv = Cursive::new(move || backend);
+        let hold = MouseEvent::Hold(MouseButton::Left);
+        siv.add_timer(Duration::from_millis(1), |_| ());
+
+        // The tooltip is on the second button, while the first has the focus.
+        siv.add_fullscreen_layer(
+            LinearLayout::horizontal()
+                .child(Button::new("Quit", |_| ()))
+                .child(
+                    TooltipView::new(Button::new("Save", |_| ()), "")
+                        .tooltip("Saves the file")
+                        .delay(Duration::from_millis(5)),
+                ),
+        );
+        siv.dump_screen();
+
+        mouse(&mut siv, 8, hold);
+        thread::sleep(Duration::from_millis(10));
+        siv.step();
+        assert!(shown(&mut siv));
+
+        // Hovering doesn't move the focus.
+        let focused = siv
+            .call_on_focused(|button: &mut Button| button.label().to_string());
+        assert_eq!(focused, Some("<Quit>".to_string()));
+
+        mouse(&mut siv, 1, hold);
+        assert!(!shown(&mut siv));
+    }
+}
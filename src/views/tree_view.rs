@@ -0,0 +1,872 @@
+use direction::Direction;
+use event::{Callback, Event, EventResult, Key, MouseButton, MouseEvent};
+use rect::Rect;
+use std::cmp::{max, min};
+use std::rc::Rc;
+use theme::{ColorStyle, Glyphs};
+use unicode_width::UnicodeWidthStr;
+use utils::markup::StyledString;
+use vec::Vec2;
+use view::{LayoutTree, ScrollBase, View};
+use Cursive;
+use Printer;
+use With;
+
+/// View to show items in a tree, where nodes can be collapsed.
+///
+/// Nodes are identified by their path: the position of each ancestor among
+/// its siblings, starting from the root, followed by the position of the
+/// node itself.
+///
+/// `<Right>` expands the selected node, `<Left>` collapses it (or goes to its
+/// parent), and `<Enter>` or a click on the node glyph toggles it. Children of
+/// collapsed nodes are skipped when moving the selection.
+///
+/// Glyphs come from the theme (see [`Glyphs`]), unless set with
+/// [`set_glyphs()`](#method.set_glyphs).
+///
+/// [`Glyphs`]: ../theme/struct.Glyphs.html
+///
+/// # Examples
+///
+/// ```rust
+/// # use cursive::views::TreeView;
+/// let mut tree = TreeView::new();
+/// let src = tree.add_item("src", "src/");
+/// let main = tree.add_child(&src, "main.rs", "src/main.rs").unwrap();
+/// tree.add_item("Cargo.toml", "Cargo.toml");
+///
+/// assert_eq!(main, vec![0, 0]);
+/// assert_eq!(tree.get_item(&main), Some(("main.rs", &"src/main.rs")));
+///
+/// tree.set_collapsed(&src, true);
+/// assert_eq!(tree.is_collapsed(&src), Some(true));
+/// ```
+pub struct TreeView<T = String> {
+    nodes: Vec<TreeNode<T>>,
+
+    // Path of each visible node, in display order.
+    rows: Vec<Vec<usize>>,
+
+    // Selected row, as an index in `rows`.
+    focus: usize,
+
+    enabled: bool,
+    scrollbase: ScrollBase,
+    last_size: Vec2,
+
+    // Width added for each level of depth.
+    indent: usize,
+    // Used instead of the theme glyphs, if set.
+    glyphs: Option<TreeGlyphs>,
+
+    // Called when "Enter" is pressed on a leaf, or when it is clicked.
+    on_submit: Option<Rc<OnSubmit<T>>>,
+}
+
+/// Closure type for callbacks when a node is submitted.
+type OnSubmit<T> = Fn(&mut Cursive, &T);
+
+/// Glyphs printed before each node of a [`TreeView`].
+///
+/// [`TreeView`]: struct.TreeView.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TreeGlyphs {
+    /// Printed before nodes with hidden children.
+    pub collapsed: String,
+    /// Printed before nodes with visible children.
+    pub expanded: String,
+    /// Printed before nodes without children.
+    pub leaf: String,
+}
+
+impl Default for TreeGlyphs {
+    fn default() -> Self {
+        TreeGlyphs {
+            collapsed: "▸ ".to_string(),
+            expanded: "▾ ".to_string(),
+            leaf: "  ".to_string(),
+        }
+    }
+}
+
+impl TreeGlyphs {
+    fn get<T>(&self, node: &TreeNode<T>) -> &str {
+        TreeGlyphs::pick(node, &self.collapsed, &self.expanded, &self.leaf)
+    }
+
+    fn pick<'a, T>(
+        node: &TreeNode<T>, collapsed: &'a str, expanded: &'a str,
+        leaf: &'a str,
+    ) -> &'a str {
+        if node.children.is_empty() {
+            leaf
+        } else if node.collapsed {
+            collapsed
+        } else {
+            expanded
+        }
+    }
+}
+
+/// Cells taken by glyphs from the theme.
+///
+/// Longer glyphs are cut, so every theme gives the same layout.
+const THEME_GLYPH_WIDTH: usize = 2;
+
+struct TreeNode<T> {
+    label: StyledString,
+    value: Rc<T>,
+    children: Vec<TreeNode<T>>,
+    collapsed: bool,
+}
+
+impl<T: 'static> Default for TreeView<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: 'static> TreeView<T> {
+    /// Creates a new empty `TreeView`.
+    pub fn new() -> Self {
+        TreeView {
+            nodes: Vec::new(),
+            rows: Vec::new(),
+            focus: 0,
+            enabled: true,
+            scrollbase: ScrollBase::new(),
+            last_size: Vec2::zero(),
+            indent: 2,
+            glyphs: None,
+            on_submit: None,
+        }
+    }
+
+    impl_enabled!(self.enabled);
+
+    /// Sets the width added for each level of depth.
+    ///
+    /// Defaults to 2.
+    pub fn set_indent(&mut self, indent: usize) {
+        self.indent = indent;
+    }
+
+    /// Sets the width added for each level of depth.
+    ///
+    /// Chainable variant.
+    pub fn indent(self, indent: usize) -> Self {
+        self.with(|s| s.set_indent(indent))
+    }
+
+    /// Sets the glyphs printed before each node.
+    ///
+    /// By default, glyphs come from the theme.
+    pub fn set_glyphs(&mut self, glyphs: TreeGlyphs) {
+        self.glyphs = Some(glyphs);
+    }
+
+    /// Sets the glyphs printed before each node.
+    ///
+    /// Chainable variant.
+    pub fn glyphs(self, glyphs: TreeGlyphs) -> Self {
+        self.with(|s| s.set_glyphs(glyphs))
+    }
+
+    /// Sets a callback to be used when `<Enter>` is pressed on a node without
+    /// children, or when such a node is clicked.
+    pub fn set_on_submit<F>(&mut self, cb: F)
+    where
+        F: Fn(&mut Cursive, &T) + 'static,
+    {
+        self.on_submit = Some(Rc::new(cb));
+    }
+
+    /// Sets a callback to be used when `<Enter>` is pressed on a node without
+    /// children, or when such a node is clicked.
+    ///
+    /// Chainable variant.
+    pub fn on_submit<F>(self, cb: F) -> Self
+    where
+        F: Fn(&mut Cursive, &T) + 'static,
+    {
+        self.with(|s| s.set_on_submit(cb))
+    }
+
+    /// Adds a node at the root of the tree, after the existing ones.
+    ///
+    /// Returns the path of the new node.
+    pub fn add_item<S>(&mut self, label: S, value: T) -> Vec<usize>
+    where
+        S: Into<StyledString>,
+    {
+        self.nodes.push(TreeNode::new(label.into(), value));
+        let path = vec![self.nodes.len() - 1];
+        self.insert_row(path.clone());
+        path
+    }
+
+    /// Adds a node after the existing children of `parent`.
+    ///
+    /// Returns the path of the new node, or `None` if there is no node at
+    /// `parent`.
+    pub fn add_child<S>(
+        &mut self, parent: &[usize], label: S, value: T,
+    ) -> Option<Vec<usize>>
+    where
+        S: Into<StyledString>,
+    {
+        let index = {
+            let node = find_mut(&mut self.nodes, parent)?;
+            node.children.push(TreeNode::new(label.into(), value));
+            node.children.len() - 1
+        };
+
+        let mut path = parent.to_vec();
+        path.push(index);
+        self.insert_row(path.clone());
+        Some(path)
+    }
+
+    /// Removes the node at the given path, with all its children.
+    ///
+    /// Returns the value of the removed node, or `None` if there is no node
+    /// at `path`.
+    pub fn remove_item(&mut self, path: &[usize]) -> Option<Rc<T>> {
+        let (&index, parent) = path.split_last()?;
+        let node = {
+            let siblings = if parent.is_empty() {
+                &mut self.nodes
+            } else {
+                &mut find_mut(&mut self.nodes, parent)?.children
+            };
+            if index >= siblings.len() {
+                return None;
+            }
+            siblings.remove(index)
+        };
+
+        // Later siblings of the removed node (and their children) moved up.
+        let selected = self.selected_path().and_then(|mut selected| {
+            if selected.starts_with(path) {
+                None
+            } else {
+                if selected.len() >= path.len()
+                    && selected.starts_with(parent)
+                    && selected[parent.len()] > index
+                {
+                    selected[parent.len()] -= 1;
+                }
+                Some(selected)
+            }
+        });
+        self.refresh(selected);
+
+        Some(node.value)
+    }
+
+    /// Removes all nodes from this view.
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.rows.clear();
+        self.focus = 0;
+    }
+
+    /// Returns `true` if this view has no node.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns the label and value of the node at the given path.
+    pub fn get_item(&self, path: &[usize]) -> Option<(&str, &T)> {
+        find(&self.nodes, path).map(|node| (node.label.source(), &*node.value))
+    }
+
+    /// Returns the number of children of the node at the given path.
+    pub fn children_count(&self, path: &[usize]) -> Option<usize> {
+        find(&self.nodes, path).map(|node| node.children.len())
+    }
+
+    /// Returns `true` if the node at the given path is collapsed.
+    pub fn is_collapsed(&self, path: &[usize]) -> Option<bool> {
+        find(&self.nodes, path).map(|node| node.collapsed)
+    }
+
+    /// Collapses or expands the node at the given path.
+    ///
+    /// Nodes are expanded when added. If the selected node gets hidden, its
+    /// collapsed ancestor is selected instead.
+    pub fn set_collapsed(&mut self, path: &[usize], collapsed: bool) {
+        if let Some(node) = find_mut(&mut self.nodes, path) {
+            node.collapsed = collapsed;
+        }
+        self.refresh(self.selected_path());
+    }
+
+    /// Returns the path of the selected node.
+    ///
+    /// Returns `None` if the tree is empty.
+    pub fn selected_path(&self) -> Option<Vec<usize>> {
+        self.rows.get(self.focus).cloned()
+    }
+
+    /// Returns the value of the selected node.
+    ///
+    /// Returns `None` if the tree is empty.
+    pub fn selection(&self) -> Option<Rc<T>> {
+        self.rows
+            .get(self.focus)
+            .and_then(|path| find(&self.nodes, path))
+            .map(|node| Rc::clone(&node.value))
+    }
+
+    /// Selects the node at the given path.
+    ///
+    /// Its ancestors are expanded if needed.
+    pub fn set_selected_path(&mut self, path: &[usize]) {
+        if find(&self.nodes, path).is_none() {
+            return;
+        }
+
+        for depth in 1..path.len() {
+            if let Some(node) = find_mut(&mut self.nodes, &path[..depth]) {
+                node.collapsed = false;
+            }
+        }
+        self.refresh(Some(path.to_vec()));
+        self.scrollbase.scroll_to(self.focus);
+    }
+
+    // Adds the row of a new node, if its parent is expanded.
+    //
+    // Paths sort in the display order, so this doesn't need to go through
+    // the whole tree.
+    fn insert_row(&mut self, path: Vec<usize>) {
+        let parent = &path[..path.len() - 1];
+        let visible = parent.is_empty()
+            || (self
+                .rows
+                .binary_search_by(|row| row[..].cmp(parent))
+                .is_ok()
+                && self.is_collapsed(parent) == Some(false));
+        if !visible {
+            return;
+        }
+
+        let row = match self.rows.binary_search(&path) {
+            Ok(row) | Err(row) => row,
+        };
+        if row <= self.focus && !self.rows.is_empty() {
+            // Keep the same node selected.
+            self.focus += 1;
+        }
+        self.rows.insert(row, path);
+    }
+
+    // Computes the visible rows, and selects the row closest to `selected`.
+    fn refresh(&mut self, selected: Option<Vec<usize>>) {
+        self.rows.clear();
+        collect_rows(&self.nodes, &mut Vec::new(), &mut self.rows);
+
+        // The deepest visible ancestor of the selected node.
+        let row = selected.and_then(|selected| {
+            self.rows
+                .iter()
+                .enumerate()
+                .filter(|&(_, row)| selected.starts_with(row))
+                .max_by_key(|&(_, row)| row.len())
+                .map(|(i, _)| i)
+        });
+        self.focus = row.unwrap_or_else(|| {
+            min(self.focus, self.rows.len().saturating_sub(1))
+        });
+    }
+
+    fn focused_node(&self) -> Option<&TreeNode<T>> {
+        self.rows
+            .get(self.focus)
+            .and_then(|path| find(&self.nodes, path))
+    }
+
+    // Returns `true` if the selected node has children.
+    fn is_branch(&self) -> bool {
+        self.focused_node()
+            .map(|node| !node.children.is_empty())
+            .unwrap_or(false)
+    }
+
+    fn toggle_focused(&mut self) {
+        if let Some(path) = self.selected_path() {
+            let collapsed = self.is_collapsed(&path).unwrap_or(false);
+            self.set_collapsed(&path, !collapsed);
+        }
+    }
+
+    fn expand_or_enter(&mut self) -> EventResult {
+        match self.focused_node().map(|node| node.collapsed) {
+            Some(true) if self.is_branch() => self.toggle_focused(),
+            Some(false) if self.is_branch() => self.focus += 1,
+            _ => return EventResult::Ignored,
+        }
+        EventResult::Consumed(None)
+    }
+
+    fn collapse_or_leave(&mut self) -> EventResult {
+        let path = match self.selected_path() {
+            Some(path) => path,
+            None => return EventResult::Ignored,
+        };
+
+        if self.is_branch() && self.is_collapsed(&path) == Some(false) {
+            self.set_collapsed(&path, true);
+        } else if path.len() > 1 {
+            self.refresh(Some(path[..path.len() - 1].to_vec()));
+        } else {
+            return EventResult::Ignored;
+        }
+        EventResult::Consumed(None)
+    }
+
+    fn submit(&mut self) -> EventResult {
+        let cb = self.on_submit.clone().unwrap();
+        EventResult::Consumed(
+            self.selection()
+                .map(|v| Callback::from_fn(move |s| cb(s, &v))),
+        )
+    }
+
+    // Returns the glyph printed for the given row.
+    fn glyph<'a>(&'a self, row: usize, theme: &'a Glyphs) -> &'a str {
+        let node = find(&self.nodes, &self.rows[row]).unwrap();
+        match self.glyphs {
+            Some(ref glyphs) => glyphs.get(node),
+            None => TreeGlyphs::pick(
+                node,
+                &theme.tree_collapsed,
+                &theme.tree_expanded,
+                &theme.tree_leaf,
+            ),
+        }
+    }
+
+    // Returns the position and width of the glyph for the given row.
+    fn glyph_span(&self, row: usize) -> (usize, usize) {
+        let path = &self.rows[row];
+        let width = match self.glyphs {
+            Some(ref glyphs) => {
+                let node = find(&self.nodes, path).unwrap();
+                glyphs.get(node).width()
+            }
+            None => THEME_GLYPH_WIDTH,
+        };
+        ((path.len() - 1) * self.indent, width)
+    }
+
+    // Keeps the selection visible after scrolling with the mouse.
+    fn focus_visible(&mut self) {
+        let start = self.scrollbase.start_line;
+        let end = start + self.scrollbase.view_height;
+        self.focus = max(start, min(self.focus, end.saturating_sub(1)));
+        self.focus = min(self.focus, self.rows.len().saturating_sub(1));
+    }
+
+    fn draw_row(&self, printer: &Printer, row: usize) {
+        let node = find(&self.nodes, &self.rows[row]).unwrap();
+        let (x, width) = self.glyph_span(row);
+        let glyph = self.glyph(row, &printer.theme.glyphs);
+
+        printer
+            .offset((x, 0))
+            .cropped((width, 1))
+            .print((0, 0), glyph);
+        let x = x + width;
+
+        let enabled = self.enabled && printer.enabled;
+        printer
+            .offset((x, 0))
+            .with_selection(row == self.focus, |printer| {
+                if row != self.focus && !enabled {
+                    printer.with_color(ColorStyle::secondary(), |printer| {
+                        printer.print_styled((0, 0), (&node.label).into())
+                    });
+                } else {
+                    printer.print_styled((0, 0), (&node.label).into());
+                }
+            });
+    }
+}
+
+impl<T> TreeNode<T> {
+    fn new(label: StyledString, value: T) -> Self {
+        TreeNode {
+            label,
+            value: Rc::new(value),
+            children: Vec::new(),
+            collapsed: false,
+        }
+    }
+}
+
+fn find<'a, T>(
+    nodes: &'a [TreeNode<T>], path: &[usize],
+) -> Option<&'a TreeNode<T>> {
+    let (&first, rest) = path.split_first()?;
+    let node = nodes.get(first)?;
+    if rest.is_empty() {
+        Some(node)
+    } else {
+        find(&node.children, rest)
+    }
+}
+
+fn find_mut<'a, T>(
+    nodes: &'a mut [TreeNode<T>], path: &[usize],
+) -> Option<&'a mut TreeNode<T>> {
+    let (&first, rest) = path.split_first()?;
+    let node = nodes.get_mut(first)?;
+    if rest.is_empty() {
+        Some(node)
+    } else {
+        find_mut(&mut node.children, rest)
+    }
+}
+
+fn collect_rows<T>(
+    nodes: &[TreeNode<T>], path: &mut Vec<usize>, rows: &mut Vec<Vec<usize>>,
+) {
+    for (i, node) in nodes.iter().enumerate() {
+        path.push(i);
+        rows.push(path.clone());
+        if !node.collapsed {
+            collect_rows(&node.children, path, rows);
+        }
+        path.pop();
+    }
+}
+
+impl<T: 'static> View for TreeView<T> {
+    fn draw(&self, printer: &Printer) {
+        self.scrollbase.draw(printer, |printer, row| {
+            self.draw_row(printer, row);
+        });
+    }
+
+    fn required_size(&mut self, constraint: Vec2) -> Vec2 {
+        let w = (0..self.rows.len())
+            .map(|row| {
+                let (x, width) = self.glyph_span(row);
+                let node = find(&self.nodes, &self.rows[row]).unwrap();
+                x + width + node.label.width()
+            })
+            .max()
+            .unwrap_or(1);
+
+        let h = self.rows.len();
+        let scrollbar = if h > constraint.y {
+            1 + self.scrollbase.right_padding
+        } else {
+            0
+        };
+
+        Vec2::new(w + scrollbar, h)
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        self.last_size = size;
+        self.scrollbase.set_heights(size.y, self.rows.len());
+        self.scrollbase.scroll_to(self.focus);
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        if !self.enabled {
+            return EventResult::Ignored;
+        }
+
+        match event {
+            Event::Key(Key::Up) if self.focus > 0 => self.focus -= 1,
+            Event::Key(Key::Down) if self.focus + 1 < self.rows.len() => {
+                self.focus += 1
+            }
+            Event::Key(Key::PageUp) => {
+                self.focus -= min(self.focus, 10);
+            }
+            Event::Key(Key::PageDown) => {
+                self.focus =
+                    min(self.focus + 10, self.rows.len().saturating_sub(1));
+            }
+            Event::Key(Key::Home) => self.focus = 0,
+            Event::Key(Key::End) => {
+                self.focus = self.rows.len().saturating_sub(1)
+            }
+            Event::Key(Key::Right) => {
+                let result = self.expand_or_enter();
+                self.scrollbase.scroll_to(self.focus);
+                return result;
+            }
+            Event::Key(Key::Left) => {
+                let result = self.collapse_or_leave();
+                self.scrollbase.scroll_to(self.focus);
+                return result;
+            }
+            Event::Key(Key::Enter) if self.is_branch() => {
+                self.toggle_focused()
+            }
+            Event::Key(Key::Enter)
+                if self.on_submit.is_some() && !self.rows.is_empty() =>
+            {
+                return self.submit();
+            }
+            Event::Mouse {
+                event: MouseEvent::WheelUp,
+                ..
+            } if self.scrollbase.can_scroll_up() => {
+                self.scrollbase.scroll_up(5);
+                self.focus_visible();
+                return EventResult::Consumed(None);
+            }
+            Event::Mouse {
+                event: MouseEvent::WheelDown,
+                ..
+            } if self.scrollbase.can_scroll_down() => {
+                self.scrollbase.scroll_down(5);
+                self.focus_visible();
+                return EventResult::Consumed(None);
+            }
+            Event::Mouse {
+                event: MouseEvent::Press(MouseButton::Left),
+                position,
+                offset,
+            } if self.scrollbase.scrollable()
+                && position
+                    .checked_sub(offset)
+                    .map(|position| {
+                        self.scrollbase.start_drag(position, self.last_size.x)
+                    })
+                    .unwrap_or(false) =>
+            {
+                self.focus_visible();
+                return EventResult::Consumed(None);
+            }
+            Event::Mouse {
+                event: MouseEvent::Hold(MouseButton::Left),
+                position,
+                offset,
+            } if self.scrollbase.is_dragging() => {
+                let position = position.saturating_sub(offset);
+                self.scrollbase.drag(position);
+                self.focus_visible();
+                return EventResult::Consumed(None);
+            }
+            Event::Mouse {
+                event: MouseEvent::Release(MouseButton::Left),
+                ..
+            } if self.scrollbase.is_dragging() => {
+                self.scrollbase.release_grab();
+                return EventResult::Consumed(None);
+            }
+            Event::Mouse {
+                event: MouseEvent::Press(_),
+                position,
+                offset,
+            } if position
+                .checked_sub(offset)
+                .map(|position| {
+                    position < self.last_size
+                        && position.y + self.scrollbase.start_line
+                            < self.rows.len()
+                })
+                .unwrap_or(false) =>
+            {
+                self.focus =
+                    position.y - offset.y + self.scrollbase.start_line;
+            }
+            Event::Mouse {
+                event: MouseEvent::Release(MouseButton::Left),
+                position,
+                offset,
+            } if position
+                .checked_sub(offset)
+                .map(|position| {
+                    position < self.last_size
+                        && position.y + self.scrollbase.start_line
+                            == self.focus
+                })
+                .unwrap_or(false) =>
+            {
+                let (x, width) = self.glyph_span(self.focus);
+                let on_glyph = position.x - offset.x >= x
+                    && position.x - offset.x < x + width;
+
+                if self.is_branch() && on_glyph {
+                    self.toggle_focused();
+                } else if !self.is_branch() && self.on_submit.is_some() {
+                    return self.submit();
+                } else {
+                    return EventResult::Ignored;
+                }
+            }
+            _ => return EventResult::Ignored,
+        }
+
+        self.scrollbase.scroll_to(self.focus);
+        EventResult::Consumed(None)
+    }
+
+    fn take_focus(&mut self, _: Direction) -> bool {
+        self.enabled && !self.rows.is_empty()
+    }
+
+    fn important_area(&self, size: Vec2) -> Rect {
+        // We scroll internally, so only count visible rows.
+        let row = self.focus.saturating_sub(self.scrollbase.start_line);
+        Rect::from_size((0, row), (size.x, 1))
+    }
+
+    fn debug_layout(&mut self, tree: &mut LayoutTree) {
+        if !self.rows.is_empty() {
+            tree.set_info(format!("selects row {}", self.focus));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backend::puppet;
+    use views::BoxView;
+
+    // a
+    // ├ b
+    // │ └ c
+    // └ d
+    // e
+    fn make_tree() -> TreeView<&'static str> {
+        let mut tree = TreeView::new();
+        let a = tree.add_item("a", "a");
+        let b = tree.add_child(&a, "b", "b").unwrap();
+        tree.add_child(&b, "c", "c");
+        tree.add_child(&a, "d", "d");
+        tree.add_item("e", "e");
+        tree
+    }
+
+    fn selected(tree: &TreeView<&'static str>) -> &'static str {
+        *tree.selection().unwrap()
+    }
+
+    #[test]
+    fn test_navigation() {
+        let mut tree = make_tree();
+        assert_eq!(tree.rows.len(), 5);
+
+        tree.on_event(Event::Key(Key::Down));
+        assert_eq!(tree.selected_path(), Some(vec![0, 0]));
+
+        // Collapse `b`, then skip its child.
+        tree.on_event(Event::Key(Key::Left));
+        assert_eq!(tree.is_collapsed(&[0, 0]), Some(true));
+        tree.on_event(Event::Key(Key::Down));
+        assert_eq!(selected(&tree), "d");
+
+        // Leaves go to their parent.
+        tree.on_event(Event::Key(Key::Left));
+        assert_eq!(selected(&tree), "a");
+        tree.on_event(Event::Key(Key::Left));
+        assert_eq!(tree.rows.len(), 2);
+        tree.on_event(Event::Key(Key::Down));
+        assert_eq!(selected(&tree), "e");
+        tree.on_event(Event::Key(Key::Up));
+
+        // Expand `a` and go to `b`.
+        tree.on_event(Event::Key(Key::Right));
+        tree.on_event(Event::Key(Key::Right));
+        assert_eq!(selected(&tree), "b");
+        tree.on_event(Event::Key(Key::Enter));
+        tree.on_event(Event::Key(Key::Down));
+        assert_eq!(selected(&tree), "c");
+    }
+
+    #[test]
+    fn test_remove_subtree() {
+        let mut tree = make_tree();
+        tree.set_selected_path(&[0, 1]);
+        assert_eq!(selected(&tree), "d");
+
+        assert_eq!(tree.remove_item(&[0, 0]).map(|v| *v), Some("b"));
+        assert_eq!(tree.rows.len(), 3);
+        assert_eq!(tree.selected_path(), Some(vec![0, 0]));
+        assert_eq!(selected(&tree), "d");
+
+        // Removing the selected node selects the next row.
+        tree.remove_item(&[0, 0]);
+        assert_eq!(selected(&tree), "e");
+        assert_eq!(tree.remove_item(&[3]), None);
+    }
+
+    #[test]
+    fn test_collapsed_selection() {
+        let mut tree = make_tree();
+        tree.set_selected_path(&[0, 0, 0]);
+        tree.set_collapsed(&[0], true);
+        assert_eq!(selected(&tree), "a");
+
+        // Selecting a hidden node expands its ancestors.
+        tree.set_selected_path(&[0, 0, 0]);
+        assert_eq!(tree.is_collapsed(&[0]), Some(false));
+        assert_eq!(selected(&tree), "c");
+    }
+
+    #[test]
+    fn test_add_rows() {
+        let mut tree = make_tree();
+        tree.set_collapsed(&[0, 0], true);
+        tree.set_selected_path(&[1]);
+
+        tree.add_child(&[0, 0], "f", "f");
+        tree.add_child(&[0], "g", "g");
+        tree.add_item("h", "h");
+        assert_eq!(selected(&tree), "e");
+
+        // Rows added one by one are the same as a full refresh.
+        let rows = tree.rows.clone();
+        tree.refresh(None);
+        assert_eq!(tree.rows, rows);
+        assert_eq!(tree.rows.len(), 6);
+    }
+
+    #[test]
+    fn test_wheel_scroll() {
+        let mut tree = TreeView::new();
+        for i in 0..20 {
+            tree.add_item(i.to_string(), i);
+        }
+        tree.layout(Vec2::new(10, 5));
+
+        tree.on_event(Event::Mouse {
+            offset: Vec2::zero(),
+            position: Vec2::new(2, 2),
+            event: MouseEvent::WheelDown,
+        });
+        tree.layout(Vec2::new(10, 5));
+
+        // The view stays where it was scrolled, and the selection follows.
+        assert_eq!(tree.scrollbase.start_line, 5);
+        assert_eq!(*tree.selection().unwrap(), 5);
+    }
+
+    #[test]
+    fn test_theme_glyphs() {
+        let backend = puppet::Backend::init(Some(Vec2::new(6, 2)));
+        let mut siv = Cursive::new(move || backend);
+        siv.set_theme_from_toml("[glyphs]\ntree_expanded = \"-->\"")
+            .unwrap();
+
+        let mut tree = TreeView::new();
+        let a = tree.add_item("a", ());
+        tree.add_child(&a, "b", ());
+        siv.add_fullscreen_layer(BoxView::with_full_screen(tree));
+
+        // Glyphs from the theme take two cells.
+        assert_eq!(siv.dump_screen(), vec!["--a   ", "    b "]);
+    }
+}